@@ -2,6 +2,7 @@ use clap_complete::{generate_to, Shell};
 use std::fs;
 
 include!("src/cli/colorpicker_tools.rs");
+include!("src/illuminant_names.rs");
 include!("src/cli/cli.rs");
 
 fn main() {
@@ -1,33 +1,80 @@
 use std::borrow::Borrow;
+use std::str::FromStr;
+#[cfg(unix)]
+use std::time::Duration;
 
 pub use atty::Stream;
 use once_cell::sync::Lazy;
 
 use crate::delta_e::ciede2000;
-use crate::{Color, Lab};
+use crate::parser::{parse_color, split_color_list};
+use crate::{Color, Error, Lab};
 
 static ANSI_LAB_REPRESENTATIONS: Lazy<Vec<(u8, Lab)>> = Lazy::new(|| {
-    (16..255)
+    (0..=255)
         .map(|code| (code, Color::from_ansi_8bit(code).to_lab()))
         .collect()
 });
 
+/// Colors with a (CIE Lab) chroma below this value are considered to be near-neutral.
+const NEUTRAL_CHROMA_THRESHOLD: f64 = 8.0;
+
+/// Options for approximating a color by one of the 256 colors of the 8-bit ANSI palette.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Ansi8BitOptions {
+    /// Also consider the 16 base colors (codes 0-15). Terminals commonly redefine these colors
+    /// (via their color scheme), so they are excluded by default.
+    pub include_base_colors: bool,
+
+    /// Map near-neutral colors to the closest pure gray (from the 24-step grayscale ramp or the
+    /// gray diagonal of the color cube), such that grays never come out slightly tinted.
+    pub prefer_grays: bool,
+
+    /// The actual RGB values of the 16 base colors, as configured in the terminal. If this is
+    /// set, the base colors are always considered (with these values instead of the standard
+    /// ones), see `parse_base_palette`.
+    pub base_palette: Option<[[u8; 3]; 16]>,
+}
+
+/// Parse the 16 base colors of a terminal palette from a list of colors that is separated by
+/// colons or whitespace, e.g. `#000000:#cd0000:#00cd00:…` (the format used by xterm and GNOME
+/// Terminal). Returns an error if any of the colors can not be parsed or if there are not
+/// exactly 16 colors.
+pub fn parse_base_palette(input: &str) -> Result<[[u8; 3]; 16], Error> {
+    let colors: Vec<Color> = input
+        .split(|c: char| c == ':' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| parse_color(part).ok_or_else(|| Error::InvalidColor(part.into())))
+        .collect::<Result<_, _>>()?;
+
+    if colors.len() != 16 {
+        return Err(Error::InvalidPalette(format!(
+            "expected 16 colors, got {}",
+            colors.len()
+        )));
+    }
+
+    let mut palette = [[0; 3]; 16];
+    for (entry, color) in palette.iter_mut().zip(&colors) {
+        let rgba = color.to_rgba();
+        *entry = [rgba.r, rgba.g, rgba.b];
+    }
+    Ok(palette)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Mode {
     Ansi8Bit,
     TrueColor,
 }
 
-#[derive(Debug)]
-pub struct UnknownColorModeError(pub String);
-
 impl Mode {
-    pub fn from_mode_str(mode_str: &str) -> Result<Option<Self>, UnknownColorModeError> {
+    pub fn from_mode_str(mode_str: &str) -> Result<Option<Self>, Error> {
         match mode_str {
             "24bit" | "truecolor" => Ok(Some(Mode::TrueColor)),
             "8bit" => Ok(Some(Mode::Ansi8Bit)),
             "off" => Ok(None),
-            value => Err(UnknownColorModeError(value.into())),
+            value => Err(Error::UnknownColorMode(value.into())),
         }
     }
 }
@@ -40,10 +87,23 @@ fn cube_to_8bit(code: u8) -> u8 {
     }
 }
 
+/// Whether the given 8-bit ANSI code represents a pure gray tone.
+fn is_gray_code(code: u8) -> bool {
+    let rgba = Color::from_ansi_8bit(code).to_rgba();
+    rgba.r == rgba.g && rgba.g == rgba.b
+}
+
 pub trait AnsiColor {
     fn from_ansi_8bit(code: u8) -> Self;
     fn to_ansi_8bit(&self) -> u8;
 
+    /// Like `to_ansi_8bit`, but with custom options for the choice of candidate colors. The
+    /// default implementation ignores the options.
+    fn to_ansi_8bit_with(&self, options: Ansi8BitOptions) -> u8 {
+        let _ = options;
+        self.to_ansi_8bit()
+    }
+
     fn to_ansi_sequence(&self, mode: Mode) -> String;
 }
 
@@ -98,10 +158,42 @@ impl AnsiColor for Color {
     ///
     /// See: <https://en.wikipedia.org/wiki/ANSI_escape_code>
     fn to_ansi_8bit(&self) -> u8 {
+        self.to_ansi_8bit_with(Ansi8BitOptions::default())
+    }
+
+    /// Like `to_ansi_8bit`, but with custom options for the choice of candidate colors.
+    fn to_ansi_8bit_with(&self, options: Ansi8BitOptions) -> u8 {
         let self_lab = self.to_lab();
-        ANSI_LAB_REPRESENTATIONS
+        let only_grays =
+            options.prefer_grays && self_lab.a.hypot(self_lab.b) < NEUTRAL_CHROMA_THRESHOLD;
+
+        let base_colors: Vec<(u8, Lab, bool)> = match options.base_palette {
+            Some(palette) => (0..16)
+                .zip(palette.iter())
+                .map(|(code, &[r, g, b])| {
+                    (code, Color::from_rgb(r, g, b).to_lab(), r == g && g == b)
+                })
+                .collect(),
+            None if options.include_base_colors => ANSI_LAB_REPRESENTATIONS[..16]
+                .iter()
+                .map(|(code, lab)| (*code, lab.clone(), is_gray_code(*code)))
+                .collect(),
+            None => vec![],
+        };
+
+        let extended_colors = ANSI_LAB_REPRESENTATIONS[16..]
             .iter()
-            .min_by_key(|(_, lab)| ciede2000(&self_lab, lab) as i32)
+            .map(|(code, lab)| (*code, lab.clone(), is_gray_code(*code)));
+
+        base_colors
+            .into_iter()
+            .chain(extended_colors)
+            .filter(|(_, _, is_gray)| !only_grays || *is_gray)
+            .min_by(|(_, lab1, _), (_, lab2, _)| {
+                ciede2000(&self_lab, lab1)
+                    .partial_cmp(&ciede2000(&self_lab, lab2))
+                    .expect("distances are not NaN")
+            })
             .expect("list of codes can not be empty")
             .0
     }
@@ -125,8 +217,14 @@ pub struct Style {
     foreground: Option<Color>,
     background: Option<Color>,
     bold: bool,
+    dim: bool,
     italic: bool,
     underline: bool,
+    blink: bool,
+    reverse: bool,
+    hidden: bool,
+    strikethrough: bool,
+    hyperlink: Option<String>,
 }
 
 impl Style {
@@ -145,6 +243,12 @@ impl Style {
         self
     }
 
+    /// Decreased intensity (also called 'faint')
+    pub fn dim(&mut self, on: bool) -> &mut Self {
+        self.dim = on;
+        self
+    }
+
     pub fn italic(&mut self, on: bool) -> &mut Self {
         self.italic = on;
         self
@@ -155,12 +259,46 @@ impl Style {
         self
     }
 
+    pub fn blink(&mut self, on: bool) -> &mut Self {
+        self.blink = on;
+        self
+    }
+
+    /// Swap the foreground and background colors
+    pub fn reverse(&mut self, on: bool) -> &mut Self {
+        self.reverse = on;
+        self
+    }
+
+    /// Hide the text (it can still be selected and copied)
+    pub fn hidden(&mut self, on: bool) -> &mut Self {
+        self.hidden = on;
+        self
+    }
+
+    pub fn strikethrough(&mut self, on: bool) -> &mut Self {
+        self.strikethrough = on;
+        self
+    }
+
+    /// Turn the text into a hyperlink to the given URL (via an OSC 8 escape sequence, which is
+    /// ignored by terminals that do not support hyperlinks).
+    pub fn hyperlink(&mut self, url: &str) -> &mut Self {
+        self.hyperlink = Some(url.into());
+        self
+    }
+
     pub fn escape_sequence(&self, mode: Mode) -> String {
+        self.escape_sequence_with(mode, Ansi8BitOptions::default())
+    }
+
+    /// Like `escape_sequence`, but with custom options for the approximation of 8-bit colors.
+    pub fn escape_sequence_with(&self, mode: Mode, options: Ansi8BitOptions) -> String {
         let mut codes: Vec<u8> = vec![];
 
         if let Some(ref fg) = self.foreground {
             match mode {
-                Mode::Ansi8Bit => codes.extend_from_slice(&[38, 5, fg.to_ansi_8bit()]),
+                Mode::Ansi8Bit => codes.extend_from_slice(&[38, 5, fg.to_ansi_8bit_with(options)]),
                 Mode::TrueColor => {
                     let rgb = fg.to_rgba();
                     codes.extend_from_slice(&[38, 2, rgb.r, rgb.g, rgb.b]);
@@ -169,7 +307,7 @@ impl Style {
         }
         if let Some(ref bg) = self.background {
             match mode {
-                Mode::Ansi8Bit => codes.extend_from_slice(&[48, 5, bg.to_ansi_8bit()]),
+                Mode::Ansi8Bit => codes.extend_from_slice(&[48, 5, bg.to_ansi_8bit_with(options)]),
                 Mode::TrueColor => {
                     let rgb = bg.to_rgba();
                     codes.extend_from_slice(&[48, 2, rgb.r, rgb.g, rgb.b]);
@@ -177,17 +315,22 @@ impl Style {
             }
         }
 
-        if self.bold {
-            codes.push(1);
-        }
-
-        if self.italic {
-            codes.push(3);
-        }
-
-        if self.underline {
-            codes.push(4);
-        }
+        let attributes = [
+            (self.bold, 1),
+            (self.dim, 2),
+            (self.italic, 3),
+            (self.underline, 4),
+            (self.blink, 5),
+            (self.reverse, 7),
+            (self.hidden, 8),
+            (self.strikethrough, 9),
+        ];
+        codes.extend(
+            attributes
+                .iter()
+                .filter(|(on, _)| *on)
+                .map(|(_, code)| code),
+        );
 
         if codes.is_empty() {
             codes.push(0);
@@ -208,10 +351,7 @@ impl From<Color> for Style {
     fn from(color: Color) -> Style {
         Style {
             foreground: Some(color),
-            background: None,
-            bold: false,
-            italic: false,
-            underline: false,
+            ..Style::default()
         }
     }
 }
@@ -234,6 +374,76 @@ impl From<&mut Style> for Style {
     }
 }
 
+impl FromStr for Style {
+    type Err = Error;
+
+    /// Parse a human-readable style specification like `bold red on #222`. A specification
+    /// consists of (at most) one foreground color (or `default`), a background color preceded
+    /// by `on`, and any of the attributes `bold`, `dim` (or `faint`), `italic`, `underline`,
+    /// `blink`, `reverse`, `hidden` and `strikethrough`, in arbitrary order.
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut style = Style::default();
+        let mut has_foreground = false;
+
+        let color =
+            |token: &str| parse_color(token).ok_or_else(|| Error::InvalidColor(token.into()));
+
+        let tokens = split_color_list(spec);
+        if tokens.is_empty() {
+            return Err(Error::InvalidStyle("empty style specification".into()));
+        }
+
+        let mut tokens = tokens.into_iter();
+        while let Some(token) = tokens.next() {
+            match token.to_lowercase().as_ref() {
+                "bold" => {
+                    style.bold(true);
+                }
+                "italic" => {
+                    style.italic(true);
+                }
+                "underline" => {
+                    style.underline(true);
+                }
+                "dim" | "faint" => {
+                    style.dim(true);
+                }
+                "blink" => {
+                    style.blink(true);
+                }
+                "reverse" => {
+                    style.reverse(true);
+                }
+                "hidden" => {
+                    style.hidden(true);
+                }
+                "strikethrough" => {
+                    style.strikethrough(true);
+                }
+                "on" => {
+                    let bg = tokens.next().ok_or_else(|| {
+                        Error::InvalidStyle("missing background color after 'on'".into())
+                    })?;
+                    style.on(color(bg)?);
+                }
+                _ if has_foreground => {
+                    return Err(Error::InvalidStyle(format!(
+                        "unexpected '{}' (only one foreground color can be given)",
+                        token
+                    )));
+                }
+                "default" => has_foreground = true,
+                _ => {
+                    style.foreground(&color(token)?);
+                    has_foreground = true;
+                }
+            }
+        }
+
+        Ok(style)
+    }
+}
+
 pub trait ToAnsiStyle {
     fn ansi_style(&self) -> Style;
 }
@@ -270,17 +480,274 @@ pub fn get_colormode() -> Option<Mode> {
     }
 }
 
+/// The level of color support of a terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSupport {
+    /// No colors at all (or output is not a terminal)
+    None,
+    /// The 16 base colors
+    Ansi16,
+    /// The 8-bit palette with 256 colors
+    Ansi256,
+    /// 24-bit colors
+    TrueColor,
+}
+
+/// Capabilities of a terminal, as returned by `detect_capabilities` and `probe_capabilities`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capabilities {
+    pub color: ColorSupport,
+    /// Support for OSC 8 hyperlinks
+    pub hyperlinks: bool,
+}
+
+impl Capabilities {
+    /// The color mode that should be used to paint text for this terminal (pastel does not have
+    /// a dedicated mode for terminals with 16 colors).
+    pub fn mode(&self) -> Option<Mode> {
+        match self.color {
+            ColorSupport::None => None,
+            ColorSupport::Ansi16 | ColorSupport::Ansi256 => Some(Mode::Ansi8Bit),
+            ColorSupport::TrueColor => Some(Mode::TrueColor),
+        }
+    }
+}
+
+fn capabilities_from_env(var: impl Fn(&str) -> Option<String>) -> Capabilities {
+    let term = var("TERM").unwrap_or_default();
+    let term_program = var("TERM_PROGRAM").unwrap_or_default();
+
+    let color = if var("NO_COLOR").is_some() || term == "dumb" {
+        ColorSupport::None
+    } else if cfg!(windows)
+        || matches!(
+            var("COLORTERM").as_deref(),
+            Some("truecolor") | Some("24bit")
+        )
+    {
+        ColorSupport::TrueColor
+    } else if term.contains("256color") || term.is_empty() {
+        ColorSupport::Ansi256
+    } else {
+        ColorSupport::Ansi16
+    };
+
+    let vte_version = var("VTE_VERSION")
+        .and_then(|v| v.parse::<u32>().ok())
+        .unwrap_or(0);
+
+    let hyperlinks = color != ColorSupport::None
+        && (matches!(
+            term_program.as_ref(),
+            "iTerm.app" | "WezTerm" | "vscode" | "Hyper" | "ghostty"
+        ) || ["kitty", "foot", "alacritty", "wezterm", "ghostty"]
+            .iter()
+            .any(|name| term.contains(name))
+            || var("WT_SESSION").is_some()
+            || var("KONSOLE_VERSION").is_some()
+            || vte_version >= 5000);
+
+    Capabilities { color, hyperlinks }
+}
+
+/// Detect the capabilities of the terminal that is connected to the given stream, based on
+/// common environment variables (`NO_COLOR`, `TERM`, `COLORTERM`, `TERM_PROGRAM`, …). Note that
+/// this does not take `PASTEL_COLOR_MODE` into account. See `probe_capabilities` for a variant
+/// that also queries the terminal itself.
+pub fn detect_capabilities(stream: Stream) -> Capabilities {
+    if !atty::is(stream) {
+        return Capabilities {
+            color: ColorSupport::None,
+            hyperlinks: false,
+        };
+    }
+
+    capabilities_from_env(|name| std::env::var(name).ok())
+}
+
+/// Like `detect_capabilities`, but additionally ask the terminal whether it supports 24-bit
+/// colors (see `probe_truecolor`). A definite answer overrides the guess that is based on
+/// `COLORTERM`. If the terminal does not answer within the given timeout, the result is the same
+/// as for `detect_capabilities`.
+#[cfg(unix)]
+pub fn probe_capabilities(stream: Stream, timeout: Duration) -> Capabilities {
+    let mut capabilities = detect_capabilities(stream);
+    if capabilities.color != ColorSupport::None {
+        match probe_truecolor(timeout) {
+            Some(true) => capabilities.color = ColorSupport::TrueColor,
+            Some(false) if capabilities.color == ColorSupport::TrueColor => {
+                capabilities.color = ColorSupport::Ansi256
+            }
+            _ => {}
+        }
+    }
+    capabilities
+}
+
+/// Interpret the terminal's answer to a DECRQSS query for the current SGR attributes, after the
+/// background color has been set to `rgb(1, 2, 3)`. Returns `Some(true)` if the terminal reports
+/// the exact 24-bit color, `Some(false)` if it reports a different (e.g. downsampled) color and
+/// `None` if the answer is not a valid DECRQSS response.
+#[cfg(unix)]
+fn parse_truecolor_probe_response(response: &str) -> Option<bool> {
+    let start = response.find("\x1bP1$r")?;
+    let sgr = &response[start + 5..];
+    let sgr = &sgr[..sgr.find('m')?];
+
+    Some(
+        ["48:2::1:2:3", "48:2:1:2:3", "48;2;1;2;3"]
+            .iter()
+            .any(|pattern| sgr.contains(pattern)),
+    )
+}
+
+/// Interpret the terminal's answer to an OSC 11 query for the background color, e.g.
+/// `ESC ] 11 ; rgb:1e1e/1e1e/2e2e ESC \`. Each channel has one to four hex digits.
+#[cfg(unix)]
+fn parse_background_response(response: &str) -> Option<Color> {
+    let start = response.find("\x1b]11;rgb:")?;
+    let spec = &response[start + 9..];
+    let end = spec.find(['\x1b', '\x07'])?;
+
+    let channels: Vec<u8> = spec[..end]
+        .split('/')
+        .map(|channel| {
+            if channel.is_empty() || channel.len() > 4 {
+                return None;
+            }
+            let value = u32::from_str_radix(channel, 16).ok()?;
+            let max = (1u32 << (4 * channel.len())) - 1;
+            Some((f64::from(value) / f64::from(max) * 255.0).round() as u8)
+        })
+        .collect::<Option<_>>()?;
+
+    match channels[..] {
+        [r, g, b] => Some(Color::from_rgb(r, g, b)),
+        _ => None,
+    }
+}
+
+/// Send a query to the terminal and collect its answer until `is_complete` returns true or the
+/// timeout is reached. Returns `None` if there is no terminal.
+#[cfg(unix)]
+fn query_terminal(
+    query: &[u8],
+    is_complete: impl Fn(&[u8]) -> bool,
+    timeout: Duration,
+) -> Option<Vec<u8>> {
+    use std::fs::OpenOptions;
+    use std::io::{Read, Write};
+    use std::os::unix::io::AsRawFd;
+    use std::time::Instant;
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let fd = tty.as_raw_fd();
+
+    // Switch to non-canonical mode without echo, such that the answer can be read directly
+    let mut termios = std::mem::MaybeUninit::<libc::termios>::uninit();
+    if unsafe { libc::tcgetattr(fd, termios.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let original = unsafe { termios.assume_init() };
+    let mut raw = original;
+    raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+        return None;
+    }
+
+    let mut response = vec![];
+    if tty.write_all(query).and_then(|_| tty.flush()).is_ok() {
+        let deadline = Instant::now() + timeout;
+        let mut buffer = [0; 64];
+        while !is_complete(&response) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let mut pollfd = libc::pollfd {
+                fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let ready = unsafe { libc::poll(&mut pollfd, 1, remaining.as_millis() as libc::c_int) };
+            if ready <= 0 {
+                break;
+            }
+            match tty.read(&mut buffer) {
+                Ok(n) if n > 0 => response.extend_from_slice(&buffer[..n]),
+                _ => break,
+            }
+        }
+    }
+
+    unsafe { libc::tcsetattr(fd, libc::TCSANOW, &original) };
+
+    Some(response)
+}
+
+/// Check whether the terminal supports 24-bit colors by actually setting a 24-bit background
+/// color and querying the terminal for the resulting attributes (via a DECRQSS request).
+/// Returns `None` if the terminal does not answer within the given timeout (many terminals do
+/// not support this kind of query) or if there is no terminal.
+#[cfg(unix)]
+pub fn probe_truecolor(timeout: Duration) -> Option<bool> {
+    let response = query_terminal(
+        b"\x1b[48;2;1;2;3m\x1bP$qm\x1b\\\x1b[0m",
+        |response| response.ends_with(b"\x1b\\"),
+        timeout,
+    )?;
+
+    parse_truecolor_probe_response(&String::from_utf8_lossy(&response))
+}
+
+/// Query the background color of the terminal (via an OSC 11 request). The query is followed by
+/// a request for the primary device attributes (DA1), which practically all terminals answer.
+/// Terminals that do not support OSC 11 therefore do not have to run into the timeout. Returns
+/// `None` if the background color could not be determined.
+#[cfg(unix)]
+pub fn query_background_color(timeout: Duration) -> Option<Color> {
+    let response = query_terminal(
+        b"\x1b]11;?\x1b\\\x1b[c",
+        |response| {
+            let response = String::from_utf8_lossy(response);
+            response
+                .find("\x1b[?")
+                .is_some_and(|start| response[start..].contains('c'))
+        },
+        timeout,
+    )?;
+
+    parse_background_response(&String::from_utf8_lossy(&response))
+}
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct Brush {
     mode: Option<Mode>,
+    ansi_8bit_options: Ansi8BitOptions,
 }
 
 impl Brush {
     pub fn from_mode(mode: Option<Mode>) -> Self {
-        Brush { mode }
+        Brush {
+            mode,
+            ansi_8bit_options: Ansi8BitOptions::default(),
+        }
+    }
+
+    /// Use custom options to approximate colors in 8-bit mode.
+    pub fn with_ansi_8bit_options(self, ansi_8bit_options: Ansi8BitOptions) -> Self {
+        Brush {
+            ansi_8bit_options,
+            ..self
+        }
+    }
+
+    pub fn ansi_8bit_options(&self) -> Ansi8BitOptions {
+        self.ansi_8bit_options
     }
 
-    pub fn from_environment(stream: Stream) -> Result<Self, UnknownColorModeError> {
+    pub fn from_environment(stream: Stream) -> Result<Self, Error> {
         let mode = if atty::is(stream) {
             let env_color_mode = std::env::var("PASTEL_COLOR_MODE").ok();
             match env_color_mode.as_deref() {
@@ -290,7 +757,19 @@ impl Brush {
         } else {
             None
         };
-        Ok(Brush { mode })
+        Ok(Brush::from_mode(mode))
+    }
+
+    /// Wrap the text in an OSC 8 hyperlink to the given URL (if colors are enabled).
+    pub fn hyperlink<S>(self, text: S, url: &str) -> String
+    where
+        S: AsRef<str>,
+    {
+        if self.mode.is_some() {
+            format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text.as_ref())
+        } else {
+            text.as_ref().into()
+        }
     }
 
     pub fn paint<S>(self, text: S, style: impl Into<Style>) -> String
@@ -298,18 +777,135 @@ impl Brush {
         S: AsRef<str>,
     {
         if let Some(ansi_mode) = self.mode {
-            format!(
+            let style = style.into();
+            let painted = format!(
                 "{begin}{text}{end}",
-                begin = style.into().escape_sequence(ansi_mode),
+                begin = style.escape_sequence_with(ansi_mode, self.ansi_8bit_options),
                 text = text.as_ref(),
                 end = "\x1b[0m"
-            )
+            );
+            match style.hyperlink {
+                Some(ref url) => self.hyperlink(painted, url),
+                None => painted,
+            }
         } else {
             text.as_ref().into()
         }
     }
 }
 
+/// A dynamic color of the terminal that can be changed at runtime with an OSC escape sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalColor {
+    /// One of the 256 palette colors (OSC 4)
+    Palette(u8),
+    /// The default foreground color (OSC 10)
+    Foreground,
+    /// The default background color (OSC 11)
+    Background,
+    /// The cursor color (OSC 12)
+    Cursor,
+}
+
+impl TerminalColor {
+    /// Look up a terminal color by its name: `foreground`, `background`, `cursor`, one of the
+    /// names of the 16 ANSI colors (e.g. `bright-red`, see [`crate::theme::ANSI_NAMES`]) or
+    /// `color<N>` for the palette color with index N.
+    pub fn from_name(name: &str) -> Option<TerminalColor> {
+        match name {
+            "foreground" => Some(TerminalColor::Foreground),
+            "background" => Some(TerminalColor::Background),
+            "cursor" => Some(TerminalColor::Cursor),
+            _ => crate::theme::ANSI_NAMES
+                .iter()
+                .position(|&n| n == name)
+                .map(|i| i as u8)
+                .or_else(|| name.strip_prefix("color")?.parse().ok())
+                .map(TerminalColor::Palette),
+        }
+    }
+
+    /// The escape sequence that sets this terminal color.
+    pub fn set_sequence(self, color: &Color) -> String {
+        let rgba = color.to_rgba();
+        let spec = format!("rgb:{:02x}/{:02x}/{:02x}", rgba.r, rgba.g, rgba.b);
+        match self {
+            TerminalColor::Palette(index) => format!("\x1b]4;{};{}\x1b\\", index, spec),
+            TerminalColor::Foreground => format!("\x1b]10;{}\x1b\\", spec),
+            TerminalColor::Background => format!("\x1b]11;{}\x1b\\", spec),
+            TerminalColor::Cursor => format!("\x1b]12;{}\x1b\\", spec),
+        }
+    }
+
+    /// The escape sequence that resets this terminal color to its configured default.
+    pub fn reset_sequence(self) -> String {
+        match self {
+            TerminalColor::Palette(index) => format!("\x1b]104;{}\x1b\\", index),
+            TerminalColor::Foreground => "\x1b]110\x1b\\".into(),
+            TerminalColor::Background => "\x1b]111\x1b\\".into(),
+            TerminalColor::Cursor => "\x1b]112\x1b\\".into(),
+        }
+    }
+}
+
+/// The escape sequence that resets all palette colors along with the foreground, background and
+/// cursor colors of the terminal to their configured defaults.
+pub fn reset_terminal_colors() -> String {
+    let mut result = String::from("\x1b]104\x1b\\");
+    for color in [
+        TerminalColor::Foreground,
+        TerminalColor::Background,
+        TerminalColor::Cursor,
+    ] {
+        result.push_str(&color.reset_sequence());
+    }
+    result
+}
+
+/// Remove all styling from the text: SGR escape sequences (colors and attributes like
+/// `ESC [ 1 ; 31 m`) as well as OSC 8 hyperlinks (the link text is kept). Other escape sequences,
+/// e.g. for cursor movement, are left untouched.
+pub fn strip_styles(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('\x1b') {
+        result.push_str(&rest[..start]);
+        let sequence = &rest[start..];
+
+        let length = if let Some(params) = sequence.strip_prefix("\x1b[") {
+            // SGR: parameters (digits, separated by ';' or ':') followed by 'm'
+            let end = params
+                .find(|c: char| !(c.is_ascii_digit() || c == ';' || c == ':'))
+                .unwrap_or(params.len());
+            params[end..].starts_with('m').then_some(2 + end + 1)
+        } else if let Some(link) = sequence.strip_prefix("\x1b]8;") {
+            // OSC 8, terminated by ST or BEL
+            link.find(['\x1b', '\x07']).map(|end| {
+                let terminator = if link[end..].starts_with("\x1b\\") {
+                    2
+                } else {
+                    1
+                };
+                4 + end + terminator
+            })
+        } else {
+            None
+        };
+
+        match length {
+            Some(length) => rest = &sequence[length..],
+            None => {
+                result.push('\x1b');
+                rest = &sequence[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,6 +963,71 @@ mod tests {
         assert_eq!(242, Color::from_rgb(108, 108, 108).to_ansi_8bit());
     }
 
+    #[test]
+    fn to_ansi_8bit_includes_last_gray() {
+        assert_eq!(255, Color::from_rgb(238, 238, 238).to_ansi_8bit());
+    }
+
+    #[test]
+    fn to_ansi_8bit_with_options() {
+        let base_colors = Ansi8BitOptions {
+            include_base_colors: true,
+            ..Ansi8BitOptions::default()
+        };
+        assert_eq!(9, Color::red().to_ansi_8bit_with(base_colors));
+        assert_eq!(196, Color::red().to_ansi_8bit());
+
+        let grays = Ansi8BitOptions {
+            prefer_grays: true,
+            ..Ansi8BitOptions::default()
+        };
+        let slate = Color::from_rgb(99, 117, 114);
+        assert_eq!(66, slate.to_ansi_8bit());
+        assert!(is_gray_code(slate.to_ansi_8bit_with(grays)));
+
+        // Saturated colors are not affected
+        assert_eq!(196, Color::red().to_ansi_8bit_with(grays));
+    }
+
+    #[test]
+    fn to_ansi_8bit_with_base_palette() {
+        let mut palette = [[0; 3]; 16];
+        for (code, entry) in palette.iter_mut().enumerate() {
+            let rgba = Color::from_ansi_8bit(code as u8).to_rgba();
+            *entry = [rgba.r, rgba.g, rgba.b];
+        }
+        // A terminal color scheme with a dark orange instead of maroon
+        palette[1] = [204, 85, 0];
+
+        let options = Ansi8BitOptions {
+            base_palette: Some(palette),
+            ..Ansi8BitOptions::default()
+        };
+        assert_eq!(1, Color::from_rgb(204, 85, 0).to_ansi_8bit_with(options));
+        assert_ne!(1, Color::maroon().to_ansi_8bit_with(options));
+    }
+
+    #[test]
+    fn parse_base_palette_formats() {
+        let palette = parse_base_palette(
+            "#000000:#cd0000:#00cd00:#cdcd00:#0000ee:#cd00cd:#00cdcd:#e5e5e5:\
+             #7f7f7f:#ff0000:#00ff00:#ffff00:#5c5cff:#ff00ff:#00ffff:#ffffff",
+        )
+        .unwrap();
+        assert_eq!([205, 0, 0], palette[1]);
+        assert_eq!([255, 255, 255], palette[15]);
+
+        assert_eq!(
+            Err(Error::InvalidPalette("expected 16 colors, got 2".into())),
+            parse_base_palette("black white")
+        );
+        assert_eq!(
+            Err(Error::InvalidColor("nocolor".into())),
+            parse_base_palette(&"black ".repeat(16).replacen("black", "nocolor", 1))
+        );
+        assert!(parse_base_palette(&"black ".repeat(16)).is_ok());
+    }
+
     #[test]
     fn ansi_style() {
         assert_eq!("\x1b[0m", Style::default().escape_sequence(Mode::TrueColor));
@@ -391,6 +1052,117 @@ mod tests {
                 .underline(true)
                 .escape_sequence(Mode::TrueColor)
         );
+
+        assert_eq!(
+            "\x1b[2;5;7;8;9m",
+            Style::default()
+                .strikethrough(true)
+                .hidden(true)
+                .reverse(true)
+                .blink(true)
+                .dim(true)
+                .escape_sequence(Mode::TrueColor)
+        );
+    }
+
+    #[test]
+    fn parse_style() {
+        let mut expected = Color::red().ansi_style();
+        expected.on(Color::from_rgb(0x22, 0x22, 0x22)).bold(true);
+        assert_eq!(Ok(expected.clone()), "bold red on #222".parse());
+        assert_eq!(Ok(expected), "red  on #222 BOLD".parse());
+
+        let mut expected = Style::default();
+        expected.on(Color::red()).italic(true).underline(true);
+        assert_eq!(
+            Ok(expected),
+            "default on rgb(255, 0, 0) italic underline".parse()
+        );
+
+        let mut expected = Color::blue().ansi_style();
+        expected.dim(true).reverse(true).strikethrough(true);
+        assert_eq!(Ok(expected), "faint blue strikethrough reverse".parse());
+
+        assert!("".parse::<Style>().is_err());
+        assert!("red blue".parse::<Style>().is_err());
+        assert!("red on".parse::<Style>().is_err());
+        assert_eq!(
+            Err(Error::InvalidColor("nocolor".into())),
+            "nocolor".parse::<Style>()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn truecolor_probe_response() {
+        assert_eq!(
+            Some(true),
+            parse_truecolor_probe_response("\x1bP1$r0;48:2::1:2:3m\x1b\\")
+        );
+        assert_eq!(
+            Some(true),
+            parse_truecolor_probe_response("\x1bP1$r48;2;1;2;3m\x1b\\")
+        );
+        assert_eq!(
+            Some(false),
+            parse_truecolor_probe_response("\x1bP1$r0;48;5;16m\x1b\\")
+        );
+        assert_eq!(None, parse_truecolor_probe_response("\x1bP0$r\x1b\\"));
+        assert_eq!(None, parse_truecolor_probe_response(""));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn background_response() {
+        assert_eq!(
+            Some(Color::from_rgb(0x1e, 0x1e, 0x2e)),
+            parse_background_response("\x1b]11;rgb:1e1e/1e1e/2e2e\x1b\\\x1b[?62;22c")
+        );
+        assert_eq!(
+            Some(Color::from_rgb(255, 0, 136)),
+            parse_background_response("\x1b]11;rgb:ff/0/88\x07")
+        );
+        assert_eq!(None, parse_background_response("\x1b[?62;22c"));
+        assert_eq!(None, parse_background_response("\x1b]11;rgb:ff/00\x1b\\"));
+    }
+
+    #[test]
+    fn capabilities_from_environment() {
+        let detect = |vars: &[(&str, &str)]| {
+            let vars: Vec<(String, String)> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            capabilities_from_env(|name| {
+                vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone())
+            })
+        };
+
+        let caps = detect(&[("TERM", "xterm-kitty"), ("COLORTERM", "truecolor")]);
+        assert_eq!(ColorSupport::TrueColor, caps.color);
+        assert!(caps.hyperlinks);
+        assert_eq!(Some(Mode::TrueColor), caps.mode());
+
+        let caps = detect(&[("TERM", "dumb"), ("COLORTERM", "truecolor")]);
+        assert_eq!(ColorSupport::None, caps.color);
+        assert!(!caps.hyperlinks);
+        assert_eq!(None, caps.mode());
+
+        let caps = detect(&[("TERM", "xterm-kitty"), ("NO_COLOR", "1")]);
+        assert_eq!(ColorSupport::None, caps.color);
+
+        if !cfg!(windows) {
+            let caps = detect(&[("TERM", "screen-256color")]);
+            assert_eq!(ColorSupport::Ansi256, caps.color);
+            assert!(!caps.hyperlinks);
+
+            let caps = detect(&[("TERM", "linux")]);
+            assert_eq!(ColorSupport::Ansi16, caps.color);
+            assert_eq!(Some(Mode::Ansi8Bit), caps.mode());
+
+            let caps = detect(&[("TERM", "xterm-256color"), ("VTE_VERSION", "6003")]);
+            assert!(caps.hyperlinks);
+        }
     }
 
     #[test]
@@ -402,4 +1174,72 @@ mod tests {
             ansi.paint("hello", Color::red().ansi_style().bold(true))
         );
     }
+
+    #[test]
+    fn brush_hyperlinks() {
+        let ansi = Brush::from_mode(Some(Mode::TrueColor));
+        assert_eq!(
+            "\x1b]8;;https://example.com\x1b\\\x1b[38;2;255;0;0mhello\x1b[0m\x1b]8;;\x1b\\",
+            ansi.paint(
+                "hello",
+                Color::red().ansi_style().hyperlink("https://example.com")
+            )
+        );
+
+        let plain = Brush::from_mode(None);
+        assert_eq!("hello", plain.hyperlink("hello", "https://example.com"));
+    }
+
+    #[test]
+    fn terminal_colors() {
+        assert_eq!(
+            Some(TerminalColor::Palette(9)),
+            TerminalColor::from_name("bright-red")
+        );
+        assert_eq!(
+            Some(TerminalColor::Palette(200)),
+            TerminalColor::from_name("color200")
+        );
+        assert_eq!(
+            Some(TerminalColor::Cursor),
+            TerminalColor::from_name("cursor")
+        );
+        assert_eq!(None, TerminalColor::from_name("color256"));
+        assert_eq!(None, TerminalColor::from_name("primary"));
+
+        let orange = Color::from_rgb(255, 128, 0);
+        assert_eq!(
+            "\x1b]4;3;rgb:ff/80/00\x1b\\",
+            TerminalColor::Palette(3).set_sequence(&orange)
+        );
+        assert_eq!(
+            "\x1b]11;rgb:ff/80/00\x1b\\",
+            TerminalColor::Background.set_sequence(&orange)
+        );
+        assert_eq!(
+            "\x1b]104\x1b\\\x1b]110\x1b\\\x1b]111\x1b\\\x1b]112\x1b\\",
+            reset_terminal_colors()
+        );
+    }
+
+    #[test]
+    fn strip_styles_removes_sgr_and_hyperlinks() {
+        let mut style = Color::red().ansi_style();
+        style.on(Color::blue()).bold(true);
+        let painted = format!(
+            "a {}b{} c",
+            style.escape_sequence(Mode::TrueColor),
+            "\x1b[0m"
+        );
+        assert_eq!("a b c", strip_styles(&painted));
+
+        assert_eq!(
+            "link",
+            strip_styles("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\")
+        );
+        assert_eq!("x", strip_styles("\x1b[38:2::1:2:3mx\x1b[m"));
+
+        // Other escape sequences are kept
+        assert_eq!("\x1b[2Aup\x1b", strip_styles("\x1b[2Aup\x1b"));
+    }
 }
@@ -0,0 +1,137 @@
+use crate::types::Scalar;
+use crate::Color;
+
+/// A potential source of visible banding between two adjacent colors of a gradient.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BandingIssue {
+    /// The (CIEDE2000) color difference between the colors at `index` and `index + 1` exceeds the
+    /// threshold, so the edge between the two steps is likely visible.
+    VisibleStep { index: usize, delta_e: Scalar },
+    /// The colors at `index` and `index + 1` are identical after quantization to 8 bits per
+    /// channel, which results in plateaus (wider bands) within the gradient.
+    Plateau { index: usize },
+}
+
+/// Find the adjacent steps of a gradient that are likely to cause visible banding: steps with a
+/// CIEDE2000 color difference above `threshold` and steps that collapse to the same 8-bit color.
+pub fn detect_banding(colors: &[Color], threshold: Scalar) -> Vec<BandingIssue> {
+    colors
+        .windows(2)
+        .enumerate()
+        .filter_map(|(index, pair)| {
+            let delta_e = pair[0].distance_delta_e_ciede2000(&pair[1]);
+            if pair[0] == pair[1] {
+                Some(BandingIssue::Plateau { index })
+            } else if delta_e > threshold {
+                Some(BandingIssue::VisibleStep { index, delta_e })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The threshold pattern that decides between the two closest quantization levels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DitherPattern {
+    /// Thresholds from a (one-dimensional) Bayer matrix of size 8
+    Ordered,
+    /// Thresholds from the golden ratio sequence, which has blue-noise-like properties (no
+    /// low-frequency patterns)
+    BlueNoise,
+}
+
+impl DitherPattern {
+    fn threshold(self, index: usize) -> Scalar {
+        match self {
+            DitherPattern::Ordered => {
+                const BAYER: [Scalar; 8] = [0.0, 4.0, 2.0, 6.0, 1.0, 5.0, 3.0, 7.0];
+                (BAYER[index % 8] + 0.5) / 8.0
+            }
+            DitherPattern::BlueNoise => {
+                let golden_ratio_conjugate = (Scalar::sqrt(5.0) - 1.0) / 2.0;
+                (0.5 + index as Scalar * golden_ratio_conjugate).fract()
+            }
+        }
+    }
+}
+
+/// Quantize a sequence of colors to `bits` bits per (gamma-encoded) RGB channel. For each color
+/// and channel, one of the two closest quantization levels is chosen according to the given
+/// pattern, such that the average over neighboring colors approximates the original value.
+/// This avoids visible bands in large gradient fills on low-bit displays.
+pub fn dither(colors: &[Color], bits: u32, pattern: DitherPattern) -> Vec<Color> {
+    assert!((1..=8).contains(&bits));
+
+    let levels = ((1u32 << bits) - 1) as Scalar;
+
+    colors
+        .iter()
+        .enumerate()
+        .map(|(index, color)| {
+            let threshold = pattern.threshold(index);
+            let quantize = |value: Scalar| {
+                let scaled = value.clamp(0.0, 1.0) * levels;
+                let lower = scaled.floor();
+                let level = if scaled - lower > threshold {
+                    lower + 1.0
+                } else {
+                    lower
+                };
+                level / levels
+            };
+
+            let rgba = color.to_rgba_float();
+            Color::from_rgba_float(
+                quantize(rgba.r),
+                quantize(rgba.g),
+                quantize(rgba.b),
+                rgba.alpha,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_banding_steps_and_plateaus() {
+        let colors = [
+            Color::black(),
+            Color::black(),
+            Color::graytone(0.01),
+            Color::white(),
+        ];
+
+        assert_eq!(
+            detect_banding(&colors, 2.0)
+                .iter()
+                .map(|issue| match issue {
+                    BandingIssue::Plateau { index } => (*index, "plateau"),
+                    BandingIssue::VisibleStep { index, .. } => (*index, "step"),
+                })
+                .collect::<Vec<_>>(),
+            vec![(0, "plateau"), (2, "step")]
+        );
+    }
+
+    #[test]
+    fn dither_preserves_average() {
+        // A gray value between two quantization levels at 2 bits (0.0, 1/3, 2/3, 1.0)
+        let colors = vec![Color::from_rgb_float(0.25, 0.25, 0.25); 64];
+
+        for pattern in [DitherPattern::Ordered, DitherPattern::BlueNoise] {
+            let dithered = dither(&colors, 2, pattern);
+
+            let mean = dithered.iter().map(|c| c.to_rgba_float().r).sum::<Scalar>() / 64.0;
+            assert!((mean - 0.25).abs() < 0.02);
+
+            for color in &dithered {
+                let r = color.to_rgba_float().r;
+                assert!(r.abs() < 1e-6 || (r - 1.0 / 3.0).abs() < 1e-2);
+            }
+        }
+    }
+}
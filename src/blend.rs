@@ -0,0 +1,203 @@
+//! Standard blend modes, as defined in the W3C "Compositing and Blending" specification.
+//!
+//! See: <https://www.w3.org/TR/compositing-1/#blending>
+
+use crate::types::Scalar;
+
+/// A blend mode, describing how the color of a source layer is combined with the color of
+/// the backdrop (see `Color::blend`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+impl BlendMode {
+    /// All blend modes along with their (CSS) names.
+    pub const ALL: &'static [(&'static str, BlendMode)] = &[
+        ("normal", BlendMode::Normal),
+        ("multiply", BlendMode::Multiply),
+        ("screen", BlendMode::Screen),
+        ("overlay", BlendMode::Overlay),
+        ("darken", BlendMode::Darken),
+        ("lighten", BlendMode::Lighten),
+        ("color-dodge", BlendMode::ColorDodge),
+        ("color-burn", BlendMode::ColorBurn),
+        ("hard-light", BlendMode::HardLight),
+        ("soft-light", BlendMode::SoftLight),
+        ("difference", BlendMode::Difference),
+        ("exclusion", BlendMode::Exclusion),
+        ("hue", BlendMode::Hue),
+        ("saturation", BlendMode::Saturation),
+        ("color", BlendMode::Color),
+        ("luminosity", BlendMode::Luminosity),
+    ];
+
+    /// Look up a blend mode by its (CSS) name, e.g. `color-dodge`.
+    pub fn from_name(name: &str) -> Option<BlendMode> {
+        BlendMode::ALL
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|&(_, mode)| mode)
+    }
+
+    /// Blend function for a single channel (only for the separable blend modes).
+    fn blend_channel(self, cb: Scalar, cs: Scalar) -> Scalar {
+        match self {
+            BlendMode::Multiply => cb * cs,
+            BlendMode::Screen => cb + cs - cb * cs,
+            BlendMode::Overlay => BlendMode::HardLight.blend_channel(cs, cb),
+            BlendMode::Darken => cb.min(cs),
+            BlendMode::Lighten => cb.max(cs),
+            BlendMode::ColorDodge => {
+                if cb == 0.0 {
+                    0.0
+                } else if cs == 1.0 {
+                    1.0
+                } else {
+                    (cb / (1.0 - cs)).min(1.0)
+                }
+            }
+            BlendMode::ColorBurn => {
+                if cb == 1.0 {
+                    1.0
+                } else if cs == 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - cb) / cs).min(1.0)
+                }
+            }
+            BlendMode::HardLight => {
+                if cs <= 0.5 {
+                    BlendMode::Multiply.blend_channel(cb, 2.0 * cs)
+                } else {
+                    BlendMode::Screen.blend_channel(cb, 2.0 * cs - 1.0)
+                }
+            }
+            BlendMode::SoftLight => {
+                if cs <= 0.5 {
+                    cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb)
+                } else {
+                    let d = if cb <= 0.25 {
+                        ((16.0 * cb - 12.0) * cb + 4.0) * cb
+                    } else {
+                        cb.sqrt()
+                    };
+                    cb + (2.0 * cs - 1.0) * (d - cb)
+                }
+            }
+            BlendMode::Difference => (cb - cs).abs(),
+            BlendMode::Exclusion => cb + cs - 2.0 * cb * cs,
+            _ => cs,
+        }
+    }
+}
+
+type Rgb = [Scalar; 3];
+
+fn lum(c: Rgb) -> Scalar {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn clip_color(c: Rgb) -> Rgb {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    c.map(|v| {
+        let mut v = v;
+        if n < 0.0 {
+            v = l + (v - l) * l / (l - n);
+        }
+        if x > 1.0 {
+            v = l + (v - l) * (1.0 - l) / (x - l);
+        }
+        v
+    })
+}
+
+fn set_lum(c: Rgb, l: Scalar) -> Rgb {
+    let d = l - lum(c);
+    clip_color(c.map(|v| v + d))
+}
+
+fn sat(c: Rgb) -> Scalar {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: Rgb, s: Scalar) -> Rgb {
+    let max = c[0].max(c[1]).max(c[2]);
+    let min = c[0].min(c[1]).min(c[2]);
+    if max == min {
+        return [0.0; 3];
+    }
+    c.map(|v| (v - min) * s / (max - min))
+}
+
+/// Blend the (non-premultiplied, gamma-encoded) RGB values of the source with those of the
+/// backdrop.
+pub(crate) fn blend_rgb(mode: BlendMode, cb: Rgb, cs: Rgb) -> Rgb {
+    match mode {
+        BlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+        BlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+        BlendMode::Color => set_lum(cs, lum(cb)),
+        BlendMode::Luminosity => set_lum(cb, lum(cs)),
+        _ => [0, 1, 2].map(|i| mode.blend_channel(cb[i], cs[i])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn separable_modes() {
+        assert_eq!(0.25, BlendMode::Multiply.blend_channel(0.5, 0.5));
+        assert_eq!(0.75, BlendMode::Screen.blend_channel(0.5, 0.5));
+        assert_eq!(0.5, BlendMode::Difference.blend_channel(1.0, 0.5));
+        assert_eq!(1.0, BlendMode::ColorDodge.blend_channel(0.5, 1.0));
+        assert_eq!(0.0, BlendMode::ColorBurn.blend_channel(0.5, 0.0));
+    }
+
+    #[test]
+    fn non_separable_modes() {
+        let gray = [0.5, 0.5, 0.5];
+        let red = [1.0, 0.0, 0.0];
+
+        // A gray backdrop takes the luminosity of the red source, but stays neutral
+        for v in blend_rgb(BlendMode::Luminosity, gray, red) {
+            assert_relative_eq!(0.3, v, epsilon = 1e-9);
+        }
+        for v in blend_rgb(BlendMode::Saturation, gray, red) {
+            assert_relative_eq!(0.5, v, epsilon = 1e-9);
+        }
+
+        // The red source takes the luminosity of the gray backdrop
+        let color = blend_rgb(BlendMode::Color, gray, red);
+        assert_relative_eq!(0.5, lum(color), epsilon = 1e-9);
+        assert!(color[0] > color[1] && color[1] == color[2]);
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(
+            Some(BlendMode::SoftLight),
+            BlendMode::from_name("soft-light")
+        );
+        assert_eq!(None, BlendMode::from_name("unknown"));
+    }
+}
@@ -1,9 +1,11 @@
-use clap::{crate_description, crate_name, crate_version, AppSettings, Arg, Command};
+use clap::{crate_description, crate_name, crate_version, AppSettings, Arg, ArgGroup, Command};
 
 // Only include `colorpicker_tools` for normal builds (not when compiling `build.rs` where
 // the module machinery does not work)
 #[cfg(pastel_normal_build)]
 use crate::colorpicker_tools::COLOR_PICKER_TOOL_NAMES;
+#[cfg(pastel_normal_build)]
+use pastel::illuminant::ILLUMINANT_NAMES;
 
 const SORT_OPTIONS: &[&str] = &["brightness", "luminance", "hue", "chroma", "random"];
 const DEFAULT_SORT_ORDER: &str = "hue";
@@ -33,16 +35,57 @@ pub fn build_cli() -> Command<'static> {
         .required(false)
         .multiple_occurrences(true);
 
+    let input_format_arg = Arg::new("input-format")
+        .long("input-format")
+        .value_name("format")
+        .help(
+            "Read packed binary pixel data from standard input instead of color names or codes, \
+             e.g. the output of 'ffmpeg -f rawvideo -pix_fmt rgb24'. 'raw-rgb8' expects three \
+             bytes (red, green, blue) per pixel, 'raw-rgba8' expects four bytes (including the \
+             alpha channel).",
+        )
+        .possible_values(["raw-rgb8", "raw-rgba8"])
+        .conflicts_with("color");
+
     let colorspace_arg = Arg::new("colorspace")
         .long("colorspace")
         .short('s')
         .value_name("name")
         .help("The colorspace in which to interpolate")
-        .possible_values(["Lab", "LCh", "RGB", "HSL", "OkLab"])
+        .possible_values(["Lab", "LCh", "RGB", "HSL", "OkLab", "OkLCh"])
         .ignore_case(true)
         .default_value("Lab");
 
-    Command::new(crate_name!())
+    let hue_interpolation_arg = Arg::new("hue-interpolation")
+        .long("hue-interpolation")
+        .value_name("method")
+        .help(
+            "The direction around the hue circle for the cylindrical color spaces (HSL, LCh, \
+             OkLCh), as in CSS: the 'shorter' or 'longer' arc between the two hues, or always \
+             'increasing' or 'decreasing' hue angles. With 'longer', a gradient from red to red \
+             passes through all hues.",
+        )
+        .possible_values(["shorter", "longer", "increasing", "decreasing"])
+        .default_value("shorter");
+
+    let ramp_command = |name, about, long_about| {
+        Command::new(name)
+            .about(about)
+            .long_about(long_about)
+            .arg(
+                Arg::new("number")
+                    .long("number")
+                    .short('n')
+                    .help("Number of colors to generate")
+                    .takes_value(true)
+                    .default_value("10")
+                    .value_name("count"),
+            )
+            .arg(colorspace_arg.clone())
+            .arg(color_arg.clone())
+    };
+
+    let app = Command::new(crate_name!())
         .version(crate_version!())
         .about(crate_description!())
         .global_setting(AppSettings::DeriveDisplayOrder)
@@ -62,6 +105,7 @@ pub fn build_cli() -> Command<'static> {
                 .long_about("Show and display some information about the given color(s).\n\n\
                 Example:\n  \
                   pastel color 556270 4ecdc4 c7f484 ff6b6b c44d58")
+                .arg(input_format_arg.clone())
                 .arg(color_arg.clone()),
         )
         .subcommand(
@@ -110,16 +154,120 @@ pub fn build_cli() -> Command<'static> {
                         .value_name("count"),
                 ),
         )
+        .subcommand(
+            Command::new("generative")
+                .about("Generate an organic palette from a noise function")
+                .long_about("Generate a palette of related but varied colors by walking along a \
+                             low-frequency noise path through the OkLCh color space. Neighboring \
+                             colors are similar, while the palette as a whole slowly wanders \
+                             through lightness, chroma and hue. The same seed always results in \
+                             the same palette.\n\n\
+                             Example:\n  \
+                               pastel generative --algorithm simplex --seed 42 -n 16")
+                .arg(
+                    Arg::new("algorithm")
+                        .long("algorithm")
+                        .short('a')
+                        .help("The noise function")
+                        .takes_value(true)
+                        .possible_values(["simplex", "value"])
+                        .default_value("simplex")
+                        .value_name("name"),
+                )
+                .arg(
+                    Arg::new("number")
+                        .long("number")
+                        .short('n')
+                        .help("Number of colors to generate")
+                        .takes_value(true)
+                        .default_value("10")
+                        .value_name("count"),
+                )
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .help("Seed for the noise function (for reproducible output)")
+                        .takes_value(true)
+                        .value_name("number"),
+                )
+                .arg(
+                    Arg::new("step")
+                        .long("step")
+                        .help("Distance between two colors along the noise path (a positive \
+                               number). Smaller values lead to more closely related colors.")
+                        .takes_value(true)
+                        .default_value("0.1")
+                        .value_name("distance"),
+                ),
+        )
+        .subcommand(
+            Command::new("sample")
+                .about("Randomly sample colors from a palette")
+                .long_about("Randomly draw colors (with replacement) from the given palette. By \
+                             default, all colors are equally likely. With '--weights', the \
+                             probability of each color is proportional to its weight.\n\n\
+                             Example:\n  \
+                               pastel sample -n 20 --weights counts.txt < palette.txt")
+                .arg(
+                    Arg::new("number")
+                        .long("number")
+                        .short('n')
+                        .help("Number of colors to sample")
+                        .takes_value(true)
+                        .default_value("10")
+                        .value_name("count"),
+                )
+                .arg(
+                    Arg::new("weights")
+                        .long("weights")
+                        .short('w')
+                        .help("Path to a file with one (non-negative) weight per line, in the \
+                               same order as the colors of the palette. Empty lines and lines \
+                               starting with '#' are ignored.")
+                        .takes_value(true)
+                        .value_name("file"),
+                )
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .help("Seed for the random number generator (for reproducible output)")
+                        .takes_value(true)
+                        .value_name("number"),
+                )
+                .arg(color_arg.clone()),
+        )
         .subcommand(
             Command::new("distinct")
                 .about("Generate a set of visually distinct colors")
                 .long_about("Generate a set of visually distinct colors by maximizing \
                              the perceived color difference between pairs of colors.\n\n\
                              The default parameters for the optimization procedure \
-                             (simulated annealing) should work fine for up to 10-20 colors.")
+                             (simulated annealing) should work fine for up to 10-20 colors.\n\n\
+                             Colors given as arguments are kept fixed. With '--extend', an \
+                             existing palette is grown by the given number of new colors.\n\n\
+                             With '--resume', the state of the optimizer is saved to a file \
+                             after the run. If that file already exists, the optimization \
+                             continues from the saved state (possibly with different \
+                             parameters) instead of starting from scratch.\n\n\
+                             Examples:\n  \
+                               pastel distinct 8 red blue\n  \
+                               pastel distinct --extend palette.txt 3 > extended.txt\n  \
+                               pastel distinct --resume state.json --max-time 1m 12\n\n\
+                             The options '--lightness-spread', '--hue-uniformity' and \
+                             '--anchor' add soft constraints (penalty terms with optional \
+                             weights) to the optimization, e.g.:\n  \
+                               pastel distinct --anchor '#3b82f6:2' --hue-uniformity 0.2 6\n\n\
+                             The options '--min-lightness', '--max-lightness', '--min-chroma' \
+                             and '--max-chroma' restrict the search to a region of the color \
+                             space, e.g. for colors that are readable on a dark background:\n  \
+                               pastel distinct --min-lightness 60 --max-chroma 60 8\n\n\
+                             With '--background', all new colors are guaranteed to be readable \
+                             on the given background color:\n  \
+                               pastel distinct --background '#1e1e1e' --min-contrast 4.5 8")
                 .arg(
                     Arg::new("number")
-                        .help("Number of distinct colors in the set")
+                        .help("Number of distinct colors in the set (or the number of new \
+                               colors, with '--extend')")
                         .takes_value(true)
                         .default_value("10")
                         .value_name("count"),
@@ -135,6 +283,156 @@ pub fn build_cli() -> Command<'static> {
                         .value_name("name")
                         .default_value("CIE76")
                 )
+                .arg(
+                    Arg::new("space")
+                        .long("space")
+                        .help("Color space in which the mutual color distances are computed. \
+                               With 'oklab', the Euclidean distance in the OkLab space is used, \
+                               which is more perceptually uniform than CIE76 and much faster \
+                               than CIEDE2000. The '--metric' option can only be given for \
+                               'cielab'.")
+                        .takes_value(true)
+                        .possible_values(["cielab", "oklab"])
+                        .value_name("name")
+                        .default_value("cielab"),
+                )
+                .arg(
+                    Arg::new("extend")
+                        .long("extend")
+                        .short('e')
+                        .help("Read an existing palette (one color per line) from the given \
+                               file, or from standard input for '-'. All of its colors are \
+                               kept fixed and the new colors are appended.")
+                        .takes_value(true)
+                        .value_name("file"),
+                )
+                .arg(
+                    Arg::new("lightness-spread")
+                        .long("lightness-spread")
+                        .help("Prefer palettes where the standard deviation of the (Lab) \
+                               lightness values is close to the given target. An optional \
+                               weight for this constraint can be appended (e.g. '20:0.5').")
+                        .takes_value(true)
+                        .value_name("target[:weight]"),
+                )
+                .arg(
+                    Arg::new("hue-uniformity")
+                        .long("hue-uniformity")
+                        .help("Weight for a constraint that prefers palettes whose (chromatic) \
+                               colors are spread uniformly around the hue circle")
+                        .takes_value(true)
+                        .value_name("weight"),
+                )
+                .arg(
+                    Arg::new("anchor")
+                        .long("anchor")
+                        .help("Prefer palettes that contain a color close to the given anchor \
+                               (brand) color. An optional weight for this constraint can be \
+                               appended (e.g. '#3b82f6:2').")
+                        .takes_value(true)
+                        .value_name("color[:weight]"),
+                )
+                .arg(
+                    Arg::new("min-lightness")
+                        .long("min-lightness")
+                        .help("Only search for colors with at least this (CIE LCh) lightness \
+                               (0-100). Fixed colors are not affected.")
+                        .takes_value(true)
+                        .value_name("L"),
+                )
+                .arg(
+                    Arg::new("max-lightness")
+                        .long("max-lightness")
+                        .help("Only search for colors with at most this (CIE LCh) lightness \
+                               (0-100), e.g. to stay readable on a light background")
+                        .takes_value(true)
+                        .value_name("L"),
+                )
+                .arg(
+                    Arg::new("min-chroma")
+                        .long("min-chroma")
+                        .help("Only search for colors with at least this (CIE LCh) chroma. \
+                               Where this is not possible within the sRGB gamut, the most \
+                               saturated color is used instead.")
+                        .takes_value(true)
+                        .value_name("C"),
+                )
+                .arg(
+                    Arg::new("max-chroma")
+                        .long("max-chroma")
+                        .help("Only search for colors with at most this (CIE LCh) chroma")
+                        .takes_value(true)
+                        .value_name("C"),
+                )
+                .arg(
+                    Arg::new("background")
+                        .long("background")
+                        .help("Only search for colors that have at least the contrast ratio of \
+                               '--min-contrast' (4.5 by default) against the given background \
+                               color. Fixed colors are not affected.")
+                        .takes_value(true)
+                        .value_name("color"),
+                )
+                .arg(
+                    Arg::new("min-contrast")
+                        .long("min-contrast")
+                        .help("The minimum WCAG contrast ratio against the '--background' color")
+                        .takes_value(true)
+                        .requires("background")
+                        .value_name("ratio"),
+                )
+                .arg(
+                    Arg::new("resume")
+                        .long("resume")
+                        .help("Continue the optimization from the state saved in the given file \
+                               (if it exists) and save the final state to it. When resuming, the \
+                               colors (and their count) are taken from the saved state.")
+                        .takes_value(true)
+                        .value_name("file"),
+                )
+                .arg(
+                    Arg::new("iterations")
+                        .long("iterations")
+                        .help("Total number of iterations for the optimization procedure. \
+                               Larger sets of colors may need more iterations.")
+                        .takes_value(true)
+                        .value_name("N")
+                        .default_value("300000"),
+                )
+                .arg(
+                    Arg::new("max-time")
+                        .long("max-time")
+                        .help("Stop the optimization after the given amount of time (e.g. '2s', \
+                               '500ms' or '1m') and return the best result found so far.")
+                        .takes_value(true)
+                        .value_name("duration"),
+                )
+                .arg(
+                    Arg::new("patience")
+                        .long("patience")
+                        .help("Stop the optimization early once the minimal distance has not \
+                               improved for N iterations. This can speed up the search for \
+                               smaller sets of colors considerably.")
+                        .takes_value(true)
+                        .value_name("N"),
+                )
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .help("Seed for the random number generator. Runs with the same seed \
+                               and parameters produce the same colors (unless '--max-time' is \
+                               used). Ignored when resuming from a saved state.")
+                        .takes_value(true)
+                        .value_name("number"),
+                )
+                .arg(
+                    Arg::new("report")
+                        .long("report")
+                        .help("After the colors, print a quality report with the minimum and \
+                               mean pairwise color distance, the closest pair of colors and the \
+                               minimum distances for simulated color vision deficiencies")
+                        .conflicts_with("print-minimal-distance"),
+                )
                 .arg(
                     Arg::new("print-minimal-distance")
                         .long("print-minimal-distance")
@@ -149,6 +447,193 @@ pub fn build_cli() -> Command<'static> {
                 ).
                 arg(color_arg.clone()),
         )
+        .subcommand(
+            Command::new("cluster")
+                .about("Partition colors into groups of similar colors")
+                .long_about("Partition the given colors into K groups of similar colors by \
+                             running the k-means algorithm in the CIE Lab color space. The \
+                             clusters are printed one after another, separated by blank lines.\n\n\
+                             Example:\n  \
+                               pastel random -n 50 | pastel cluster -k 4")
+                .arg(
+                    Arg::new("clusters")
+                        .long("clusters")
+                        .short('k')
+                        .help("Number of clusters")
+                        .takes_value(true)
+                        .default_value("4")
+                        .value_name("count"),
+                )
+                .arg(
+                    Arg::new("centroids")
+                        .long("centroids")
+                        .short('c')
+                        .help("Only print the centroid (mean color) of each cluster"),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("extract-from-image")
+                .about("Extract the dominant colors from an image")
+                .long_about("Extract the N dominant colors from a PNG or JPEG image by running \
+                             the k-means algorithm on its pixels in the CIE Lab color space. \
+                             The colors are printed in order of decreasing dominance (number of \
+                             pixels). Large images are subsampled and transparent pixels are \
+                             ignored.\n\n\
+                             Example:\n  \
+                               pastel extract-from-image --number 5 photo.jpg")
+                .arg(
+                    Arg::new("file")
+                        .help("Path to the image file")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("number")
+                        .long("number")
+                        .short('n')
+                        .help("Number of colors to extract")
+                        .takes_value(true)
+                        .default_value("5")
+                        .value_name("count"),
+                )
+                .arg(
+                    Arg::new("metric")
+                        .long("metric")
+                        .short('m')
+                        .help("Distance metric to assign pixels to the closest color. The \
+                               CIEDE2000 is more accurate, but also much slower.")
+                        .takes_value(true)
+                        .possible_values(["CIEDE2000", "CIE76"])
+                        .value_name("name")
+                        .default_value("CIE76"),
+                ),
+        )
+        .subcommand(
+            Command::new("bands")
+                .about("Show how the given colors are distributed over hue bands")
+                .long_about("Sort the given colors into bands of equal hue range (LCh hue) and \
+                             show a bar chart with the number of colors per band. Colors with \
+                             a very low chroma are counted separately as 'gray'.\n\n\
+                             Example:\n  \
+                               pastel random -n 100 | pastel bands --width 30")
+                .arg(
+                    Arg::new("width")
+                        .long("width")
+                        .short('w')
+                        .help("Width of a single hue band (in degrees)")
+                        .takes_value(true)
+                        .default_value("30")
+                        .value_name("degrees"),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("diff-colors")
+                .about("Generate color pairs for marking removed/added content")
+                .long_about("Generate a red-ish and a green-ish color (for removed and added \
+                             content) with equal perceived lightness. The hues are chosen such \
+                             that the two colors stay distinguishable for people with protanopia \
+                             or deuteranopia. If a count larger than one is given, a sequence of \
+                             pairs with increasing intensity is generated (e.g. for heatmaps). \
+                             One color is printed per line: for each intensity level, the \
+                             'removed' color comes first, followed by the 'added' color (and the \
+                             yellow-ish and blue-ish colors, if requested). The chroma is reduced \
+                             where needed, such that all colors lie within the sRGB gamut.\n\n\
+                             Example:\n  \
+                               pastel diff-colors -n 3 --yellow-blue")
+                .arg(
+                    Arg::new("number")
+                        .long("number")
+                        .short('n')
+                        .help("Number of intensity levels to generate")
+                        .takes_value(true)
+                        .default_value("1")
+                        .value_name("count"),
+                )
+                .arg(
+                    Arg::new("lightness")
+                        .long("lightness")
+                        .short('l')
+                        .help("Common CIE LCh lightness of all colors (number between 0 and 100)")
+                        .takes_value(true)
+                        .default_value("60")
+                        .value_name("value"),
+                )
+                .arg(
+                    Arg::new("yellow-blue")
+                        .long("yellow-blue")
+                        .help("Also generate a yellow-ish and a blue-ish color (e.g. for modified \
+                               content) after each red/green pair"),
+                ),
+        )
+        .subcommand(
+            Command::new("stats")
+                .about("Show summary statistics for a set of colors")
+                .long_about("Show summary statistics for the given set of colors (a palette), such \
+                             as the mean lightness and chroma as well as the colorfulness metric by \
+                             Hasler and Süsstrunk, which can be used to compare the vibrancy of \
+                             different palettes.\n\n\
+                             Examples:\n  \
+                               pastel random -n 20 | pastel stats\n  \
+                               ffmpeg -i video.mp4 -f rawvideo -pix_fmt rgb24 - \\\n    \
+                                 | pastel stats --input-format raw-rgb8")
+                .arg(input_format_arg.clone())
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("alpha-report")
+                .about("Summarize the transparency of a set of colors")
+                .long_about("Summarize the alpha values of the given set of colors (a palette): \
+                             the number of opaque, translucent and fully transparent colors, and \
+                             a histogram of the alpha values. In addition, all colors whose \
+                             appearance (after compositing) on one of the background colors \
+                             does not reach the given contrast ratio against that background \
+                             are listed, e.g. overlays or borders that are hardly visible.\n\n\
+                             Example:\n  \
+                               pastel alpha-report --background white,'#121212' < tokens.txt")
+                .arg(
+                    Arg::new("background")
+                        .long("background")
+                        .short('b')
+                        .help("Comma-separated list of background colors")
+                        .takes_value(true)
+                        .use_value_delimiter(true)
+                        .default_value("white,black")
+                        .value_name("colors"),
+                )
+                .arg(
+                    Arg::new("threshold")
+                        .long("threshold")
+                        .short('t')
+                        .help("Minimum (WCAG) contrast ratio between a composited color and its \
+                               background. The default of 3 is the WCAG requirement for user \
+                               interface components.")
+                        .takes_value(true)
+                        .default_value("3")
+                        .value_name("ratio"),
+                )
+                .arg(input_format_arg.clone())
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("roles")
+                .about("Guess the theme roles of the colors in a palette")
+                .long_about("Heuristically label the colors of a palette with theme roles \
+                             (background, foreground, accent, error, warning and success), based \
+                             on their lightness, chroma, hue and frequency. This can be used to \
+                             bootstrap the conversion of a color scheme between different \
+                             formats. The background is the most frequent neutral color, the \
+                             foreground is the neutral color with the highest contrast to it and \
+                             the status roles are assigned to colorful red, orange/yellow and \
+                             green tones. Roles that can not be assigned are omitted. Named \
+                             palettes ('name: color' lines, as printed by 'pastel scan-css') are \
+                             also accepted on standard input.\n\n\
+                             Examples:\n  \
+                               pastel roles '#1e1e2e' '#cdd6f4' '#89b4fa' '#f38ba8' '#f9e2af' \
+                               '#a6e3a1'\n  \
+                               pastel scan-css style.scss | pastel roles")
+                .arg(color_arg.clone()),
+        )
         .subcommand(
             Command::new("sort-by")
                 .about("Sort colors by the given property")
@@ -193,53 +678,180 @@ pub fn build_cli() -> Command<'static> {
                   - zenity (https://wiki.gnome.org/Projects/Zenity)\n  \
                   - yad (https://github.com/v1cont/yad)\n  \
                   - hyprpicker (https://github.com/hyprwm/hyprpicker)\n  \
-                  - macOS built-in color picker")
+                  - macOS built-in color picker\n\
+                \n\
+                If pastel is built with the 'native-picker' feature, colors are picked without an \
+                external tool when none of the above is installed ('--color-picker native'): \
+                via the color picker of the XDG desktop portal (Wayland, most desktop \
+                environments) or directly from the X11 screen (click to pick).\n\
+                \n\
+                When picking several colors, the colors picked so far are listed below the \
+                spectrum.")
                 .arg(
                     Arg::new("count")
                         .help("Number of colors to pick")
                         .default_value("1")
                 )
+                .arg(
+                    Arg::new("average")
+                        .long("average")
+                        .short('a')
+                        .help("Average over an area around the picked pixel, e.g. '5x5'")
+                        .long_help("Average the picked pixel with its neighborhood, an area of \
+                                    the given size (e.g. '5x5' or just '5') centered on the \
+                                    pixel. This avoids picking the color of single pixels in \
+                                    anti-aliased text or dithered images. The colors are \
+                                    averaged in linear light. Only available with the built-in \
+                                    X11 color picker ('native-picker' feature).")
+                        .takes_value(true)
+                        .value_name("WxH")
+                )
+        )
+        .subcommand(
+            Command::new("pick-from-image")
+                .about("Pick colors from an image file")
+                .long_about("Pick colors from the pixels of a PNG or JPEG image instead of the \
+                             screen. The pixel positions can be given with '--at X,Y' (the \
+                             origin is the top left corner). Without '--at', a preview of the \
+                             image is shown in the terminal: move the cursor with the arrow keys \
+                             (or h/j/k/l, upper case to move faster), pick the color under the \
+                             cursor with Enter and finish with 'q'. Escape cancels.\n\n\
+                             The preview is drawn on standard error, such that the picked colors \
+                             can be captured from standard output. This also works over SSH.\n\n\
+                             Examples:\n  \
+                               pastel pick-from-image photo.png --at 120,300\n  \
+                               pastel pick-from-image logo.png --at 0,0 --at 10,20 | pastel format hex")
+                .arg(
+                    Arg::new("file")
+                        .help("Path to the image file")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("at")
+                        .long("at")
+                        .help("Position of the pixel to pick, e.g. '120,300'. Can be given \
+                               multiple times.")
+                        .takes_value(true)
+                        .multiple_occurrences(true)
+                        .value_name("X,Y"),
+                ),
         )
         .subcommand(
             Command::new("format")
                 .about("Convert a color to the given format")
                 .long_about("Convert the given color(s) to a specific format.\n\n\
-                Example:\n  \
-                  pastel random -n 20 | pastel format rgb")
+                Examples:\n  \
+                  pastel random -n 20 | pastel format rgb\n  \
+                  pastel format all teal\n  \
+                  pastel random | pastel format --template '{name} {hex} rgba({r},{g},{b},{a:.2})'")
                 .arg(
                     Arg::new("type")
-                        .help("Output format type. Note that the 'ansi-*-escapecode' formats print \
+                        .help("Output format type: 'rgb', 'rgb-float', 'hex', \
+                               'hsl', 'hsl-hue', 'hsl-saturation', 'hsl-lightness', \
+                               'hsv', 'hsv-hue', 'hsv-saturation', 'hsv-value', \
+                               'lch', 'lch-lightness', 'lch-chroma', 'lch-hue', \
+                               'lab', 'lab-a', 'lab-b', \
+                               'oklab', 'oklab-l', 'oklab-a', 'oklab-b', \
+                               'luminance', 'brightness', 'colorfulness', 'cct', \
+                               'ansi-8bit', 'ansi-24bit', \
+                               'ansi-8bit-escapecode', 'ansi-24bit-escapecode', \
+                               'cmyk' or 'name'. Several format types can be given as a \
+                               comma-separated list (e.g. 'hex,rgb,name'), which prints one line \
+                               per format. 'all' is a shortcut for \
+                               'hex,rgb,hsl,lab,lch,oklab,cmyk,name'. \
+                               Note that the 'ansi-*-escapecode' formats print \
                                ansi escape sequences to the terminal that will not be visible \
                                unless something else is printed in addition.")
-                        .possible_values(["rgb", "rgb-float", "hex",
-                                           "hsl", "hsl-hue", "hsl-saturation", "hsl-lightness",
-                                           "hsv", "hsv-hue", "hsv-saturation", "hsv-value",
-                                           "lch", "lch-lightness", "lch-chroma", "lch-hue",
-                                           "lab", "lab-a", "lab-b",
-                                           "oklab", "oklab-l", "oklab-a", "oklab-b",
-                                           "luminance", "brightness",
-                                           "ansi-8bit", "ansi-24bit",
-                                           "ansi-8bit-escapecode", "ansi-24bit-escapecode",
-                                           "cmyk", "name"])
-                        .ignore_case(true)
                         .default_value("hex")
                 )
+                .arg(
+                    Arg::new("template")
+                        .long("template")
+                        .short('t')
+                        .value_name("template")
+                        .help("Format the color with a custom template instead of a fixed format \
+                               type. Placeholders like '{hex}' or '{lch-chroma}' can be any of \
+                               the format types above, the RGB channels '{r}', '{g}', '{b}' and \
+                               '{a}' (alpha), or the CMYK channels '{cmyk-c}', '{cmyk-m}', \
+                               '{cmyk-y}' and '{cmyk-k}'. The number of decimal places of numeric \
+                               placeholders can be set via '{a:.2}'. Use '{{' and '}}' for \
+                               literal braces. With a template, all positional arguments are \
+                               interpreted as colors.")
+                        .takes_value(true)
+                )
+                .arg(
+                    Arg::new("confidence")
+                        .long("confidence")
+                        .help("For the 'name' format: append '(exact)' if the color matches the \
+                               named color exactly. Otherwise, prefix the name with '~' and \
+                               append the (CIEDE2000) color difference, e.g. '~salmon (ΔE 4.2)'.")
+                        .conflicts_with("exact-only")
+                )
+                .arg(
+                    Arg::new("exact-only")
+                        .long("exact-only")
+                        .help("For the 'name' format: only print the name if the color matches \
+                               the named color exactly, and an empty string otherwise")
+                )
+                .arg(
+                    Arg::new("prefer-grays")
+                        .long("prefer-grays")
+                        .help("For the 'ansi-8bit' formats: map near-neutral colors to the closest \
+                               pure gray tone of the 8-bit palette")
+                )
+                .arg(
+                    Arg::new("include-base-colors")
+                        .long("include-base-colors")
+                        .help("For the 'ansi-8bit' formats: also consider the 16 base colors of \
+                               the 8-bit palette. These are excluded by default, as most \
+                               terminals redefine them via their color scheme. Alternatively, \
+                               set PASTEL_TERMINAL_PALETTE to the 16 actual colors of your \
+                               terminal (separated by colons) to use them for all 8-bit output.")
+                )
+                .arg(input_format_arg.clone())
                 .arg(color_arg.clone()),
         )
         .subcommand(
             Command::new("paint")
                 .about("Print colored text using ANSI escape sequences")
+                .long_about("Print colored text using ANSI escape sequences.\n\n\
+                             Examples:\n  \
+                               pastel paint red 'Hello world'\n  \
+                               pastel random -n 1 | pastel paint - --text-from message.txt\n  \
+                               tail -f app.log | pastel paint --cycle palette.txt")
                 .arg(
                     Arg::new("color")
-                        .help("The foreground color. Use '-' to read the color from STDIN.")
-                        .required(true),
+                        .help("The foreground color. Use '-' to read the color from STDIN. \
+                               A full style specification like 'bold red on #222' can also \
+                               be given.")
+                        .required_unless_present("cycle"),
                 )
                 .arg(
                     Arg::new("text")
                         .help("The text to be printed in color. If no argument is given, \
-                               the input is read from STDIN.")
+                               the input is read from STDIN (or from the file given by \
+                               '--text-from').")
                         .multiple_occurrences(true)
                 )
+                .arg(
+                    Arg::new("text-from")
+                        .long("text-from")
+                        .help("Read the text from the given file instead of STDIN. This allows \
+                               for reading the color from STDIN at the same time.")
+                        .takes_value(true)
+                        .value_name("file")
+                        .conflicts_with("text"),
+                )
+                .arg(
+                    Arg::new("cycle")
+                        .long("cycle")
+                        .short('c')
+                        .help("Paint each line of the text in a different color, cycling through \
+                               the colors in the given palette file (one color per line). No \
+                               foreground color argument is expected in this mode.")
+                        .takes_value(true)
+                        .value_name("palette-file"),
+                )
                 .arg(
                     Arg::new("on")
                         .short('o')
@@ -266,6 +878,34 @@ pub fn build_cli() -> Command<'static> {
                         .long("underline")
                         .help("Draw a line below the text"),
                 )
+                .arg(
+                    Arg::new("dim")
+                        .short('d')
+                        .long("dim")
+                        .help("Print the text with decreased intensity (faint)"),
+                )
+                .arg(
+                    Arg::new("strikethrough")
+                        .short('s')
+                        .long("strikethrough")
+                        .help("Draw a line through the text"),
+                )
+                .arg(
+                    Arg::new("blink")
+                        .long("blink")
+                        .help("Let the text blink"),
+                )
+                .arg(
+                    Arg::new("reverse")
+                        .short('r')
+                        .long("reverse")
+                        .help("Swap the foreground and background colors"),
+                )
+                .arg(
+                    Arg::new("hidden")
+                        .long("hidden")
+                        .help("Hide the text (it can still be selected and copied)"),
+                )
                 .arg(
                     Arg::new("no-newline")
                         .short('n')
@@ -274,216 +914,2079 @@ pub fn build_cli() -> Command<'static> {
                 ),
         )
         .subcommand(
-            Command::new("gradient")
-                .about("Generate an interpolating sequence of colors")
-                .long_about("Generate a sequence of colors that interpolates between the specified colors.\n\
-                            The interpolation is performed in the specified color space.\n\n\
-                            Example:\n  \
-                              pastel gradient --colorspace=HSL ffffcc fd8d3c\n  \
-                              pastel gradient 555ee4 white d84341 -n 15")
+            Command::new("strip")
+                .about("Remove colors and other styling from the text on STDIN")
+                .long_about("Read text from STDIN and remove all ANSI styling escape sequences \
+                             (colors and attributes like bold, as produced by 'pastel paint' or \
+                             'pastel colorize') as well as OSC 8 hyperlinks. Other escape \
+                             sequences are kept.\n\n\
+                             Example:\n  \
+                               pastel colorize --rules log.rules < app.log | pastel strip")
+        )
+        .subcommand(
+            Command::new("colorize")
+                .about("Colorize lines from STDIN according to matching rules")
+                .long_about("Read lines from STDIN and paint all parts that match one of the \
+                             given rules (like a tiny log colorizer). The rules file contains \
+                             one rule of the form '<regex> => <style>' per line, where the style \
+                             consists of a foreground color (or 'default'), a background color \
+                             preceded by 'on', and any of 'bold', 'dim', 'italic', 'underline', \
+                             'blink', 'reverse', 'hidden' and 'strikethrough' (for \
+                             example 'bold red on #222'). If matches of several rules overlap, the rule \
+                             that comes first takes precedence. Empty lines and lines starting \
+                             with '#' are ignored.\n\n\
+                             Example rules file:\n  \
+                               ERROR|FATAL => white on crimson bold\n  \
+                               WARN(ING)? => orange\n  \
+                               \\d+\\.\\d+\\.\\d+\\.\\d+ => steelblue underline\n\n\
+                             Alternatively, the text can be painted with a gradient ('--gradient') \
+                             that spans the whole text along the given angle, like lolcat. With \
+                             '--speed', every line is animated before it comes to rest.\n\n\
+                             Examples:\n  \
+                               tail -f app.log | pastel colorize --rules log.rules\n  \
+                               fortune | pastel colorize --gradient @rainbow --angle 30\n  \
+                               figlet pastel | pastel colorize --gradient gold crimson --speed 1")
                 .arg(
-                    Arg::new("color")
-                        .value_name("color")
-                        .help("Color stops in the color gradient")
-                        .multiple_occurrences(true)
-                        .required(true),
+                    Arg::new("rules")
+                        .long("rules")
+                        .short('r')
+                        .help("Path to the rules file")
+                        .takes_value(true)
+                        .value_name("file")
+                        .required_unless_present("gradient"),
                 )
                 .arg(
-                    Arg::new("number")
-                        .long("number")
-                        .short('n')
-                        .help("Number of colors to generate")
-                        .takes_value(true)
-                        .default_value("10")
-                        .value_name("count"),
+                    Arg::new("whole-line")
+                        .long("whole-line")
+                        .short('l')
+                        .help("Paint the whole line in the style of the first matching rule"),
                 )
                 .arg(
-                    colorspace_arg.clone()
+                    Arg::new("gradient")
+                        .long("gradient")
+                        .short('g')
+                        .help("Paint the text with a gradient through the given color stops \
+                               (as in 'pastel gradient'), or a saved or built-in gradient \
+                               ('@name', e.g. '@rainbow')")
+                        .takes_value(true)
+                        .multiple_values(true)
+                        .value_name("color")
+                        .conflicts_with_all(&["rules", "whole-line"]),
                 )
-        )
-        .subcommand(
-            Command::new("mix")
-                .about("Mix two colors in the given colorspace")
-                .long_about(
-                    "Create new colors by interpolating between two colors in the given colorspace.\n\n\
-                     Example:\n  \
-                       pastel mix --colorspace=RGB red blue")
                 .arg(
                     colorspace_arg.clone()
                 )
                 .arg(
-                    Arg::new("fraction")
-                        .long("fraction")
-                        .short('f')
-                        .help("The number between 0.0 and 1.0 determining how much to \
-                              mix in from the base color.")
+                    Arg::new("angle")
+                        .long("angle")
+                        .short('a')
+                        .help("Direction of the gradient in degrees (0 is left to right, 90 is \
+                               top to bottom). The gradient spans the whole text, so the input \
+                               is read completely before anything is printed.")
                         .takes_value(true)
-                        .default_value("0.5"),
+                        .allow_hyphen_values(true)
+                        .default_value("0")
+                        .value_name("degrees"),
                 )
                 .arg(
-                    Arg::new("base")
-                        .value_name("color")
-                        .help("The base color which will be mixed with the other colors")
-                        .required(true),
-                )
-                .arg(color_arg.clone()),
-        )
+                    Arg::new("speed")
+                        .long("speed")
+                        .help("Animate every line by cycling through the gradient, with the given \
+                               number of cycles per second (only if the output is a terminal)")
+                        .takes_value(true)
+                        .value_name("cycles")
+                        .requires("gradient"),
+                ),
+        )
+        .subcommand(
+            Command::new("palette")
+                .about("Save, list and show named palettes")
+                .long_about(
+                    "Manage a store of named palettes in the 'palettes' folder of pastel's \
+                     configuration directory ($PASTEL_CONFIG_DIR, or the 'pastel' folder in \
+                     $XDG_CONFIG_HOME or ~/.config). Each palette is a text file with one color \
+                     per line.\n\n\
+                     Examples:\n  \
+                       pastel distinct 6 | pastel palette save mytheme\n  \
+                       pastel palette append mytheme coral\n  \
+                       pastel palette show mytheme | pastel sort-by hue",
+                )
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("save")
+                        .about("Save colors under the given name (replacing an existing palette)")
+                        .arg(
+                            Arg::new("name")
+                                .help("Name of the palette (letters, digits, '-' and '_')")
+                                .required(true),
+                        )
+                        .arg(color_arg.clone()),
+                )
+                .subcommand(
+                    Command::new("append")
+                        .about("Append colors to a palette (which is created if necessary)")
+                        .arg(
+                            Arg::new("name")
+                                .help("Name of the palette")
+                                .required(true),
+                        )
+                        .arg(color_arg.clone()),
+                )
+                .subcommand(
+                    Command::new("show")
+                        .about("Print the colors of a palette")
+                        .arg(
+                            Arg::new("name")
+                                .help("Name of the palette")
+                                .required(true),
+                        ),
+                )
+                .subcommand(Command::new("list").about("List the names of all saved palettes"))
+                .subcommand(
+                    Command::new("remove")
+                        .about("Delete a palette")
+                        .arg(
+                            Arg::new("name")
+                                .help("Name of the palette")
+                                .required(true),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("scan-css")
+                .about("Extract the color scheme from CSS/SCSS files")
+                .long_about("Collect all SCSS variables ('$name: …') and CSS custom properties \
+                             ('--name: …') that define a color and print them as a named \
+                             palette. References to other variables ('$other' or \
+                             'var(--other, fallback)') are resolved. Values that are computed \
+                             from other colors (like 'darken($primary, 10%)') are skipped.\n\n\
+                             Example:\n  \
+                               pastel scan-css src/styles/_variables.scss")
+                .arg(
+                    Arg::new("file")
+                        .help("CSS or SCSS file(s) to scan")
+                        .required(true)
+                        .multiple_occurrences(true),
+                ),
+        )
+        .subcommand(
+            Command::new("grep")
+                .about("Find and highlight color literals in text")
+                .long_about("Scan the given files (or STDIN) for hex codes ('#ff8000'), \
+                             functional notations like 'rgb(…)', 'hsl(…)' or 'lab(…)' and CSS \
+                             color names. References like 'issue #123' or 'PR #4567' and \
+                             color names within identifiers (like '.red-button') are skipped. \
+                             Lines that contain colors are printed with every color \
+                             highlighted in its own color. With '--list', just the colors are \
+                             printed (in order of appearance), such that they can be piped into \
+                             other pastel commands.\n\n\
+                             Examples:\n  \
+                               pastel grep src/styles/*.css\n  \
+                               pastel grep --unique theme.toml | pastel sort-by luminance")
+                .arg(
+                    Arg::new("file")
+                        .help("File(s) to scan. If no file is given, the text is read from STDIN.")
+                        .multiple_occurrences(true),
+                )
+                .arg(
+                    Arg::new("list")
+                        .long("list")
+                        .short('l')
+                        .help("Print the colors instead of the matching lines"),
+                )
+                .arg(
+                    Arg::new("unique")
+                        .long("unique")
+                        .short('u')
+                        .help("Print every color only once (implies '--list')"),
+                )
+                .arg(
+                    Arg::new("line-number")
+                        .long("line-number")
+                        .short('n')
+                        .help("Prefix the matching lines with their line number"),
+                )
+                .arg(
+                    Arg::new("no-names")
+                        .long("no-names")
+                        .help("Ignore color names (like 'red' or 'tan'), which may also be \
+                               ordinary words"),
+                ),
+        )
+        .subcommand(
+            Command::new("replace")
+                .about("Rewrite the color literals in files")
+                .long_about("Find all color literals (like 'pastel grep') in the given files (or \
+                             STDIN) and rewrite them, leaving the surrounding text untouched. \
+                             With '--to', every color is converted to the given notation. With \
+                             '--map old=new', all occurrences of a color (in any notation) are \
+                             replaced by another color. Replaced colors keep the notation of the \
+                             original literal (hex, rgb or hsl), unless '--to' is given. Color \
+                             names are only rewritten with '--names'.\n\n\
+                             The result is printed to STDOUT, or written back to the files with \
+                             '--in-place'.\n\n\
+                             Examples:\n  \
+                               pastel replace --to oklch style.css\n  \
+                               pastel replace --map '#ff0000=crimson' --map navy=#1e3a8a \
+                               --in-place theme.scss")
+                .arg(
+                    Arg::new("file")
+                        .help("File(s) to rewrite. If no file is given, the text is read from \
+                               STDIN.")
+                        .multiple_occurrences(true),
+                )
+                .arg(
+                    Arg::new("to")
+                        .long("to")
+                        .short('t')
+                        .help("Convert all colors to the given notation")
+                        .takes_value(true)
+                        .possible_values(["hex", "rgb", "hsl", "oklch"])
+                        .value_name("notation"),
+                )
+                .arg(
+                    Arg::new("map")
+                        .long("map")
+                        .short('m')
+                        .help("Replace a color by another one, e.g. 'red=#0080ff'. Can be given \
+                               multiple times.")
+                        .takes_value(true)
+                        .multiple_occurrences(true)
+                        .value_name("old=new"),
+                )
+                .arg(
+                    Arg::new("in-place")
+                        .long("in-place")
+                        .short('i')
+                        .help("Write the result back to the files instead of printing it")
+                        .requires("file"),
+                )
+                .arg(
+                    Arg::new("names")
+                        .long("names")
+                        .help("Also rewrite color names (like 'red' or 'tan'). These are ignored \
+                               by default, since they may also be ordinary words. Names within \
+                               identifiers (like '.red-button') are never rewritten."),
+                ),
+        )
+        .subcommand(
+            Command::new("gradient")
+                .about("Generate an interpolating sequence of colors")
+                .long_about("Generate a sequence of colors that interpolates between the specified colors.\n\
+                            The interpolation is performed in the specified color space.\n\n\
+                            Instead of a list of colors, the name of a saved gradient or of a \
+                            built-in preset can be given as '@name'. Gradients can be saved with \
+                            '--save <name>' to the 'gradients' folder in pastel's configuration \
+                            directory ($PASTEL_CONFIG_DIR, or the 'pastel' folder in \
+                            $XDG_CONFIG_HOME or ~/.config).\n\n\
+                            Examples:\n  \
+                              pastel gradient --colorspace=HSL ffffcc fd8d3c\n  \
+                              pastel gradient 555ee4 white d84341 -n 15\n  \
+                              pastel gradient 555ee4 white d84341 --save cool-warm\n  \
+                              pastel gradient @sunset -n 64 --range 0.2..0.8 --reverse\n  \
+                              pastel gradient black red white --easing ease-in-out\n  \
+                              pastel gradient red yellow@0.3 blue -s OkLab --format css\n  \
+                              pastel gradient red red -n 12 -s OkLCh --hue-interpolation longer\n  \
+                              pastel gradient navy skyblue -n 256 --dither ordered --bits 6 \
+                              --check-banding")
+                .arg(
+                    Arg::new("color")
+                        .value_name("color")
+                        .help("Color stops in the color gradient, or '@name' for a saved or \
+                               built-in gradient")
+                        .multiple_occurrences(true)
+                        .required_unless_present("list"),
+                )
+                .arg(
+                    Arg::new("save")
+                        .long("save")
+                        .help("Save the gradient under the given name")
+                        .takes_value(true)
+                        .value_name("name"),
+                )
+                .arg(
+                    Arg::new("list")
+                        .long("list")
+                        .help("List the names of all saved and built-in gradients"),
+                )
+                .arg(
+                    Arg::new("reverse")
+                        .long("reverse")
+                        .short('r')
+                        .help("Reverse the direction of the gradient"),
+                )
+                .arg(
+                    Arg::new("range")
+                        .long("range")
+                        .help("Only sample the part of the gradient between the two given \
+                               positions (numbers between 0.0 and 1.0), e.g. '0.2..0.8'")
+                        .takes_value(true)
+                        .value_name("start..end"),
+                )
+                .arg(
+                    Arg::new("easing")
+                        .long("easing")
+                        .help("Easing function for the transitions between the color stops \
+                               (linear, ease-in, ease-out or ease-in-out). A comma-separated \
+                               list sets one easing function per segment (of the gradient after \
+                               applying '--range' and '--reverse'), e.g. 'ease-in,linear'.")
+                        .takes_value(true)
+                        .value_name("name"),
+                )
+                .arg(
+                    Arg::new("number")
+                        .long("number")
+                        .short('n')
+                        .help("Number of colors to generate")
+                        .takes_value(true)
+                        .default_value("10")
+                        .value_name("count"),
+                )
+                .arg(
+                    Arg::new("dither")
+                        .long("dither")
+                        .help("Quantize the colors to the number of bits given by '--bits', \
+                               alternating between the two closest levels (ordered: Bayer \
+                               pattern, blue-noise: golden ratio sequence). This avoids visible \
+                               bands in large fills on low-bit displays.")
+                        .takes_value(true)
+                        .possible_values(["ordered", "blue-noise"])
+                        .value_name("pattern"),
+                )
+                .arg(
+                    Arg::new("bits")
+                        .long("bits")
+                        .help("Number of bits per channel for '--dither'")
+                        .takes_value(true)
+                        .default_value("6")
+                        .value_name("N"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .short('f')
+                        .help("Print the gradient as a CSS 'linear-gradient()' or as an SVG \
+                               '<linearGradient>' element with the positions of the color stops \
+                               instead of the sampled colors. If the interpolation cannot be \
+                               expressed in the output format (e.g. easing functions, or other \
+                               color spaces than RGB in SVG), the gradient is approximated by \
+                               '--number' evenly spaced stops.")
+                        .takes_value(true)
+                        .possible_values(["css", "svg"])
+                        .value_name("name")
+                        .conflicts_with_all(&["dither", "check-banding"]),
+                )
+                .arg(
+                    Arg::new("check-banding")
+                        .long("check-banding")
+                        .help("Instead of the colors, print the adjacent steps that are likely to \
+                               cause visible banding: steps with a color difference (CIEDE2000) \
+                               above the '--banding-threshold' and steps with identical colors")
+                        .conflicts_with("save"),
+                )
+                .arg(
+                    Arg::new("banding-threshold")
+                        .long("banding-threshold")
+                        .help("Largest color difference (CIEDE2000) between adjacent steps that \
+                               is considered invisible")
+                        .takes_value(true)
+                        .default_value("1.0")
+                        .value_name("delta-E"),
+                )
+                .arg(
+                    colorspace_arg.clone()
+                )
+                .arg(hue_interpolation_arg.clone())
+        )
+        .subcommand(
+            Command::new("mesh")
+                .about("Generate a two-dimensional grid of interpolated colors")
+                .long_about("Generate a W×H grid of colors by bilinear interpolation between \
+                            four corner colors, e.g. for background meshes or heatmap textures. \
+                            The interpolation is performed in the specified color space. The \
+                            colors are printed row by row, or written to a PNG or SVG image \
+                            (with one pixel per grid cell) via '--image'.\n\n\
+                            Examples:\n  \
+                              pastel mesh --size 4x3 red yellow blue white\n  \
+                              pastel mesh --size 512x512 --image mesh.png navy teal purple coral")
+                .arg(
+                    Arg::new("top-left")
+                        .value_name("top-left")
+                        .help("The color in the top left corner")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("top-right")
+                        .value_name("top-right")
+                        .help("The color in the top right corner")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("bottom-left")
+                        .value_name("bottom-left")
+                        .help("The color in the bottom left corner")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("bottom-right")
+                        .value_name("bottom-right")
+                        .help("The color in the bottom right corner")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("size")
+                        .long("size")
+                        .help("Number of columns and rows of the grid, e.g. '16x9' (each between \
+                               2 and 1024)")
+                        .takes_value(true)
+                        .default_value("5x5")
+                        .value_name("WxH"),
+                )
+                .arg(
+                    Arg::new("image")
+                        .long("image")
+                        .help("Write the grid to the given image file instead of printing the \
+                               colors. The format (PNG or SVG) is chosen by the file extension.")
+                        .takes_value(true)
+                        .value_name("file"),
+                )
+                .arg(
+                    colorspace_arg.clone().default_value("OkLab")
+                )
+        )
+        .subcommand(
+            Command::new("diverging")
+                .about("Generate a diverging color scale")
+                .long_about("Generate a diverging color scale that runs from the 'negative' color \
+                            through a neutral midpoint to the 'positive' color. The lightness of \
+                            the two end colors is equalized such that both halves of the scale \
+                            have a symmetric lightness profile.\n\n\
+                            Example:\n  \
+                              pastel diverging steelblue firebrick -n 11\n  \
+                              pastel diverging --midpoint '#f7f7f7' -s OkLab purple green")
+                .arg(
+                    Arg::new("negative")
+                        .value_name("negative")
+                        .help("The color at the negative end of the scale")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("positive")
+                        .value_name("positive")
+                        .help("The color at the positive end of the scale")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("midpoint")
+                        .long("midpoint")
+                        .help("The neutral color in the middle of the scale")
+                        .takes_value(true)
+                        .default_value("white")
+                        .value_name("color"),
+                )
+                .arg(
+                    Arg::new("number")
+                        .long("number")
+                        .short('n')
+                        .help("Number of colors to generate")
+                        .takes_value(true)
+                        .default_value("11")
+                        .value_name("count"),
+                )
+                .arg(
+                    Arg::new("colorspace")
+                        .long("colorspace")
+                        .short('s')
+                        .value_name("name")
+                        .help("The colorspace in which to interpolate")
+                        .possible_values(["Lab", "OkLab"])
+                        .ignore_case(true)
+                        .default_value("Lab"),
+                )
+        )
+        .subcommand(
+            Command::new("cyclic")
+                .about("Generate a perceptually uniform cyclic color scale")
+                .long_about("Generate a cyclic color scale (e.g. for visualizing angles or phases) \
+                            by going around the hue circle in the OkLCh color space (the \
+                            cylindrical version of OkLab). Lightness and chroma are constant along \
+                            the scale. The chroma is chosen as large as possible while keeping all \
+                            colors inside the sRGB gamut. The last color connects smoothly to the \
+                            first one.\n\n\
+                            Example:\n  \
+                              pastel cyclic -n 256 --lightness 0.75")
+                .arg(
+                    Arg::new("number")
+                        .long("number")
+                        .short('n')
+                        .help("Number of colors to generate")
+                        .takes_value(true)
+                        .default_value("16")
+                        .value_name("count"),
+                )
+                .arg(
+                    Arg::new("lightness")
+                        .long("lightness")
+                        .short('l')
+                        .help("OkLab lightness of all colors (number between 0.0 and 1.0)")
+                        .takes_value(true)
+                        .default_value("0.7")
+                        .value_name("value"),
+                )
+                .arg(
+                    Arg::new("hue-offset")
+                        .long("hue-offset")
+                        .help("OkLCh hue of the first color (in degrees)")
+                        .takes_value(true)
+                        .default_value("0")
+                        .value_name("degrees"),
+                )
+        )
+        .subcommand(
+            Command::new("mix")
+                .about("Mix two colors in the given colorspace")
+                .long_about(
+                    "Create new colors by interpolating between two colors in the given colorspace.\n\n\
+                     Example:\n  \
+                       pastel mix --colorspace=RGB red blue")
+                .arg(
+                    colorspace_arg.clone()
+                )
+                .arg(hue_interpolation_arg.clone())
+                .arg(
+                    Arg::new("fraction")
+                        .long("fraction")
+                        .short('f')
+                        .help("The number between 0.0 and 1.0 determining how much to \
+                              mix in from the base color.")
+                        .takes_value(true)
+                        .default_value("0.5"),
+                )
+                .arg(
+                    Arg::new("base")
+                        .value_name("color")
+                        .help("The base color which will be mixed with the other colors")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("tint")
+                .about("Add, subtract or multiply a tint color to/with each color")
+                .long_about(
+                    "Combine each color channel-wise (in linear RGB space) with a tint color. \
+                     This can be used to give a whole palette a global warm or cool cast.\n\n\
+                     Example:\n  \
+                       pastel tint --multiply '#ffeedd' < palette.txt")
+                .arg(
+                    Arg::new("add")
+                        .long("add")
+                        .help("Add the tint color (brightens)")
+                        .takes_value(true)
+                        .value_name("color"),
+                )
+                .arg(
+                    Arg::new("subtract")
+                        .long("subtract")
+                        .help("Subtract the tint color (darkens)")
+                        .takes_value(true)
+                        .value_name("color"),
+                )
+                .arg(
+                    Arg::new("multiply")
+                        .long("multiply")
+                        .help("Multiply with the tint color (like a color filter in front of a \
+                               light source)")
+                        .takes_value(true)
+                        .value_name("color"),
+                )
+                .group(
+                    ArgGroup::new("operation")
+                        .args(&["add", "subtract", "multiply"])
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("blend")
+                .about("Blend colors over a backdrop with a blend mode")
+                .long_about(
+                    "Blend each (source) color over the backdrop color, using one of the \
+                     standard blend modes (as in CSS or image editors). The blending is \
+                     performed on the gamma-encoded RGB values. Semi-transparent source colors \
+                     are alpha composited afterwards.\n\n\
+                     Example:\n  \
+                       pastel blend --mode multiply '#f4e8c1' steelblue coral")
+                .arg(
+                    Arg::new("mode")
+                        .long("mode")
+                        .short('m')
+                        .help("The blend mode")
+                        .possible_values([
+                            "normal", "multiply", "screen", "overlay", "darken", "lighten",
+                            "color-dodge", "color-burn", "hard-light", "soft-light",
+                            "difference", "exclusion", "hue", "saturation", "color",
+                            "luminosity",
+                        ])
+                        .ignore_case(true)
+                        .default_value("normal"),
+                )
+                .arg(
+                    Arg::new("backdrop")
+                        .value_name("backdrop")
+                        .help("The backdrop color")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("composite")
+                .about("Alpha composite semi-transparent colors over a backdrop")
+                .long_about(
+                    "Place the given (semi-transparent) colors on top of each other over the \
+                     backdrop color and print the resulting color. This predicts how stacked \
+                     translucent layers, e.g. UI overlays, are actually rendered. With \
+                     '--preview', the backdrop, the individual layers and the result are shown \
+                     side by side over a checkerboard.\n\n\
+                     Example:\n  \
+                       pastel composite --preview white 'rgba(0, 0, 255, 0.3)' '#ff000040'")
+                .arg(
+                    Arg::new("preview")
+                        .long("preview")
+                        .help("Show the backdrop, the layers and the resulting stack (only in \
+                               an interactive terminal)"),
+                )
+                .arg(
+                    Arg::new("backdrop")
+                        .value_name("backdrop")
+                        .help("The backdrop color")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("under")
+                .about("Show how a color would look under different light sources")
+                .long_about(
+                    "Show how a surface of the given color (as seen in daylight) would appear \
+                     under different standard illuminants, e.g. incandescent or fluorescent light. \
+                     The colors are computed with the Bradford chromatic adaptation transform. In \
+                     an interactive terminal, the simulated colors are shown side by side. \
+                     Otherwise, they are printed one per line.\n\n\
+                     Example:\n  \
+                       pastel under --illuminant A,D50,F2 cornflowerblue",
+                )
+                .arg(
+                    Arg::new("illuminant")
+                        .long("illuminant")
+                        .short('i')
+                        .help("Comma-separated list of standard illuminants")
+                        .takes_value(true)
+                        .use_value_delimiter(true)
+                        .possible_values(ILLUMINANT_NAMES)
+                        .ignore_case(true)
+                        .default_value("A,D50,F2")
+                        .value_name("names"),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("proof")
+                .about("Simulate how colors would look when printed")
+                .long_about(
+                    "Simulate how the given colors would be reproduced by a print process with a \
+                     limited gamut (soft proofing). The colors are separated into CMYK, limited \
+                     by the total ink coverage and darkened by dot gain. The result is compressed \
+                     into the range between the paper white and the darkest printable black, and \
+                     into the smaller chroma range of the process. This is only a rough \
+                     approximation, not a replacement for ICC-based color management.\n\n\
+                     A warning is printed (on stderr) for each color that shifts by more than \
+                     the given CIEDE2000 color difference.\n\n\
+                     Example:\n  \
+                       pastel proof --profile newsprint crimson navy gold",
+                )
+                .arg(
+                    Arg::new("profile")
+                        .long("profile")
+                        .short('p')
+                        .help("The print process:\n  \
+                               newsprint:    offset print on newsprint paper\n  \
+                               office-laser: office laser printer on plain paper")
+                        .takes_value(true)
+                        .possible_values(["newsprint", "office-laser"])
+                        .ignore_case(true)
+                        .default_value("newsprint")
+                        .value_name("name"),
+                )
+                .arg(
+                    Arg::new("threshold")
+                        .long("threshold")
+                        .short('t')
+                        .help("Warn about colors that shift by more than this (CIEDE2000) delta E")
+                        .takes_value(true)
+                        .default_value("10")
+                        .value_name("delta-E"),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("colorblind")
+                .about("Simulate a color under a certain colorblindness profile")
+                .long_about(
+                    "Convert the given color to how it would look to a person with protanopia, \
+                    deuteranopia, tritanopia or achromatopsia.\n\n\
+                     With '--severity', the model by Machado et al. is used, which also covers \
+                     the much more common anomalous trichromacy (protanomaly, deuteranomaly and \
+                     tritanomaly) with severities below 1.\n\n\
+                     Examples:\n  \
+                       pastel distinct 3 | pastel colorblind deuter\n  \
+                       pastel colorblind --severity 0.6 prot red green")
+                .arg(
+                    Arg::new("type")
+                        .help("The type of colorblindness that should be simulated (protanopia, \
+                               deuteranopia, tritanopia, achromatopsia)")
+                        .possible_values(["prot", "deuter", "trit", "achroma"])
+                        .ignore_case(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("severity")
+                        .long("severity")
+                        .short('s')
+                        .help("The severity of the deficiency, between 0 (normal color vision) \
+                               and 1 (dichromacy)")
+                        .takes_value(true)
+                        .value_name("severity"),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("daltonize")
+                .about("Adjust colors to be easier to distinguish for colorblind people")
+                .long_about(
+                    "Shift the given colors such that they are easier to distinguish for people \
+                     with protanopia, deuteranopia or tritanopia (daltonization). The \
+                     information that is lost for the given type of colorblindness is moved into \
+                     the color channels that can still be perceived. This is the counterpart of \
+                     'pastel colorblind' and can be used to post-process existing palettes.\n\n\
+                     Example:\n  \
+                       pastel daltonize deuter red green | pastel colorblind deuter")
+                .arg(
+                    Arg::new("type")
+                        .help("The type of colorblindness that should be compensated \
+                               (protanopia, deuteranopia, tritanopia)")
+                        .possible_values(["prot", "deuter", "trit"])
+                        .ignore_case(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("strength")
+                        .long("strength")
+                        .short('s')
+                        .help("The strength of the correction (0 leaves the colors unchanged)")
+                        .takes_value(true)
+                        .default_value("1")
+                        .value_name("strength"),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("set")
+                .about("Set a color property to a specific value")
+                .long_about("Set the given property to a specific value\n\
+                Example:\n  \
+                  pastel random | pastel set luminance 0.9")
+                .arg(
+                    Arg::new("property")
+                        .help("The property that should be changed")
+                        .possible_values(["lightness", "hue", "chroma",
+                                           "lab-a", "lab-b",
+                                           "oklab-l", "oklab-a", "oklab-b",
+                                           "red", "green", "blue",
+                                           "hsl-hue", "hsl-saturation", "hsl-lightness",
+                                           "alpha"])
+                        .ignore_case(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("value")
+                        .help("The new numerical value of the property")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
         .subcommand(
-            Command::new("colorblind")
-                .about("Simulate a color under a certain colorblindness profile")
+            Command::new("curves")
+                .about("Apply tone curves to the RGB channels")
+                .long_about(
+                    "Apply user-defined tone curves to the red, green and/or blue channel of each \
+                     color (like the 'curves' tool in image editors). A curve is specified by a \
+                     channel prefix and a list of control points 'x,y' (separated by semicolons) \
+                     with values between 0.0 and 1.0. The curve is interpolated smoothly between \
+                     the control points.\n\n\
+                     Example:\n  \
+                       pastel curves --spec 'r:0,0;0.5,0.6;1,1' --spec 'b:0,0.1;1,0.9' coral",
+                )
+                .arg(
+                    Arg::new("spec")
+                        .long("spec")
+                        .short('c')
+                        .help("Curve specification for one or more channels, e.g. 'r:0,0;0.5,0.6;1,1' \
+                               or 'rgb:0,0.1;1,1'. Can be given multiple times.")
+                        .takes_value(true)
+                        .multiple_occurrences(true)
+                        .required(true)
+                        .value_name("spec"),
+                )
+                .arg(
+                    Arg::new("space")
+                        .long("space")
+                        .help("Apply the curves to gamma-encoded sRGB values or to linear \
+                               light intensities")
+                        .possible_values(["gamma", "linear"])
+                        .ignore_case(true)
+                        .default_value("gamma"),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("filter")
+                .about("Apply a color grading filter")
+                .long_about(
+                    "Apply a named color grading filter to each color. The built-in filters are \
+                     'sepia', 'fade', 'cool-shadows', 'warm-highlights' and 'duotone'.\n\n\
+                     Custom filters can be defined with environment variables of the form \
+                     PASTEL_FILTER_<NAME>, containing a transformation pipeline in the same format \
+                     as for 'pastel lut export'. For example, after setting\n  \
+                       PASTEL_FILTER_MY_LOOK='curves:rgb:0,0.05;1,1 rotate:-5'\n\
+                     the filter can be applied with 'pastel filter my-look'.\n\n\
+                     Example:\n  \
+                       pastel random -n 5 | pastel filter sepia",
+                )
+                .arg(
+                    Arg::new("name")
+                        .help("Name of a built-in or user-defined filter")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("lut")
+                .about("Apply or export 3D color lookup tables (.cube files)")
+                .long_about(
+                    "Apply a 3D color lookup table in the '.cube' format to colors, or export a \
+                     lookup table that represents a pipeline of pastel transformations (for use \
+                     in image and video editing software).\n\n\
+                     Examples:\n  \
+                       pastel lut apply film.cube coral\n  \
+                       pastel lut export 'saturate:0.1 rotate:-10' > warm.cube",
+                )
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("apply")
+                        .about("Map colors through a lookup table")
+                        .arg(
+                            Arg::new("file")
+                                .help("Path to the '.cube' file")
+                                .required(true),
+                        )
+                        .arg(color_arg.clone()),
+                )
+                .subcommand(
+                    Command::new("export")
+                        .about("Write a lookup table for a transformation pipeline")
+                        .long_about(
+                            "Write a lookup table for a pipeline of transformations to standard \
+                             output, in the '.cube' format. The pipeline is a whitespace-separated \
+                             list of steps of the form 'name' or 'name:argument'. Supported steps \
+                             are: saturate, desaturate, lighten, darken, rotate, complement, \
+                             to-gray, colorblind (prot, deuter, trit or achroma), curves (multiple curve \
+                             specifications can be separated by '|') and duotone (given as \
+                             'duotone:<shadow>:<highlight>').\n\n\
+                             Example:\n  \
+                               pastel lut export --size 17 'curves:rgb:0,0.05;1,0.95 saturate:-0.1'",
+                        )
+                        .arg(
+                            Arg::new("pipeline")
+                                .help("The transformation pipeline, e.g. 'saturate:0.2 rotate:30'")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("size")
+                                .long("size")
+                                .short('s')
+                                .help("Number of grid points along each axis (2 to 256)")
+                                .takes_value(true)
+                                .default_value("33"),
+                        )
+                        .arg(
+                            Arg::new("title")
+                                .long("title")
+                                .help("Title to store in the lookup table")
+                                .takes_value(true),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("tokens")
+                .about("Import, export and transform design tokens (JSON)")
+                .long_about(
+                    "Read and write color tokens in the W3C Design Tokens format ('$value', \
+                     '$type') or in the Style Dictionary format ('value', 'type'). Token names \
+                     are the dot-separated paths of the tokens within their groups. References \
+                     to other tokens ('{color.base.blue}') are preserved.\n\n\
+                     Examples:\n  \
+                       pastel tokens import tokens.json\n  \
+                       pastel tokens import tokens.json --aliases | pastel tokens export \
+                       --format style-dictionary\n  \
+                       pastel tokens transform tokens.json 'saturate:0.1 rotate:-10' > warm.json",
+                )
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("import")
+                        .about("Print the color tokens of a token file as a named palette")
+                        .long_about(
+                            "Print all color tokens of a W3C or Style Dictionary token file as \
+                             a named palette ('name: #hex'). The file format is detected \
+                             automatically. Aliases are resolved unless '--aliases' is given.",
+                        )
+                        .arg(
+                            Arg::new("file")
+                                .help("Path to the JSON token file")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("aliases")
+                                .long("aliases")
+                                .help("Print references to other tokens as '{name}' instead of \
+                                       resolving them"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("export")
+                        .about("Write a named palette as a token file")
+                        .long_about(
+                            "Convert a named palette with lines of the form 'name: color' or \
+                             'name: {alias}' (as printed by 'pastel tokens import') into a JSON \
+                             token file. Dots in the names create nested groups. Lines with just \
+                             a color are named 'color-1', 'color-2', etc.\n\n\
+                             Supported formats are W3C Design Tokens, Style Dictionary, Tokens \
+                             Studio for Figma ('figma') and Sketch palettes ('sketch', for the \
+                             Sketch Palettes plugin, aliases are resolved).\n\n\
+                             Example:\n  \
+                               pastel distinct 8 | pastel format hex | pastel tokens export \
+                               --format sketch > distinct.sketchpalette",
+                        )
+                        .arg(
+                            Arg::new("file")
+                                .help("File with the named palette. If not given, the palette \
+                                       is read from STDIN."),
+                        )
+                        .arg(
+                            Arg::new("format")
+                                .long("format")
+                                .short('f')
+                                .help("Format of the token file")
+                                .possible_values(["w3c", "style-dictionary", "figma", "sketch"])
+                                .default_value("w3c"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("transform")
+                        .about("Apply a transformation pipeline to all color tokens")
+                        .long_about(
+                            "Apply a pipeline of transformations to every color token of a token \
+                             file and write the modified file to standard output. Aliases, all \
+                             other tokens and the structure of the file are left unchanged. See \
+                             'pastel lut export --help' for the supported pipeline steps.",
+                        )
+                        .arg(
+                            Arg::new("file")
+                                .help("Path to the JSON token file")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("pipeline")
+                                .help("The transformation pipeline, e.g. 'saturate:0.2 rotate:30'")
+                                .required(true),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Export colors via an external exporter program")
+                .long_about(
+                    "Pass colors to an external exporter program, which can write them in any \
+                     format that pastel does not support itself. The exporter receives a JSON \
+                     document on standard input, and its standard output is printed. Exporters \
+                     given by name (without a path) are first looked up as \
+                     'pastel-export-<name>' in the PATH.\n\n\
+                     The document has the following (stable) schema. The version is only \
+                     increased for incompatible changes, new fields may be added at any time:\n\n  \
+                       {\n    \
+                         \"version\": 1,\n    \
+                         \"colors\": [\n      \
+                           {\n        \
+                             \"name\": \"primary\",\n        \
+                             \"hex\": \"#ff8000\",\n        \
+                             \"rgb\": { \"r\": 255, \"g\": 128, \"b\": 0 },\n        \
+                             \"alpha\": 1.0,\n        \
+                             \"hsl\": { \"h\": 30.1, \"s\": 1.0, \"l\": 0.5 },\n        \
+                             \"oklab\": { \"l\": 0.73, \"a\": 0.09, \"b\": 0.15 }\n      \
+                           }\n    \
+                         ]\n  \
+                       }\n\n\
+                     If colors are read from standard input, lines of the form 'name: color' \
+                     (as printed by 'pastel tokens import') are supported. Other colors are \
+                     named 'color-1', 'color-2', etc.\n\n\
+                     Examples:\n  \
+                       pastel export --via ./to-scss.py red blue > colors.scss\n  \
+                       pastel tokens import tokens.json | pastel export --via ase > tokens.ase",
+                )
+                .arg(
+                    Arg::new("via")
+                        .long("via")
+                        .help("The exporter program: a path, or a name to look up in the PATH")
+                        .takes_value(true)
+                        .value_name("program")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("saturate")
+                .long_about(
+                    "Increase the saturation of a color by adding a certain amount to the HSL \
+                     saturation channel. If the amount is negative, the color will be desaturated \
+                     instead.",
+                )
+                .about("Increase color saturation by a specified amount")
+                .arg(
+                    Arg::new("amount")
+                        .help("Amount of saturation to add (number between 0.0 and 1.0)")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("desaturate")
+                .long_about(
+                    "Decrease the saturation of a color by subtracting a certain amount from the \
+                     HSL saturation channel. If the amount is negative, the color will be saturated \
+                     instead.",
+                )
+                .about("Decrease color saturation by a specified amount")
+                .arg(
+                    Arg::new("amount")
+                        .help("Amount of saturation to subtract (number between 0.0 and 1.0)")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("lighten")
+                .long_about(
+                    "Lighten a color by adding a certain amount to the HSL lightness channel. \
+                     If the amount is negative, the color will be darkened.",
+                )
+                .about("Lighten color by a specified amount")
+                .arg(
+                    Arg::new("amount")
+                        .help("Amount of lightness to add (number between 0.0 and 1.0)")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("darken")
+                .long_about(
+                    "Darken a color by subtracting a certain amount from the lightness channel. \
+                     If the amount is negative, the color will be lightened.",
+                )
+                .about("Darken color by a specified amount")
+                .arg(
+                    Arg::new("amount")
+                        .help("Amount of lightness to subtract (number between 0.0 and 1.0)")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("rotate")
+                .about("Rotate the hue channel by the specified angle")
+                .long_about(
+                    "Rotate the HSL hue channel of a color by the specified angle (in \
+                     degrees). A rotation by 180° returns the complementary color. A \
+                     rotation by 360° returns to the original color.",
+                )
+                .arg(
+                    Arg::new("degrees")
+                        .help("angle by which to rotate (in degrees, can be negative)")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("max-chroma")
+                .about("Maximize the chroma of a color within the sRGB gamut")
+                .long_about(
+                    "Find the most colorful version of the given color by increasing its CIE LCh \
+                     chroma as far as possible while keeping lightness and hue fixed and staying \
+                     inside the sRGB gamut. Grays are left unchanged.\n\n\
+                     Example:\n  \
+                       pastel max-chroma 'lch(60, 10, 140)'",
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("match-lightness")
+                .about("Adjust the lightness of colors to match a reference color")
+                .long_about(
+                    "Set the CIE LCh lightness (L*) of each color to the lightness of the \
+                     reference color, such that all colors have the same visual weight (e.g. \
+                     for icon sets or category colors). The hue is kept, and the chroma is \
+                     reduced if necessary to stay within the sRGB gamut.\n\n\
+                     Example:\n  \
+                       pastel match-lightness steelblue crimson gold seagreen",
+                )
+                .arg(
+                    Arg::new("reference")
+                        .value_name("reference")
+                        .help("The color whose lightness is matched")
+                        .required(true),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("match-chroma")
+                .about("Set the chroma of all colors to a common value")
+                .long_about(
+                    "Set the OkLCh chroma of each color to a common value, e.g. to harmonize a \
+                     palette that was collected from different sources. By default, the median \
+                     chroma of all given colors is used. Lightness and hue are kept, and the \
+                     chroma is reduced if necessary to stay within the sRGB gamut. Gray colors \
+                     are left unchanged.\n\n\
+                     Examples:\n  \
+                       pastel match-chroma crimson steelblue gold seagreen\n  \
+                       pastel match-chroma --target 0.1 < palette.txt",
+                )
+                .arg(
+                    Arg::new("target")
+                        .long("target")
+                        .short('t')
+                        .help("The target chroma: 'median' or an OkLCh chroma value (typically \
+                               between 0 and 0.37)")
+                        .takes_value(true)
+                        .default_value("median")
+                        .value_name("median|value"),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("complement")
+                .about("Get the complementary color (hue rotated by 180°)")
+                .long_about(
+                    "Compute the complementary color by rotating the HSL hue channel by 180°.",
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(
+            Command::new("harmonies")
+                .about("Generate classical color harmonies from a base color")
+                .long_about(
+                    "Generate a color harmony from the given base color by rotating its hue \
+                     around the color wheel (or by varying the lightness, for \
+                     'monochromatic'). The hue can be measured in HSL (the classical color \
+                     wheel) or in OkLCh (perceptually uniform, preserves lightness and chroma).\n\n\
+                     Examples:\n  \
+                       pastel harmonies triadic coral\n  \
+                       pastel harmonies analogous -n 5 --angle 20 --colorspace OkLCh teal",
+                )
+                .arg(
+                    Arg::new("harmony")
+                        .help("The type of harmony")
+                        .possible_values([
+                            "complementary",
+                            "split-complementary",
+                            "triadic",
+                            "tetradic",
+                            "analogous",
+                            "monochromatic",
+                        ])
+                        .ignore_case(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("colorspace")
+                        .long("colorspace")
+                        .short('s')
+                        .help("The color space in which the hue is measured")
+                        .possible_values(["HSL", "OkLCh"])
+                        .ignore_case(true)
+                        .default_value("HSL"),
+                )
+                .arg(
+                    Arg::new("number")
+                        .long("number")
+                        .short('n')
+                        .help("Number of colors for 'analogous' (default: 3) and 'monochromatic' \
+                               (default: 5)")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("angle")
+                        .long("angle")
+                        .help("Hue difference between neighboring colors for 'analogous' (in \
+                               degrees)")
+                        .takes_value(true)
+                        .default_value("30"),
+                )
+                .arg(color_arg.clone()),
+        )
+        .subcommand(ramp_command(
+            "shades",
+            "Generate a ramp of shades (mixtures with black)",
+            "Generate a ramp of shades (mixtures with black). The base color is mixed with black \
+             in evenly spaced steps, starting with the base color itself.\n\n\
+             Example:\n  \
+               pastel shades -n 10 --colorspace OkLab steelblue",
+        ))
+        .subcommand(ramp_command(
+            "tints",
+            "Generate a ramp of tints (mixtures with white)",
+            "Generate a ramp of tints (mixtures with white). The base color is mixed with white \
+             in evenly spaced steps, starting with the base color itself.\n\n\
+             Example:\n  \
+               pastel tints -n 10 --colorspace OkLab steelblue",
+        ))
+        .subcommand(ramp_command(
+            "tones",
+            "Generate a ramp of tones (mixtures with gray)",
+            "Generate a ramp of tones (mixtures with gray). The base color is mixed with gray (#808080) \
+             in evenly spaced steps, starting with the base color itself.\n\n\
+             Example:\n  \
+               pastel tones -n 10 --colorspace OkLab steelblue",
+        ))
+        .subcommand(
+            Command::new("gray")
+                .about("Create a gray tone from a given lightness")
+                .long_about("Create a gray tone from a given lightness value.")
+                .arg(
+                    Arg::new("lightness")
+                        .help("Lightness of the created gray tone (number between 0.0 and 1.0)")
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("kelvin")
+                .about("Create the color of a light source with a given color temperature")
+                .long_about("Create the color of a black-body radiator (an ideal light source) \
+                             with the given color temperature in Kelvin. Low temperatures result \
+                             in warm, reddish colors, high temperatures in cool, bluish colors. \
+                             The colors are scaled to full brightness. The inverse conversion is \
+                             available via 'pastel format cct'.\n\n\
+                             Examples:\n  \
+                               pastel kelvin 2700 4000 6500\n  \
+                               pastel kelvin 1800K")
+                .arg(
+                    Arg::new("temperature")
+                        .help("Color temperature in Kelvin, between 1000 and 40000. An optional \
+                               'K' suffix is allowed.")
+                        .multiple_occurrences(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("wavelength")
+                .about("Create the color of monochromatic light with a given wavelength")
+                .long_about("Create the color of monochromatic light with the given wavelength in \
+                             nanometers, based on the CIE 1931 color matching functions. Spectral \
+                             colors lie outside of the sRGB gamut, so the closest displayable \
+                             color is shown. Colors fade out towards both ends of the visible \
+                             range (380 nm to 780 nm).\n\n\
+                             Examples:\n  \
+                               pastel wavelength 450 532 650\n  \
+                               seq 380 20 780 | xargs pastel wavelength | pastel format hex")
+                .arg(
+                    Arg::new("wavelength")
+                        .help("Wavelength in nanometers, between 380 and 780. An optional 'nm' \
+                               suffix is allowed.")
+                        .multiple_occurrences(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("system-colors")
+                .about("Read the colors of the current GTK or KDE desktop theme")
+                .long_about("Read the accent, background and foreground colors (and all other \
+                             named colors) from the desktop theme configuration. For GTK, colors \
+                             are taken from '@define-color' statements in gtk.css and from the \
+                             'gtk-color-scheme' setting in settings.ini (within 'gtk-3.0' and \
+                             'gtk-4.0' in $XDG_CONFIG_HOME or ~/.config). For KDE, colors are \
+                             read from kdeglobals. Alternatively, a GTK style sheet or a KDE \
+                             color scheme can be given via '--file'.\n\n\
+                             If color names are given, only those colors are printed, which \
+                             allows for using them in pipelines.\n\n\
+                             Examples:\n  \
+                               pastel system-colors\n  \
+                               pastel system-colors accent | pastel lighten 0.1\n  \
+                               pastel system-colors --file BreezeDark.colors view-background-normal")
+                .arg(
+                    Arg::new("source")
+                        .long("source")
+                        .short('s')
+                        .help("The desktop toolkit to read the theme configuration from. 'auto' \
+                               prefers KDE on KDE desktops and GTK otherwise.")
+                        .possible_values(["auto", "gtk", "kde"])
+                        .default_value("auto"),
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .short('f')
+                        .help("Read colors from the given file instead: a GTK style sheet \
+                               ('*.css'), a GTK 'settings.ini' file or a KDE color scheme")
+                        .takes_value(true)
+                        .value_name("path")
+                        .conflicts_with("source"),
+                )
+                .arg(
+                    Arg::new("name")
+                        .help("Names of the colors to print (e.g. 'accent', 'background' or \
+                               'foreground')")
+                        .multiple_occurrences(true),
+                ),
+        )
+        .subcommand(
+            Command::new("system-accent")
+                .about("Print the accent color of the operating system")
+                .long_about("Print the accent (highlight) color of the operating system. On \
+                             macOS, this is read from the 'AppleAccentColor' user default, on \
+                             Windows from the 'AccentColor' value of the desktop window manager \
+                             in the registry. On other systems, the accent color of the GTK or \
+                             KDE theme is used (see 'pastel system-colors').\n\n\
+                             Example:\n  \
+                               pastel system-accent | pastel textcolor"),
+        )
+        .subcommand(
+            Command::new("edit")
+                .about("Interactively adjust a color with keybindings")
+                .long_about("Start an interactive editor for a color. The hue, saturation, \
+                             lightness (HSL) and chroma (LCh) can be nudged with the keys \
+                             h/H, s/S, l/L and c/C (lower case decreases, upper case increases), \
+                             or with the arrow keys (hue and lightness). The hex, RGB, HSL and \
+                             LCh values and the contrast ratios against white and black are \
+                             updated live. 'r' resets the color. Enter (or 'q') prints the final \
+                             color and exits, Escape cancels.\n\n\
+                             The editor is drawn on standard error, such that the final color \
+                             can be captured from standard output.\n\n\
+                             Example:\n  \
+                               color=$(pastel edit '#3b82f6' | pastel format hex)")
+                .arg(
+                    Arg::new("color")
+                        .value_name("color")
+                        .help("The initial color")
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("terminal-background")
+                .about("Print the background color of the terminal")
+                .long_about("Print the background color of the terminal, as reported by the \
+                             terminal in response to an OSC 11 query. If the terminal does not \
+                             answer (within 100 ms), an error is returned. The environment \
+                             variable PASTEL_TERMINAL_BACKGROUND overrides the detection. pastel \
+                             uses this color to adapt its color panels to light terminal \
+                             backgrounds.\n\n\
+                             Example:\n  \
+                               pastel terminal-background | pastel textcolor"),
+        )
+        .subcommand(
+            Command::new("hash-color")
+                .about("Create a stable color from an arbitrary string")
+                .long_about("Deterministically map arbitrary strings (like user names, host names \
+                             or branch names) to colors. The hue is derived from a hash of the \
+                             string, while lightness and chroma (in OkLCh) are fixed. The same \
+                             string always results in the same color. If no strings are given, \
+                             they are read from standard input (one per line).\n\n\
+                             Examples:\n  \
+                               pastel paint \"$(pastel hash-color \"$(hostname)\")\" \"$(hostname)\"\n  \
+                               PS1=\"\\[$(pastel hash-color --against '#1e1e2e' \
+                               --format ansi-escapecode \"$USER\")\\]\\u\\[\\e[0m\\] $ \"")
+                .arg(
+                    Arg::new("lightness")
+                        .long("lightness")
+                        .short('l')
+                        .help("OkLCh lightness of the created colors (number between 0.0 and 1.0)")
+                        .takes_value(true)
+                        .default_value("0.7"),
+                )
+                .arg(
+                    Arg::new("chroma")
+                        .long("chroma")
+                        .short('c')
+                        .help("OkLCh chroma of the created colors (non-negative number). The \
+                               chroma is reduced if necessary to stay within the sRGB gamut.")
+                        .takes_value(true)
+                        .default_value("0.12"),
+                )
+                .arg(
+                    Arg::new("against")
+                        .long("against")
+                        .short('a')
+                        .help("Background color (e.g. of the terminal). The lightness of the \
+                               created colors is adjusted if necessary to reach the contrast \
+                               ratio given by '--min-contrast'.")
+                        .takes_value(true)
+                        .value_name("color"),
+                )
+                .arg(
+                    Arg::new("min-contrast")
+                        .long("min-contrast")
+                        .help("Minimum (WCAG) contrast ratio with respect to the '--against' \
+                               color (number between 1 and 21)")
+                        .takes_value(true)
+                        .value_name("ratio")
+                        .default_value("4.5"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .short('f')
+                        .help("Print the colors in the given format instead of showing them. \
+                               'ansi-escapecode' prints the escape sequence to switch the \
+                               foreground color (24-bit or 8-bit, depending on the terminal), \
+                               which can be used directly in a shell prompt.")
+                        .takes_value(true)
+                        .value_name("type")
+                        .possible_values(["hex", "rgb", "hsl", "ansi-escapecode",
+                                          "ansi-8bit-escapecode", "ansi-24bit-escapecode"]),
+                )
+                .arg(
+                    Arg::new("text")
+                        .help("The string(s) to map to a color")
+                        .multiple_occurrences(true),
+                ),
+        )
+        .subcommand(
+            Command::new("to-gray")
+                .about("Completely desaturate a color (preserving luminance)")
                 .long_about(
-                    "Convert the given color to how it would look to a person with protanopia, \
-                    deuteranopia, or tritanopia \n\n\
-                     Example:\n  \
-                       pastel distinct 3 | pastel colorblind deuter")
-                .arg(
-                    Arg::new("type")
-                        .help("The type of colorblindness that should be simulated (protanopia, \
-                               deuteranopia, tritanopia)")
-                        .possible_values(["prot", "deuter", "trit"])
-                        .ignore_case(true)
-                        .required(true),
+                    "Completely desaturate the given color while preserving the luminance.\n\
+                     \n\
+                     For a definition of 'luminance', see:\n\n  \
+                     https://www.w3.org/TR/2008/REC-WCAG20-20081211/#relativeluminancedef",
                 )
                 .arg(color_arg.clone()),
         )
         .subcommand(
-            Command::new("set")
-                .about("Set a color property to a specific value")
-                .long_about("Set the given property to a specific value\n\
-                Example:\n  \
-                  pastel random | pastel set luminance 0.9")
+            Command::new("textcolor")
+                .about("Get a readable text color for the given background color")
+                .long_about("Return a readable foreground text color (either black or white) for a \
+                            given background color. This can also be used in the opposite way, \
+                            i.e. to create a background color for a given text color.\n\n\
+                            With '--candidates', the most readable color of the given palette is \
+                            chosen instead. If a minimum contrast is required ('--min-contrast'), \
+                            the candidates are treated in order of preference: the first one \
+                            that reaches the minimum contrast is chosen.\n\n\
+                            Examples:\n  \
+                              pastel textcolor --candidates '#1e293b #f8fafc #64748b' teal\n  \
+                              pastel textcolor --algorithm apca --min-contrast 60 --verbose \
+                              --candidates 'navy #333 black' skyblue")
                 .arg(
-                    Arg::new("property")
-                        .help("The property that should be changed")
-                        .possible_values(["lightness", "hue", "chroma",
-                                           "lab-a", "lab-b",
-                                           "oklab-l", "oklab-a", "oklab-b",
-                                           "red", "green", "blue",
-                                           "hsl-hue", "hsl-saturation", "hsl-lightness",
-                                           "alpha"])
-                        .ignore_case(true)
-                        .required(true),
+                    Arg::new("candidates")
+                        .long("candidates")
+                        .short('c')
+                        .help("Choose the text color from these colors (separated by spaces) \
+                               instead of black and white")
+                        .takes_value(true)
+                        .multiple_occurrences(true)
+                        .value_name("colors"),
                 )
                 .arg(
-                    Arg::new("value")
-                        .help("The new numerical value of the property")
-                        .required(true),
+                    Arg::new("min-contrast")
+                        .long("min-contrast")
+                        .help("Choose the first candidate with at least this contrast (a WCAG \
+                               contrast ratio, or an absolute APCA Lc value)")
+                        .takes_value(true)
+                        .value_name("contrast"),
+                )
+                .arg(
+                    Arg::new("algorithm")
+                        .long("algorithm")
+                        .short('a')
+                        .help("Contrast algorithm")
+                        .takes_value(true)
+                        .possible_values(["wcag2", "apca"])
+                        .value_name("name")
+                        .default_value("wcag2"),
+                )
+                .arg(
+                    Arg::new("verbose")
+                        .long("verbose")
+                        .short('v')
+                        .help("Print the contrast of the chosen text color to STDERR"),
                 )
                 .arg(color_arg.clone()),
         )
         .subcommand(
-            Command::new("saturate")
-                .long_about(
-                    "Increase the saturation of a color by adding a certain amount to the HSL \
-                     saturation channel. If the amount is negative, the color will be desaturated \
-                     instead.",
+            Command::new("contrast")
+                .about("Compute the contrast of text colors on a background color")
+                .long_about("Compute the contrast of each (text) color on the given background \
+                            color. The 'wcag2' algorithm computes the WCAG 2 contrast ratio \
+                            between 1.0 and 21.0 (4.5 or higher is recommended for text). The \
+                            'apca' algorithm computes the APCA lightness contrast (Lc), which \
+                            depends on the polarity: it ranges from about 106 (dark text on a \
+                            light background) to about -108 (light text on a dark background). \
+                            An absolute value of 60 or higher is recommended for body text.\n\n\
+                            With '--explain', the luminances of both colors are printed as well.\n\n\
+                            Examples:\n  \
+                              pastel contrast white black\n  \
+                              pastel contrast --algorithm apca '#fafafa' '#333' '#777'\n  \
+                              pastel contrast --explain white '#777'")
+                .arg(
+                    Arg::new("algorithm")
+                        .long("algorithm")
+                        .short('a')
+                        .help("Contrast algorithm")
+                        .takes_value(true)
+                        .possible_values(["wcag2", "apca"])
+                        .value_name("name")
+                        .default_value("wcag2"),
                 )
-                .about("Increase color saturation by a specified amount")
                 .arg(
-                    Arg::new("amount")
-                        .help("Amount of saturation to add (number between 0.0 and 1.0)")
+                    Arg::new("explain")
+                        .long("explain")
+                        .help("Break the contrast down into the luminances of both colors"),
+                )
+                .arg(
+                    Arg::new("background")
+                        .value_name("background")
+                        .help("The background color")
                         .required(true),
                 )
                 .arg(color_arg.clone()),
         )
         .subcommand(
-            Command::new("desaturate")
-                .long_about(
-                    "Decrease the saturation of a color by subtracting a certain amount from the \
-                     HSL saturation channel. If the amount is negative, the color will be saturated \
-                     instead.",
+            Command::new("adjust-contrast")
+                .about("Adjust text colors until they reach a contrast on a background color")
+                .long_about("Minimally change the lightness (in OkLCh) of each (text) color, such \
+                            that it reaches the given contrast on the background color. Hue and \
+                            chroma are preserved as far as possible. The color is made lighter or \
+                            darker, whichever requires the smaller change. Colors that already \
+                            reach the target are printed unchanged.\n\n\
+                            The target is a WCAG 2 contrast ratio (default: 4.5) or an absolute \
+                            APCA Lc value (default: 60), depending on the algorithm. If it can not \
+                            be reached, black or white is printed instead and a warning is \
+                            printed on stderr.\n\n\
+                            Examples:\n  \
+                              pastel adjust-contrast white '#6a9fb5'\n  \
+                              pastel adjust-contrast --algorithm apca --target 75 '#1e1e2e' navy")
+                .arg(
+                    Arg::new("target")
+                        .long("target")
+                        .short('t')
+                        .help("The contrast to reach (a WCAG contrast ratio between 1 and 21, or \
+                               an absolute APCA Lc value between 0 and 108)")
+                        .takes_value(true)
+                        .value_name("contrast"),
                 )
-                .about("Decrease color saturation by a specified amount")
                 .arg(
-                    Arg::new("amount")
-                        .help("Amount of saturation to subtract (number between 0.0 and 1.0)")
+                    Arg::new("algorithm")
+                        .long("algorithm")
+                        .short('a')
+                        .help("Contrast algorithm")
+                        .takes_value(true)
+                        .possible_values(["wcag2", "apca"])
+                        .value_name("name")
+                        .default_value("wcag2"),
+                )
+                .arg(
+                    Arg::new("verbose")
+                        .long("verbose")
+                        .short('v')
+                        .help("Print the contrast of the adjusted color to STDERR"),
+                )
+                .arg(
+                    Arg::new("background")
+                        .value_name("background")
+                        .help("The background color")
                         .required(true),
                 )
                 .arg(color_arg.clone()),
         )
         .subcommand(
-            Command::new("lighten")
-                .long_about(
-                    "Lighten a color by adding a certain amount to the HSL lightness channel. \
-                     If the amount is negative, the color will be darkened.",
-                )
-                .about("Lighten color by a specified amount")
+            Command::new("a11y-report")
+                .about("Print an accessibility report for a set of colors")
+                .long_about("Print an accessibility report for the given set of colors (a \
+                             palette). The first part is a matrix of the WCAG contrast ratios \
+                             between all pairs of colors, where each ratio is marked with the \
+                             conformance level it reaches: 'AAA' (7 or higher), 'AA' (4.5 or \
+                             higher) or 'fail'. With '--large-text', the lower thresholds for \
+                             large text are used instead (4.5 and 3). The second part shows the \
+                             whole palette as it is seen with protanopia, deuteranopia and \
+                             tritanopia. All colors need to be opaque.\n\n\
+                             Example:\n  \
+                               pastel a11y-report white '#1e1e2e' '#f38ba8' '#a6e3a1'")
                 .arg(
-                    Arg::new("amount")
-                        .help("Amount of lightness to add (number between 0.0 and 1.0)")
-                        .required(true),
+                    Arg::new("large-text")
+                        .long("large-text")
+                        .help("Use the WCAG thresholds for large text (at least 18pt, or 14pt \
+                               bold)"),
                 )
+                .arg(input_format_arg.clone())
                 .arg(color_arg.clone()),
         )
         .subcommand(
-            Command::new("darken")
-                .long_about(
-                    "Darken a color by subtracting a certain amount from the lightness channel. \
-                     If the amount is negative, the color will be lightened.",
+            Command::new("distance")
+                .about("Compute the perceptual distance between colors")
+                .long_about("Compute the perceptual distance (color difference) between a \
+                            reference color and each of the other colors. The CIE76 and \
+                            CIEDE2000 metrics compute the delta-E in the CIELAB color space, \
+                            where a distance of about 1 to 2.3 is just noticeable. 'OkLab' \
+                            computes the Euclidean distance in the OkLab color space, where \
+                            distances are about a hundred times smaller.\n\n\
+                            With '--threshold', pastel exits with status 1 if any of the \
+                            distances is below the threshold, i.e. if the colors are too \
+                            similar. With '--explain', the distance is broken down into the \
+                            lightness, chroma and hue differences, along with the dominant \
+                            one.\n\n\
+                            Examples:\n  \
+                              pastel distance red orange\n  \
+                              pastel distance --explain '#336699' '#3a6a9a'\n  \
+                              pastel distance --metric CIE76 '#336699' '#336798' '#3a6a9a'\n  \
+                              pastel distance --threshold 10 '#333' '#3a3a3a' > /dev/null \
+                              || echo 'too similar'")
+                .arg(
+                    Arg::new("metric")
+                        .long("metric")
+                        .short('m')
+                        .help("Distance metric")
+                        .takes_value(true)
+                        .possible_values(["CIEDE2000", "CIE76", "OkLab"])
+                        .ignore_case(true)
+                        .value_name("name")
+                        .default_value("CIEDE2000"),
                 )
-                .about("Darken color by a specified amount")
                 .arg(
-                    Arg::new("amount")
-                        .help("Amount of lightness to subtract (number between 0.0 and 1.0)")
+                    Arg::new("threshold")
+                        .long("threshold")
+                        .short('t')
+                        .help("Exit with status 1 if any distance is below this value")
+                        .takes_value(true)
+                        .value_name("distance"),
+                )
+                .arg(
+                    Arg::new("explain")
+                        .long("explain")
+                        .help("Break the distance down into the coordinates of both colors and \
+                               the lightness, chroma and hue differences"),
+                )
+                .arg(
+                    Arg::new("color")
+                        .help("The reference color")
                         .required(true),
                 )
-                .arg(color_arg.clone()),
+                .arg(
+                    Arg::new("colors")
+                        .help("The colors to compare with the reference color. If no colors are \
+                               given, they are read from standard input.")
+                        .multiple_occurrences(true),
+                ),
         )
         .subcommand(
-            Command::new("rotate")
-                .about("Rotate the hue channel by the specified angle")
-                .long_about(
-                    "Rotate the HSL hue channel of a color by the specified angle (in \
-                     degrees). A rotation by 180° returns the complementary color. A \
-                     rotation by 360° returns to the original color.",
+            Command::new("distance-matrix")
+                .about("Print the matrix of mutual distances between colors")
+                .long_about("Compute the perceptual distance between all pairs of colors. By \
+                            default, the matrix is printed as a table where small distances are \
+                            highlighted. Alternatively, it can be written as CSV or JSON for \
+                            further analysis. See 'pastel distance --help' for the supported \
+                            metrics.\n\n\
+                            Examples:\n  \
+                              pastel distinct 6 | pastel distance-matrix\n  \
+                              pastel distance-matrix --format csv red orange yellow > matrix.csv")
+                .arg(
+                    Arg::new("metric")
+                        .long("metric")
+                        .short('m')
+                        .help("Distance metric")
+                        .takes_value(true)
+                        .possible_values(["CIEDE2000", "CIE76", "OkLab"])
+                        .ignore_case(true)
+                        .value_name("name")
+                        .default_value("CIEDE2000"),
                 )
                 .arg(
-                    Arg::new("degrees")
-                        .help("angle by which to rotate (in degrees, can be negative)")
-                        .required(true),
+                    Arg::new("format")
+                        .long("format")
+                        .short('f')
+                        .help("Output format")
+                        .takes_value(true)
+                        .possible_values(["table", "csv", "json"])
+                        .value_name("name")
+                        .default_value("table"),
                 )
                 .arg(color_arg.clone()),
         )
         .subcommand(
-            Command::new("complement")
-                .about("Get the complementary color (hue rotated by 180°)")
-                .long_about(
-                    "Compute the complementary color by rotating the HSL hue channel by 180°.",
+            Command::new("roundtrip")
+                .about("Check the precision of conversions between color spaces")
+                .long_about("Convert each color to every supported color space (and \
+                            representation) and back, and print the maximum error in the RGB \
+                            and alpha channels (in the range from 0 to 1). Spaces where the error \
+                            exceeds the tolerance are flagged, and pastel exits with status 1. \
+                            Note that most conversions back to pastel's internal representation \
+                            pass through 8-bit RGB values, so colors that can not be represented \
+                            exactly show errors of up to 0.002. CMYK does not store the alpha \
+                            channel.\n\n\
+                            Example:\n  \
+                              pastel roundtrip '#336699' 'hsl(200, 40%, 33.3%)'")
+                .arg(
+                    Arg::new("tolerance")
+                        .long("tolerance")
+                        .short('t')
+                        .help("Maximum channel error that is not considered to be a loss of \
+                               precision")
+                        .takes_value(true)
+                        .value_name("error")
+                        .default_value("1e-6"),
                 )
                 .arg(color_arg.clone()),
         )
         .subcommand(
-            Command::new("gray")
-                .about("Create a gray tone from a given lightness")
-                .long_about("Create a gray tone from a given lightness value.")
+            Command::new("elevation")
+                .about("Generate Material dark-theme elevation overlay colors")
+                .long_about("Generate the surface colors for different elevation levels of the \
+                            Material Design dark theme, where a white overlay with increasing \
+                            opacity (5% at 1dp up to 16% at 24dp) is placed over the surface \
+                            color. With '--named', the colors are printed as a named palette.\n\n\
+                            Examples:\n  \
+                              pastel elevation --levels 0..24dp\n  \
+                              pastel elevation '#1e1b2e' --levels 1,4,8 --named")
                 .arg(
-                    Arg::new("lightness")
-                        .help("Lightness of the created gray tone (number between 0.0 and 1.0)")
+                    Arg::new("surface")
+                        .value_name("surface")
+                        .help("The surface color at 0dp")
+                        .default_value("#121212"),
+                )
+                .arg(
+                    Arg::new("levels")
+                        .long("levels")
+                        .help("Either a range like '0..24dp' (all standard Material elevation \
+                               levels in that range) or a list of levels like '1,4,8dp'")
+                        .takes_value(true)
+                        .default_value("0..24dp")
+                        .value_name("levels"),
+                )
+                .arg(
+                    Arg::new("overlay")
+                        .long("overlay")
+                        .help("The color of the overlay")
+                        .takes_value(true)
+                        .default_value("white")
+                        .value_name("color"),
+                )
+                .arg(
+                    Arg::new("named")
+                        .long("named")
+                        .help("Print the colors as a named palette ('elevation-<level>dp: <hex>')"),
+                ),
+        )
+        .subcommand(
+            Command::new("states")
+                .about("Generate interaction state colors for a design system")
+                .long_about("Generate the colors of the hover, focus, pressed, dragged and \
+                            disabled states of a component, as a named palette. Following the \
+                            Material Design guidelines, the base color is placed as a state layer \
+                            with a fixed opacity (8% for hover, 10% for focus and pressed, 16% \
+                            for dragged) on top of the container color. For the disabled state, \
+                            the base color is desaturated and placed with 38% opacity.\n\n\
+                            Example:\n  \
+                              pastel states '#6750a4' --on '#fffbfe' --prefix primary")
+                .arg(
+                    Arg::new("base")
+                        .value_name("base")
+                        .help("The base color of the component content (used for the state \
+                               layer)")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("on")
+                        .long("on")
+                        .help("The container color on which the state layer is placed")
+                        .takes_value(true)
+                        .default_value("white")
+                        .value_name("container"),
+                )
+                .arg(
+                    Arg::new("prefix")
+                        .long("prefix")
+                        .help("Prefix for the names of the states, e.g. 'primary' for \
+                               'primary-hover'")
+                        .takes_value(true)
+                        .value_name("name"),
+                ),
+        )
+        .subcommand(
+            Command::new("tonal-palette")
+                .about("Generate a tonal palette (50, 100, …, 900) from a base color")
+                .long_about("Generate a Material/Tailwind-style tonal palette with the steps 50, \
+                            100, 200, …, 900 (from light to dark) from a base color. The steps \
+                            have fixed lightness values in OkLCh and keep the hue of the base \
+                            color. The chroma is highest in the mid tones and reduced towards the \
+                            light and dark ends (and where necessary to stay within the sRGB \
+                            gamut). The step closest to the base color is the base color itself.\n\n\
+                            Examples:\n  \
+                              pastel tonal-palette '#3b82f6'\n  \
+                              pastel tonal-palette '#3b82f6' --name brand --format css-vars")
+                .arg(
+                    Arg::new("color")
+                        .value_name("color")
+                        .help("The base color of the palette")
                         .required(true),
+                )
+                .arg(
+                    Arg::new("name")
+                        .long("name")
+                        .help("Name of the palette, used as a prefix for the steps, e.g. 'brand' \
+                               for 'brand-500' (default for the export formats: 'primary')")
+                        .takes_value(true)
+                        .value_name("name"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .short('f')
+                        .help("Print the palette as CSS custom properties, SCSS variables or as \
+                               an entry for the colors of a Tailwind configuration")
+                        .takes_value(true)
+                        .possible_values(["css-vars", "scss", "tailwind"])
+                        .value_name("name"),
                 ),
         )
         .subcommand(
-            Command::new("to-gray")
-                .about("Completely desaturate a color (preserving luminance)")
+            Command::new("theme")
+                .about("Generate and export terminal color schemes")
                 .long_about(
-                    "Completely desaturate the given color while preserving the luminance.\n\
-                     \n\
-                     For a definition of 'luminance', see:\n\n  \
-                     https://www.w3.org/TR/2008/REC-WCAG20-20081211/#relativeluminancedef",
+                    "Derive a complete terminal color scheme (the 16 ANSI colors along with \
+                     background, foreground and cursor colors) from a background color and an \
+                     optional accent color, and write it in the configuration format of a \
+                     terminal emulator.\n\n\
+                     Examples:\n  \
+                       pastel theme generate '#1e1e2e' '#89b4fa'\n  \
+                       pastel theme generate '#1e1e2e' | pastel theme export --format kitty",
                 )
-                .arg(color_arg.clone()),
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("generate")
+                        .about("Generate a terminal color scheme from one or two seed colors")
+                        .long_about(
+                            "Generate a terminal color scheme and print it as a named palette \
+                             ('name: #hex'). The foreground and the gray tones take over the hue \
+                             of the background. The six chromatic colors (red, green, yellow, \
+                             blue, magenta and cyan) keep a contrast ratio of at least 4.5 \
+                             against the background and are optimized to be as distinct as \
+                             possible (as in 'pastel distinct'), while staying close to their \
+                             usual hues. Their chroma follows the accent color, which replaces \
+                             the chromatic color with the closest hue and is used for the \
+                             cursor. The bright variants are lighter on dark backgrounds and \
+                             darker on light backgrounds.",
+                        )
+                        .arg(
+                            Arg::new("background")
+                                .help("The background color of the scheme")
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("accent")
+                                .help("An accent color that is included in the scheme"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("export")
+                        .about("Write a terminal color scheme for a terminal emulator")
+                        .long_about(
+                            "Convert a color scheme as printed by 'pastel theme generate' into \
+                             the configuration format of a terminal emulator. The scheme needs \
+                             lines of the form 'name: color' for 'background', 'foreground', \
+                             'cursor' (optional) and the 16 ANSI colors 'black', 'red', …, \
+                             'bright-white'.",
+                        )
+                        .arg(
+                            Arg::new("file")
+                                .help("File with the color scheme. If not given, the scheme is \
+                                       read from STDIN."),
+                        )
+                        .arg(
+                            Arg::new("format")
+                                .long("format")
+                                .short('f')
+                                .help("The terminal emulator")
+                                .takes_value(true)
+                                .possible_values([
+                                    "alacritty",
+                                    "kitty",
+                                    "wezterm",
+                                    "windows-terminal",
+                                    "xresources",
+                                    "iterm2",
+                                ])
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::new("name")
+                                .long("name")
+                                .help("Name of the color scheme (for WezTerm and Windows \
+                                       Terminal)")
+                                .takes_value(true)
+                                .value_name("name")
+                                .default_value("pastel"),
+                        ),
+                ),
         )
         .subcommand(
-            Command::new("textcolor")
-                .about("Get a readable text color for the given background color")
-                .long_about("Return a readable foreground text color (either black or white) for a \
-                            given background color. This can also be used in the opposite way, \
-                            i.e. to create a background color for a given text color.")
+            Command::new("apply")
+                .about("Set the colors of the running terminal")
+                .long_about(
+                    "Change the palette colors, the foreground, background and cursor color of \
+                     the running terminal via OSC 4/10/11/12 escape sequences, e.g. to preview a \
+                     color scheme without editing configuration files. The colors are read as a \
+                     named palette with lines of the form 'name: color', where the names are \
+                     'background', 'foreground', 'cursor', the ANSI color names ('black', 'red', \
+                     …, 'bright-white') or 'color0' to 'color255'. The changes last until the \
+                     terminal is reset or closed. Not all terminals support all sequences.\n\n\
+                     Examples:\n  \
+                       pastel theme generate '#1e1e2e' | pastel apply\n  \
+                       echo 'red: tomato' | pastel apply\n  \
+                       pastel apply --reset",
+                )
+                .arg(
+                    Arg::new("file")
+                        .help("File with the named colors. If not given, the colors are read \
+                               from STDIN."),
+                )
+                .arg(
+                    Arg::new("reset")
+                        .long("reset")
+                        .help("Reset all colors to the defaults of the terminal configuration")
+                        .conflicts_with("file"),
+                ),
+        )
+        .subcommand(
+            Command::new("glass")
+                .about("Predict the colors of a semi-transparent layer over backgrounds")
+                .long_about("Predict the result of placing a semi-transparent 'glass' color \
+                            (e.g. a frosted-glass panel) over each of the given background \
+                            colors. Prints the lightest and darkest resulting color, the largest \
+                            color difference (CIEDE2000) between the results and the worst-case \
+                            contrast of the text color on the glass. For 'apca', the absolute \
+                            value of the lightness contrast is used.\n\n\
+                            Examples:\n  \
+                              pastel glass white --opacity 0.7 --text black navy coral gold\n  \
+                              pastel random -n 50 | pastel glass --list '#222' --opacity 0.8")
+                .arg(
+                    Arg::new("glass")
+                        .value_name("glass")
+                        .help("The color of the glass layer")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("opacity")
+                        .long("opacity")
+                        .short('o')
+                        .help("Opacity of the glass layer (between 0.0 and 1.0)")
+                        .takes_value(true)
+                        .default_value("0.6")
+                        .value_name("alpha"),
+                )
+                .arg(
+                    Arg::new("text")
+                        .long("text")
+                        .short('t')
+                        .help("The text color for the contrast check. Defaults to black or \
+                               white, whichever has more contrast to the glass color.")
+                        .takes_value(true)
+                        .value_name("color"),
+                )
+                .arg(
+                    Arg::new("algorithm")
+                        .long("algorithm")
+                        .short('a')
+                        .help("Contrast algorithm")
+                        .takes_value(true)
+                        .possible_values(["wcag2", "apca"])
+                        .value_name("name")
+                        .default_value("wcag2"),
+                )
+                .arg(
+                    Arg::new("list")
+                        .long("list")
+                        .short('l')
+                        .help("Only print the resulting color for each background"),
+                )
                 .arg(color_arg.clone()),
         )
         .subcommand(
             Command::new("colorcheck")
-                .about("Check if your terminal emulator supports 24-bit colors."),
+                .about("Check if your terminal emulator supports 24-bit colors.")
+                .arg(
+                    Arg::new("probe")
+                        .long("probe")
+                        .help("Instead of showing color panels, automatically check for 24-bit \
+                               support by setting a 24-bit color and querying the terminal for \
+                               the result. Prints '24bit' (exit code 0), '8bit' (exit code 1) \
+                               or 'unknown' (exit code 2, if the terminal does not support the \
+                               query). Example:\n  \
+                                 pastel colorcheck --probe && export PASTEL_COLOR_MODE=24bit")
+                ),
+        )
+        .subcommand(
+            Command::new("demo")
+                .about("Show a short tour of pastel's capabilities")
+                .long_about("Show a short tour of pastel's capabilities in a single screen: \
+                             gradients in different color spaces, a set of distinct colors, \
+                             colorblindness simulations and a table of contrast ratios. This \
+                             can also be used to check the color support of your terminal."),
+        )
+        .subcommand(
+            Command::new("ansi-map")
+                .about("Print a lookup table between the 8-bit ANSI colors and a palette")
+                .long_about(
+                    "Map each of the 256 colors of the 8-bit ANSI palette to the index of the \
+                     closest color in the given palette (or, with '--reverse', each palette color \
+                     to the closest ANSI color). The mapping can be printed as a table or as a C \
+                     array, for use as a static lookup table in terminal applications.\n\n\
+                     Example:\n  \
+                       pastel ansi-map --format c 282828 cc241d 98971a d79921 458588",
+                )
+                .arg(
+                    Arg::new("reverse")
+                        .long("reverse")
+                        .short('r')
+                        .help("Map the palette colors to ANSI colors instead"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .short('f')
+                        .help("Output format")
+                        .possible_values(["table", "c"])
+                        .default_value("table"),
+                )
+                .arg(
+                    Arg::new("metric")
+                        .long("metric")
+                        .short('m')
+                        .help("Distance metric to find the closest colors")
+                        .possible_values(["CIEDE2000", "CIE76"])
+                        .value_name("name")
+                        .default_value("CIEDE2000"),
+                )
+                .arg(color_arg.clone()),
         )
         .arg(
             Arg::new("color-mode")
@@ -496,6 +2999,29 @@ pub fn build_cli() -> Command<'static> {
                 .hide_possible_values(true)
                 .hide_default_value(true)
         )
+        .arg(
+            Arg::new("hyperlinks")
+                .long("hyperlinks")
+                .value_name("url-template")
+                .help("Turn hex codes and color names in the output of 'color' and 'list' into \
+                       hyperlinks (in terminals that support OSC 8 hyperlinks). An optional URL \
+                       template can be given, where '{hex}' is replaced by the hex code of the \
+                       color (without '#'). The default template can be changed by setting \
+                       PASTEL_HYPERLINK_TEMPLATE.")
+                .takes_value(true)
+                .min_values(0)
+                .require_equals(true)
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_name("format")
+                .help("Write the colors produced by commands like 'gradient', 'random' or \
+                       'distinct' as packed binary pixel data (three or four bytes per color) \
+                       instead of text. This can be piped into tools like 'ffmpeg' or written \
+                       to a frame buffer device.")
+                .possible_values(["raw-rgb8", "raw-rgba8"])
+        )
         .arg(
             Arg::new("force-color")
                 .short('f')
@@ -509,7 +3035,43 @@ pub fn build_cli() -> Command<'static> {
                 .possible_values(COLOR_PICKER_TOOL_NAMES.iter())
                 .ignore_case(true)
                 .help("Use a specific tool to pick the colors")
-        )
+        );
+
+    #[cfg(feature = "serve")]
+    let app = app.subcommand(
+        Command::new("serve")
+            .about("Serve pastel functionality over a local HTTP/JSON API")
+            .long_about(
+                "Start a small HTTP server that answers requests with JSON, such that editors \
+                 and design tools can use a single long-lived process instead of running pastel \
+                 for every request. All endpoints take their parameters from the query string:\n\n  \
+                   /convert?color=<color>&format=<type>   (format types as in 'pastel format', \
+                 default: all)\n  \
+                   /contrast?foreground=<color>&background=<color>\n  \
+                   /distinct?count=<n>&metric=<CIE76|CIEDE2000>&fixed=<color>&iterations=<n>   \
+                 (at most 100 colors and 1000000 iterations)\n\n\
+                 Example:\n  \
+                   pastel serve --port 7878 &\n  \
+                   curl 'http://127.0.0.1:7878/convert?color=%23ff8000&format=hsl,name'",
+            )
+            .arg(
+                Arg::new("host")
+                    .long("host")
+                    .help("The address to listen on")
+                    .takes_value(true)
+                    .default_value("127.0.0.1"),
+            )
+            .arg(
+                Arg::new("port")
+                    .long("port")
+                    .short('p')
+                    .help("The port to listen on")
+                    .takes_value(true)
+                    .default_value("7878"),
+            ),
+    );
+
+    app
 }
 
 #[test]
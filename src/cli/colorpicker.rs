@@ -2,6 +2,8 @@ use std::io::{self, Write};
 use std::process::Command;
 
 use crate::colorpicker_tools::COLOR_PICKER_TOOLS;
+#[cfg(all(unix, feature = "native-picker"))]
+use crate::colorpicker_tools::NATIVE_COLOR_PICKER;
 use crate::config::Config;
 use crate::error::{PastelError, Result};
 use crate::hdcanvas::Canvas;
@@ -59,7 +61,7 @@ pub fn print_colorspectrum(config: &Config) -> Result<()> {
 pub fn run_external_colorpicker(picker: Option<&str>) -> Result<String> {
     for tool in COLOR_PICKER_TOOLS
         .iter()
-        .filter(|t| picker.map_or(true, |p| t.command.eq_ignore_ascii_case(p)))
+        .filter(|t| picker.is_none_or(|p| t.command.eq_ignore_ascii_case(p)))
     {
         let result = Command::new(tool.command).args(tool.version_args).output();
 
@@ -93,5 +95,22 @@ pub fn run_external_colorpicker(picker: Option<&str>) -> Result<String> {
         }
     }
 
+    #[cfg(all(unix, feature = "native-picker"))]
+    if picker.is_none_or(|p| p.eq_ignore_ascii_case(NATIVE_COLOR_PICKER)) {
+        return crate::native_picker::pick_color();
+    }
+
     Err(PastelError::NoColorPickerFound)
 }
+
+/// Pick a color from the screen and average it with its neighborhood of `width`x`height` pixels.
+/// External tools only report a single pixel, so this requires the built-in X11 color picker.
+#[cfg_attr(not(all(unix, feature = "native-picker")), allow(unused_variables))]
+pub fn run_averaging_colorpicker(picker: Option<&str>, width: u16, height: u16) -> Result<String> {
+    #[cfg(all(unix, feature = "native-picker"))]
+    if picker.is_none_or(|p| p.eq_ignore_ascii_case(NATIVE_COLOR_PICKER)) {
+        return crate::native_picker::pick_color_averaged(width, height);
+    }
+
+    Err(PastelError::AveragingUnsupported)
+}
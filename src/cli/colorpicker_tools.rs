@@ -135,8 +135,16 @@ pub static COLOR_PICKER_TOOLS: Lazy<Vec<ColorPickerTool>> = Lazy::new(|| {
     ]
 });
 
-pub static COLOR_PICKER_TOOL_NAMES: Lazy<Vec<&'static str>> =
-    Lazy::new(|| COLOR_PICKER_TOOLS.iter().map(|t| t.command).collect());
+/// The name of the built-in color picker (used if no external tool is available)
+#[cfg(all(unix, feature = "native-picker"))]
+pub const NATIVE_COLOR_PICKER: &str = "native";
+
+pub static COLOR_PICKER_TOOL_NAMES: Lazy<Vec<&'static str>> = Lazy::new(|| {
+    let names = COLOR_PICKER_TOOLS.iter().map(|t| t.command);
+    #[cfg(all(unix, feature = "native-picker"))]
+    let names = names.chain(std::iter::once(NATIVE_COLOR_PICKER));
+    names.collect()
+});
 
 #[cfg(target_os = "linux")]
 pub fn gdbus_parse_color(raw: String) -> Result<String, &'static str> {
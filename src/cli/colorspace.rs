@@ -1,15 +1,29 @@
+use clap::ArgMatches;
+
+use pastel::gradient::InterpolationSpace;
 use pastel::Color;
-use pastel::{Fraction, LCh, Lab, OkLab, HSLA, RGBA};
+use pastel::{Fraction, HueInterpolation};
+
+pub type MixingFunction = Box<dyn Fn(&Color, &Color, Fraction) -> Color>;
+
+pub fn get_mixing_function(colorspace_name: &str) -> MixingFunction {
+    get_mixing_function_with_hue(colorspace_name, HueInterpolation::Shorter)
+}
 
-pub fn get_mixing_function(
+pub fn get_mixing_function_with_hue(
     colorspace_name: &str,
-) -> Box<dyn Fn(&Color, &Color, Fraction) -> Color> {
-    match colorspace_name.to_lowercase().as_ref() {
-        "rgb" => Box::new(|c1: &Color, c2: &Color, f: Fraction| c1.mix::<RGBA<f64>>(c2, f)),
-        "hsl" => Box::new(|c1: &Color, c2: &Color, f: Fraction| c1.mix::<HSLA>(c2, f)),
-        "lab" => Box::new(|c1: &Color, c2: &Color, f: Fraction| c1.mix::<Lab>(c2, f)),
-        "lch" => Box::new(|c1: &Color, c2: &Color, f: Fraction| c1.mix::<LCh>(c2, f)),
-        "oklab" => Box::new(|c1: &Color, c2: &Color, f: Fraction| c1.mix::<OkLab>(c2, f)),
-        _ => unreachable!("Unknown color space"),
+    hue: HueInterpolation,
+) -> MixingFunction {
+    let space = InterpolationSpace::from_name(colorspace_name)
+        .unwrap_or_else(|| unreachable!("Unknown color space"));
+    Box::new(move |c1: &Color, c2: &Color, f: Fraction| space.mix_with_hue(c1, c2, f, hue))
+}
+
+pub fn get_hue_interpolation(matches: &ArgMatches) -> HueInterpolation {
+    match matches.value_of("hue-interpolation") {
+        Some("longer") => HueInterpolation::Longer,
+        Some("increasing") => HueInterpolation::Increasing,
+        Some("decreasing") => HueInterpolation::Decreasing,
+        _ => HueInterpolation::Shorter,
     }
 }
@@ -0,0 +1,160 @@
+use crate::commands::prelude::*;
+
+use pastel::ColorblindnessType;
+
+pub struct A11yReportCommand;
+
+/// Width of a cell of the contrast matrix, e.g. '21.00 AAA '
+const CELL_WIDTH: usize = 10;
+
+/// Width of the labels of the color vision deficiency simulations
+const LABEL_WIDTH: usize = 14;
+
+/// The WCAG conformance level that a contrast ratio reaches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Fail,
+    Aa,
+    Aaa,
+}
+
+impl Level {
+    /// The thresholds are lower for large text (at least 18pt, or 14pt bold).
+    fn of(contrast: f64, large_text: bool) -> Level {
+        let (aa, aaa) = if large_text { (3.0, 4.5) } else { (4.5, 7.0) };
+        if contrast >= aaa {
+            Level::Aaa
+        } else if contrast >= aa {
+            Level::Aa
+        } else {
+            Level::Fail
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Level::Fail => "fail",
+            Level::Aa => "AA",
+            Level::Aaa => "AAA",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Level::Fail => Color::from_rgb(0xc6, 0x28, 0x28),
+            Level::Aa => Color::from_rgb(0xf9, 0xa8, 0x25),
+            Level::Aaa => Color::from_rgb(0x2e, 0x7d, 0x32),
+        }
+    }
+}
+
+fn swatch(brush: Brush, color: &Color) -> String {
+    brush.paint(
+        color.to_rgb_hex_string(true),
+        color.text_color().ansi_style().on(color),
+    )
+}
+
+/// Print the contrast ratio of every color (as text color, in the rows) on every other color (as
+/// background color, in the columns), along with the WCAG level it passes.
+fn print_contrast_matrix(
+    out: &mut Output,
+    brush: Brush,
+    colors: &[Color],
+    large_text: bool,
+) -> Result<()> {
+    writeln!(
+        out.handle,
+        "contrast ratios (text in rows, background in columns):"
+    )?;
+    writeln!(out.handle)?;
+
+    // The hex codes are aligned with the contrast ratios
+    let header: Vec<String> = colors.iter().map(|c| swatch(brush, c)).collect();
+    writeln!(
+        out.handle,
+        "{:9}{}",
+        "",
+        header.join(&" ".repeat(CELL_WIDTH - 6))
+    )?;
+
+    for (i, text) in colors.iter().enumerate() {
+        let cells: Vec<String> = colors
+            .iter()
+            .enumerate()
+            .map(|(j, background)| {
+                if i == j {
+                    return " ".repeat(CELL_WIDTH);
+                }
+                let contrast = text.contrast_ratio(background);
+                let level = Level::of(contrast, large_text);
+                let color = level.color();
+                brush.paint(
+                    format!("{:5.2} {:4}", contrast, level.label()),
+                    color.text_color().ansi_style().on(&color),
+                )
+            })
+            .collect();
+        let line = format!("{}  {}", swatch(brush, text), cells.join(" "));
+        writeln!(out.handle, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+/// Print the whole palette as it is seen with the different kinds of color vision deficiency.
+fn print_simulations(out: &mut Output, brush: Brush, colors: &[Color]) -> Result<()> {
+    writeln!(out.handle, "color vision deficiency simulation:")?;
+    writeln!(out.handle)?;
+
+    let simulations = [
+        ("normal", None),
+        ("protanopia", Some(ColorblindnessType::Protanopia)),
+        ("deuteranopia", Some(ColorblindnessType::Deuteranopia)),
+        ("tritanopia", Some(ColorblindnessType::Tritanopia)),
+    ];
+    for (label, cb_ty) in simulations {
+        let swatches: Vec<String> = colors
+            .iter()
+            .map(|c| match cb_ty {
+                Some(cb_ty) => c.simulate_colorblindness(cb_ty),
+                None => c.clone(),
+            })
+            .map(|c| swatch(brush, &c))
+            .collect();
+        writeln!(
+            out.handle,
+            "  {:w$}{}",
+            label,
+            swatches.join(" "),
+            w = LABEL_WIDTH
+        )?;
+    }
+
+    Ok(())
+}
+
+impl GenericCommand for A11yReportCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut colors: Vec<Color> = vec![];
+        for color in ColorArgIterator::from_matches(config, matches)? {
+            colors.push(color?);
+        }
+
+        if colors.is_empty() {
+            return Err(PastelError::ColorArgRequired);
+        }
+
+        // Every color is used as a background, so the contrast ratios (and the swatches) are only
+        // well-defined for opaque colors
+        if let Some(color) = colors.iter().find(|c| c.to_rgba().alpha < 1.0) {
+            return Err(PastelError::TranslucentColor(color.to_rgb_hex_string(true)));
+        }
+
+        print_contrast_matrix(out, config.brush, &colors, matches.is_present("large-text"))?;
+        writeln!(out.handle)?;
+        print_simulations(out, config.brush, &colors)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::{self, Write};
+
+use crate::commands::prelude::*;
+use crate::print_warning;
+
+use pastel::ContrastAlgorithm;
+
+pub struct AdjustContrastCommand;
+
+impl ColorCommand for AdjustContrastCommand {
+    fn run(
+        &self,
+        out: &mut Output,
+        matches: &ArgMatches,
+        config: &Config,
+        color: &Color,
+    ) -> Result<()> {
+        let mut print_spectrum = PrintSpectrum::Yes;
+        let background = ColorArgIterator::from_color_arg(
+            config,
+            matches.value_of("background").expect("required argument"),
+            &mut print_spectrum,
+        )?;
+
+        // The default target and the range of possible (absolute) contrast values
+        let (algorithm, default_target, (min_target, max_target)) =
+            match matches.value_of("algorithm").expect("required argument") {
+                "wcag2" => (ContrastAlgorithm::Wcag2, 4.5, (1.0, 21.0)),
+                "apca" => (ContrastAlgorithm::Apca, 60.0, (0.0, 108.0)),
+                _ => unreachable!("Unknown contrast algorithm"),
+            };
+        let target = match matches.value_of("target") {
+            Some(_) => finite_number_arg(matches, "target")?,
+            None => default_target,
+        };
+        if !(min_target..=max_target).contains(&target) {
+            return Err(PastelError::ContrastTargetOutOfRange(
+                min_target, max_target,
+            ));
+        }
+        let format_contrast = |contrast: f64| match algorithm {
+            ContrastAlgorithm::Wcag2 => format!("{:.2}", contrast),
+            ContrastAlgorithm::Apca => format!("{:.1}", contrast),
+        };
+
+        let adjustment = color.adjust_contrast(&background, target, algorithm);
+        if !adjustment.achieved {
+            print_warning(&format!(
+                "{} can not reach a contrast of {} on {} (using {} with a contrast of {})",
+                color.to_rgb_hex_string(true),
+                target,
+                background.to_rgb_hex_string(true),
+                adjustment.color.to_rgb_hex_string(true),
+                format_contrast(adjustment.contrast),
+            ));
+        }
+
+        out.show_color(config, &adjustment.color)?;
+
+        if matches.is_present("verbose") {
+            let label = match algorithm {
+                ContrastAlgorithm::Wcag2 => "contrast ratio",
+                ContrastAlgorithm::Apca => "APCA contrast (Lc)",
+            };
+            writeln!(
+                io::stderr(),
+                "{}: {} (target {}: {})",
+                label,
+                format_contrast(adjustment.contrast),
+                target,
+                if adjustment.achieved {
+                    "reached"
+                } else {
+                    "not reached"
+                }
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use crate::commands::prelude::*;
+
+pub struct AlphaReportCommand;
+
+/// Number of bins of the alpha histogram
+const HISTOGRAM_BINS: usize = 10;
+
+/// Maximum length of a histogram bar
+const HISTOGRAM_WIDTH: usize = 40;
+
+impl GenericCommand for AlphaReportCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut colors: Vec<Color> = vec![];
+        for color in ColorArgIterator::from_matches(config, matches)? {
+            colors.push(color?);
+        }
+
+        if colors.is_empty() {
+            return Err(PastelError::ColorArgRequired);
+        }
+
+        let mut print_spectrum = PrintSpectrum::Yes;
+        let backgrounds = matches
+            .values_of("background")
+            .expect("required argument")
+            .map(|bg| ColorArgIterator::from_color_arg(config, bg, &mut print_spectrum))
+            .collect::<Result<Vec<_>>>()?;
+
+        let threshold = number_arg(matches, "threshold")?;
+
+        let alphas: Vec<f64> = colors.iter().map(|c| c.to_rgba().alpha).collect();
+        let opaque = alphas.iter().filter(|a| **a == 1.0).count();
+        let transparent = alphas.iter().filter(|a| **a == 0.0).count();
+
+        writeln!(out.handle, "count: {}", colors.len())?;
+        writeln!(out.handle, "opaque: {}", opaque)?;
+        writeln!(
+            out.handle,
+            "translucent: {}",
+            colors.len() - opaque - transparent
+        )?;
+        writeln!(out.handle, "transparent: {}", transparent)?;
+
+        let mut histogram = [0; HISTOGRAM_BINS];
+        for alpha in &alphas {
+            let bin = ((alpha * HISTOGRAM_BINS as f64) as usize).min(HISTOGRAM_BINS - 1);
+            histogram[bin] += 1;
+        }
+        let max_count = histogram.iter().copied().max().unwrap_or(0).max(1);
+
+        writeln!(out.handle)?;
+        writeln!(out.handle, "alpha histogram:")?;
+        for (bin, count) in histogram.iter().enumerate() {
+            let bar_length = (count * HISTOGRAM_WIDTH).div_ceil(max_count);
+            let line = format!(
+                "  {:.1}-{:.1} {:4} {}",
+                bin as f64 / HISTOGRAM_BINS as f64,
+                (bin + 1) as f64 / HISTOGRAM_BINS as f64,
+                count,
+                "█".repeat(bar_length)
+            );
+            writeln!(out.handle, "{}", line.trim_end())?;
+        }
+
+        let swatch = |c: &Color| {
+            let mut style = c.text_color().ansi_style();
+            style.on(c);
+            config.brush.paint(c.to_rgb_hex_string(true), style)
+        };
+
+        let mut low_contrast = vec![];
+        for color in &colors {
+            for background in &backgrounds {
+                let flattened = background.composite(color);
+                let contrast = flattened.contrast_ratio(background);
+                if contrast < threshold {
+                    low_contrast.push((color, background, flattened, contrast));
+                }
+            }
+        }
+
+        writeln!(out.handle)?;
+        if low_contrast.is_empty() {
+            writeln!(
+                out.handle,
+                "all colors reach a contrast of {} on all backgrounds",
+                threshold
+            )?;
+        } else {
+            writeln!(out.handle, "low contrast (below {}):", threshold)?;
+            for (color, background, flattened, contrast) in low_contrast {
+                writeln!(
+                    out.handle,
+                    "  {} on {}: {} ({:.2})",
+                    swatch(color),
+                    swatch(background),
+                    swatch(&flattened),
+                    contrast
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::commands::prelude::*;
+
+use pastel::distinct::DistanceMetric;
+
+pub struct AnsiMapCommand;
+
+fn nearest(color: &Color, candidates: &[Color], metric: DistanceMetric) -> usize {
+    let distance = |other: &Color| metric.distance(color, other);
+
+    candidates
+        .iter()
+        .enumerate()
+        .min_by(|(_, c1), (_, c2)| {
+            distance(c1)
+                .partial_cmp(&distance(c2))
+                .expect("distances are not NaN")
+        })
+        .map(|(i, _)| i)
+        .expect("list of candidates can not be empty")
+}
+
+fn swatch(brush: Brush, color: &Color) -> String {
+    let mut style = color.text_color().ansi_style();
+    style.on(color);
+    brush.paint(color.to_rgb_hex_string(true), style)
+}
+
+impl GenericCommand for AnsiMapCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut palette: Vec<Color> = vec![];
+        for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+            palette.push(color?);
+        }
+        if palette.is_empty() {
+            return Err(PastelError::ColorArgRequired);
+        }
+
+        let metric = match matches.value_of("metric").expect("required argument") {
+            "CIE76" => DistanceMetric::CIE76,
+            "CIEDE2000" => DistanceMetric::CIEDE2000,
+            _ => unreachable!("Unknown distance metric"),
+        };
+
+        let ansi_colors: Vec<Color> = (0..=255).map(Color::from_ansi_8bit).collect();
+
+        // Each entry maps a source index to the index of the nearest target color
+        let reverse = matches.is_present("reverse");
+        let (sources, targets) = if reverse {
+            (&palette, &ansi_colors)
+        } else {
+            (&ansi_colors, &palette)
+        };
+        let mapping: Vec<usize> = sources
+            .iter()
+            .map(|c| nearest(c, targets, metric))
+            .collect();
+
+        match matches.value_of("format").expect("required argument") {
+            "table" => {
+                for (i, &j) in mapping.iter().enumerate() {
+                    writeln!(
+                        out.handle,
+                        "{:>3} {} -> {:>3} {}",
+                        i,
+                        swatch(config.brush, &sources[i]),
+                        j,
+                        swatch(config.brush, &targets[j])
+                    )?;
+                }
+            }
+            "c" => {
+                let (name, element_type) = if reverse {
+                    ("palette_to_ansi", "unsigned char")
+                } else if palette.len() <= 256 {
+                    ("ansi_to_palette", "unsigned char")
+                } else {
+                    ("ansi_to_palette", "unsigned short")
+                };
+
+                writeln!(
+                    out.handle,
+                    "static const {} {}[{}] = {{",
+                    element_type,
+                    name,
+                    mapping.len()
+                )?;
+                for row in mapping.chunks(16) {
+                    let row: Vec<String> = row.iter().map(|j| j.to_string()).collect();
+                    writeln!(out.handle, "    {},", row.join(", "))?;
+                }
+                writeln!(out.handle, "}};")?;
+            }
+            _ => unreachable!("Unknown output format"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::commands::prelude::*;
+use crate::commands::tokens::{parse_named_lines, read_file_or_stdin};
+use crate::tokens;
+
+use pastel::ansi::{reset_terminal_colors, TerminalColor};
+
+pub struct ApplyCommand;
+
+impl GenericCommand for ApplyCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, _: &Config) -> Result<()> {
+        if matches.is_present("reset") {
+            write!(out.handle, "{}", reset_terminal_colors())?;
+            return Ok(());
+        }
+
+        let source = read_file_or_stdin(matches.value_of("file"))?;
+        let tokens = parse_named_lines(&source)?;
+
+        let mut sequences = String::new();
+        for token in &tokens {
+            let slot = TerminalColor::from_name(&token.name)
+                .ok_or_else(|| PastelError::UnknownTerminalColor(token.name.clone()))?;
+            let color = tokens::resolve(&tokens, &token.value)
+                .map_err(|reason| PastelError::TokenFileError("-".into(), reason))?;
+            sequences.push_str(&slot.set_sequence(&color));
+        }
+        write!(out.handle, "{}", sequences)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use crate::commands::prelude::*;
+use crate::hdcanvas::Canvas;
+
+/// Colors with an LCh chroma below this value are counted as gray tones.
+const GRAY_CHROMA_THRESHOLD: f64 = 8.0;
+
+/// Maximum length of a bar in the chart (in characters).
+const MAX_BAR_LENGTH: usize = 40;
+
+struct Band {
+    label: String,
+    color: Color,
+    count: usize,
+}
+
+pub struct BandsCommand;
+
+impl GenericCommand for BandsCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let width = number_arg(matches, "width")?;
+        if !(width > 0.0 && width <= 360.0) {
+            return Err(PastelError::HueBandWidthOutOfRange);
+        }
+
+        let num_hue_bands = (360.0 / width).ceil() as usize;
+
+        let mut bands: Vec<Band> = (0..num_hue_bands)
+            .map(|i| {
+                let from = i as f64 * width;
+                let to = f64::min(360.0, from + width);
+                Band {
+                    label: format!("{:3.0}°-{:3.0}°", from, to),
+                    color: Color::from_lch(65.0, 50.0, (from + to) / 2.0, 1.0),
+                    count: 0,
+                }
+            })
+            .collect();
+        bands.push(Band {
+            label: "gray".into(),
+            color: Color::graytone(0.5),
+            count: 0,
+        });
+
+        for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+            let lch = color?.to_lch();
+            let index = if lch.c < GRAY_CHROMA_THRESHOLD {
+                num_hue_bands
+            } else {
+                usize::min((lch.h / width) as usize, num_hue_bands - 1)
+            };
+            bands[index].count += 1;
+        }
+
+        if !config.interactive_mode {
+            for band in bands {
+                writeln!(out.handle, "{} {}", band.label, band.count)?;
+            }
+            return Ok(());
+        }
+
+        let max_count = bands.iter().map(|b| b.count).max().unwrap_or(0).max(1);
+        let label_width = bands
+            .iter()
+            .map(|b| b.label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let bar_position_x = config.padding + label_width + 2;
+
+        let mut canvas = Canvas::new(
+            2 * bands.len() + 2,
+            bar_position_x + MAX_BAR_LENGTH + 8,
+            config.brush,
+        );
+
+        for (i, band) in bands.iter().enumerate() {
+            let row = 2 * i + 2;
+            let bar_length = band.count * MAX_BAR_LENGTH / max_count;
+
+            canvas.draw_text(row, config.padding, &band.label);
+            canvas.draw_rect(row, bar_position_x, 2, bar_length, &band.color);
+            canvas.draw_text(
+                row,
+                bar_position_x + bar_length + 1,
+                &band.count.to_string(),
+            );
+        }
+
+        canvas.print(out.handle)
+    }
+}
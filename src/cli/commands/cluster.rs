@@ -0,0 +1,46 @@
+use crate::commands::prelude::*;
+
+use pastel::cluster;
+
+pub struct ClusterCommand;
+
+impl GenericCommand for ClusterCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let k = matches.value_of("clusters").expect("required argument");
+        let k = k
+            .parse::<usize>()
+            .map_err(|_| PastelError::CouldNotParseNumber(k.into()))?;
+
+        if k < 1 {
+            return Err(PastelError::ClusterCountMustBeLargerThanZero);
+        }
+
+        let mut colors: Vec<Color> = vec![];
+        for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+            colors.push(color?);
+        }
+
+        if k > colors.len() {
+            return Err(PastelError::ClusterCountCannotBeMoreThanColors);
+        }
+
+        let clusters = cluster::kmeans(&colors, k);
+
+        if matches.is_present("centroids") {
+            for cluster in clusters {
+                out.show_color(config, &cluster.centroid)?;
+            }
+        } else {
+            for (i, cluster) in clusters.iter().enumerate() {
+                if i > 0 {
+                    writeln!(out.handle)?;
+                }
+                for color in &cluster.colors {
+                    out.show_color(config, color)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,8 +1,12 @@
-use crate::colorspace::get_mixing_function;
+use crate::colorspace::{get_hue_interpolation, get_mixing_function_with_hue};
 use crate::commands::prelude::*;
 
+use pastel::blend::BlendMode;
+use pastel::curves::{ChannelCurves, CurveSpace};
+use pastel::pipeline::Pipeline;
 use pastel::ColorblindnessType;
 use pastel::Fraction;
+use pastel::TintOperation;
 
 fn clamp(lower: f64, upper: f64, x: f64) -> f64 {
     f64::max(f64::min(upper, x), lower)
@@ -56,12 +60,24 @@ color_command!(ComplementCommand, _config, _matches, color, {
     color.complementary()
 });
 
-color_command!(ToGrayCommand, _config, _matches, color, { color.to_gray() });
+color_command!(MaxChromaCommand, _config, _matches, color, {
+    color.maximize_chroma()
+});
+
+color_command!(MatchLightnessCommand, config, matches, color, {
+    let mut print_spectrum = PrintSpectrum::Yes;
 
-color_command!(TextColorCommand, _config, _matches, color, {
-    color.text_color()
+    let reference = ColorArgIterator::from_color_arg(
+        config,
+        matches.value_of("reference").expect("required argument"),
+        &mut print_spectrum,
+    )?;
+
+    color.match_lightness(&reference)
 });
 
+color_command!(ToGrayCommand, _config, _matches, color, { color.to_gray() });
+
 color_command!(MixCommand, config, matches, color, {
     let mut print_spectrum = PrintSpectrum::Yes;
 
@@ -72,27 +88,120 @@ color_command!(MixCommand, config, matches, color, {
     )?;
     let fraction = Fraction::from(1.0 - number_arg(matches, "fraction")?);
 
-    let mix = get_mixing_function(matches.value_of("colorspace").expect("required argument"));
+    let mix = get_mixing_function_with_hue(
+        matches.value_of("colorspace").expect("required argument"),
+        get_hue_interpolation(matches),
+    );
 
     mix(&base, color, fraction)
 });
 
-color_command!(ColorblindCommand, config, matches, color, {
-    // The type of colorblindness selected (protanopia, deuteranopia, tritanopia)
-    let cb_ty = matches.value_of("type").expect("required argument");
-    let cb_ty = cb_ty.to_lowercase();
+color_command!(TintCommand, config, matches, color, {
+    let (operation, tint) = [
+        (TintOperation::Add, "add"),
+        (TintOperation::Subtract, "subtract"),
+        (TintOperation::Multiply, "multiply"),
+    ]
+    .into_iter()
+    .find_map(|(operation, name)| Some((operation, matches.value_of(name)?)))
+    .expect("required argument group");
+
+    let tint = ColorArgIterator::from_color_arg(config, tint, &mut PrintSpectrum::Yes)?;
+
+    color.tint(&tint, operation)
+});
+
+color_command!(BlendCommand, config, matches, color, {
+    let backdrop = ColorArgIterator::from_color_arg(
+        config,
+        matches.value_of("backdrop").expect("required argument"),
+        &mut PrintSpectrum::Yes,
+    )?;
+    let mode = matches.value_of("mode").expect("required argument");
+    let mode = BlendMode::from_name(mode).expect("known blend mode");
+
+    backdrop.blend(color, mode)
+});
+
+color_command!(CurvesCommand, config, matches, color, {
+    let mut curves = ChannelCurves::default();
+    for spec in matches.values_of("spec").expect("required argument") {
+        curves
+            .add_spec(spec)
+            .map_err(|reason| PastelError::CurveSpecError(spec.into(), reason.to_string()))?;
+    }
 
-    // Convert the string to the corresponding enum variant
-    let cb_ty = match cb_ty.as_ref() {
+    let space = match matches
+        .value_of("space")
+        .expect("required argument")
+        .to_lowercase()
+        .as_ref()
+    {
+        "gamma" => CurveSpace::Gamma,
+        "linear" => CurveSpace::Linear,
+        _ => unreachable!("Unknown curve space"),
+    };
+
+    curves.apply(color, space)
+});
+
+color_command!(FilterCommand, config, matches, color, {
+    let name = matches.value_of("name").expect("required argument");
+
+    // User-defined filters (e.g. PASTEL_FILTER_MY_LOOK) take precedence over built-in presets
+    let env_name = format!("PASTEL_FILTER_{}", name.to_uppercase().replace('-', "_"));
+    let pipeline = match std::env::var(&env_name) {
+        Ok(spec) => spec
+            .parse::<Pipeline>()
+            .map_err(|reason| PastelError::PipelineError(spec.clone(), reason.to_string()))?,
+        Err(_) => Pipeline::preset(name).ok_or_else(|| PastelError::UnknownFilter(name.into()))?,
+    };
+
+    pipeline.apply(color)
+});
+
+/// The type of colorblindness selected with the 'type' argument (protanopia, deuteranopia,
+/// tritanopia or achromatopsia).
+fn colorblindness_type(matches: &ArgMatches) -> ColorblindnessType {
+    match matches
+        .value_of("type")
+        .expect("required argument")
+        .to_lowercase()
+        .as_ref()
+    {
         "prot" => ColorblindnessType::Protanopia,
         "deuter" => ColorblindnessType::Deuteranopia,
         "trit" => ColorblindnessType::Tritanopia,
+        "achroma" => ColorblindnessType::Achromatopsia,
         &_ => {
             unreachable!("Unknown property");
         }
-    };
+    }
+}
+
+color_command!(ColorblindCommand, config, matches, color, {
+    let cb_ty = colorblindness_type(matches);
+
+    match matches.value_of("severity") {
+        Some(_) => {
+            let severity = number_arg(matches, "severity")?;
+            if !(0.0..=1.0).contains(&severity) {
+                return Err(PastelError::SeverityOutOfRange);
+            }
+            color.simulate_color_vision_deficiency(cb_ty, severity)
+        }
+        None => color.simulate_colorblindness(cb_ty),
+    }
+});
+
+color_command!(DaltonizeCommand, config, matches, color, {
+    let cb_ty = colorblindness_type(matches);
+    let strength = finite_number_arg(matches, "strength")?;
+    if strength < 0.0 {
+        return Err(PastelError::NegativeStrength);
+    }
 
-    color.simulate_colorblindness(cb_ty)
+    color.daltonize(cb_ty, strength)
 });
 
 color_command!(SetCommand, config, matches, color, {
@@ -1,5 +1,8 @@
 use crate::commands::prelude::*;
 use crate::hdcanvas::Canvas;
+use crate::terminal;
+
+use std::time::Duration;
 
 use pastel::ansi::{Brush, Mode};
 
@@ -41,8 +44,30 @@ fn print_board(out: &mut Output, config: &Config, mode: Mode) -> Result<()> {
     canvas.print(out.handle)
 }
 
+/// How long to wait for the terminal to answer the truecolor probe
+const PROBE_TIMEOUT: Duration = Duration::from_millis(200);
+
+fn run_probe(out: &mut Output) -> Result<()> {
+    let (result, exit_code) = match terminal::probe_truecolor(PROBE_TIMEOUT) {
+        Some(true) => ("24bit", 0),
+        Some(false) => ("8bit", 1),
+        None => ("unknown", 2),
+    };
+
+    writeln!(out.handle, "{}", result)?;
+
+    match exit_code {
+        0 => Ok(()),
+        code => Err(PastelError::ExitCode(code)),
+    }
+}
+
 impl GenericCommand for ColorCheckCommand {
-    fn run(&self, out: &mut Output, _: &ArgMatches, config: &Config) -> Result<()> {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        if matches.is_present("probe") {
+            return run_probe(out);
+        }
+
         writeln!(out.handle, "\n8-bit mode:")?;
         print_board(out, config, Mode::Ansi8Bit)?;
 
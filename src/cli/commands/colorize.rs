@@ -0,0 +1,236 @@
+use std::io::{self, BufRead, Read};
+use std::time::Duration;
+
+use regex::Regex;
+
+use crate::commands::gradient::parse_gradient;
+use crate::commands::prelude::*;
+
+use pastel::ansi::Style;
+use pastel::gradient::{Gradient, InterpolationSpace};
+use pastel::{ColorScale, Fraction};
+
+pub struct ColorizeCommand;
+
+/// A rule that paints all matches of a regular expression in a certain style.
+struct Rule {
+    regex: Regex,
+    style: Style,
+}
+
+/// Parse a rules file with one `<regex> => <style>` rule per line. Empty lines and lines
+/// starting with '#' are ignored.
+fn parse_rules(source: &str) -> Result<Vec<Rule>> {
+    let mut rules = vec![];
+
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (pattern, style) = line.rsplit_once("=>").ok_or_else(|| {
+            PastelError::ColorizeRuleError(line_number, "expected '<regex> => <style>'".into())
+        })?;
+
+        let regex = Regex::new(pattern.trim())
+            .map_err(|err| PastelError::ColorizeRuleError(line_number, err.to_string()))?;
+        let style = style
+            .parse::<Style>()
+            .map_err(|err| PastelError::ColorizeRuleError(line_number, err.to_string()))?;
+
+        rules.push(Rule { regex, style });
+    }
+
+    Ok(rules)
+}
+
+/// Paint all matches of the rules within the given line. If matches of different rules overlap,
+/// the rule that comes first wins.
+fn colorize_line(line: &str, rules: &[Rule], brush: Brush) -> String {
+    let mut assignment: Vec<Option<usize>> = vec![None; line.len()];
+
+    for (ri, rule) in rules.iter().enumerate() {
+        for m in rule.regex.find_iter(line) {
+            if m.range().all(|i| assignment[i].is_none()) {
+                for a in &mut assignment[m.range()] {
+                    *a = Some(ri);
+                }
+            }
+        }
+    }
+
+    let mut result = String::new();
+    let mut start = 0;
+    while start < line.len() {
+        let current = assignment[start];
+        let end = (start..line.len())
+            .find(|&i| assignment[i] != current)
+            .unwrap_or(line.len());
+
+        let segment = &line[start..end];
+        match current {
+            Some(ri) => result.push_str(&brush.paint(segment, &rules[ri].style)),
+            None => result.push_str(segment),
+        }
+        start = end;
+    }
+
+    result
+}
+
+/// Terminal cells are about twice as high as wide. Rows are stretched accordingly, such that
+/// the angle of the gradient looks right.
+const CELL_ASPECT_RATIO: f64 = 2.0;
+
+/// Every line is animated for this number of frames (at 30 frames per second).
+const ANIMATION_FRAMES: usize = 15;
+const ANIMATION_FRAME_DURATION: Duration = Duration::from_millis(33);
+
+/// Paints text with a gradient along a certain direction.
+struct GradientPainter {
+    gradient: Gradient,
+    direction: (f64, f64),
+    range: (f64, f64),
+}
+
+impl GradientPainter {
+    /// The gradient spans the whole text (given as its lines) along the direction of the angle
+    /// (in degrees, 0° is left to right, 90° is top to bottom).
+    fn new(gradient: Gradient, angle: f64, lines: &[String]) -> Self {
+        let angle = angle.to_radians();
+        let direction = (angle.cos(), angle.sin() * CELL_ASPECT_RATIO);
+        let project = |col: usize, row: usize| col as f64 * direction.0 + row as f64 * direction.1;
+
+        let mut range = (f64::INFINITY, f64::NEG_INFINITY);
+        for (row, line) in lines.iter().enumerate() {
+            let last = line.chars().count().saturating_sub(1);
+            for p in [project(0, row), project(last, row)] {
+                range = (range.0.min(p), range.1.max(p));
+            }
+        }
+
+        GradientPainter {
+            gradient,
+            direction,
+            range,
+        }
+    }
+
+    /// The position within the gradient for the character at the given position. The phase
+    /// shifts the gradient (for the animation), which is mirrored at both ends, such that it can
+    /// be cycled through without jumps.
+    fn position(&self, col: usize, row: usize, phase: f64) -> Fraction {
+        let p = col as f64 * self.direction.0 + row as f64 * self.direction.1;
+        let width = self.range.1 - self.range.0;
+        let t = if width > 0.0 {
+            (p - self.range.0) / width
+        } else {
+            0.0
+        };
+        let t = (t / 2.0 + phase).rem_euclid(1.0);
+        Fraction::from(1.0 - (1.0 - 2.0 * t).abs())
+    }
+
+    fn paint_line(&self, line: &str, row: usize, phase: f64, brush: Brush) -> String {
+        line.chars()
+            .enumerate()
+            .map(|(col, c)| {
+                if c.is_whitespace() {
+                    return c.to_string();
+                }
+                let color = self
+                    .gradient
+                    .sample(self.position(col, row, phase))
+                    .expect("non-empty gradient");
+                brush.paint(c.to_string(), color)
+            })
+            .collect()
+    }
+}
+
+fn colorize_with_gradient(
+    out: &mut Output,
+    matches: &ArgMatches,
+    config: &Config,
+    color_args: Vec<&str>,
+) -> Result<()> {
+    let colorspace = matches.value_of("colorspace").expect("required argument");
+    let gradient = parse_gradient(config, &color_args, colorspace)?;
+
+    let space = InterpolationSpace::from_name(&gradient.colorspace)
+        .unwrap_or_else(|| unreachable!("Unknown color space"));
+    let mut color_scale = ColorScale::empty();
+    for (color, position) in gradient.stops {
+        color_scale.add_stop(color, Fraction::from(position));
+    }
+
+    let angle = finite_number_arg(matches, "angle")?;
+    let speed = if matches.is_present("speed") {
+        Some(finite_number_arg(matches, "speed")?)
+    } else {
+        None
+    };
+
+    // The extent of the whole text is needed to stretch the gradient across it, even for a
+    // horizontal gradient (which spans the longest line). The input is therefore buffered
+    // completely, unlike in the rules mode.
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
+    let lines: Vec<String> = text.lines().map(String::from).collect();
+
+    let painter = GradientPainter::new(Gradient::new(color_scale, space), angle, &lines);
+
+    for (row, line) in lines.iter().enumerate() {
+        // Animate each line by cycling through the gradient until it comes to rest
+        if let (Some(speed), true) = (speed, config.interactive_mode) {
+            for frame in (1..ANIMATION_FRAMES).rev() {
+                let phase = speed * frame as f64 * ANIMATION_FRAME_DURATION.as_secs_f64();
+                write!(
+                    out.handle,
+                    "\r{}",
+                    painter.paint_line(line, row, phase, config.brush)
+                )?;
+                out.handle.flush()?;
+                std::thread::sleep(ANIMATION_FRAME_DURATION);
+            }
+            write!(out.handle, "\r")?;
+        }
+        writeln!(
+            out.handle,
+            "{}",
+            painter.paint_line(line, row, 0.0, config.brush)
+        )?;
+    }
+
+    Ok(())
+}
+
+impl GenericCommand for ColorizeCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        if let Some(color_args) = matches.values_of("gradient") {
+            return colorize_with_gradient(out, matches, config, color_args.collect());
+        }
+
+        let path = matches.value_of("rules").expect("required argument");
+        let rules = parse_rules(&std::fs::read_to_string(path)?)?;
+
+        let whole_line = matches.is_present("whole-line");
+
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            let output = if whole_line {
+                match rules.iter().find(|rule| rule.regex.is_match(&line)) {
+                    Some(rule) => config.brush.paint(&line, &rule.style),
+                    None => line,
+                }
+            } else {
+                colorize_line(&line, &rules, config.brush)
+            };
+            writeln!(out.handle, "{}", output)?;
+        }
+
+        Ok(())
+    }
+}
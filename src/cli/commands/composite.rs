@@ -0,0 +1,76 @@
+use crate::commands::prelude::*;
+use crate::hdcanvas::Canvas;
+use crate::utility::checkerboard_colors;
+
+pub struct CompositeCommand;
+
+/// Show the backdrop, the individual layers and the resulting stack side by side. Each swatch
+/// is drawn over a checkerboard, such that transparency is visible.
+fn print_preview(
+    out: &mut Output,
+    config: &Config,
+    backdrop: &Color,
+    layers: &[Color],
+) -> Result<()> {
+    let swatch_size = 8;
+    let num_swatches = layers.len() + 2;
+
+    let mut canvas = Canvas::new(
+        swatch_size + 4,
+        config.padding + num_swatches * (swatch_size + config.padding),
+        config.brush,
+    );
+    let background = out.terminal_background(config);
+    canvas.set_text_color(background.as_ref().map(Color::text_color));
+    let (dark, light) = checkerboard_colors(background.as_ref());
+
+    let swatches = std::iter::once(("backdrop".to_string(), vec![backdrop.clone()]))
+        .chain(
+            layers
+                .iter()
+                .enumerate()
+                .map(|(i, layer)| (format!("layer {}", i + 1), vec![layer.clone()])),
+        )
+        .chain(std::iter::once((
+            "result".to_string(),
+            std::iter::once(backdrop.clone())
+                .chain(layers.iter().cloned())
+                .collect(),
+        )));
+
+    for (i, (name, stack)) in swatches.enumerate() {
+        let col = config.padding + i * (swatch_size + config.padding);
+        canvas.draw_checkerboard(0, col, swatch_size, swatch_size, &dark, &light);
+        for color in stack {
+            canvas.draw_rect(0, col, swatch_size, swatch_size, &color);
+        }
+        canvas.draw_text(swatch_size + 2, col, &name);
+    }
+
+    canvas.print(out.handle)
+}
+
+impl GenericCommand for CompositeCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let backdrop = ColorArgIterator::from_color_arg(
+            config,
+            matches.value_of("backdrop").expect("required argument"),
+            &mut PrintSpectrum::Yes,
+        )?;
+
+        let mut layers = vec![];
+        for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+            layers.push(color?);
+        }
+
+        let result = layers
+            .iter()
+            .fold(backdrop.clone(), |stack, layer| stack.composite(layer));
+
+        if matches.is_present("preview") && config.interactive_mode {
+            print_preview(out, config, &backdrop, &layers)?;
+        }
+
+        out.show_color(config, &result)
+    }
+}
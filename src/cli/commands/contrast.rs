@@ -0,0 +1,77 @@
+use crate::commands::prelude::*;
+
+use pastel::Contrast;
+
+pub struct ContrastCommand;
+
+fn explain(out: &mut Output, algorithm: &str, details: &Contrast) -> Result<()> {
+    let (text, background) = (details.text_luminance, details.background_luminance);
+    if algorithm == "wcag2" {
+        writeln!(out.handle, "  text luminance:       {:.4}", text)?;
+        writeln!(out.handle, "  background luminance: {:.4}", background)?;
+        writeln!(
+            out.handle,
+            "  ratio:                ({:.4} + 0.05) / ({:.4} + 0.05) = {:.2}",
+            text.max(background),
+            text.min(background),
+            details.contrast
+        )?;
+    } else {
+        writeln!(out.handle, "  text luminance (Y):       {:.4}", text)?;
+        writeln!(out.handle, "  background luminance (Y): {:.4}", background)?;
+        let polarity = if background > text {
+            "dark text on a light background"
+        } else {
+            "light text on a dark background"
+        };
+        writeln!(out.handle, "  polarity:                 {}", polarity)?;
+    }
+    Ok(())
+}
+
+impl ColorCommand for ContrastCommand {
+    fn run(
+        &self,
+        out: &mut Output,
+        matches: &ArgMatches,
+        config: &Config,
+        color: &Color,
+    ) -> Result<()> {
+        let mut print_spectrum = PrintSpectrum::Yes;
+        let background = ColorArgIterator::from_color_arg(
+            config,
+            matches.value_of("background").expect("required argument"),
+            &mut print_spectrum,
+        )?;
+
+        let algorithm = matches.value_of("algorithm").expect("required argument");
+        let details = match algorithm {
+            "wcag2" => color.contrast_ratio_details(&background),
+            "apca" => color.contrast_apca_details(&background),
+            _ => unreachable!("Unknown contrast algorithm"),
+        };
+        let contrast = match algorithm {
+            "wcag2" => format!("{:.2}", details.contrast),
+            _ => format!("{:.1}", details.contrast),
+        };
+
+        if config.interactive_mode {
+            writeln!(
+                out.handle,
+                "{} {}",
+                config
+                    .brush
+                    .paint(" Aa ", color.ansi_style().on(&background)),
+                contrast
+            )?;
+        } else {
+            writeln!(out.handle, "{}", contrast)?;
+        }
+
+        if matches.is_present("explain") {
+            explain(out, algorithm, &details)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::commands::prelude::*;
+
+use pastel::OkLab;
+
+pub struct CyclicCommand;
+
+impl GenericCommand for CyclicCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let count = matches.value_of("number").expect("required argument");
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| PastelError::CouldNotParseNumber(count.into()))?;
+        if count < 2 {
+            return Err(PastelError::GradientNumberMustBeLargerThanOne);
+        }
+
+        let lightness = number_arg(matches, "lightness")?;
+        let hue_offset = number_arg(matches, "hue-offset")?;
+
+        // A constant chroma is required for a perceptually uniform scale, so we need to use the
+        // smallest of all maximum chroma values along the hue circle.
+        let chroma = (0..360)
+            .map(|hue| OkLab::max_chroma(lightness, hue as f64))
+            .fold(f64::MAX, f64::min);
+
+        // The hue circle is divided into `count` (and not `count - 1`) segments such that the
+        // last color is followed by the first one without any discontinuity.
+        for i in 0..count {
+            let hue = hue_offset + 360.0 * i as f64 / count as f64;
+            let OkLab { l, a, b, alpha } = OkLab::from_lch(lightness, chroma, hue, 1.0);
+            out.show_color(config, &Color::from_oklab(l, a, b, alpha))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use crate::colorspace::get_mixing_function;
+use crate::commands::prelude::*;
+
+use pastel::ansi::Style;
+use pastel::distinct::{self, DistanceMetric, OptimizationBudget};
+use pastel::ColorblindnessType;
+use pastel::Fraction;
+
+pub struct DemoCommand;
+
+const GRADIENT_WIDTH: usize = 48;
+const LABEL_WIDTH: usize = 12;
+
+fn heading(out: &mut Output, config: &Config, title: &str) -> Result<()> {
+    let mut style = Style::default();
+    style.bold(true);
+    writeln!(out.handle, "\n {}\n", config.brush.paint(title, style))?;
+    Ok(())
+}
+
+fn swatches(config: &Config, colors: &[Color]) -> String {
+    colors
+        .iter()
+        .map(|c| config.brush.paint("      ", c.ansi_style().on(c)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn print_gradients(out: &mut Output, config: &Config) -> Result<()> {
+    heading(out, config, "Gradients (pastel gradient)")?;
+
+    let start = Color::from_rgb(0x55, 0x5e, 0xe4);
+    let end = Color::from_rgb(0xff, 0xd7, 0x00);
+
+    for colorspace in &["RGB", "HSL", "Lab", "OkLab"] {
+        let mix = get_mixing_function(&colorspace.to_lowercase());
+        let bar: String = (0..GRADIENT_WIDTH)
+            .map(|i| {
+                let fraction = Fraction::from(i as f64 / (GRADIENT_WIDTH - 1) as f64);
+                let color = mix(&start, &end, fraction);
+                config.brush.paint(" ", color.ansi_style().on(&color))
+            })
+            .collect();
+        writeln!(
+            out.handle,
+            "   {:width$}{}",
+            colorspace,
+            bar,
+            width = LABEL_WIDTH
+        )?;
+    }
+
+    Ok(())
+}
+
+fn print_distinct_and_colorblindness(out: &mut Output, config: &Config) -> Result<()> {
+    heading(out, config, "Distinct colors (pastel distinct 6)")?;
+
+    let (mut colors, _) = distinct::distinct_colors(
+        6,
+        DistanceMetric::CIE76,
+        vec![],
+        OptimizationBudget::default(),
+        &mut |_| {},
+    );
+    distinct::rearrange_sequence(&mut colors, DistanceMetric::CIE76);
+
+    writeln!(
+        out.handle,
+        "   {:width$}{}",
+        "",
+        swatches(config, &colors),
+        width = LABEL_WIDTH
+    )?;
+
+    heading(out, config, "Colorblindness simulation (pastel colorblind)")?;
+
+    let simulations = [
+        ("normal", None),
+        ("protanopia", Some(ColorblindnessType::Protanopia)),
+        ("deuteranopia", Some(ColorblindnessType::Deuteranopia)),
+        ("tritanopia", Some(ColorblindnessType::Tritanopia)),
+    ];
+    for (label, cb_ty) in &simulations {
+        let simulated: Vec<Color> = colors
+            .iter()
+            .map(|c| match cb_ty {
+                Some(cb_ty) => c.simulate_colorblindness(*cb_ty),
+                None => c.clone(),
+            })
+            .collect();
+        writeln!(
+            out.handle,
+            "   {:width$}{}",
+            label,
+            swatches(config, &simulated),
+            width = LABEL_WIDTH
+        )?;
+    }
+
+    Ok(())
+}
+
+fn print_contrast_table(out: &mut Output, config: &Config) -> Result<()> {
+    heading(out, config, "Contrast ratios (pastel textcolor)")?;
+
+    let backgrounds = [
+        Color::from_rgb(0x1d, 0x2b, 0x53),
+        Color::from_rgb(0x7e, 0x25, 0x53),
+        Color::from_rgb(0x00, 0x87, 0x51),
+        Color::from_rgb(0xff, 0xa3, 0x00),
+        Color::from_rgb(0xc2, 0xc3, 0xc7),
+    ];
+
+    for bg in &backgrounds {
+        let mut row = String::new();
+        for fg in &[Color::black(), Color::white()] {
+            let mut style = fg.ansi_style();
+            style.on(bg);
+            if *fg == bg.text_color() {
+                style.bold(true);
+            }
+            row.push_str(
+                &config
+                    .brush
+                    .paint(format!(" Aa {:5.2}:1 ", fg.contrast_ratio(bg)), style),
+            );
+        }
+        writeln!(
+            out.handle,
+            "   {:width$}{}",
+            bg.to_rgb_hex_string(true),
+            row,
+            width = LABEL_WIDTH
+        )?;
+    }
+
+    Ok(())
+}
+
+impl GenericCommand for DemoCommand {
+    fn run(&self, out: &mut Output, _: &ArgMatches, config: &Config) -> Result<()> {
+        print_gradients(out, config)?;
+        print_distinct_and_colorblindness(out, config)?;
+        print_contrast_table(out, config)?;
+        writeln!(out.handle)?;
+
+        Ok(())
+    }
+}
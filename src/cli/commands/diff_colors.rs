@@ -0,0 +1,88 @@
+use crate::commands::prelude::*;
+
+use pastel::{ColorblindnessType, LCh};
+
+/// LCh chroma of the most intense colors in the generated sequence.
+const MAX_CHROMA: f64 = 45.0;
+
+/// The LCh chroma of the most intense color with the given lightness and hue, reduced such that
+/// the color lies within the sRGB gamut (and is not clipped).
+fn max_chroma(lightness: f64, hue: f64) -> f64 {
+    MAX_CHROMA.min(LCh::max_chroma(lightness, hue))
+}
+
+/// The smallest perceived difference between two colors, as seen with normal vision and with the
+/// two most common types of red-green colorblindness.
+fn worst_case_distance(c1: &Color, c2: &Color) -> f64 {
+    [
+        ColorblindnessType::Protanopia,
+        ColorblindnessType::Deuteranopia,
+    ]
+    .into_iter()
+    .map(|cb_ty| {
+        c1.simulate_colorblindness(cb_ty)
+            .distance_delta_e_ciede2000(&c2.simulate_colorblindness(cb_ty))
+    })
+    .fold(c1.distance_delta_e_ciede2000(c2), f64::min)
+}
+
+/// Search the two given hue ranges (in steps of two degrees) for the pair of hues that maximizes
+/// the worst-case distance between the corresponding colors.
+fn optimal_hue_pair(lightness: f64, hues1: (i32, i32), hues2: (i32, i32)) -> (f64, f64) {
+    let mut best = (0.0, 0.0);
+    let mut best_distance = f64::MIN;
+
+    for h1 in (hues1.0..=hues1.1).step_by(2) {
+        let c1 = Color::from_lch(lightness, max_chroma(lightness, h1 as f64), h1 as f64, 1.0);
+        for h2 in (hues2.0..=hues2.1).step_by(2) {
+            let c2 = Color::from_lch(lightness, max_chroma(lightness, h2 as f64), h2 as f64, 1.0);
+
+            let distance = worst_case_distance(&c1, &c2);
+            if distance > best_distance {
+                best_distance = distance;
+                best = (h1 as f64, h2 as f64);
+            }
+        }
+    }
+
+    best
+}
+
+pub struct DiffColorsCommand;
+
+impl GenericCommand for DiffColorsCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let count = matches.value_of("number").expect("required argument");
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| PastelError::CouldNotParseNumber(count.into()))?;
+        if count < 1 {
+            return Err(PastelError::DiffColorCountMustBeLargerThanZero);
+        }
+
+        let lightness = finite_number_arg(matches, "lightness")?;
+        if !(0.0..=100.0).contains(&lightness) {
+            return Err(PastelError::DiffColorLightnessOutOfRange);
+        }
+
+        let mut hues = vec![];
+        let (red, green) = optimal_hue_pair(lightness, (-20, 40), (120, 180));
+        hues.push(red);
+        hues.push(green);
+
+        if matches.is_present("yellow-blue") {
+            let (yellow, blue) = optimal_hue_pair(lightness, (60, 110), (230, 300));
+            hues.push(yellow);
+            hues.push(blue);
+        }
+
+        for level in 1..=count {
+            for &hue in &hues {
+                let chroma = max_chroma(lightness, hue) * level as f64 / count as f64;
+                out.show_color(config, &Color::from_lch(lightness, chroma, hue, 1.0))?;
+            }
+        }
+
+        Ok(())
+    }
+}
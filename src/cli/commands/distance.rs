@@ -0,0 +1,101 @@
+use crate::commands::prelude::*;
+
+use pastel::delta_e::DeltaE;
+
+pub struct DistanceCommand;
+
+/// Compute the distance between two colors according to the given metric ('CIE76', 'CIEDE2000'
+/// or 'OkLab', case-insensitive).
+pub(super) fn distance(metric: &str, c1: &Color, c2: &Color) -> DeltaE {
+    match metric.to_lowercase().as_ref() {
+        "cie76" => c1.distance_delta_e_cie76_details(c2),
+        "ciede2000" => c1.distance_delta_e_ciede2000_details(c2),
+        "oklab" => c1.distance_oklab_details(c2),
+        _ => unreachable!("Unknown distance metric"),
+    }
+}
+
+/// Number of decimal places to print for distances of the given metric. OkLab distances are
+/// about a hundred times smaller than CIELAB ones.
+pub(super) fn precision(metric: &str) -> usize {
+    if metric.eq_ignore_ascii_case("oklab") {
+        4
+    } else {
+        2
+    }
+}
+
+impl GenericCommand for DistanceCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let metric = matches.value_of("metric").expect("required argument");
+        let precision = precision(metric);
+
+        let threshold = match matches.value_of("threshold") {
+            Some(_) => Some(number_arg(matches, "threshold")?),
+            None => None,
+        };
+
+        let mut print_spectrum = PrintSpectrum::Yes;
+        let reference = ColorArgIterator::from_color_arg(
+            config,
+            matches.value_of("color").expect("required argument"),
+            &mut print_spectrum,
+        )?;
+
+        let mut below_threshold = false;
+        for color in ColorArgIterator::from_args(config, matches.values_of("colors"))? {
+            let color = color?;
+            let delta = distance(metric, &reference, &color);
+            let dist = delta.total;
+            below_threshold |= threshold.is_some_and(|t| dist < t);
+
+            if config.interactive_mode {
+                writeln!(
+                    out.handle,
+                    "{}{} {:9} {:.*}",
+                    config
+                        .brush
+                        .paint("  ", reference.ansi_style().on(&reference)),
+                    config.brush.paint("  ", color.ansi_style().on(&color)),
+                    color.to_rgb_hex_string(true),
+                    precision,
+                    dist
+                )?;
+            } else {
+                writeln!(out.handle, "{:.*}", precision, dist)?;
+            }
+
+            if matches.is_present("explain") {
+                let coordinates = |c: &Color| {
+                    if metric.eq_ignore_ascii_case("oklab") {
+                        let oklab = c.to_oklab();
+                        format!("OkLab({:.4}, {:.4}, {:.4})", oklab.l, oklab.a, oklab.b)
+                    } else {
+                        let lab = c.to_lab();
+                        format!("Lab({:.2}, {:.2}, {:.2})", lab.l, lab.a, lab.b)
+                    }
+                };
+                writeln!(
+                    out.handle,
+                    "  {} → {}",
+                    coordinates(&reference),
+                    coordinates(&color)
+                )?;
+                writeln!(
+                    out.handle,
+                    "  lightness: {:+.*}",
+                    precision, delta.lightness
+                )?;
+                writeln!(out.handle, "  chroma:    {:+.*}", precision, delta.chroma)?;
+                writeln!(out.handle, "  hue:       {:+.*}", precision, delta.hue)?;
+                writeln!(out.handle, "  dominant:  {}", delta.dominant_term())?;
+            }
+        }
+
+        if below_threshold {
+            return Err(PastelError::ExitCode(1));
+        }
+
+        Ok(())
+    }
+}
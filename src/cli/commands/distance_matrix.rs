@@ -0,0 +1,155 @@
+use std::io::Write;
+
+use serde_json::json;
+
+use super::distance::{distance, precision};
+use crate::commands::prelude::*;
+
+use pastel::{Fraction, HSLA};
+
+pub struct DistanceMatrixCommand;
+
+fn blue_red_yellow(f: f64) -> Color {
+    let blue = Color::from_rgb(0, 0, 120);
+    let red = Color::from_rgb(224, 0, 119);
+    let yellow = Color::from_rgb(255, 255, 0);
+
+    if f < 0.5 {
+        blue.mix::<HSLA>(&red, Fraction::from(2.0 * f))
+    } else {
+        red.mix::<HSLA>(&yellow, Fraction::from(2.0 * (f - 0.5)))
+    }
+}
+
+/// Print the matrix of mutual distances as a table with colored cells (small distances are
+/// highlighted).
+pub(super) fn print_distance_matrix(
+    out: &mut dyn Write,
+    brush: Brush,
+    colors: &[Color],
+    distance: &dyn Fn(&Color, &Color) -> f64,
+    precision: usize,
+) -> Result<()> {
+    let count = colors.len();
+
+    let mut min = f64::MAX;
+    let mut max = 0.0;
+    for i in 0..count {
+        for j in 0..count {
+            if i != j {
+                let dist = distance(&colors[i], &colors[j]);
+                if dist < min {
+                    min = dist;
+                }
+                if dist > max {
+                    max = dist;
+                }
+            }
+        }
+    }
+
+    let color_to_string = |c: &Color| -> String {
+        let tc = c.text_color();
+        let mut style = tc.ansi_style();
+        style.on(c);
+        brush.paint(c.to_rgb_hex_string(false), style)
+    };
+
+    let width = precision + 4;
+    write!(out, "\n\n{:6}  ", "")?;
+    for c in colors {
+        // The hex codes (six characters) are aligned with the distances
+        write!(out, "{:w$}{} ", "", color_to_string(c), w = width - 6)?;
+    }
+    writeln!(out, "\n")?;
+
+    for (i, c1) in colors.iter().enumerate() {
+        write!(out, "{}  ", color_to_string(c1))?;
+        for (j, c2) in colors.iter().enumerate() {
+            if i == j {
+                write!(out, "{:w$} ", "", w = width)?;
+            } else {
+                let dist = distance(c1, c2);
+
+                let magnitude = if max > min {
+                    (dist - min) / (max - min)
+                } else {
+                    0.0
+                };
+                let magnitude = 1.0 - magnitude.powf(0.3);
+
+                let bg = blue_red_yellow(magnitude);
+                let mut style = bg.text_color().ansi_style();
+                style.on(bg);
+
+                write!(
+                    out,
+                    "{} ",
+                    brush.paint(format!("{:w$.p$}", dist, w = width, p = precision), style)
+                )?;
+            }
+        }
+        writeln!(out)?;
+    }
+    writeln!(out, "\n")?;
+
+    Ok(())
+}
+
+impl GenericCommand for DistanceMatrixCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut colors: Vec<Color> = vec![];
+        for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+            colors.push(color?);
+        }
+
+        let metric = matches.value_of("metric").expect("required argument");
+        let distances: Vec<Vec<f64>> = colors
+            .iter()
+            .map(|c1| {
+                colors
+                    .iter()
+                    .map(|c2| distance(metric, c1, c2).total)
+                    .collect()
+            })
+            .collect();
+        let hex: Vec<String> = colors.iter().map(|c| c.to_rgb_hex_string(true)).collect();
+
+        match matches.value_of("format").expect("required argument") {
+            "table" => {
+                print_distance_matrix(
+                    out.handle,
+                    config.brush,
+                    &colors,
+                    &|c1, c2| distance(metric, c1, c2).total,
+                    precision(metric),
+                )?;
+            }
+            "csv" => {
+                writeln!(out.handle, ",{}", hex.join(","))?;
+                for (name, row) in hex.iter().zip(&distances) {
+                    let row: Vec<String> = row
+                        .iter()
+                        .map(|d| format!("{:.*}", precision(metric) + 2, d))
+                        .collect();
+                    writeln!(out.handle, "{},{}", name, row.join(","))?;
+                }
+            }
+            "json" => {
+                let metric = ["CIEDE2000", "CIE76", "OkLab"]
+                    .into_iter()
+                    .find(|m| m.eq_ignore_ascii_case(metric))
+                    .unwrap_or(metric);
+                let document = json!({
+                    "metric": metric,
+                    "colors": hex,
+                    "distances": distances,
+                });
+                writeln!(out.handle, "{:#}", document)?;
+            }
+            _ => unreachable!("Unknown output format"),
+        }
+
+        Ok(())
+    }
+}
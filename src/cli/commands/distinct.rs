@@ -1,21 +1,70 @@
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
+use std::time::Duration;
 
+use rand::prelude::*;
+use rand_chacha::ChaCha12Rng;
+
+use super::distance_matrix::print_distance_matrix;
 use crate::commands::prelude::*;
+use crate::palettes;
 
 use pastel::ansi::Stream;
-use pastel::distinct::{self, DistanceMetric, IterationStatistics};
-use pastel::{Fraction, HSLA};
+use pastel::distinct::{
+    self, DistanceMetric, IterationStatistics, OptimizationBudget, OptimizerState,
+    PaletteConstraints,
+};
+use pastel::ColorblindnessType;
 
 pub struct DistinctCommand;
 
-fn print_iteration(out: &mut dyn Write, brush: Brush, stats: &IterationStatistics) -> Result<()> {
-    let result = stats.distance_result;
+/// Read a palette with one color per line from a file (or from STDIN for `-`).
+fn read_palette(path: &str) -> Result<Vec<Color>> {
+    let source = if path == "-" {
+        let mut source = String::new();
+        io::stdin().read_to_string(&mut source)?;
+        source
+    } else {
+        std::fs::read_to_string(path)?
+    };
+
+    palettes::parse(&source)
+}
+
+/// Distances are computed with OkLab coordinates scaled by 100 (to be comparable to CIELAB), but
+/// printed as plain ΔE OK values, like in `pastel distance`.
+fn unscaled(metric: DistanceMetric, distance: f64) -> f64 {
+    if metric == DistanceMetric::OkLab {
+        distance / distinct::OKLAB_SCALE
+    } else {
+        distance
+    }
+}
+
+/// Number of decimal places to print for distances of the given metric.
+fn precision(metric: DistanceMetric) -> usize {
+    if metric == DistanceMetric::OkLab {
+        4
+    } else {
+        2
+    }
+}
+
+fn print_iteration(
+    out: &mut dyn Write,
+    brush: Brush,
+    metric: DistanceMetric,
+    stats: &IterationStatistics,
+) -> Result<()> {
+    let result = stats.evaluation;
+    let precision = precision(metric);
     write!(
         out,
-        "[{:10.}] D_mean = {:<6.2}; D_min = {:<6.2}; T = {:.6} ",
+        "[{:10.}] D_mean = {:<6.*}; D_min = {:<6.*}; T = {:.6} ",
         stats.iteration,
-        result.mean_closest_distance,
-        result.min_closest_distance,
+        precision,
+        unscaled(metric, result.mean_closest_distance),
+        precision,
+        unscaled(metric, result.min_closest_distance),
         stats.temperature
     )?;
     print_colors(out, brush, &stats.colors, Some(result.closest_pair))?;
@@ -46,83 +95,170 @@ fn print_colors(
     Ok(())
 }
 
-fn blue_red_yellow(f: f64) -> Color {
-    let blue = Color::from_rgb(0, 0, 120);
-    let red = Color::from_rgb(224, 0, 119);
-    let yellow = Color::from_rgb(255, 255, 0);
+/// Minimum and mean distance over all pairs of colors, along with the indices of the closest pair.
+fn pairwise_distances(colors: &[Color], metric: DistanceMetric) -> (f64, f64, (usize, usize)) {
+    let distance = |c1: &Color, c2: &Color| unscaled(metric, metric.distance(c1, c2));
 
-    if f < 0.5 {
-        blue.mix::<HSLA>(&red, Fraction::from(2.0 * f))
-    } else {
-        red.mix::<HSLA>(&yellow, Fraction::from(2.0 * (f - 0.5)))
+    let mut min = (f64::MAX, (0, 0));
+    let mut sum = 0.0;
+    let mut num_pairs = 0;
+    for i in 0..colors.len() {
+        for j in (i + 1)..colors.len() {
+            let dist = distance(&colors[i], &colors[j]);
+            if dist < min.0 {
+                min = (dist, (i, j));
+            }
+            sum += dist;
+            num_pairs += 1;
+        }
     }
+
+    (min.0, sum / num_pairs as f64, min.1)
 }
 
-fn print_distance_matrix(
+/// Print a summary of the quality of the color set: the minimum and mean pairwise distance
+/// (also for simulated color vision deficiencies), along with the closest pair of colors.
+fn print_report(
     out: &mut dyn Write,
     brush: Brush,
     colors: &[Color],
     metric: DistanceMetric,
 ) -> Result<()> {
-    let count = colors.len();
+    let swatch = |c: &Color| {
+        let mut style = c.text_color().ansi_style();
+        style.on(c);
+        brush.paint(c.to_rgb_hex_string(true), style)
+    };
+
+    writeln!(out, "metric: {:?}", metric)?;
+
+    let precision = precision(metric);
+    let (min, mean, (i, j)) = pairwise_distances(colors, metric);
+    writeln!(
+        out,
+        "min distance: {:.*} ({}, {})",
+        precision,
+        min,
+        swatch(&colors[i]),
+        swatch(&colors[j])
+    )?;
+    writeln!(out, "mean distance: {:.*}", precision, mean)?;
+
+    for (name, cb_ty) in [
+        ("protanopia", ColorblindnessType::Protanopia),
+        ("deuteranopia", ColorblindnessType::Deuteranopia),
+        ("tritanopia", ColorblindnessType::Tritanopia),
+    ] {
+        let simulated: Vec<Color> = colors
+            .iter()
+            .map(|c| c.simulate_colorblindness(cb_ty))
+            .collect();
+        let (min, _, (i, j)) = pairwise_distances(&simulated, metric);
+        writeln!(
+            out,
+            "min distance ({}): {:.*} ({}, {})",
+            name,
+            precision,
+            min,
+            swatch(&colors[i]),
+            swatch(&colors[j])
+        )?;
+    }
+
+    Ok(())
+}
 
-    let distance = |c1: &Color, c2: &Color| match metric {
-        DistanceMetric::CIE76 => c1.distance_delta_e_cie76(c2),
-        DistanceMetric::CIEDE2000 => c1.distance_delta_e_ciede2000(c2),
+/// Parse a duration like `2s`, `1.5s`, `500ms` or `1m`. A plain number is interpreted as seconds.
+fn parse_duration(duration: &str) -> Result<Duration> {
+    let (number, factor) = if let Some(number) = duration.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = duration.strip_suffix('s') {
+        (number, 1.0)
+    } else if let Some(number) = duration.strip_suffix('m') {
+        (number, 60.0)
+    } else {
+        (duration, 1.0)
     };
 
-    let mut min = f64::MAX;
-    let mut max = 0.0;
-    for i in 0..count {
-        for j in 0..count {
-            if i != j {
-                let dist = distance(&colors[i], &colors[j]);
-                if dist < min {
-                    min = dist;
-                }
-                if dist > max {
-                    max = dist;
-                }
-            }
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .and_then(|n| Duration::try_from_secs_f64(n * factor).ok())
+        .ok_or_else(|| PastelError::InvalidDuration(duration.into()))
+}
+
+/// Split a constraint specification like `20:0.5` into the value and its (optional) weight.
+fn parse_weighted(spec: &str) -> Result<(&str, f64)> {
+    match spec.rsplit_once(':') {
+        Some((value, weight)) => {
+            let weight = weight
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| PastelError::CouldNotParseNumber(weight.into()))?;
+            Ok((value.trim(), weight))
         }
+        None => Ok((spec.trim(), 1.0)),
     }
+}
 
-    let color_to_string = |c: &Color| -> String {
-        let tc = c.text_color();
-        let mut style = tc.ansi_style();
-        style.on(c);
-        brush.paint(c.to_rgb_hex_string(false), style)
+fn parse_constraints(matches: &ArgMatches, config: &Config) -> Result<PaletteConstraints> {
+    let parse_number = |number: &str| {
+        number
+            .parse::<f64>()
+            .map_err(|_| PastelError::CouldNotParseNumber(number.into()))
     };
 
-    write!(out, "\n\n{:6}  ", "")?;
-    for c in colors {
-        write!(out, "{} ", color_to_string(c))?;
+    let mut constraints = PaletteConstraints::default();
+
+    if let Some(spec) = matches.value_of("lightness-spread") {
+        let (target, weight) = parse_weighted(spec)?;
+        constraints.lightness_spread = Some((parse_number(target)?, weight));
+    }
+    if let Some(weight) = matches.value_of("hue-uniformity") {
+        constraints.hue_uniformity = parse_number(weight)?;
+    }
+    if let Some(spec) = matches.value_of("anchor") {
+        let (color, weight) = parse_weighted(spec)?;
+        constraints.anchor = Some((
+            ColorArgIterator::from_color_arg(config, color, &mut PrintSpectrum::No)?,
+            weight,
+        ));
     }
-    writeln!(out, "\n")?;
 
-    for c1 in colors {
-        write!(out, "{}  ", color_to_string(c1))?;
-        for c2 in colors {
-            if c1 == c2 {
-                write!(out, "{:6} ", "")?;
-            } else {
-                let dist = distance(c1, c2);
+    let limit = |name: &str, value: &mut f64| -> Result<()> {
+        if matches.value_of(name).is_some() {
+            *value = finite_number_arg(matches, name)?;
+        }
+        Ok(())
+    };
+    let region = &mut constraints.region;
+    limit("min-lightness", &mut region.lightness.0)?;
+    limit("max-lightness", &mut region.lightness.1)?;
+    limit("min-chroma", &mut region.chroma.0)?;
+    limit("max-chroma", &mut region.chroma.1)?;
 
-                let magnitude = (dist - min) / (max - min);
-                let magnitude = 1.0 - magnitude.powf(0.3);
+    region.lightness = (region.lightness.0.max(0.0), region.lightness.1.min(100.0));
+    region.chroma.0 = region.chroma.0.max(0.0);
+    if region.lightness.0 > region.lightness.1 {
+        return Err(PastelError::DistinctInvalidRegion("lightness".into()));
+    }
+    if region.chroma.0 > region.chroma.1 {
+        return Err(PastelError::DistinctInvalidRegion("chroma".into()));
+    }
 
-                let bg = blue_red_yellow(magnitude);
-                let mut style = bg.text_color().ansi_style();
-                style.on(bg);
+    if let Some(background) = matches.value_of("background") {
+        let background =
+            ColorArgIterator::from_color_arg(config, background, &mut PrintSpectrum::No)?;
+        let ratio = parse_number(matches.value_of("min-contrast").unwrap_or("4.5"))?;
+        region.min_contrast = Some((background, ratio));
 
-                write!(out, "{} ", brush.paint(format!("{:6.2}", dist), style))?;
-            }
+        if region.lightness_ranges().is_empty() {
+            return Err(PastelError::DistinctContrastUnreachable(ratio));
         }
-        writeln!(out)?;
     }
-    writeln!(out, "\n")?;
 
-    Ok(())
+    Ok(constraints)
 }
 
 impl GenericCommand for DistinctCommand {
@@ -137,51 +273,157 @@ impl GenericCommand for DistinctCommand {
             .parse::<usize>()
             .map_err(|_| PastelError::CouldNotParseNumber(count.into()))?;
 
-        if count < 2 {
-            return Err(PastelError::DistinctColorCountMustBeLargerThanOne);
+        let space = matches.value_of("space").expect("required argument");
+        if space == "oklab" && matches.occurrences_of("metric") > 0 {
+            return Err(PastelError::DistinctMetricNotApplicable(space.into()));
         }
 
-        let distance_metric = match matches.value_of("metric").expect("required argument") {
-            "CIE76" => DistanceMetric::CIE76,
-            "CIEDE2000" => DistanceMetric::CIEDE2000,
+        let distance_metric = match (
+            space,
+            matches.value_of("metric").expect("required argument"),
+        ) {
+            ("oklab", _) => DistanceMetric::OkLab,
+            (_, "CIE76") => DistanceMetric::CIE76,
+            (_, "CIEDE2000") => DistanceMetric::CIEDE2000,
             _ => unreachable!("Unknown distance metric"),
         };
 
-        let fixed_colors = match matches.values_of("color") {
+        let mut fixed_colors = match matches.value_of("extend") {
+            Some(path) => read_palette(path)?,
             None => vec![],
-            Some(positionals) => {
+        };
+        if let Some(positionals) = matches.values_of("color") {
+            fixed_colors.extend(
                 ColorArgIterator::FromPositionalArguments(config, positionals, PrintSpectrum::Yes)
-                    .collect::<Result<Vec<_>>>()?
+                    .collect::<Result<Vec<_>>>()?,
+            );
+        }
+
+        let resume_path = matches.value_of("resume");
+        let resume_state = match resume_path {
+            Some(path) if std::path::Path::new(path).exists() => {
+                if !fixed_colors.is_empty() {
+                    return Err(PastelError::DistinctResumeWithFixedColors);
+                }
+
+                let source = std::fs::read_to_string(path)?;
+                let state = OptimizerState::from_json(&source).map_err(|reason| {
+                    PastelError::DistinctStateError(path.into(), reason.to_string())
+                })?;
+                fixed_colors = state.colors[..state.num_fixed_colors].to_vec();
+                Some(state)
             }
+            _ => None,
         };
 
         let num_fixed_colors = fixed_colors.len();
+
+        // When extending a palette, the count refers to the number of new colors
+        let count = if let Some(state) = &resume_state {
+            state.colors.len()
+        } else if matches.is_present("extend") {
+            num_fixed_colors + count
+        } else {
+            count
+        };
+
+        if count < 2 {
+            return Err(PastelError::DistinctColorCountMustBeLargerThanOne);
+        }
         if num_fixed_colors > count {
             return Err(PastelError::DistinctColorFixedColorsCannotBeMoreThanCount);
         }
 
+        let mut budget = OptimizationBudget::default();
+        if let Some(iterations) = matches.value_of("iterations") {
+            budget.num_iterations = iterations
+                .parse::<usize>()
+                .map_err(|_| PastelError::CouldNotParseNumber(iterations.into()))?;
+        }
+        if let Some(max_time) = matches.value_of("max-time") {
+            budget.max_time = Some(parse_duration(max_time)?);
+        }
+        if let Some(patience) = matches.value_of("patience") {
+            budget.patience = Some(
+                patience
+                    .parse::<usize>()
+                    .map_err(|_| PastelError::CouldNotParseNumber(patience.into()))?,
+            );
+        }
+
+        let constraints = parse_constraints(matches, config)?;
+
+        let mut rng = match matches.value_of("seed") {
+            Some(seed) => ChaCha12Rng::seed_from_u64(
+                seed.parse::<u64>()
+                    .map_err(|_| PastelError::CouldNotParseNumber(seed.into()))?,
+            ),
+            None => ChaCha12Rng::from_entropy(),
+        };
+
         let mut callback: Box<dyn FnMut(&IterationStatistics)> = if verbose_output {
             Box::new(|stats: &IterationStatistics| {
-                print_iteration(&mut stderr_lock, brush_stderr, stats).ok();
+                print_iteration(&mut stderr_lock, brush_stderr, distance_metric, stats).ok();
             })
         } else {
             Box::new(|_: &IterationStatistics| {})
         };
 
-        let (mut colors, distance_result) =
-            distinct::distinct_colors(count, distance_metric, fixed_colors, callback.as_mut());
+        let (mut colors, distance_result, state) = match &resume_state {
+            Some(state) => distinct::resume_distinct_colors(
+                state,
+                distance_metric,
+                constraints,
+                budget,
+                callback.as_mut(),
+            ),
+            None => distinct::distinct_colors_with_state(
+                count,
+                distance_metric,
+                fixed_colors,
+                constraints,
+                budget,
+                callback.as_mut(),
+                &mut rng,
+            ),
+        };
+
+        if let Some(path) = resume_path {
+            std::fs::write(path, state.to_json())?;
+        }
 
         if matches.is_present("print-minimal-distance") {
-            writeln!(out.handle, "{:.3}", distance_result.min_closest_distance)?;
+            writeln!(
+                out.handle,
+                "{:.*}",
+                precision(distance_metric) + 1,
+                unscaled(distance_metric, distance_result.min_closest_distance)
+            )?;
         } else {
-            distinct::rearrange_sequence(&mut colors, distance_metric);
+            if matches.is_present("extend") {
+                // Keep the order of the existing palette and only rearrange the new colors
+                distinct::rearrange_sequence(&mut colors[num_fixed_colors..], distance_metric);
+            } else {
+                distinct::rearrange_sequence(&mut colors, distance_metric);
+            }
 
             if verbose_output {
-                print_distance_matrix(&mut stderr.lock(), brush_stderr, &colors, distance_metric)?;
+                print_distance_matrix(
+                    &mut stderr.lock(),
+                    brush_stderr,
+                    &colors,
+                    &|c1, c2| unscaled(distance_metric, distance_metric.distance(c1, c2)),
+                    precision(distance_metric),
+                )?;
+            }
+
+            for color in &colors {
+                out.show_color(config, color)?;
             }
 
-            for color in colors {
-                out.show_color(config, &color)?;
+            if matches.is_present("report") {
+                writeln!(out.handle)?;
+                print_report(out.handle, config.brush, &colors, distance_metric)?;
             }
         }
 
@@ -0,0 +1,89 @@
+use crate::colorspace::get_mixing_function;
+use crate::commands::prelude::*;
+
+use pastel::{Fraction, LCh, OkLab};
+
+fn lightness(color: &Color, colorspace: &str) -> f64 {
+    match colorspace {
+        "lab" => color.to_lab().l,
+        "oklab" => color.to_oklab().l,
+        _ => unreachable!("Unknown color space"),
+    }
+}
+
+/// Set the lightness of the color, reducing its chroma (if necessary) such that it stays within
+/// the sRGB gamut. Otherwise, the color would be clipped and lose the requested lightness.
+fn with_lightness(color: &Color, lightness: f64, colorspace: &str) -> Color {
+    match colorspace {
+        "lab" => {
+            let lch = color.to_lch();
+            let chroma = lch.c.min(LCh::max_chroma(lightness, lch.h));
+            Color::from_lch(lightness, chroma, lch.h, lch.alpha)
+        }
+        "oklab" => {
+            let oklab = color.to_oklab();
+            let chroma = oklab.a.hypot(oklab.b);
+            let hue = oklab.b.atan2(oklab.a).to_degrees();
+            let scale = if chroma > 0.0 {
+                (OkLab::max_chroma(lightness, hue) / chroma).min(1.0)
+            } else {
+                1.0
+            };
+            Color::from_oklab(lightness, scale * oklab.a, scale * oklab.b, oklab.alpha)
+        }
+        _ => unreachable!("Unknown color space"),
+    }
+}
+
+pub struct DivergingCommand;
+
+impl GenericCommand for DivergingCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let count = matches.value_of("number").expect("required argument");
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| PastelError::CouldNotParseNumber(count.into()))?;
+        if count < 2 {
+            return Err(PastelError::GradientNumberMustBeLargerThanOne);
+        }
+
+        let mut print_spectrum = PrintSpectrum::Yes;
+        let mut color_arg = |name: &str| {
+            ColorArgIterator::from_color_arg(
+                config,
+                matches.value_of(name).expect("required argument"),
+                &mut print_spectrum,
+            )
+        };
+
+        let negative = color_arg("negative")?;
+        let positive = color_arg("positive")?;
+        let midpoint = color_arg("midpoint")?;
+
+        let colorspace = matches
+            .value_of("colorspace")
+            .expect("required argument")
+            .to_lowercase();
+        let mix = get_mixing_function(&colorspace);
+
+        // Use the same lightness for both ends to get a symmetric lightness profile
+        let end_lightness =
+            (lightness(&negative, &colorspace) + lightness(&positive, &colorspace)) / 2.0;
+        let negative = with_lightness(&negative, end_lightness, &colorspace);
+        let positive = with_lightness(&positive, end_lightness, &colorspace);
+
+        for i in 0..count {
+            let position = i as f64 / (count as f64 - 1.0);
+
+            let color = if position < 0.5 {
+                mix(&negative, &midpoint, Fraction::from(2.0 * position))
+            } else {
+                mix(&midpoint, &positive, Fraction::from(2.0 * position - 1.0))
+            };
+
+            out.show_color(config, &color)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+// The editor itself is only available on Unix
+#![cfg_attr(not(unix), allow(dead_code))]
+
+use std::io::Write;
+
+use crate::commands::prelude::*;
+use crate::terminal::Key;
+#[cfg(unix)]
+use crate::terminal::{HiddenCursor, RawMode};
+
+use pastel::ansi::{self, Stream};
+use pastel::Format;
+
+pub struct EditCommand;
+
+/// Step sizes for the keybindings
+const HUE_STEP: f64 = 5.0;
+const SATURATION_STEP: f64 = 0.05;
+const LIGHTNESS_STEP: f64 = 0.02;
+const CHROMA_STEP: f64 = 2.0;
+
+const HELP: &str = "h/H hue  s/S saturation  l/L lightness  c/C chroma  r reset  \
+                    Enter accept  Esc cancel";
+
+enum Action {
+    Update(Color),
+    Accept,
+    Cancel,
+    Ignore,
+}
+
+fn with_chroma_delta(color: &Color, delta: f64) -> Color {
+    let lch = color.to_lch();
+    Color::from_lch(lch.l, (lch.c + delta).max(0.0), lch.h, lch.alpha)
+}
+
+fn handle_key(key: Key, color: &Color, original: &Color) -> Action {
+    match key {
+        Key::Char('h') | Key::Left => Action::Update(color.rotate_hue(-HUE_STEP)),
+        Key::Char('H') | Key::Right => Action::Update(color.rotate_hue(HUE_STEP)),
+        Key::Char('s') => Action::Update(color.desaturate(SATURATION_STEP)),
+        Key::Char('S') => Action::Update(color.saturate(SATURATION_STEP)),
+        Key::Char('l') | Key::Down => Action::Update(color.darken(LIGHTNESS_STEP)),
+        Key::Char('L') | Key::Up => Action::Update(color.lighten(LIGHTNESS_STEP)),
+        Key::Char('c') => Action::Update(with_chroma_delta(color, -CHROMA_STEP)),
+        Key::Char('C') => Action::Update(with_chroma_delta(color, CHROMA_STEP)),
+        Key::Char('r') => Action::Update(original.clone()),
+        Key::Char('q') | Key::Enter => Action::Accept,
+        Key::Escape | Key::Interrupt => Action::Cancel,
+        _ => Action::Ignore,
+    }
+}
+
+/// The lines of the editor panel: a swatch of the current color next to its coordinates and
+/// contrast ratios, followed by the keybindings.
+fn render(brush: Brush, color: &Color) -> Vec<String> {
+    let swatch = brush.paint("          ", color.ansi_style().on(color));
+    let info = [
+        format!("Hex: {}", color.to_rgb_hex_string(true)),
+        format!("RGB: {}", color.to_rgb_string(Format::Spaces)),
+        format!("HSL: {}", color.to_hsl_string(Format::Spaces)),
+        format!("LCh: {}", color.to_lch_string(Format::Spaces)),
+        format!(
+            "Contrast: {:.2} on white, {:.2} on black",
+            color.contrast_ratio(&Color::white()),
+            color.contrast_ratio(&Color::black())
+        ),
+    ];
+
+    let mut lines: Vec<String> = info
+        .iter()
+        .map(|line| format!("  {}  {}", swatch, line))
+        .collect();
+    lines.push(String::new());
+    lines.push(format!("  {}", HELP));
+    lines
+}
+
+#[cfg(unix)]
+fn edit(brush: Brush, original: &Color) -> Result<Option<Color>> {
+    let raw_mode = RawMode::enable().ok_or(PastelError::NoInteractiveTerminal)?;
+    let mut panel = std::io::stderr();
+
+    let _hidden_cursor = HiddenCursor::hide()?;
+
+    let mut color = original.clone();
+    let mut lines = render(brush, &color);
+    writeln!(panel, "{}", lines.join("\n"))?;
+
+    let result = loop {
+        panel.flush()?;
+        let action = handle_key(raw_mode.read_key()?, &color, original);
+        // Move to the start of the panel and clear it
+        write!(panel, "\x1b[{}A\r\x1b[J", lines.len())?;
+        match action {
+            Action::Update(new_color) => color = new_color,
+            Action::Accept => break Some(color),
+            Action::Cancel => break None,
+            Action::Ignore => {}
+        }
+        lines = render(brush, &color);
+        writeln!(panel, "{}", lines.join("\n"))?;
+    };
+
+    Ok(result)
+}
+
+#[cfg(not(unix))]
+fn edit(_brush: Brush, _original: &Color) -> Result<Option<Color>> {
+    Err(PastelError::NoInteractiveTerminal)
+}
+
+impl GenericCommand for EditCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let original = ColorArgIterator::from_color_arg(
+            config,
+            matches.value_of("color").expect("required argument"),
+            &mut PrintSpectrum::Yes,
+        )?;
+
+        if !atty::is(Stream::Stdin) || !atty::is(Stream::Stderr) {
+            return Err(PastelError::NoInteractiveTerminal);
+        }
+
+        // The panel is drawn on STDERR, which is a terminal even if STDOUT is not
+        let brush = if config.interactive_mode {
+            config.brush
+        } else {
+            Brush::from_mode(ansi::get_colormode())
+        };
+
+        match edit(brush, &original)? {
+            Some(color) => out.show_color(config, &color),
+            None => Err(PastelError::EditCancelled),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn updated_color(key: Key, color: &Color) -> Color {
+        match handle_key(key, color, &Color::black()) {
+            Action::Update(color) => color,
+            _ => panic!("expected a color update for {:?}", key),
+        }
+    }
+
+    #[test]
+    fn keybindings() {
+        let color = Color::from_hsl(100.0, 0.5, 0.5);
+
+        let hue = updated_color(Key::Char('H'), &color).to_hsla().h;
+        assert!((hue - 105.0).abs() < 1e-6);
+        assert_eq!(
+            updated_color(Key::Char('H'), &color),
+            updated_color(Key::Right, &color)
+        );
+
+        let lightness = updated_color(Key::Up, &color).to_hsla().l;
+        assert!((lightness - 0.52).abs() < 1e-6);
+
+        let saturation = updated_color(Key::Char('s'), &color).to_hsla().s;
+        assert!((saturation - 0.45).abs() < 1e-6);
+
+        assert!(updated_color(Key::Char('c'), &color).to_lch().c < color.to_lch().c);
+        assert_eq!(Color::black(), updated_color(Key::Char('r'), &color));
+
+        assert!(matches!(
+            handle_key(Key::Enter, &color, &color),
+            Action::Accept
+        ));
+        assert!(matches!(
+            handle_key(Key::Char('q'), &color, &color),
+            Action::Accept
+        ));
+        assert!(matches!(
+            handle_key(Key::Escape, &color, &color),
+            Action::Cancel
+        ));
+        assert!(matches!(
+            handle_key(Key::Interrupt, &color, &color),
+            Action::Cancel
+        ));
+        assert!(matches!(
+            handle_key(Key::Char('x'), &color, &color),
+            Action::Ignore
+        ));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::commands::prelude::*;
+
+pub struct ElevationCommand;
+
+/// The elevation levels (in dp) of the Material Design dark theme.
+const STANDARD_LEVELS: [u32; 10] = [0, 1, 2, 3, 4, 6, 8, 12, 16, 24];
+
+/// Parse a range of elevation levels like `0..24dp` (all standard levels in that range) or a
+/// list of levels like `1,4,8dp`.
+fn parse_levels(spec: &str) -> Result<Vec<u32>> {
+    let invalid = || PastelError::InvalidElevationLevels(spec.into());
+    let level = |s: &str| {
+        s.trim()
+            .trim_end_matches("dp")
+            .parse::<u32>()
+            .map_err(|_| invalid())
+    };
+
+    let levels: Vec<u32> = match spec.split_once("..") {
+        Some((start, end)) => {
+            let (start, end) = (level(start)?, level(end)?);
+            STANDARD_LEVELS
+                .iter()
+                .copied()
+                .filter(|l| (start..=end).contains(l))
+                .collect()
+        }
+        None => spec.split(',').map(level).collect::<Result<_>>()?,
+    };
+
+    if levels.is_empty() {
+        return Err(invalid());
+    }
+    Ok(levels)
+}
+
+/// Opacity of the overlay for the given elevation, following the Material Design dark theme
+/// (5% at 1dp up to 16% at 24dp).
+fn overlay_opacity(level: u32) -> f64 {
+    if level == 0 {
+        0.0
+    } else {
+        ((4.5 * (level as f64 + 1.0).ln() + 2.0).round() / 100.0).min(1.0)
+    }
+}
+
+impl GenericCommand for ElevationCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut print_spectrum = PrintSpectrum::Yes;
+        let mut color_arg = |name: &str| {
+            ColorArgIterator::from_color_arg(
+                config,
+                matches.value_of(name).expect("required argument"),
+                &mut print_spectrum,
+            )
+        };
+
+        let surface = color_arg("surface")?;
+        let overlay = color_arg("overlay")?.to_rgba_float();
+
+        let levels = parse_levels(matches.value_of("levels").expect("required argument"))?;
+
+        for level in levels {
+            let opacity = overlay_opacity(level);
+            let color = surface.composite(&Color::from_rgba_float(
+                overlay.r, overlay.g, overlay.b, opacity,
+            ));
+
+            if matches.is_present("named") {
+                let name = format!("elevation-{}dp", level);
+                out.show_named_color(config, &name, &color)?;
+            } else {
+                out.show_color(config, &color)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use std::io::{ErrorKind, Read};
+use std::process::{Child, Stdio};
+
+use serde_json::{json, Value};
+
+use super::tokens::parse_named_lines;
+use crate::commands::prelude::*;
+use crate::tokens;
+
+pub struct ExportCommand;
+
+/// Version of the JSON document that is passed to external exporters. It is only increased for
+/// changes that are not backwards-compatible (new fields can be added at any time).
+const SCHEMA_VERSION: u32 = 1;
+
+fn color_json(name: &str, color: &Color) -> Value {
+    let rgba = color.to_rgba();
+    let hsl = color.to_hsla();
+    let oklab = color.to_oklab();
+    json!({
+        "name": name,
+        "hex": color.to_rgb_hex_string(true),
+        "rgb": { "r": rgba.r, "g": rgba.g, "b": rgba.b },
+        "alpha": rgba.alpha,
+        "hsl": { "h": hsl.h, "s": hsl.s, "l": hsl.l },
+        "oklab": { "l": oklab.l, "a": oklab.a, "b": oklab.b },
+    })
+}
+
+/// Start an exporter. Names without a path (e.g. 'scss') are first looked up as
+/// 'pastel-export-scss' in the PATH.
+fn spawn(via: &str) -> std::io::Result<Child> {
+    let spawn = |program: &str| {
+        std::process::Command::new(program)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+    };
+
+    if via.contains(std::path::is_separator) {
+        return spawn(via);
+    }
+    match spawn(&format!("pastel-export-{}", via)) {
+        Err(e) if e.kind() == ErrorKind::NotFound => spawn(via),
+        result => result,
+    }
+}
+
+impl GenericCommand for ExportCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut colors = vec![];
+        match matches.values_of("color") {
+            Some(_) => {
+                for (i, color) in
+                    ColorArgIterator::from_args(config, matches.values_of("color"))?.enumerate()
+                {
+                    colors.push((format!("color-{}", i + 1), color?));
+                }
+            }
+            None => {
+                if atty::is(atty::Stream::Stdin) {
+                    return Err(PastelError::ColorArgRequired);
+                }
+                let mut source = String::new();
+                std::io::stdin().read_to_string(&mut source)?;
+                let tokens = parse_named_lines(&source)?;
+                for token in &tokens {
+                    let color = tokens::resolve(&tokens, &token.value)
+                        .map_err(|reason| PastelError::TokenFileError("-".into(), reason))?;
+                    colors.push((token.name.clone(), color));
+                }
+            }
+        }
+
+        let document = json!({
+            "version": SCHEMA_VERSION,
+            "colors": colors
+                .iter()
+                .map(|(name, color)| color_json(name, color))
+                .collect::<Vec<_>>(),
+        });
+
+        let via = matches.value_of("via").expect("required argument");
+        let exporter_error = |reason: String| PastelError::ExporterError(via.into(), reason);
+
+        let mut child = spawn(via).map_err(|e| exporter_error(e.to_string()))?;
+
+        // Write the document in a separate thread, such that exporters can produce output
+        // before they have read all of their input.
+        let mut stdin = child.stdin.take().expect("piped stdin");
+        let writer = std::thread::spawn(move || {
+            use std::io::Write;
+            writeln!(stdin, "{:#}", document)
+        });
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| exporter_error(e.to_string()))?;
+        // Exporters are free to ignore (parts of) their input
+        let _ = writer.join();
+
+        if !output.status.success() {
+            return Err(exporter_error(output.status.to_string()));
+        }
+        out.handle.write_all(&output.stdout)?;
+
+        Ok(())
+    }
+}
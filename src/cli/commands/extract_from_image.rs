@@ -0,0 +1,69 @@
+use crate::commands::prelude::*;
+
+use pastel::cluster;
+use pastel::distinct::DistanceMetric;
+
+pub struct ExtractFromImageCommand;
+
+/// Larger images are subsampled to (roughly) this number of pixels before clustering.
+const MAX_SAMPLES: usize = 10_000;
+
+/// Read the (non-transparent) pixels of an image, taking every n-th pixel for large images.
+fn read_pixels(path: &str) -> Result<Vec<Color>> {
+    let image = image::open(path)
+        .map_err(|err| PastelError::ImageError(path.into(), err.to_string()))?
+        .into_rgba8();
+
+    let num_pixels = image.width() as usize * image.height() as usize;
+    let step = num_pixels.div_ceil(MAX_SAMPLES).max(1);
+
+    Ok(image
+        .pixels()
+        .step_by(step)
+        .filter(|pixel| pixel[3] > 0)
+        .map(|pixel| Color::from_rgb(pixel[0], pixel[1], pixel[2]))
+        .collect())
+}
+
+impl GenericCommand for ExtractFromImageCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let count = matches.value_of("number").expect("required argument");
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| PastelError::CouldNotParseNumber(count.into()))?;
+        if count < 1 {
+            return Err(PastelError::ImageColorCountMustBeLargerThanZero);
+        }
+
+        let distance_metric = match matches.value_of("metric").expect("required argument") {
+            "CIE76" => DistanceMetric::CIE76,
+            "CIEDE2000" => DistanceMetric::CIEDE2000,
+            _ => unreachable!("Unknown distance metric"),
+        };
+
+        let path = matches.value_of("file").expect("required argument");
+        let pixels = read_pixels(path)?;
+        if pixels.is_empty() {
+            return Err(PastelError::ImageError(
+                path.into(),
+                "the image does not contain any non-transparent pixels".into(),
+            ));
+        }
+
+        let mut clusters = cluster::kmeans_with_metric(
+            &pixels,
+            count.min(pixels.len()),
+            distance_metric,
+            &mut rand::thread_rng(),
+        );
+
+        // The most dominant colors come first
+        clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.colors.len()));
+
+        for cluster in clusters {
+            out.show_color(config, &cluster.centroid)?;
+        }
+
+        Ok(())
+    }
+}
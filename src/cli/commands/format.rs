@@ -1,75 +1,314 @@
 use crate::commands::prelude::*;
 use crate::utility::similar_colors;
 
-use pastel::ansi::Mode;
+use pastel::ansi::{Ansi8BitOptions, Mode};
 use pastel::Format;
 
 pub struct FormatCommand;
 
-impl ColorCommand for FormatCommand {
-    fn run(
+/// The format types that can be passed to `pastel format`.
+const FORMAT_TYPES: &[&str] = &[
+    "rgb",
+    "rgb-float",
+    "hex",
+    "hsl",
+    "hsl-hue",
+    "hsl-saturation",
+    "hsl-lightness",
+    "hsv",
+    "hsv-hue",
+    "hsv-saturation",
+    "hsv-value",
+    "lch",
+    "lch-lightness",
+    "lch-chroma",
+    "lch-hue",
+    "lab",
+    "lab-a",
+    "lab-b",
+    "oklab",
+    "oklab-l",
+    "oklab-a",
+    "oklab-b",
+    "luminance",
+    "brightness",
+    "colorfulness",
+    "cct",
+    "ansi-8bit",
+    "ansi-24bit",
+    "ansi-8bit-escapecode",
+    "ansi-24bit-escapecode",
+    "cmyk",
+    "name",
+    "all",
+];
+
+/// The representations printed by `pastel format all`.
+pub(super) const ALL_FORMAT_TYPES: &[&str] =
+    &["hex", "rgb", "hsl", "lab", "lch", "oklab", "cmyk", "name"];
+
+/// How the 'name' format type deals with colors that do not exactly match a named color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum NameStyle {
+    /// The name of the closest named color
+    #[default]
+    Closest,
+    /// Mark exact matches with '(exact)', and inexact ones with a '~' and the delta E
+    WithConfidence,
+    /// The name for exact matches, an empty string otherwise
+    ExactOnly,
+}
+
+/// Options for the format types that are not fully determined by the color itself.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct FormatOptions {
+    pub ansi_8bit: Ansi8BitOptions,
+    pub name_style: NameStyle,
+}
+
+fn color_name(color: &Color, style: NameStyle) -> String {
+    let closest = similar_colors(color)[0];
+
+    let rgba = color.to_rgba();
+    let named = closest.color.to_rgba();
+    let exact = (rgba.r, rgba.g, rgba.b) == (named.r, named.g, named.b);
+
+    match (style, exact) {
+        (NameStyle::Closest, _) => closest.name.to_owned(),
+        (NameStyle::WithConfidence, true) => format!("{} (exact)", closest.name),
+        (NameStyle::WithConfidence, false) => format!(
+            "~{} (ΔE {:.1})",
+            closest.name,
+            closest.color.distance_delta_e_ciede2000(color)
+        ),
+        (NameStyle::ExactOnly, true) => closest.name.to_owned(),
+        (NameStyle::ExactOnly, false) => String::new(),
+    }
+}
+
+/// The value of a numeric format type (or template placeholder), together with the default
+/// number of decimal places.
+fn numeric_value(name: &str, color: &Color) -> Option<(f64, usize)> {
+    Some(match name {
+        "r" => (color.to_rgba().r as f64, 0),
+        "g" => (color.to_rgba().g as f64, 0),
+        "b" => (color.to_rgba().b as f64, 0),
+        "a" => (color.to_rgba().alpha, 2),
+        "hsl-hue" => (color.to_hsla().h, 0),
+        "hsl-saturation" => (color.to_hsla().s, 4),
+        "hsl-lightness" => (color.to_hsla().l, 4),
+        "hsv-hue" => (color.to_hsva().h, 0),
+        "hsv-saturation" => (color.to_hsva().s, 4),
+        "hsv-value" => (color.to_hsva().v, 4),
+        "lch-lightness" => (color.to_lch().l, 2),
+        "lch-chroma" => (color.to_lch().c, 2),
+        "lch-hue" => (color.to_lch().h, 2),
+        "lab-a" => (color.to_lab().a, 2),
+        "lab-b" => (color.to_lab().b, 2),
+        "oklab-l" => (color.to_oklab().l, 4),
+        "oklab-a" => (color.to_oklab().a, 4),
+        "oklab-b" => (color.to_oklab().b, 4),
+        "cmyk-c" => (color.to_cmyk().c * 100.0, 0),
+        "cmyk-m" => (color.to_cmyk().m * 100.0, 0),
+        "cmyk-y" => (color.to_cmyk().y * 100.0, 0),
+        "cmyk-k" => (color.to_cmyk().k * 100.0, 0),
+        "luminance" => (color.luminance(), 3),
+        "brightness" => (color.brightness(), 3),
+        "colorfulness" => (color.colorfulness(), 2),
+        "cct" => (color.estimate_cct(), 0),
+        _ => return None,
+    })
+}
+
+/// Format the color according to the given format type (or template placeholder).
+pub(super) fn format_value(name: &str, color: &Color, options: FormatOptions) -> Option<String> {
+    if let Some((value, precision)) = numeric_value(name, color) {
+        return Some(format!("{:.*}", precision, value));
+    }
+
+    let replace_escape = |code: &str| code.replace('\x1b', "\\x1b");
+    let ansi_8bit_sequence =
+        || format!("\x1b[38;5;{}m", color.to_ansi_8bit_with(options.ansi_8bit));
+
+    Some(match name {
+        "rgb" => color.to_rgb_string(Format::Spaces),
+        "rgb-float" => color.to_rgb_float_string(Format::Spaces),
+        "hex" => color.to_rgb_hex_string(true),
+        "hsl" => color.to_hsl_string(Format::Spaces),
+        "hsv" => color.to_hsv_string(Format::Spaces),
+        "lch" => color.to_lch_string(Format::Spaces),
+        "lab" => color.to_lab_string(Format::Spaces),
+        "oklab" => color.to_oklab_string(Format::Spaces),
+        "ansi-8bit" => replace_escape(&ansi_8bit_sequence()),
+        "ansi-24bit" => replace_escape(&color.to_ansi_sequence(Mode::TrueColor)),
+        "ansi-8bit-escapecode" => ansi_8bit_sequence(),
+        "ansi-24bit-escapecode" => color.to_ansi_sequence(Mode::TrueColor),
+        "cmyk" => color.to_cmyk_string(Format::Spaces),
+        "name" => color_name(color, options.name_style),
+        _ => return None,
+    })
+}
+
+/// Replace all `{placeholder}` or `{placeholder:.N}` occurrences in the template by the
+/// corresponding value of the color. Literal braces can be written as `{{` and `}}`.
+fn render_template(template: &str, color: &Color, options: FormatOptions) -> Result<String> {
+    let error = |reason: String| PastelError::TemplateError(template.into(), reason);
+
+    let mut output = String::new();
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                output.push('{');
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                output.push('}');
+            }
+            '}' => return Err(error("unmatched '}'".into())),
+            '{' => {
+                let rest = chars.as_str();
+                let end = rest
+                    .find('}')
+                    .ok_or_else(|| error("unterminated placeholder".into()))?;
+                let placeholder = &rest[..end];
+                chars = rest[end + 1..].chars();
+
+                let (name, precision) = match placeholder.split_once(':') {
+                    Some((name, spec)) => {
+                        let precision = spec
+                            .strip_prefix('.')
+                            .and_then(|p| p.parse::<usize>().ok())
+                            .ok_or_else(|| {
+                                error(format!("invalid precision '{}', expected '.N'", spec))
+                            })?;
+                        (name.trim(), Some(precision))
+                    }
+                    None => (placeholder.trim(), None),
+                };
+                let name = name.to_lowercase();
+
+                let value = match (precision, numeric_value(&name, color)) {
+                    (Some(precision), Some((value, _))) => format!("{:.*}", precision, value),
+                    (Some(_), None) if format_value(&name, color, options).is_some() => {
+                        return Err(error(format!("'{}' is not a numeric placeholder", name)))
+                    }
+                    _ => format_value(&name, color, options)
+                        .ok_or_else(|| error(format!("unknown placeholder '{}'", name)))?,
+                };
+                output.push_str(&value);
+            }
+            c => output.push(c),
+        }
+    }
+
+    Ok(output)
+}
+
+impl FormatCommand {
+    fn print(
         &self,
         out: &mut Output,
         matches: &ArgMatches,
         config: &Config,
+        format_types: &[&str],
         color: &Color,
     ) -> Result<()> {
-        let format_type = matches.value_of("type").expect("required argument");
-        let format_type = format_type.to_lowercase();
-
-        let replace_escape = |code: &str| code.replace('\x1b', "\\x1b");
-
-        let output = match format_type.as_ref() {
-            "rgb" => color.to_rgb_string(Format::Spaces),
-            "rgb-float" => color.to_rgb_float_string(Format::Spaces),
-            "hex" => color.to_rgb_hex_string(true),
-            "hsl" => color.to_hsl_string(Format::Spaces),
-            "hsl-hue" => format!("{:.0}", color.to_hsla().h),
-            "hsl-saturation" => format!("{:.4}", color.to_hsla().s),
-            "hsl-lightness" => format!("{:.4}", color.to_hsla().l),
-            "hsv" => color.to_hsv_string(Format::Spaces),
-            "hsv-hue" => format!("{:.0}", color.to_hsva().h),
-            "hsv-saturation" => format!("{:.4}", color.to_hsva().s),
-            "hsv-value" => format!("{:.4}", color.to_hsva().v),
-            "lch" => color.to_lch_string(Format::Spaces),
-            "lch-lightness" => format!("{:.2}", color.to_lch().l),
-            "lch-chroma" => format!("{:.2}", color.to_lch().c),
-            "lch-hue" => format!("{:.2}", color.to_lch().h),
-            "lab" => color.to_lab_string(Format::Spaces),
-            "lab-a" => format!("{:.2}", color.to_lab().a),
-            "lab-b" => format!("{:.2}", color.to_lab().b),
-            "oklab" => color.to_oklab_string(Format::Spaces),
-            "oklab-l" => format!("{:.4}", color.to_oklab().l),
-            "oklab-a" => format!("{:.4}", color.to_oklab().a),
-            "oklab-b" => format!("{:.4}", color.to_oklab().b),
-            "luminance" => format!("{:.3}", color.luminance()),
-            "brightness" => format!("{:.3}", color.brightness()),
-            "ansi-8bit" => replace_escape(&color.to_ansi_sequence(Mode::Ansi8Bit)),
-            "ansi-24bit" => replace_escape(&color.to_ansi_sequence(Mode::TrueColor)),
-            "ansi-8bit-escapecode" => color.to_ansi_sequence(Mode::Ansi8Bit),
-            "ansi-24bit-escapecode" => color.to_ansi_sequence(Mode::TrueColor),
-            "cmyk" => color.to_cmyk_string(Format::Spaces),
-            "name" => similar_colors(color)[0].name.to_owned(),
-            &_ => {
-                unreachable!("Unknown format type");
-            }
+        let options = FormatOptions {
+            ansi_8bit: Ansi8BitOptions {
+                include_base_colors: matches.is_present("include-base-colors"),
+                prefer_grays: matches.is_present("prefer-grays"),
+                ..config.brush.ansi_8bit_options()
+            },
+            name_style: if matches.is_present("confidence") {
+                NameStyle::WithConfidence
+            } else if matches.is_present("exact-only") {
+                NameStyle::ExactOnly
+            } else {
+                NameStyle::Closest
+            },
+        };
+
+        let outputs = match matches.value_of("template") {
+            Some(template) => vec![(render_template(template, color, options)?, true)],
+            None => format_types
+                .iter()
+                .map(|&format_type| {
+                    let output = format_value(format_type, color, options)
+                        .ok_or_else(|| PastelError::UnknownFormatType(format_type.into()))?;
+                    let write_colored_line = !matches!(
+                        format_type,
+                        "ansi-8bit-escapecode" | "ansi-24bit-escapecode"
+                    );
+                    Ok((output, write_colored_line))
+                })
+                .collect::<Result<_>>()?,
         };
 
-        let write_colored_line = !matches!(
-            format_type.as_ref(),
-            "ansi-8bit-escapecode" | "ansi-24bit-escapecode"
-        );
-
-        if write_colored_line {
-            writeln!(
-                out.handle,
-                "{}",
-                config
-                    .brush
-                    .paint(output, color.text_color().ansi_style().on(color))
-            )?;
+        for (output, write_colored_line) in outputs {
+            if write_colored_line {
+                writeln!(
+                    out.handle,
+                    "{}",
+                    config
+                        .brush
+                        .paint(output, color.text_color().ansi_style().on(color))
+                )?;
+            } else {
+                write!(out.handle, "{}", output)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl GenericCommand for FormatCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        if matches.is_present("template") {
+            // The format type is not needed, so all positional arguments are colors
+            let mut color_args = matches
+                .values_of("type")
+                .filter(|_| matches.occurrences_of("type") > 0)
+                .into_iter()
+                .flatten()
+                .chain(matches.values_of("color").into_iter().flatten())
+                .peekable();
+            if color_args.peek().is_none() {
+                for color in ColorArgIterator::from_matches(config, matches)? {
+                    self.print(out, matches, config, &[], &color?)?;
+                }
+            } else {
+                let mut print_spectrum = PrintSpectrum::Yes;
+                for arg in color_args {
+                    let color = ColorArgIterator::from_color_arg(config, arg, &mut print_spectrum)?;
+                    self.print(out, matches, config, &[], &color)?;
+                }
+            }
+            return Ok(());
+        }
+
+        let format_types: Vec<String> = matches
+            .value_of("type")
+            .expect("required argument")
+            .split(',')
+            .map(str::to_lowercase)
+            .collect();
+        if let Some(unknown) = format_types
+            .iter()
+            .find(|t| !FORMAT_TYPES.contains(&t.as_str()))
+        {
+            return Err(PastelError::UnknownFormatType(unknown.clone()));
+        }
+        let format_types: Vec<&str> = if format_types.iter().any(|t| t == "all") {
+            ALL_FORMAT_TYPES.to_vec()
         } else {
-            write!(out.handle, "{}", output)?;
+            format_types.iter().map(String::as_str).collect()
+        };
+
+        for color in ColorArgIterator::from_matches(config, matches)? {
+            self.print(out, matches, config, &format_types, &color?)?;
         }
 
         Ok(())
@@ -0,0 +1,39 @@
+use crate::commands::prelude::*;
+
+use pastel::noise::{self, Noise, SimplexNoise, ValueNoise};
+
+pub struct GenerativeCommand;
+
+impl GenericCommand for GenerativeCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let count = matches.value_of("number").expect("required argument");
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| PastelError::CouldNotParseNumber(count.into()))?;
+
+        let step = finite_number_arg(matches, "step")?;
+        if step <= 0.0 {
+            return Err(PastelError::NoiseStepMustBePositive);
+        }
+
+        let seed = match matches.value_of("seed") {
+            Some(seed) => seed
+                .parse::<u64>()
+                .map_err(|_| PastelError::CouldNotParseNumber(seed.into()))?,
+            None => rand::random(),
+        };
+
+        let noise: Box<dyn Noise> = match matches.value_of("algorithm").expect("required argument")
+        {
+            "simplex" => Box::new(SimplexNoise::new(seed)),
+            "value" => Box::new(ValueNoise::new(seed)),
+            _ => unreachable!("Unknown noise algorithm"),
+        };
+
+        for color in noise::noise_palette(noise.as_ref(), count, step) {
+            out.show_color(config, &color)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use crate::commands::prelude::*;
+
+pub struct GlassCommand;
+
+impl GenericCommand for GlassCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut print_spectrum = PrintSpectrum::Yes;
+
+        let glass = ColorArgIterator::from_color_arg(
+            config,
+            matches.value_of("glass").expect("required argument"),
+            &mut print_spectrum,
+        )?;
+
+        let opacity = number_arg(matches, "opacity")?;
+        if !(0.0..=1.0).contains(&opacity) {
+            return Err(PastelError::OpacityOutOfRange);
+        }
+        let rgba = glass.to_rgba();
+        let glass = Color::from_rgba(rgba.r, rgba.g, rgba.b, opacity);
+
+        let text = match matches.value_of("text") {
+            Some(text) => ColorArgIterator::from_color_arg(config, text, &mut print_spectrum)?,
+            None => glass.text_color(),
+        };
+
+        let mut backgrounds = vec![];
+        for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+            backgrounds.push(color?);
+        }
+        if backgrounds.is_empty() {
+            return Err(PastelError::ColorArgRequired);
+        }
+
+        let results: Vec<Color> = backgrounds.iter().map(|bg| bg.composite(&glass)).collect();
+
+        if matches.is_present("list") {
+            for color in &results {
+                out.show_color(config, color)?;
+            }
+            return Ok(());
+        }
+
+        let (algorithm, contrast): (&str, fn(&Color, &Color) -> f64) =
+            match matches.value_of("algorithm").expect("required argument") {
+                "wcag2" => ("wcag2", |text, bg| text.contrast_ratio(bg)),
+                "apca" => ("apca", |text, bg| text.contrast_apca(bg).abs()),
+                _ => unreachable!("Unknown contrast algorithm"),
+            };
+
+        let swatch = |c: &Color| {
+            let mut style = c.text_color().ansi_style();
+            style.on(c);
+            config.brush.paint(c.to_rgb_hex_string(true), style)
+        };
+
+        let by_luminance = |a: &&Color, b: &&Color| a.luminance().total_cmp(&b.luminance());
+        let lightest = results
+            .iter()
+            .max_by(by_luminance)
+            .expect("at least one color");
+        let darkest = results
+            .iter()
+            .min_by(by_luminance)
+            .expect("at least one color");
+
+        let mut max_difference = 0.0;
+        for (i, c1) in results.iter().enumerate() {
+            for c2 in &results[i + 1..] {
+                max_difference = c1.distance_delta_e_ciede2000(c2).max(max_difference);
+            }
+        }
+
+        let (worst_index, worst_contrast) = results
+            .iter()
+            .map(|c| contrast(&text, c))
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .expect("at least one color");
+
+        writeln!(
+            out.handle,
+            "glass: {} at {:.0}% opacity",
+            swatch(&glass),
+            100.0 * opacity
+        )?;
+        writeln!(out.handle, "lightest: {}", swatch(lightest))?;
+        writeln!(out.handle, "darkest: {}", swatch(darkest))?;
+        writeln!(out.handle, "max. difference: {:.2}", max_difference)?;
+        writeln!(
+            out.handle,
+            "worst contrast ({}, text {}): {:.2} on {} (background {})",
+            algorithm,
+            swatch(&text),
+            worst_contrast,
+            swatch(&results[worst_index]),
+            swatch(&backgrounds[worst_index])
+        )?;
+
+        Ok(())
+    }
+}
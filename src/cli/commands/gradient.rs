@@ -1,13 +1,245 @@
-use crate::colorspace::get_mixing_function;
+use crate::colorspace::get_hue_interpolation;
 use crate::commands::prelude::*;
+use crate::gradients::{self, NamedGradient};
 
+use pastel::banding::{self, BandingIssue, DitherPattern};
+use pastel::gradient::{Easing, Gradient, InterpolationSpace};
 use pastel::ColorScale;
-use pastel::Fraction;
+use pastel::{Fraction, HueInterpolation};
 
 pub struct GradientCommand;
 
+/// Parse a range of positions like `0.2..0.8`.
+fn parse_range(range: &str) -> Result<(Fraction, Fraction)> {
+    let position = |p: &str| {
+        p.trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|p| (0.0..=1.0).contains(p))
+            .map(Fraction::from)
+    };
+
+    range
+        .split_once("..")
+        .and_then(|(start, end)| Some((position(start)?, position(end)?)))
+        .ok_or_else(|| PastelError::InvalidGradientRange(range.into()))
+}
+
+/// Split a color stop like `red@0.3` into the color and the (optional) position.
+fn parse_stop(stop: &str) -> Result<(&str, Option<f64>)> {
+    match stop.rsplit_once('@') {
+        Some((color, position)) if !color.is_empty() => position
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|p| (0.0..=1.0).contains(p))
+            .map(|p| (color, Some(p)))
+            .ok_or_else(|| PastelError::InvalidGradientStop(stop.into())),
+        _ => Ok((stop, None)),
+    }
+}
+
+/// Fill in the missing positions of the color stops. The first and the last stop are placed at
+/// 0.0 and 1.0 by default, stops in between are distributed evenly between their neighbors.
+fn fill_positions(positions: &[Option<f64>]) -> Vec<f64> {
+    let n = positions.len();
+    let mut known: Vec<(usize, f64)> = positions
+        .iter()
+        .enumerate()
+        .filter_map(|(i, p)| p.map(|p| (i, p)))
+        .collect();
+    if positions[0].is_none() {
+        known.insert(0, (0, 0.0));
+    }
+    if positions[n - 1].is_none() {
+        known.push((n - 1, 1.0));
+    }
+
+    let mut filled = vec![0.0; n];
+    for w in known.windows(2) {
+        let ((i, start), (j, end)) = (w[0], w[1]);
+        for (k, position) in filled.iter_mut().enumerate().take(j + 1).skip(i) {
+            *position = start + (end - start) * (k - i) as f64 / (j - i) as f64;
+        }
+    }
+    filled
+}
+
+/// Assemble a gradient from the color stops on the command line (like `red yellow@0.3 blue`), or
+/// load a saved or built-in gradient if a single `@name` is given.
+pub(super) fn parse_gradient(
+    config: &Config,
+    color_args: &[&str],
+    colorspace: &str,
+) -> Result<NamedGradient> {
+    let mut print_spectrum = PrintSpectrum::Yes;
+
+    match color_args {
+        [name] if name.starts_with('@') => gradients::load(&name[1..]),
+        _ => {
+            let color_count = color_args.len();
+            if color_count < 2 {
+                return Err(PastelError::GradientColorCountMustBeLargerThanOne);
+            }
+
+            let parsed = color_args
+                .iter()
+                .map(|stop| parse_stop(stop))
+                .collect::<Result<Vec<_>>>()?;
+            let positions = fill_positions(&parsed.iter().map(|(_, p)| *p).collect::<Vec<_>>());
+
+            if let Some(i) = (1..color_count).find(|&i| positions[i] <= positions[i - 1]) {
+                return Err(PastelError::InvalidGradientStop(color_args[i].into()));
+            }
+
+            let mut stops = vec![];
+            for ((color, _), position) in parsed.into_iter().zip(positions) {
+                let color = ColorArgIterator::from_color_arg(config, color, &mut print_spectrum)?;
+                stops.push((color, position));
+            }
+
+            // Like in CSS, the first and last colors extend to the ends of the gradient
+            if let Some((color, position)) = stops.first().cloned() {
+                if position > 0.0 {
+                    stops.insert(0, (color, 0.0));
+                }
+            }
+            if let Some((color, position)) = stops.last().cloned() {
+                if position < 1.0 {
+                    stops.push((color, 1.0));
+                }
+            }
+
+            Ok(NamedGradient {
+                colorspace: colorspace.into(),
+                stops,
+            })
+        }
+    }
+}
+
+/// The color stops for a CSS or SVG gradient. If the interpolation of the gradient cannot be
+/// reproduced by the output format (`exact` is false, or an easing function is used), the gradient
+/// is approximated by `count` evenly spaced stops.
+fn export_stops(gradient: &Gradient, count: usize, exact: bool) -> Vec<(Color, f64)> {
+    if exact && gradient.is_linear() {
+        gradient
+            .color_scale()
+            .stops()
+            .map(|(color, position)| (color.clone(), position.value()))
+            .collect()
+    } else {
+        let last = (count - 1) as f64;
+        gradient
+            .take(count)
+            .enumerate()
+            .map(|(i, color)| (color, i as f64 / last))
+            .collect()
+    }
+}
+
+fn percent(position: f64) -> String {
+    format!("{}%", (position * 1000.0).round() / 10.0)
+}
+
+fn to_css(gradient: &Gradient, count: usize) -> String {
+    let space = match gradient.interpolation_space() {
+        InterpolationSpace::Rgb => None,
+        InterpolationSpace::Hsl => Some("hsl"),
+        InterpolationSpace::Lab => Some("lab"),
+        InterpolationSpace::LCh => Some("lch"),
+        InterpolationSpace::OkLab => Some("oklab"),
+        InterpolationSpace::OkLCh => Some("oklch"),
+    };
+    let is_cylindrical = matches!(
+        gradient.interpolation_space(),
+        InterpolationSpace::Hsl | InterpolationSpace::LCh | InterpolationSpace::OkLCh
+    );
+    let hue = match gradient.hue_interpolation() {
+        HueInterpolation::Shorter => None,
+        HueInterpolation::Longer => Some("longer"),
+        HueInterpolation::Increasing => Some("increasing"),
+        HueInterpolation::Decreasing => Some("decreasing"),
+    };
+
+    let mut parts = vec![];
+    match (space, hue) {
+        (Some(space), Some(hue)) if is_cylindrical => {
+            parts.push(format!("in {} {} hue", space, hue))
+        }
+        (Some(space), _) => parts.push(format!("in {}", space)),
+        (None, _) => {}
+    }
+    for (color, position) in export_stops(gradient, count, true) {
+        parts.push(format!(
+            "{} {}",
+            color.to_rgb_hex_string(true),
+            percent(position)
+        ));
+    }
+
+    format!("linear-gradient({})", parts.join(", "))
+}
+
+/// SVG gradients are always interpolated in sRGB.
+fn to_svg(gradient: &Gradient, count: usize) -> String {
+    let exact = gradient.interpolation_space() == InterpolationSpace::Rgb;
+
+    let mut svg = String::from("<linearGradient id=\"gradient\">\n");
+    for (color, position) in export_stops(gradient, count, exact) {
+        let rgba = color.to_rgba();
+        let opacity = if rgba.alpha < 1.0 {
+            format!(
+                " stop-opacity=\"{}\"",
+                (rgba.alpha * 1000.0).round() / 1000.0
+            )
+        } else {
+            String::new()
+        };
+        svg.push_str(&format!(
+            "  <stop offset=\"{}\" stop-color=\"#{:02x}{:02x}{:02x}\"{}/>\n",
+            percent(position),
+            rgba.r,
+            rgba.g,
+            rgba.b,
+            opacity
+        ));
+    }
+    svg.push_str("</linearGradient>");
+    svg
+}
+
+/// Parse a single easing function (for all segments) or a comma-separated list with one easing
+/// function per segment.
+fn parse_easing(gradient: Gradient, easing: &str) -> Result<Gradient> {
+    let easings = easing
+        .split(',')
+        .map(|name| {
+            Easing::from_name(name.trim())
+                .ok_or_else(|| PastelError::InvalidEasing(name.trim().into()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(match easings.as_slice() {
+        [easing] => gradient.with_easing(*easing),
+        _ => easings
+            .into_iter()
+            .enumerate()
+            .fold(gradient, |gradient, (segment, easing)| {
+                gradient.with_segment_easing(segment, easing)
+            }),
+    })
+}
+
 impl GenericCommand for GradientCommand {
     fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        if matches.is_present("list") {
+            for name in gradients::names() {
+                writeln!(out.handle, "{}", name)?;
+            }
+            return Ok(());
+        }
+
         let count = matches.value_of("number").expect("required argument");
         let count = count
             .parse::<usize>()
@@ -16,34 +248,114 @@ impl GenericCommand for GradientCommand {
             return Err(PastelError::GradientNumberMustBeLargerThanOne);
         }
 
-        let mut print_spectrum = PrintSpectrum::Yes;
-
-        let mix = get_mixing_function(matches.value_of("colorspace").expect("required argument"));
-
-        let colors = matches
+        let color_args: Vec<&str> = matches
             .values_of("color")
             .expect("required argument")
-            .map(|color| ColorArgIterator::from_color_arg(config, color, &mut print_spectrum));
+            .collect();
+        let colorspace = matches.value_of("colorspace").expect("required argument");
+        let mut gradient = parse_gradient(config, &color_args, colorspace)?;
+
+        // An explicitly given color space overrides the one of a saved gradient
+        if matches.occurrences_of("colorspace") > 0 {
+            gradient.colorspace = matches
+                .value_of("colorspace")
+                .expect("required argument")
+                .into();
+        }
 
-        let color_count = colors.len();
-        if color_count < 2 {
-            return Err(PastelError::GradientColorCountMustBeLargerThanOne);
+        if let Some(name) = matches.value_of("save") {
+            gradients::save(name, &gradient)?;
         }
 
+        let space = InterpolationSpace::from_name(&gradient.colorspace)
+            .unwrap_or_else(|| unreachable!("Unknown color space"));
+        let hue = get_hue_interpolation(matches);
+        let mix = |c1: &Color, c2: &Color, f: Fraction| space.mix_with_hue(c1, c2, f, hue);
+
         let mut color_scale = ColorScale::empty();
 
-        for (i, color) in colors.enumerate() {
-            let position = Fraction::from(i as f64 / (color_count as f64 - 1.0));
+        for (color, position) in gradient.stops {
+            color_scale.add_stop(color, Fraction::from(position));
+        }
+
+        if let Some(range) = matches.value_of("range") {
+            let (start, end) = parse_range(range)?;
+            color_scale = color_scale.subrange(start, end, &mix);
+        }
 
-            color_scale.add_stop(color?, position);
+        if matches.is_present("reverse") {
+            color_scale = color_scale.reversed();
         }
 
-        for i in 0..count {
-            let position = Fraction::from(i as f64 / (count as f64 - 1.0));
+        let mut gradient = Gradient::new(color_scale, space).with_hue_interpolation(hue);
+        if let Some(easing) = matches.value_of("easing") {
+            gradient = parse_easing(gradient, easing)?;
+        }
+
+        match matches.value_of("format") {
+            Some("css") => {
+                writeln!(out.handle, "{}", to_css(&gradient, count))?;
+                return Ok(());
+            }
+            Some("svg") => {
+                writeln!(out.handle, "{}", to_svg(&gradient, count))?;
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        let mut colors: Vec<Color> = gradient.take(count).collect();
+
+        if let Some(pattern) = matches.value_of("dither") {
+            let pattern = match pattern {
+                "ordered" => DitherPattern::Ordered,
+                "blue-noise" => DitherPattern::BlueNoise,
+                _ => unreachable!("Unknown dither pattern"),
+            };
+
+            let bits = matches.value_of("bits").expect("required argument");
+            let bits = bits
+                .parse::<u32>()
+                .ok()
+                .filter(|b| (1..=8).contains(b))
+                .ok_or_else(|| PastelError::InvalidBitDepth(bits.into()))?;
+
+            colors = banding::dither(&colors, bits, pattern);
+        }
 
-            let color = color_scale.sample(position, &mix).expect("gradient color");
+        if matches.is_present("check-banding") {
+            let threshold = number_arg(matches, "banding-threshold")?;
+            let issues = banding::detect_banding(&colors, threshold);
 
-            out.show_color(config, &color)?;
+            let mut num_steps = 0;
+            let mut num_plateaus = 0;
+            for issue in &issues {
+                match issue {
+                    BandingIssue::VisibleStep { index, delta_e } => {
+                        num_steps += 1;
+                        writeln!(
+                            out.handle,
+                            "{}-{}: visible step (delta E = {:.2})",
+                            index,
+                            index + 1,
+                            delta_e
+                        )?;
+                    }
+                    BandingIssue::Plateau { index } => {
+                        num_plateaus += 1;
+                        writeln!(out.handle, "{}-{}: identical colors", index, index + 1)?;
+                    }
+                }
+            }
+            writeln!(
+                out.handle,
+                "{} visible steps, {} plateaus",
+                num_steps, num_plateaus
+            )?;
+        } else {
+            for color in &colors {
+                out.show_color(config, color)?;
+            }
         }
 
         Ok(())
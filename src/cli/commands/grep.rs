@@ -0,0 +1,178 @@
+use std::io::Read;
+
+use regex::Regex;
+
+use crate::commands::prelude::*;
+
+use pastel::named::NAMED_COLORS;
+use pastel::parser::parse_color;
+
+pub struct GrepCommand;
+
+/// Hex codes, functional notations (like 'rgb(…)' or 'lab(…)') and words that might be color
+/// names. All candidates are validated by the color parser (or the list of named colors).
+const COLOR_PATTERN: &str = concat!(
+    r"(?i)#[0-9a-f]{3,8}\b",
+    r"|\b(?:rgba?|hsla?|hsva?|gray|(?:cie)?lab|(?:cie)?lch|oklab)\([^()\n]*\)",
+    r"|\b[a-z]+\b",
+);
+
+/// A color literal within a line of text.
+pub(super) struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub color: Color,
+}
+
+pub(super) fn color_regex() -> Regex {
+    Regex::new(COLOR_PATTERN).expect("valid regex")
+}
+
+/// Words that introduce references like 'issue #123' or 'PR #4567' rather than colors.
+const REFERENCE_WORDS: &[&str] = &[
+    "bug", "closes", "fix", "fixes", "issue", "issues", "pr", "pull", "request", "see", "ticket",
+];
+
+/// Whether a hex code at the given position is a plausible color literal. The '#' must not be
+/// part of a word (like 'a#fff' or the '&#123;' entity). Short hex codes that only consist of
+/// digits are skipped if they follow a word like 'issue' or 'PR', since e.g. 'issue #123'
+/// refers to an issue.
+fn is_hex_literal(text: &str, start: usize, digits: &str) -> bool {
+    let before = &text[..start];
+    if before
+        .chars()
+        .next_back()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '&')
+    {
+        return false;
+    }
+    if digits.len() > 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return true;
+    }
+    let previous_word = before
+        .trim_end()
+        .rsplit(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or("");
+    !REFERENCE_WORDS
+        .iter()
+        .any(|word| word.eq_ignore_ascii_case(previous_word))
+}
+
+/// Whether a word at the given position can be a color name. Words within identifiers like
+/// '.red-button', '$tan' or 'var(--bg-white)' are skipped.
+fn is_name_literal(text: &str, start: usize, end: usize) -> bool {
+    let is_identifier = |c: char| matches!(c, '-' | '.' | '$' | '_' | '@' | '#');
+    !text[..start].chars().next_back().is_some_and(is_identifier)
+        && !text[end..].chars().next().is_some_and(is_identifier)
+}
+
+/// Find all color literals in the given line. Words are only considered if they are CSS color
+/// names, such that e.g. 'face' is not interpreted as a hex code.
+pub(super) fn find_colors(regex: &Regex, line: &str, include_names: bool) -> Vec<Match> {
+    regex
+        .find_iter(line)
+        .filter_map(|m| {
+            let text = m.as_str();
+            let color = if let Some(digits) = text.strip_prefix('#') {
+                if !is_hex_literal(line, m.start(), digits) {
+                    return None;
+                }
+                parse_color(text)?
+            } else if text.ends_with(')') {
+                parse_color(text)?
+            } else if include_names && is_name_literal(line, m.start(), m.end()) {
+                NAMED_COLORS
+                    .iter()
+                    .find(|nc| nc.name.eq_ignore_ascii_case(text))
+                    .map(|nc| nc.color.clone())?
+            } else {
+                return None;
+            };
+            Some(Match {
+                start: m.start(),
+                end: m.end(),
+                color,
+            })
+        })
+        .collect()
+}
+
+/// Paint every color literal in its own color.
+fn highlight_line(line: &str, matches: &[Match], brush: Brush) -> String {
+    let mut result = String::new();
+    let mut position = 0;
+    for m in matches {
+        result.push_str(&line[position..m.start]);
+        let text_color = m.color.text_color();
+        result.push_str(&brush.paint(&line[m.start..m.end], text_color.ansi_style().on(&m.color)));
+        position = m.end;
+    }
+    result.push_str(&line[position..]);
+    result
+}
+
+impl GenericCommand for GrepCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let regex = color_regex();
+        let include_names = !matches.is_present("no-names");
+        let unique = matches.is_present("unique");
+        let list = unique || matches.is_present("list");
+        let line_numbers = matches.is_present("line-number");
+
+        let sources = match matches.values_of("file") {
+            Some(paths) => paths
+                .map(|path| {
+                    let source = std::fs::read_to_string(path)
+                        .map_err(|err| PastelError::FileReadError(path.into(), err))?;
+                    Ok((Some(path), source))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => {
+                let mut source = String::new();
+                std::io::stdin().read_to_string(&mut source)?;
+                vec![(None, source)]
+            }
+        };
+        let show_paths = sources.len() > 1;
+
+        let mut colors: Vec<Color> = vec![];
+        for (path, source) in &sources {
+            for (i, line) in source.lines().enumerate() {
+                let found = find_colors(&regex, line, include_names);
+                if found.is_empty() {
+                    continue;
+                }
+
+                if list {
+                    for m in found {
+                        if !(unique && colors.contains(&m.color)) {
+                            colors.push(m.color);
+                        }
+                    }
+                    continue;
+                }
+
+                let mut prefix = String::new();
+                if let (true, Some(path)) = (show_paths, path) {
+                    prefix.push_str(&format!("{}:", path));
+                }
+                if line_numbers {
+                    prefix.push_str(&format!("{}:", i + 1));
+                }
+                writeln!(
+                    out.handle,
+                    "{}{}",
+                    prefix,
+                    highlight_line(line, &found, config.brush)
+                )?;
+            }
+        }
+
+        for color in colors {
+            out.show_color(config, &color)?;
+        }
+
+        Ok(())
+    }
+}
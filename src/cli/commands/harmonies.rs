@@ -0,0 +1,52 @@
+use crate::commands::prelude::*;
+
+use pastel::HueSpace;
+
+pub struct HarmoniesCommand;
+
+impl ColorCommand for HarmoniesCommand {
+    fn run(
+        &self,
+        out: &mut Output,
+        matches: &ArgMatches,
+        config: &Config,
+        color: &Color,
+    ) -> Result<()> {
+        let space = match matches
+            .value_of("colorspace")
+            .expect("required argument")
+            .to_lowercase()
+            .as_ref()
+        {
+            "hsl" => HueSpace::HSL,
+            "oklch" => HueSpace::OkLCh,
+            _ => unreachable!("Unknown hue space"),
+        };
+
+        let count = |default: usize| -> Result<usize> {
+            match matches.value_of("number") {
+                Some(n) => n
+                    .parse::<usize>()
+                    .map_err(|_| PastelError::CouldNotParseNumber(n.into())),
+                None => Ok(default),
+            }
+        };
+
+        let harmony = matches.value_of("harmony").expect("required argument");
+        let colors = match harmony.to_lowercase().as_ref() {
+            "complementary" => vec![color.clone(), color.rotate_hue_in(180.0, space)],
+            "split-complementary" => color.split_complementary(space),
+            "triadic" => color.triadic(space),
+            "tetradic" => color.tetradic(space),
+            "analogous" => color.analogous(count(3)?, number_arg(matches, "angle")?, space),
+            "monochromatic" => color.monochromatic(count(5)?, space),
+            _ => unreachable!("Unknown harmony"),
+        };
+
+        for color in colors {
+            out.show_color(config, &color)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use std::io::{self, BufRead};
+
+use crate::commands::prelude::*;
+
+use pastel::ansi::{self, Mode};
+use pastel::parser::parse_color;
+use pastel::Format;
+
+pub struct HashColorCommand;
+
+impl HashColorCommand {
+    fn print(out: &mut Output, matches: &ArgMatches, config: &Config, color: &Color) -> Result<()> {
+        match matches.value_of("format") {
+            None => out.show_color(config, color)?,
+            Some("hex") => writeln!(out.handle, "{}", color.to_rgb_hex_string(true))?,
+            Some("rgb") => writeln!(out.handle, "{}", color.to_rgb_string(Format::Spaces))?,
+            Some("hsl") => writeln!(out.handle, "{}", color.to_hsl_string(Format::Spaces))?,
+            Some("ansi-escapecode") => {
+                let mode = ansi::get_colormode().unwrap_or(Mode::Ansi8Bit);
+                write!(out.handle, "{}", color.to_ansi_sequence(mode))?
+            }
+            Some("ansi-8bit-escapecode") => {
+                write!(out.handle, "{}", color.to_ansi_sequence(Mode::Ansi8Bit))?
+            }
+            Some("ansi-24bit-escapecode") => {
+                write!(out.handle, "{}", color.to_ansi_sequence(Mode::TrueColor))?
+            }
+            Some(_) => unreachable!("Unknown format type"),
+        }
+
+        Ok(())
+    }
+}
+
+impl GenericCommand for HashColorCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let lightness = finite_number_arg(matches, "lightness")?;
+        if !(0.0..=1.0).contains(&lightness) {
+            return Err(PastelError::OkLchLightnessOutOfRange);
+        }
+        let chroma = finite_number_arg(matches, "chroma")?;
+        if chroma < 0.0 {
+            return Err(PastelError::NegativeChroma);
+        }
+
+        let background = match matches.value_of("against") {
+            Some(bg) => {
+                Some(parse_color(bg).ok_or_else(|| PastelError::ColorParseError(bg.into()))?)
+            }
+            None => None,
+        };
+        let min_contrast = finite_number_arg(matches, "min-contrast")?;
+        if !(1.0..=21.0).contains(&min_contrast) {
+            return Err(PastelError::ContrastTargetOutOfRange(1.0, 21.0));
+        }
+
+        let hash_color = |text: &str| {
+            let color = Color::from_string_hash(text, lightness, chroma);
+            match background {
+                Some(ref background) => color.with_minimum_contrast(background, min_contrast),
+                None => color,
+            }
+        };
+
+        match matches.values_of("text") {
+            Some(texts) => {
+                for text in texts {
+                    Self::print(out, matches, config, &hash_color(text))?;
+                }
+            }
+            None => {
+                for line in io::stdin().lock().lines() {
+                    Self::print(out, matches, config, &hash_color(&line?))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, Read};
 
 use clap::{ArgMatches, Values};
 
 use crate::colorpicker::{print_colorspectrum, run_external_colorpicker};
-use crate::config::Config;
+use crate::config::{Config, RawFormat};
 use crate::{PastelError, Result};
 
 use pastel::parser::parse_color;
@@ -16,15 +16,27 @@ pub fn number_arg(matches: &ArgMatches, name: &str) -> Result<f64> {
         .map_err(|_| PastelError::CouldNotParseNumber(value_str.into()))
 }
 
+/// Like `number_arg`, but rejects infinite values and NaN.
+pub fn finite_number_arg(matches: &ArgMatches, name: &str) -> Result<f64> {
+    let value = number_arg(matches, name)?;
+    if !value.is_finite() {
+        let value_str = matches.value_of(name).expect("required argument");
+        return Err(PastelError::NonFiniteNumber(value_str.into()));
+    }
+    Ok(value)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum PrintSpectrum {
     Yes,
     No,
 }
 
+#[allow(clippy::enum_variant_names)]
 pub enum ColorArgIterator<'a> {
     FromPositionalArguments(&'a Config<'a>, Values<'a>, PrintSpectrum),
     FromStdin,
+    FromRawStdin(RawFormat, io::StdinLock<'static>),
 }
 
 impl<'a> ColorArgIterator<'a> {
@@ -45,6 +57,48 @@ impl<'a> ColorArgIterator<'a> {
         }
     }
 
+    /// Like `from_args`, but also takes the `--input-format` option into account (for commands
+    /// that support reading binary pixel data).
+    pub fn from_matches(config: &'a Config, matches: &'a ArgMatches) -> Result<Self> {
+        // Not all commands define this option
+        let input_format = matches.try_get_one::<String>("input-format").ok().flatten();
+        let raw_format = match input_format.and_then(|f| RawFormat::from_name(f)) {
+            Some(raw_format) => raw_format,
+            None => return Self::from_args(config, matches.values_of("color")),
+        };
+
+        if atty::is(atty::Stream::Stdin) {
+            return Err(PastelError::ColorArgRequired);
+        }
+        Ok(ColorArgIterator::FromRawStdin(
+            raw_format,
+            io::stdin().lock(),
+        ))
+    }
+
+    fn color_from_raw_pixel(format: RawFormat, reader: &mut impl Read) -> Result<Option<Color>> {
+        let mut pixel = [0u8; 4];
+        let pixel = &mut pixel[..format.bytes_per_pixel()];
+
+        let mut filled = 0;
+        while filled < pixel.len() {
+            match reader.read(&mut pixel[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(PastelError::IncompleteRawPixel),
+                Ok(n) => filled += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(Some(match format {
+            RawFormat::Rgb8 => Color::from_rgb(pixel[0], pixel[1], pixel[2]),
+            RawFormat::Rgba8 => {
+                Color::from_rgba(pixel[0], pixel[1], pixel[2], f64::from(pixel[3]) / 255.0)
+            }
+        }))
+    }
+
     pub fn color_from_stdin() -> Result<Color> {
         let stdin = io::stdin();
         let mut lock = stdin.lock();
@@ -103,6 +157,10 @@ impl<'a> Iterator for ColorArgIterator<'a> {
                 Err(PastelError::CouldNotReadFromStdin) => None,
                 err @ Err(_) => Some(err),
             },
+
+            ColorArgIterator::FromRawStdin(format, ref mut reader) => {
+                Self::color_from_raw_pixel(*format, reader).transpose()
+            }
         }
     }
 }
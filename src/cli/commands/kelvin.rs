@@ -0,0 +1,23 @@
+use crate::commands::prelude::*;
+
+use pastel::Color;
+
+pub struct KelvinCommand;
+
+impl GenericCommand for KelvinCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        for value in matches.values_of("temperature").expect("required argument") {
+            let number = value.trim().trim_end_matches(['K', 'k']);
+            let kelvin = number
+                .parse::<f64>()
+                .map_err(|_| PastelError::CouldNotParseNumber(value.into()))?;
+            if !(1000.0..=40000.0).contains(&kelvin) {
+                return Err(PastelError::InvalidTemperature(value.into()));
+            }
+
+            out.show_color(config, &Color::from_temperature(kelvin))?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::commands::prelude::*;
 use crate::commands::sort::key_function;
+use crate::utility::color_url;
 
 use pastel::ansi::ToAnsiStyle;
 use pastel::named::{NamedColor, NAMED_COLORS};
@@ -18,12 +19,15 @@ impl GenericCommand for ListCommand {
             for nc in colors {
                 let bg = &nc.color;
                 let fg = bg.text_color();
+                let mut style = fg.ansi_style();
+                style.on(bg);
+                if let Some(ref template) = config.hyperlink_template {
+                    style.hyperlink(&color_url(template, bg));
+                }
                 writeln!(
                     out.handle,
                     "{}",
-                    config
-                        .brush
-                        .paint(format!(" {:24}", nc.name), fg.ansi_style().on(bg))
+                    config.brush.paint(format!(" {:24}", nc.name), style)
                 )?;
             }
         } else {
@@ -0,0 +1,51 @@
+use crate::commands::prelude::*;
+
+use pastel::lut::Lut3D;
+use pastel::pipeline::Pipeline;
+
+/// The largest supported number of grid points per axis, as in the `.cube` specification.
+const MAX_LUT_SIZE: usize = 256;
+
+pub struct LutCommand;
+
+impl GenericCommand for LutCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        match matches.subcommand() {
+            Some(("apply", matches)) => {
+                let path = matches.value_of("file").expect("required argument");
+                let source = std::fs::read_to_string(path)
+                    .map_err(|err| PastelError::FileReadError(path.into(), err))?;
+                let lut = Lut3D::parse_cube(&source).map_err(|reason| {
+                    PastelError::LutParseError(path.into(), reason.to_string())
+                })?;
+
+                for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+                    out.show_color(config, &lut.apply(&color?))?;
+                }
+            }
+            Some(("export", matches)) => {
+                let spec = matches.value_of("pipeline").expect("required argument");
+                let pipeline: Pipeline = spec.parse().map_err(|reason: pastel::Error| {
+                    PastelError::PipelineError(spec.into(), reason.to_string())
+                })?;
+
+                let size_str = matches.value_of("size").expect("required argument");
+                let size = size_str
+                    .parse::<usize>()
+                    .map_err(|_| PastelError::CouldNotParseNumber(size_str.into()))?;
+                if !(2..=MAX_LUT_SIZE).contains(&size) {
+                    return Err(PastelError::LutSizeOutOfRange(MAX_LUT_SIZE));
+                }
+
+                let mut lut = Lut3D::from_fn(size, |color| pipeline.apply(color))
+                    .expect("valid lookup table size");
+                lut.set_title(matches.value_of("title").map(|t| t.into()));
+
+                write!(out.handle, "{}", lut.to_cube())?;
+            }
+            _ => unreachable!("Unknown lut subcommand"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::commands::prelude::*;
+
+use pastel::OkLCh;
+
+pub struct MatchChromaCommand;
+
+fn oklch_chroma(color: &Color) -> f64 {
+    OkLCh::from(color).c
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+impl GenericCommand for MatchChromaCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut colors: Vec<Color> = vec![];
+        for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+            colors.push(color?);
+        }
+
+        if colors.is_empty() {
+            return Ok(());
+        }
+
+        let target = match matches.value_of("target").expect("required argument") {
+            "median" => median(&mut colors.iter().map(oklch_chroma).collect::<Vec<_>>()),
+            value => value
+                .parse::<f64>()
+                .ok()
+                .filter(|c| *c >= 0.0)
+                .ok_or_else(|| PastelError::CouldNotParseNumber(value.into()))?,
+        };
+
+        for color in &colors {
+            out.show_color(config, &color.with_oklch_chroma(target))?;
+        }
+
+        Ok(())
+    }
+}
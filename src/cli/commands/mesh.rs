@@ -0,0 +1,115 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::colorspace::get_mixing_function;
+use crate::commands::prelude::*;
+
+use pastel::Fraction;
+
+pub struct MeshCommand;
+
+/// The maximum number of columns and rows. The whole grid is computed before it is printed (or
+/// written to an image), so this also limits the memory usage.
+const MAX_MESH_SIZE: usize = 1024;
+
+/// Parse a grid size like `16x9`.
+fn parse_size(size: &str) -> Result<(usize, usize)> {
+    let valid = |n: usize| (2..=MAX_MESH_SIZE).contains(&n);
+    size.split_once(['x', 'X'])
+        .and_then(|(w, h)| Some((w.trim().parse().ok()?, h.trim().parse().ok()?)))
+        .filter(|&(w, h): &(usize, usize)| valid(w) && valid(h))
+        .ok_or_else(|| PastelError::InvalidMeshSize(size.into()))
+}
+
+fn to_svg(grid: &[Vec<Color>]) -> String {
+    let height = grid.len();
+    let width = grid[0].len();
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {w} {h}\" width=\"{w}\" \
+         height=\"{h}\" shape-rendering=\"crispEdges\">\n",
+        w = width,
+        h = height
+    );
+    for (y, row) in grid.iter().enumerate() {
+        for (x, color) in row.iter().enumerate() {
+            writeln!(
+                svg,
+                "  <rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>",
+                x,
+                y,
+                color.to_rgb_hex_string(true)
+            )
+            .expect("writing to a string");
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn write_png(path: &str, grid: &[Vec<Color>]) -> Result<()> {
+    let image = image::RgbaImage::from_fn(grid[0].len() as u32, grid.len() as u32, |x, y| {
+        let rgba = grid[y as usize][x as usize].to_rgba();
+        image::Rgba([rgba.r, rgba.g, rgba.b, (rgba.alpha * 255.0).round() as u8])
+    });
+
+    image
+        .save_with_format(path, image::ImageFormat::Png)
+        .map_err(|err| PastelError::ImageError(path.into(), err.to_string()))
+}
+
+impl GenericCommand for MeshCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let (width, height) = parse_size(matches.value_of("size").expect("required argument"))?;
+
+        let mut print_spectrum = PrintSpectrum::Yes;
+        let mut color_arg = |name: &str| {
+            ColorArgIterator::from_color_arg(
+                config,
+                matches.value_of(name).expect("required argument"),
+                &mut print_spectrum,
+            )
+        };
+
+        let top_left = color_arg("top-left")?;
+        let top_right = color_arg("top-right")?;
+        let bottom_left = color_arg("bottom-left")?;
+        let bottom_right = color_arg("bottom-right")?;
+
+        let mix = get_mixing_function(matches.value_of("colorspace").expect("required argument"));
+
+        let grid: Vec<Vec<Color>> = (0..height)
+            .map(|y| {
+                let fy = Fraction::from(y as f64 / (height - 1) as f64);
+                (0..width)
+                    .map(|x| {
+                        let fx = Fraction::from(x as f64 / (width - 1) as f64);
+                        let top = mix(&top_left, &top_right, fx);
+                        let bottom = mix(&bottom_left, &bottom_right, fx);
+                        mix(&top, &bottom, fy)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        match matches.value_of("image") {
+            Some(path) => {
+                let is_svg = Path::new(path)
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+                if is_svg {
+                    std::fs::write(path, to_svg(&grid))?;
+                } else {
+                    write_png(path, &grid)?;
+                }
+            }
+            None => {
+                for color in grid.iter().flatten() {
+                    out.show_color(config, color)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -3,34 +3,138 @@ use crate::error::Result;
 use crate::output::Output;
 use clap::ArgMatches;
 
+mod a11y_report;
+mod adjust_contrast;
+mod alpha_report;
+mod ansi_map;
+mod apply;
+mod bands;
+mod cluster;
 mod color_commands;
 mod colorcheck;
+mod colorize;
+mod composite;
+mod contrast;
+mod cyclic;
+mod demo;
+mod diff_colors;
+mod distance;
+mod distance_matrix;
 mod distinct;
+mod diverging;
+mod edit;
+mod elevation;
+mod export;
+mod extract_from_image;
 mod format;
+mod generative;
+mod glass;
 mod gradient;
 mod gray;
+mod grep;
+mod harmonies;
+mod hash_color;
 mod io;
+mod kelvin;
 mod list;
+mod lut;
+mod match_chroma;
+mod mesh;
 mod paint;
+mod palette;
 mod pick;
+mod pick_from_image;
 mod prelude;
+mod proof;
+mod ramp;
 mod random;
+mod replace;
+mod roles;
+mod roundtrip;
+mod sample;
+mod scan_css;
+#[cfg(feature = "serve")]
+mod serve;
 mod show;
 mod sort;
+mod states;
+mod stats;
+mod strip;
+mod system_accent;
+mod system_colors;
+mod terminal_background;
+mod text_color;
+mod theme;
+mod tokens;
+mod tonal_palette;
 mod traits;
+mod under;
+mod wavelength;
 
 use traits::{ColorCommand, GenericCommand};
 
+use a11y_report::A11yReportCommand;
+use adjust_contrast::AdjustContrastCommand;
+use alpha_report::AlphaReportCommand;
+use ansi_map::AnsiMapCommand;
+use apply::ApplyCommand;
+use bands::BandsCommand;
+use cluster::ClusterCommand;
 use colorcheck::ColorCheckCommand;
+use colorize::ColorizeCommand;
+use composite::CompositeCommand;
+use contrast::ContrastCommand;
+use cyclic::CyclicCommand;
+use demo::DemoCommand;
+use diff_colors::DiffColorsCommand;
+use distance::DistanceCommand;
+use distance_matrix::DistanceMatrixCommand;
 use distinct::DistinctCommand;
+use diverging::DivergingCommand;
+use edit::EditCommand;
+use elevation::ElevationCommand;
+use export::ExportCommand;
+use extract_from_image::ExtractFromImageCommand;
 use format::FormatCommand;
+use generative::GenerativeCommand;
+use glass::GlassCommand;
 use gradient::GradientCommand;
 use gray::GrayCommand;
+use grep::GrepCommand;
+use harmonies::HarmoniesCommand;
+use hash_color::HashColorCommand;
+use kelvin::KelvinCommand;
 use list::ListCommand;
+use lut::LutCommand;
+use match_chroma::MatchChromaCommand;
+use mesh::MeshCommand;
 use paint::PaintCommand;
+use palette::PaletteCommand;
 use pick::PickCommand;
+use pick_from_image::PickFromImageCommand;
+use proof::ProofCommand;
+use ramp::RampCommand;
 use random::RandomCommand;
+use replace::ReplaceCommand;
+use roles::RolesCommand;
+use roundtrip::RoundtripCommand;
+use sample::SampleCommand;
+use scan_css::ScanCssCommand;
+#[cfg(feature = "serve")]
+use serve::ServeCommand;
 use sort::SortCommand;
+use states::StatesCommand;
+use stats::StatsCommand;
+use strip::StripCommand;
+use system_accent::SystemAccentCommand;
+use system_colors::SystemColorsCommand;
+use terminal_background::TerminalBackgroundCommand;
+use text_color::TextColorCommand;
+use theme::ThemeCommand;
+use tokens::TokensCommand;
+use tonal_palette::TonalPaletteCommand;
+use under::UnderCommand;
+use wavelength::WavelengthCommand;
 
 use io::ColorArgIterator;
 
@@ -44,26 +148,88 @@ impl Command {
         match command {
             "color" => Command::WithColor(Box::new(show::ShowCommand)),
             "saturate" => Command::WithColor(Box::new(color_commands::SaturateCommand)),
+            "curves" => Command::WithColor(Box::new(color_commands::CurvesCommand)),
+            "filter" => Command::WithColor(Box::new(color_commands::FilterCommand)),
+            "lut" => Command::Generic(Box::new(LutCommand)),
+            "tokens" => Command::Generic(Box::new(TokensCommand)),
+            "export" => Command::Generic(Box::new(ExportCommand)),
             "desaturate" => Command::WithColor(Box::new(color_commands::DesaturateCommand)),
             "lighten" => Command::WithColor(Box::new(color_commands::LightenCommand)),
             "darken" => Command::WithColor(Box::new(color_commands::DarkenCommand)),
             "rotate" => Command::WithColor(Box::new(color_commands::RotateCommand)),
             "colorblind" => Command::WithColor(Box::new(color_commands::ColorblindCommand)),
+            "daltonize" => Command::WithColor(Box::new(color_commands::DaltonizeCommand)),
+            "proof" => Command::WithColor(Box::new(ProofCommand)),
+            "under" => Command::WithColor(Box::new(UnderCommand)),
             "set" => Command::WithColor(Box::new(color_commands::SetCommand)),
             "complement" => Command::WithColor(Box::new(color_commands::ComplementCommand)),
+            "harmonies" => Command::WithColor(Box::new(HarmoniesCommand)),
+            "shades" => Command::WithColor(Box::new(RampCommand::new(pastel::Color::black()))),
+            "tints" => Command::WithColor(Box::new(RampCommand::new(pastel::Color::white()))),
+            "tones" => Command::WithColor(Box::new(RampCommand::new(pastel::Color::gray()))),
+            "max-chroma" => Command::WithColor(Box::new(color_commands::MaxChromaCommand)),
+            "match-lightness" => {
+                Command::WithColor(Box::new(color_commands::MatchLightnessCommand))
+            }
+            "match-chroma" => Command::Generic(Box::new(MatchChromaCommand)),
             "mix" => Command::WithColor(Box::new(color_commands::MixCommand)),
+            "tint" => Command::WithColor(Box::new(color_commands::TintCommand)),
+            "blend" => Command::WithColor(Box::new(color_commands::BlendCommand)),
+            "composite" => Command::Generic(Box::new(CompositeCommand)),
             "to-gray" => Command::WithColor(Box::new(color_commands::ToGrayCommand)),
-            "textcolor" => Command::WithColor(Box::new(color_commands::TextColorCommand)),
+            "textcolor" => Command::WithColor(Box::new(TextColorCommand)),
+            "contrast" => Command::WithColor(Box::new(ContrastCommand)),
+            "adjust-contrast" => Command::WithColor(Box::new(AdjustContrastCommand)),
+            "a11y-report" => Command::Generic(Box::new(A11yReportCommand)),
+            "distance" => Command::Generic(Box::new(DistanceCommand)),
+            "distance-matrix" => Command::Generic(Box::new(DistanceMatrixCommand)),
+            "roundtrip" => Command::Generic(Box::new(RoundtripCommand)),
+            "elevation" => Command::Generic(Box::new(ElevationCommand)),
+            "glass" => Command::Generic(Box::new(GlassCommand)),
+            "states" => Command::Generic(Box::new(StatesCommand)),
+            "tonal-palette" => Command::Generic(Box::new(TonalPaletteCommand)),
+            "theme" => Command::Generic(Box::new(ThemeCommand)),
+            "apply" => Command::Generic(Box::new(ApplyCommand)),
+            "terminal-background" => Command::Generic(Box::new(TerminalBackgroundCommand)),
+            "edit" => Command::Generic(Box::new(EditCommand)),
+            "pick-from-image" => Command::Generic(Box::new(PickFromImageCommand)),
+            "grep" => Command::Generic(Box::new(GrepCommand)),
+            "replace" => Command::Generic(Box::new(ReplaceCommand)),
+            "strip" => Command::Generic(Box::new(StripCommand)),
             "pick" => Command::Generic(Box::new(PickCommand)),
             "gray" => Command::Generic(Box::new(GrayCommand)),
+            "hash-color" => Command::Generic(Box::new(HashColorCommand)),
+            "kelvin" => Command::Generic(Box::new(KelvinCommand)),
+            "wavelength" => Command::Generic(Box::new(WavelengthCommand)),
+            "system-colors" => Command::Generic(Box::new(SystemColorsCommand)),
+            "system-accent" => Command::Generic(Box::new(SystemAccentCommand)),
             "list" => Command::Generic(Box::new(ListCommand)),
             "sort-by" => Command::Generic(Box::new(SortCommand)),
+            "alpha-report" => Command::Generic(Box::new(AlphaReportCommand)),
+            "stats" => Command::Generic(Box::new(StatsCommand)),
+            "roles" => Command::Generic(Box::new(RolesCommand)),
             "random" => Command::Generic(Box::new(RandomCommand)),
+            "generative" => Command::Generic(Box::new(GenerativeCommand)),
+            "sample" => Command::Generic(Box::new(SampleCommand)),
             "distinct" => Command::Generic(Box::new(DistinctCommand)),
+            "cluster" => Command::Generic(Box::new(ClusterCommand)),
+            "extract-from-image" => Command::Generic(Box::new(ExtractFromImageCommand)),
+            "bands" => Command::Generic(Box::new(BandsCommand)),
+            "diff-colors" => Command::Generic(Box::new(DiffColorsCommand)),
             "gradient" => Command::Generic(Box::new(GradientCommand)),
+            "mesh" => Command::Generic(Box::new(MeshCommand)),
+            "diverging" => Command::Generic(Box::new(DivergingCommand)),
+            "cyclic" => Command::Generic(Box::new(CyclicCommand)),
             "paint" => Command::Generic(Box::new(PaintCommand)),
-            "format" => Command::WithColor(Box::new(FormatCommand)),
+            "colorize" => Command::Generic(Box::new(ColorizeCommand)),
+            "palette" => Command::Generic(Box::new(PaletteCommand)),
+            "scan-css" => Command::Generic(Box::new(ScanCssCommand)),
+            #[cfg(feature = "serve")]
+            "serve" => Command::Generic(Box::new(ServeCommand)),
+            "format" => Command::Generic(Box::new(FormatCommand)),
             "colorcheck" => Command::Generic(Box::new(ColorCheckCommand)),
+            "ansi-map" => Command::Generic(Box::new(AnsiMapCommand)),
+            "demo" => Command::Generic(Box::new(DemoCommand)),
             _ => unreachable!("Unknown subcommand"),
         }
     }
@@ -76,7 +242,7 @@ impl Command {
         match self {
             Command::Generic(cmd) => cmd.run(&mut out, matches, config),
             Command::WithColor(cmd) => {
-                for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+                for color in ColorArgIterator::from_matches(config, matches)? {
                     cmd.run(&mut out, matches, config, &color?)?;
                 }
 
@@ -1,6 +1,8 @@
-use std::io::{self, Read};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
 
 use crate::commands::prelude::*;
+use crate::palettes;
 
 use super::io::ColorArgIterator;
 
@@ -9,58 +11,128 @@ use pastel::parser::parse_color;
 
 pub struct PaintCommand;
 
+/// Read a palette file with one color per line (see `palettes::parse`).
+fn read_palette(path: &str) -> Result<Vec<Color>> {
+    let colors = palettes::parse(&std::fs::read_to_string(path)?)?;
+
+    if colors.is_empty() {
+        return Err(PastelError::PaletteFileEmpty(path.into()));
+    }
+
+    Ok(colors)
+}
+
 impl GenericCommand for PaintCommand {
     fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
-        let fg = matches.value_of("color").expect("required argument");
-        let fg = if fg.trim() == "default" {
-            None
-        } else {
-            let mut print_spectrum = PrintSpectrum::Yes;
-            Some(ColorArgIterator::from_color_arg(
-                config,
-                fg,
-                &mut print_spectrum,
-            )?)
-        };
-
         let bg = if let Some(bg) = matches.value_of("on") {
             Some(parse_color(bg).ok_or_else(|| PastelError::ColorParseError(bg.into()))?)
         } else {
             None
         };
 
-        let text = match matches.values_of("text") {
-            Some(values) => values.map(|v| v.to_string()).collect::<Vec<_>>().join(" "),
-            _ => {
-                let mut buffer = String::new();
-                io::stdin().read_to_string(&mut buffer)?;
-                buffer
+        // Add the attributes from the command-line options to a base style
+        let with_options = |mut style: Style| {
+            if let Some(ref bg) = bg {
+                style.on(bg);
+            }
+
+            if matches.is_present("bold") {
+                style.bold(true);
+            }
+            if matches.is_present("dim") {
+                style.dim(true);
+            }
+            if matches.is_present("italic") {
+                style.italic(true);
+            }
+            if matches.is_present("underline") {
+                style.underline(true);
+            }
+            if matches.is_present("blink") {
+                style.blink(true);
+            }
+            if matches.is_present("reverse") {
+                style.reverse(true);
             }
+            if matches.is_present("hidden") {
+                style.hidden(true);
+            }
+            if matches.is_present("strikethrough") {
+                style.strikethrough(true);
+            }
+            style
         };
 
-        let mut style = Style::default();
+        let newline = if matches.is_present("no-newline") {
+            ""
+        } else {
+            "\n"
+        };
 
-        if let Some(fg) = fg {
-            style.foreground(&fg);
-        }
+        if let Some(palette_path) = matches.value_of("cycle") {
+            let palette = read_palette(palette_path)?;
+
+            // Without a foreground color argument, all positional arguments are part of the text
+            let words: Vec<&str> = matches
+                .values_of("color")
+                .into_iter()
+                .flatten()
+                .chain(matches.values_of("text").into_iter().flatten())
+                .collect();
+
+            let lines: Box<dyn Iterator<Item = io::Result<String>>> = if !words.is_empty() {
+                Box::new(std::iter::once(Ok(words.join(" "))))
+            } else if let Some(path) = matches.value_of("text-from") {
+                Box::new(BufReader::new(File::open(path)?).lines())
+            } else {
+                Box::new(io::stdin().lock().lines())
+            };
+
+            let mut lines = lines.enumerate().peekable();
+            while let Some((i, line)) = lines.next() {
+                let style = with_options(palette[i % palette.len()].ansi_style());
+                let end = if lines.peek().is_some() {
+                    "\n"
+                } else {
+                    newline
+                };
+                write!(out.handle, "{}{}", config.brush.paint(line?, style), end)?;
+            }
 
-        if let Some(bg) = bg {
-            style.on(bg);
+            return Ok(());
         }
 
-        style.bold(matches.is_present("bold"));
-        style.italic(matches.is_present("italic"));
-        style.underline(matches.is_present("underline"));
+        // The foreground argument can be a color or a full style specification like 'bold red on
+        // #222' (or 'default' to keep the terminal's foreground color)
+        let fg = matches.value_of("color").expect("required argument");
+        let mut print_spectrum = PrintSpectrum::Yes;
+        let style = match ColorArgIterator::from_color_arg(config, fg, &mut print_spectrum) {
+            Ok(color) => color.ansi_style(),
+            Err(err @ PastelError::ColorParseError(_)) => match fg.parse::<Style>() {
+                Ok(style) => style,
+                Err(_) if !fg.trim().contains(char::is_whitespace) => return Err(err),
+                Err(reason) => {
+                    return Err(PastelError::StyleParseError(fg.into(), reason.to_string()))
+                }
+            },
+            Err(err) => return Err(err),
+        };
+
+        let text = match (matches.values_of("text"), matches.value_of("text-from")) {
+            (Some(values), _) => values.map(|v| v.to_string()).collect::<Vec<_>>().join(" "),
+            (None, Some(path)) => std::fs::read_to_string(path)?,
+            (None, None) => {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            }
+        };
 
         write!(
             out.handle,
             "{}{}",
-            config.brush.paint(text, style),
-            if matches.is_present("no-newline") {
-                ""
-            } else {
-                "\n"
-            }
+            config.brush.paint(text, with_options(style)),
+            newline
         )?;
 
         Ok(())
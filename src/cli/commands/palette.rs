@@ -0,0 +1,41 @@
+use crate::commands::prelude::*;
+use crate::palettes;
+
+pub struct PaletteCommand;
+
+impl GenericCommand for PaletteCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        match matches.subcommand() {
+            Some(("save", matches)) => {
+                let name = matches.value_of("name").expect("required argument");
+                let colors = ColorArgIterator::from_args(config, matches.values_of("color"))?
+                    .collect::<Result<Vec<_>>>()?;
+                palettes::save(name, &colors)?;
+            }
+            Some(("append", matches)) => {
+                let name = matches.value_of("name").expect("required argument");
+                let colors = ColorArgIterator::from_args(config, matches.values_of("color"))?
+                    .collect::<Result<Vec<_>>>()?;
+                palettes::append(name, &colors)?;
+            }
+            Some(("show", matches)) => {
+                let name = matches.value_of("name").expect("required argument");
+                for color in palettes::load(name)? {
+                    out.show_color(config, &color)?;
+                }
+            }
+            Some(("list", _)) => {
+                for name in palettes::names() {
+                    writeln!(out.handle, "{}", name)?;
+                }
+            }
+            Some(("remove", matches)) => {
+                let name = matches.value_of("name").expect("required argument");
+                palettes::remove(name)?;
+            }
+            _ => unreachable!("Unknown palette subcommand"),
+        }
+
+        Ok(())
+    }
+}
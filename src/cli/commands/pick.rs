@@ -1,27 +1,75 @@
 use crate::commands::prelude::*;
 
-use crate::colorpicker::{print_colorspectrum, run_external_colorpicker};
+use crate::colorpicker::{
+    print_colorspectrum, run_averaging_colorpicker, run_external_colorpicker,
+};
+
+use pastel::ansi::Stream;
 
 pub struct PickCommand;
 
+/// The largest side length of the area to average over
+const MAX_AVERAGE_SIZE: u16 = 100;
+
+/// Parse the size of the area to average over, either 'WxH' or a single side length.
+fn parse_average_size(size: &str) -> Result<(u16, u16)> {
+    let (width, height) = size.split_once(['x', 'X']).unwrap_or((size, size));
+    width
+        .trim()
+        .parse()
+        .ok()
+        .zip(height.trim().parse().ok())
+        .filter(|&(w, h): &(u16, u16)| {
+            (1..=MAX_AVERAGE_SIZE).contains(&w) && (1..=MAX_AVERAGE_SIZE).contains(&h)
+        })
+        .ok_or_else(|| PastelError::InvalidAverageSize(size.into()))
+}
+
 impl GenericCommand for PickCommand {
     fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
         let count = matches.value_of("count").expect("required argument");
         let count = count
             .parse::<usize>()
             .map_err(|_| PastelError::CouldNotParseNumber(count.into()))?;
+        let average = matches
+            .value_of("average")
+            .map(parse_average_size)
+            .transpose()?;
 
         print_colorspectrum(config)?;
 
-        let mut color_strings = Vec::new();
-        for _ in 0..count {
-            color_strings.push(run_external_colorpicker(config.colorpicker)?);
-        }
+        // When picking several colors, show the colors picked so far on STDERR
+        let preview = count > 1 && atty::is(Stream::Stderr);
+        let preview_config = Config {
+            interactive_mode: true,
+            brush: Brush::from_environment(Stream::Stderr)?,
+            ..config.clone()
+        };
+        let mut stderr = std::io::stderr();
+        let mut preview_out = Output::new(&mut stderr);
 
         let mut print_spectrum = PrintSpectrum::No;
-
-        for color_str in color_strings {
+        let mut colors = Vec::new();
+        for i in 1..=count {
+            let color_str = match average {
+                Some((width, height)) => {
+                    run_averaging_colorpicker(config.colorpicker, width, height)?
+                }
+                None => run_external_colorpicker(config.colorpicker)?,
+            };
             let color = ColorArgIterator::from_color_arg(config, &color_str, &mut print_spectrum)?;
+
+            if preview {
+                preview_out.show_named_color(
+                    &preview_config,
+                    &format!("({}/{})", i, count),
+                    &color,
+                )?;
+            }
+            colors.push(color);
+        }
+
+        for color in colors {
             out.show_color(config, &color)?;
         }
 
@@ -0,0 +1,227 @@
+// The interactive mode is only available on Unix
+#![cfg_attr(not(unix), allow(dead_code))]
+
+use std::io::Write;
+
+use crate::commands::prelude::*;
+use crate::hdcanvas::Canvas;
+use crate::terminal::Key;
+#[cfg(unix)]
+use crate::terminal::RawMode;
+
+use image::RgbaImage;
+use pastel::ansi::{self, Stream};
+
+pub struct PickFromImageCommand;
+
+/// The maximum size of the preview in the interactive mode, in pixels of the preview. Every
+/// terminal cell shows two pixels on top of each other.
+const PREVIEW_WIDTH: usize = 64;
+const PREVIEW_HEIGHT: usize = 48;
+
+/// Step size of the upper case keybindings (in pixels of the preview)
+const FAST_STEP: usize = 8;
+
+const HELP: &str = "arrows/hjkl move  HJKL move faster  Enter pick  q finish  Esc cancel";
+
+/// Parse a pixel position like '120,300' (x, y from the top left corner).
+fn parse_position(position: &str) -> Result<(u32, u32)> {
+    position
+        .split_once(',')
+        .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)))
+        .ok_or_else(|| PastelError::InvalidImagePosition(position.into()))
+}
+
+fn pixel_color(image: &RgbaImage, x: u32, y: u32) -> Color {
+    let pixel = image.get_pixel(x, y);
+    Color::from_rgba(pixel[0], pixel[1], pixel[2], f64::from(pixel[3]) / 255.0)
+}
+
+/// A downscaled view of the image, small enough to fit into the terminal.
+struct Preview<'a> {
+    image: &'a RgbaImage,
+    scale: f64,
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Preview<'a> {
+    fn new(image: &'a RgbaImage) -> Self {
+        let scale = (f64::from(image.width()) / PREVIEW_WIDTH as f64)
+            .max(f64::from(image.height()) / PREVIEW_HEIGHT as f64)
+            .max(1.0);
+        let size = |length: u32| ((f64::from(length) / scale).ceil() as usize).max(1);
+
+        Preview {
+            image,
+            scale,
+            width: size(image.width()),
+            height: size(image.height()),
+        }
+    }
+
+    /// The pixel of the image in the center of the given preview pixel.
+    fn image_position(&self, col: usize, row: usize) -> (u32, u32) {
+        let position =
+            |i: usize, length: u32| (((i as f64 + 0.5) * self.scale) as u32).min(length - 1);
+        (
+            position(col, self.image.width()),
+            position(row, self.image.height()),
+        )
+    }
+
+    /// Draw the preview with a crosshair around the cursor.
+    fn render(&self, brush: Brush, cursor: (usize, usize)) -> Vec<u8> {
+        // The canvas needs an even number of rows
+        let mut canvas = Canvas::new(self.height + self.height % 2, self.width, brush);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let (x, y) = self.image_position(col, row);
+                canvas.draw_rect(row, col, 1, 1, &pixel_color(self.image, x, y));
+            }
+        }
+
+        let (col, row) = cursor;
+        let (x, y) = self.image_position(col, row);
+        let marker = pixel_color(self.image, x, y).text_color();
+        let neighbors = [
+            (col.checked_sub(1), Some(row)),
+            (Some(col + 1), Some(row)),
+            (Some(col), row.checked_sub(1)),
+            (Some(col), Some(row + 1)),
+        ];
+        for (c, r) in neighbors {
+            if let (Some(c), Some(r)) = (c, r) {
+                if c < self.width && r < self.height {
+                    canvas.draw_rect(r, c, 1, 1, &marker);
+                }
+            }
+        }
+
+        let mut lines = Vec::new();
+        canvas.print(&mut lines).expect("writing to a buffer");
+        lines
+    }
+}
+
+/// Move the cursor, staying within the preview.
+fn move_cursor(cursor: (usize, usize), key: Key, preview: &Preview) -> (usize, usize) {
+    let (col, row) = cursor;
+    let (col, row) = match key {
+        Key::Char('h') | Key::Left => (col.saturating_sub(1), row),
+        Key::Char('l') | Key::Right => (col + 1, row),
+        Key::Char('k') | Key::Up => (col, row.saturating_sub(1)),
+        Key::Char('j') | Key::Down => (col, row + 1),
+        Key::Char('H') => (col.saturating_sub(FAST_STEP), row),
+        Key::Char('L') => (col + FAST_STEP, row),
+        Key::Char('K') => (col, row.saturating_sub(FAST_STEP)),
+        Key::Char('J') => (col, row + FAST_STEP),
+        _ => (col, row),
+    };
+    (col.min(preview.width - 1), row.min(preview.height - 1))
+}
+
+#[cfg(unix)]
+fn pick_interactively(brush: Brush, image: &RgbaImage) -> Result<Vec<Color>> {
+    let raw_mode = RawMode::enable().ok_or(PastelError::NoInteractiveTerminal)?;
+    let mut panel = std::io::stderr();
+
+    let preview = Preview::new(image);
+    let mut cursor = (preview.width / 2, preview.height / 2);
+    let mut picked: Vec<Color> = vec![];
+
+    let result = loop {
+        let (x, y) = preview.image_position(cursor.0, cursor.1);
+        let color = pixel_color(image, x, y);
+
+        let canvas = preview.render(brush, cursor);
+        panel.write_all(&canvas)?;
+        writeln!(
+            panel,
+            "\n  {} {:9} at {},{}  ({} picked)\n  {}",
+            brush.paint("   ", color.ansi_style().on(&color)),
+            color.to_rgb_hex_string(true),
+            x,
+            y,
+            picked.len(),
+            HELP
+        )?;
+        panel.flush()?;
+
+        let key = raw_mode.read_key()?;
+
+        // Move to the start of the panel and clear it
+        let lines = canvas.iter().filter(|&&b| b == b'\n').count() + 3;
+        write!(panel, "\x1b[{}A\r\x1b[J", lines)?;
+
+        match key {
+            Key::Enter | Key::Char(' ') => picked.push(color),
+            Key::Char('q') => {
+                if picked.is_empty() {
+                    picked.push(color);
+                }
+                break Some(picked);
+            }
+            Key::Escape | Key::Interrupt => break None,
+            key => cursor = move_cursor(cursor, key, &preview),
+        }
+    };
+
+    panel.flush()?;
+    result.ok_or(PastelError::PickCancelled)
+}
+
+#[cfg(not(unix))]
+fn pick_interactively(_brush: Brush, _image: &RgbaImage) -> Result<Vec<Color>> {
+    Err(PastelError::NoInteractiveTerminal)
+}
+
+impl GenericCommand for PickFromImageCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let path = matches.value_of("file").expect("required argument");
+        let positions = matches
+            .values_of("at")
+            .map(|positions| positions.map(parse_position).collect::<Result<Vec<_>>>())
+            .transpose()?;
+
+        let image = image::open(path)
+            .map_err(|err| PastelError::ImageError(path.into(), err.to_string()))?
+            .into_rgba8();
+
+        let colors = match positions {
+            Some(positions) => positions
+                .into_iter()
+                .map(|(x, y)| {
+                    if x < image.width() && y < image.height() {
+                        Ok(pixel_color(&image, x, y))
+                    } else {
+                        Err(PastelError::ImagePositionOutOfBounds(
+                            format!("{},{}", x, y),
+                            image.width(),
+                            image.height(),
+                        ))
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => {
+                if !atty::is(Stream::Stdin) || !atty::is(Stream::Stderr) {
+                    return Err(PastelError::NoInteractiveTerminal);
+                }
+
+                // The preview is drawn on STDERR, which is a terminal even if STDOUT is not
+                let brush = if config.interactive_mode {
+                    config.brush
+                } else {
+                    Brush::from_mode(ansi::get_colormode())
+                };
+                pick_interactively(brush, &image)?
+            }
+        };
+
+        for color in colors {
+            out.show_color(config, &color)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use crate::commands::prelude::*;
+use crate::print_warning;
+
+use pastel::proof::PrintProfile;
+
+pub struct ProofCommand;
+
+impl ColorCommand for ProofCommand {
+    fn run(
+        &self,
+        out: &mut Output,
+        matches: &ArgMatches,
+        config: &Config,
+        color: &Color,
+    ) -> Result<()> {
+        let profile_name = matches.value_of("profile").expect("required argument");
+        let profile = PrintProfile::from_name(profile_name).expect("Unknown print profile");
+
+        let threshold = matches.value_of("threshold").expect("required argument");
+        let threshold = threshold
+            .parse::<f64>()
+            .map_err(|_| PastelError::CouldNotParseNumber(threshold.into()))?;
+
+        let proofed = profile.proof(color);
+
+        let delta_e = color.distance_delta_e_ciede2000(&proofed);
+        if delta_e > threshold {
+            print_warning(&format!(
+                "{} shifts by ΔE {:.1} on {} (to {})",
+                color.to_rgb_hex_string(true),
+                delta_e,
+                profile_name.to_lowercase(),
+                proofed.to_rgb_hex_string(true),
+            ));
+        }
+
+        out.show_color(config, &proofed)
+    }
+}
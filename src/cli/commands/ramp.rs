@@ -0,0 +1,41 @@
+use crate::colorspace::get_mixing_function;
+use crate::commands::prelude::*;
+
+use pastel::Fraction;
+
+/// Generate a ramp from the given color towards a fixed target color: black for shades, white
+/// for tints and gray for tones.
+pub struct RampCommand {
+    target: Color,
+}
+
+impl RampCommand {
+    pub fn new(target: Color) -> RampCommand {
+        RampCommand { target }
+    }
+}
+
+impl ColorCommand for RampCommand {
+    fn run(
+        &self,
+        out: &mut Output,
+        matches: &ArgMatches,
+        config: &Config,
+        color: &Color,
+    ) -> Result<()> {
+        let count = matches.value_of("number").expect("required argument");
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| PastelError::CouldNotParseNumber(count.into()))?;
+
+        let mix = get_mixing_function(matches.value_of("colorspace").expect("required argument"));
+
+        // The ramp starts at the base color and stops one step short of the target color
+        for i in 0..count {
+            let fraction = Fraction::from(i as f64 / count as f64);
+            out.show_color(config, &mix(color, &self.target, fraction))?;
+        }
+
+        Ok(())
+    }
+}
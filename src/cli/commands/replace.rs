@@ -0,0 +1,158 @@
+use std::io::Read;
+
+use crate::commands::grep::{color_regex, find_colors};
+use crate::commands::prelude::*;
+
+use pastel::{Format, OkLCh};
+
+pub struct ReplaceCommand;
+
+/// The notation of a color literal in the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Notation {
+    Hex,
+    Rgb,
+    Hsl,
+    OkLCh,
+}
+
+impl Notation {
+    fn from_name(name: &str) -> Option<Notation> {
+        match name {
+            "hex" => Some(Notation::Hex),
+            "rgb" => Some(Notation::Rgb),
+            "hsl" => Some(Notation::Hsl),
+            "oklch" => Some(Notation::OkLCh),
+            _ => None,
+        }
+    }
+
+    /// The notation of an existing literal. Notations that can not be written are replaced by
+    /// hex codes.
+    fn of_literal(literal: &str) -> Notation {
+        let literal = literal.to_lowercase();
+        if literal.starts_with("rgb") {
+            Notation::Rgb
+        } else if literal.starts_with("hsl") {
+            Notation::Hsl
+        } else {
+            Notation::Hex
+        }
+    }
+
+    fn format(self, color: &Color) -> String {
+        match self {
+            Notation::Hex => color.to_rgb_hex_string(true),
+            Notation::Rgb => color.to_rgb_string(Format::Spaces),
+            Notation::Hsl => color.to_hsl_string(Format::Spaces),
+            Notation::OkLCh => {
+                let OkLCh { l, c, h, alpha } = OkLCh::from(color);
+                // Grays have no meaningful hue
+                let (c, h) = if c < 5e-4 { (0.0, 0.0) } else { (c, h) };
+                let alpha = if alpha < 1.0 {
+                    format!(" / {:.3}", alpha)
+                } else {
+                    String::new()
+                };
+                format!("oklch({:.2}% {:.4} {:.2}{})", l * 100.0, c, h, alpha)
+            }
+        }
+    }
+}
+
+/// Parse an 'old=new' pair of colors.
+fn parse_mapping(config: &Config, mapping: &str) -> Result<(Color, Color)> {
+    let (old, new) = mapping
+        .split_once('=')
+        .ok_or_else(|| PastelError::InvalidColorMapping(mapping.into()))?;
+    let color =
+        |c: &str| ColorArgIterator::from_color_arg(config, c.trim(), &mut PrintSpectrum::No);
+    Ok((color(old)?, color(new)?))
+}
+
+/// Rewrite the color literals in the given text. Literals of colors that appear in the mappings
+/// are replaced by the new color. If a target notation is given, all literals are rewritten in
+/// that notation, otherwise only the replaced ones (keeping the notation of the original literal
+/// where possible).
+fn rewrite(
+    source: &str,
+    mappings: &[(Color, Color)],
+    notation: Option<Notation>,
+    include_names: bool,
+) -> String {
+    // Compare the 8-bit values, such that e.g. 'red' and '#f00' are the same color
+    let key = |color: &Color| color.to_rgb_hex_string(false);
+
+    let mut result = String::with_capacity(source.len());
+    let mut position = 0;
+    for m in find_colors(&color_regex(), source, include_names) {
+        let literal = &source[m.start..m.end];
+        let mapped = mappings
+            .iter()
+            .find(|(old, _)| key(old) == key(&m.color))
+            .map(|(_, new)| new);
+
+        let replacement = match (mapped, notation) {
+            (Some(color), _) => notation
+                .unwrap_or_else(|| Notation::of_literal(literal))
+                .format(color),
+            (None, Some(notation)) => notation.format(&m.color),
+            (None, None) => literal.to_string(),
+        };
+
+        result.push_str(&source[position..m.start]);
+        result.push_str(&replacement);
+        position = m.end;
+    }
+    result.push_str(&source[position..]);
+    result
+}
+
+impl GenericCommand for ReplaceCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let notation = matches
+            .value_of("to")
+            .map(|name| Notation::from_name(name).expect("possible value"));
+        let mappings = matches
+            .values_of("map")
+            .map(|mappings| {
+                mappings
+                    .map(|mapping| parse_mapping(config, mapping))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+        if notation.is_none() && mappings.is_empty() {
+            return Err(PastelError::NothingToReplace);
+        }
+        let include_names = matches.is_present("names");
+
+        match matches.values_of("file") {
+            Some(paths) => {
+                for path in paths {
+                    let source = std::fs::read_to_string(path)
+                        .map_err(|err| PastelError::FileReadError(path.into(), err))?;
+                    let result = rewrite(&source, &mappings, notation, include_names);
+                    if matches.is_present("in-place") {
+                        if result != source {
+                            std::fs::write(path, result)?;
+                        }
+                    } else {
+                        write!(out.handle, "{}", result)?;
+                    }
+                }
+            }
+            None => {
+                let mut source = String::new();
+                std::io::stdin().read_to_string(&mut source)?;
+                write!(
+                    out.handle,
+                    "{}",
+                    rewrite(&source, &mappings, notation, include_names)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use std::io::Read;
+
+use crate::commands::prelude::*;
+use crate::commands::tokens::parse_named_lines;
+use crate::tokens;
+
+pub struct RolesCommand;
+
+/// Colors with a (CIE LCh) chroma below this value are considered to be neutral (candidates for
+/// the background and foreground roles).
+const NEUTRAL_CHROMA: f64 = 20.0;
+
+/// Maximum distance (in degrees) from the typical LCh hue of a status role.
+const STATUS_HUE_TOLERANCE: f64 = 35.0;
+
+/// Status roles along with their typical LCh hue (red, orange/yellow and green).
+const STATUS_ROLES: &[(&str, f64)] = &[("error", 35.0), ("warning", 80.0), ("success", 135.0)];
+
+/// A unique color of the palette along with the number of times it appears.
+struct Entry {
+    color: Color,
+    count: usize,
+    assigned: bool,
+}
+
+impl Entry {
+    fn is_neutral(&self) -> bool {
+        self.color.to_lch().c < NEUTRAL_CHROMA
+    }
+}
+
+fn hue_distance(h1: f64, h2: f64) -> f64 {
+    let d = (h1 - h2).abs() % 360.0;
+    d.min(360.0 - d)
+}
+
+/// Find the best unassigned entry (according to `key`) among those that satisfy `filter`, and
+/// mark it as assigned.
+fn assign(
+    entries: &mut [Entry],
+    filter: impl Fn(&Entry) -> bool,
+    key: impl Fn(&Entry) -> f64,
+) -> Option<Color> {
+    let best = entries
+        .iter_mut()
+        .filter(|e| !e.assigned && filter(e))
+        .max_by(|e1, e2| key(e1).total_cmp(&key(e2)))?;
+    best.assigned = true;
+    Some(best.color.clone())
+}
+
+/// Heuristically assign theme roles to the colors of a palette.
+fn infer_roles(colors: &[Color]) -> Vec<(&'static str, Color)> {
+    let mut entries: Vec<Entry> = vec![];
+    for color in colors {
+        match entries
+            .iter_mut()
+            .find(|e| e.color.to_rgba() == color.to_rgba())
+        {
+            Some(entry) => entry.count += 1,
+            None => entries.push(Entry {
+                color: color.clone(),
+                count: 1,
+                assigned: false,
+            }),
+        }
+    }
+
+    let mut roles = vec![];
+
+    // The background is usually the most frequent neutral color, preferably a very light or a
+    // very dark one.
+    let extremeness = |e: &Entry| (e.color.to_lch().l - 50.0).abs() / 50.0;
+    let background = assign(&mut entries, Entry::is_neutral, |e| {
+        e.count as f64 + extremeness(e)
+    })
+    .or_else(|| assign(&mut entries, |_| true, extremeness));
+
+    if let Some(background) = background {
+        // The foreground is the neutral color with the highest contrast to the background
+        let contrast = |e: &Entry| e.color.contrast_ratio(&background);
+        let foreground = assign(&mut entries, Entry::is_neutral, contrast)
+            .or_else(|| assign(&mut entries, |_| true, contrast));
+
+        roles.push(("background", background.clone()));
+        if let Some(foreground) = foreground {
+            roles.push(("foreground", foreground));
+        }
+    }
+
+    let mut status_roles = vec![];
+    for &(role, hue) in STATUS_ROLES {
+        let distance = |e: &Entry| hue_distance(e.color.to_lch().h, hue);
+        if let Some(color) = assign(
+            &mut entries,
+            |e| !e.is_neutral() && distance(e) <= STATUS_HUE_TOLERANCE,
+            |e| -distance(e),
+        ) {
+            status_roles.push((role, color));
+        }
+    }
+
+    // The accent is the most frequent (or most colorful) remaining chromatic color
+    if let Some(accent) = assign(
+        &mut entries,
+        |e| !e.is_neutral(),
+        |e| e.count as f64 + e.color.to_lch().c / 1000.0,
+    ) {
+        roles.push(("accent", accent));
+    }
+
+    roles.extend(status_roles);
+    roles
+}
+
+impl GenericCommand for RolesCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut colors: Vec<Color> = vec![];
+        match matches.values_of("color") {
+            Some(color_args) => {
+                for color in ColorArgIterator::from_args(config, Some(color_args))? {
+                    colors.push(color?);
+                }
+            }
+            None => {
+                // Also accept named palettes like the ones printed by 'pastel scan-css'
+                if atty::is(atty::Stream::Stdin) {
+                    return Err(PastelError::ColorArgRequired);
+                }
+                let mut source = String::new();
+                std::io::stdin().read_to_string(&mut source)?;
+                let tokens = parse_named_lines(&source)?;
+                for token in &tokens {
+                    colors.push(
+                        tokens::resolve(&tokens, &token.value)
+                            .map_err(|reason| PastelError::TokenFileError("-".into(), reason))?,
+                    );
+                }
+            }
+        }
+
+        if colors.is_empty() {
+            return Err(PastelError::ColorArgRequired);
+        }
+
+        for (role, color) in infer_roles(&colors) {
+            out.show_named_color(config, role, &color)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::commands::prelude::*;
+
+pub struct RoundtripCommand;
+
+/// Conversion of a color to some color space and back.
+type Roundtrip = fn(&Color) -> Color;
+
+/// All color spaces (and representations) that colors can be converted to and back from.
+const SPACES: &[(&str, Roundtrip)] = &[
+    ("rgb", |c| Color::from(&c.to_rgba())),
+    ("rgb-float", |c| Color::from(&c.to_rgba_float())),
+    ("hsl", |c| Color::from(&c.to_hsla())),
+    ("hsv", |c| Color::from(&c.to_hsva())),
+    ("xyz", |c| Color::from(&c.to_xyz())),
+    ("lms", |c| Color::from(&c.to_lms())),
+    ("lab", |c| Color::from(&c.to_lab())),
+    ("lch", |c| Color::from(&c.to_lch())),
+    ("oklab", |c| Color::from(&c.to_oklab())),
+    ("cmyk", |c| Color::from(&c.to_cmyk())),
+];
+
+/// Maximum difference in the RGB and alpha channels (in the range from 0 to 1).
+fn max_channel_error(c1: &Color, c2: &Color) -> f64 {
+    let (c1, c2) = (c1.to_rgba_float(), c2.to_rgba_float());
+    [c1.r - c2.r, c1.g - c2.g, c1.b - c2.b, c1.alpha - c2.alpha]
+        .iter()
+        .fold(0.0, |max, d| d.abs().max(max))
+}
+
+impl GenericCommand for RoundtripCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let tolerance = number_arg(matches, "tolerance")?;
+
+        let mut lossy = false;
+        for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+            let color = color?;
+            writeln!(out.handle, "{}", color.to_rgb_hex_string(true))?;
+            for (name, roundtrip) in SPACES {
+                let error = max_channel_error(&color, &roundtrip(&color));
+                let flag = if error > tolerance {
+                    lossy = true;
+                    "  precision lost"
+                } else {
+                    ""
+                };
+                writeln!(out.handle, "  {:10} {:.2e}{}", name, error, flag)?;
+            }
+        }
+
+        if lossy {
+            return Err(PastelError::ExitCode(1));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use rand::distributions::{Distribution, Uniform, WeightedIndex};
+use rand::prelude::*;
+use rand_chacha::ChaCha12Rng;
+
+use crate::commands::prelude::*;
+
+pub struct SampleCommand;
+
+/// Read one weight per line. Empty lines and lines starting with '#' are ignored.
+fn read_weights(path: &str) -> Result<Vec<f64>> {
+    let mut weights = vec![];
+    for line in std::fs::read_to_string(path)?.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        weights.push(
+            line.parse::<f64>()
+                .map_err(|_| PastelError::CouldNotParseNumber(line.into()))?,
+        );
+    }
+
+    Ok(weights)
+}
+
+impl GenericCommand for SampleCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let count = matches.value_of("number").expect("required argument");
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| PastelError::CouldNotParseNumber(count.into()))?;
+
+        let mut rng = match matches.value_of("seed") {
+            Some(seed) => ChaCha12Rng::seed_from_u64(
+                seed.parse::<u64>()
+                    .map_err(|_| PastelError::CouldNotParseNumber(seed.into()))?,
+            ),
+            None => ChaCha12Rng::from_entropy(),
+        };
+
+        let mut palette: Vec<Color> = vec![];
+        for color in ColorArgIterator::from_args(config, matches.values_of("color"))? {
+            palette.push(color?);
+        }
+
+        if palette.is_empty() {
+            return Err(PastelError::ColorArgRequired);
+        }
+
+        let index: Box<dyn Fn(&mut ChaCha12Rng) -> usize> = match matches.value_of("weights") {
+            Some(path) => {
+                let weights = read_weights(path)?;
+                if weights.len() != palette.len() {
+                    return Err(PastelError::SampleWeightCountMismatch(
+                        weights.len(),
+                        palette.len(),
+                    ));
+                }
+                let distribution =
+                    WeightedIndex::new(weights).map_err(|_| PastelError::InvalidSampleWeights)?;
+                Box::new(move |rng| distribution.sample(rng))
+            }
+            None => {
+                let distribution = Uniform::from(0..palette.len());
+                Box::new(move |rng| distribution.sample(rng))
+            }
+        };
+
+        for _ in 0..count {
+            out.show_color(config, &palette[index(&mut rng)])?;
+        }
+
+        Ok(())
+    }
+}
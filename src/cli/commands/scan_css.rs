@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use crate::commands::prelude::*;
+
+use pastel::parser::parse_color;
+
+pub struct ScanCssCommand;
+
+/// Maximum length of a `$a: $b; $b: var(--c); …` chain (protects against cycles).
+const MAX_RESOLVE_DEPTH: usize = 32;
+
+/// Remove `/* … */` comments as well as SCSS `// …` line comments. The latter are only removed
+/// if the `//` is preceded by whitespace (or at the beginning of a line), such that URLs like
+/// `url(https://…)` stay intact.
+fn strip_comments(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        rest = match rest[start..].find("*/") {
+            Some(end) => &rest[start + end + 2..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+
+    result
+        .lines()
+        .map(|line| {
+            let comment = line
+                .match_indices("//")
+                .map(|(i, _)| i)
+                .find(|&i| i == 0 || line[..i].ends_with(char::is_whitespace));
+            match comment {
+                Some(i) => &line[..i],
+                None => line,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Collect all SCSS variable (`$name: value`) and CSS custom property (`--name: value`)
+/// definitions, in the order of their first definition. Later definitions override the value
+/// of earlier ones.
+fn add_variable_definitions(source: &str, definitions: &mut Vec<(String, String)>) {
+    for declaration in strip_comments(source).split([';', '{', '}']) {
+        let (name, value) = match declaration.split_once(':') {
+            Some(parts) => parts,
+            None => continue,
+        };
+
+        let name = name.trim();
+        if !(name.starts_with('$') || name.starts_with("--")) || name.contains(char::is_whitespace)
+        {
+            continue;
+        }
+
+        let value = value
+            .trim()
+            .trim_end_matches("!default")
+            .trim_end_matches("!important")
+            .trim();
+
+        match definitions.iter_mut().find(|(n, _)| n == name) {
+            Some(definition) => definition.1 = value.into(),
+            None => definitions.push((name.into(), value.into())),
+        }
+    }
+}
+
+/// Resolve a value that is either a color or a reference to another variable (`$name` or
+/// `var(--name, fallback)`).
+fn resolve(value: &str, variables: &HashMap<&str, &str>, depth: usize) -> Option<Color> {
+    if depth > MAX_RESOLVE_DEPTH {
+        return None;
+    }
+
+    let value = value.trim();
+
+    if value.starts_with('$') {
+        return resolve(variables.get(value)?, variables, depth + 1);
+    }
+
+    if let Some(inner) = value.strip_prefix("var(").and_then(|v| v.strip_suffix(')')) {
+        let (name, fallback) = match inner.split_once(',') {
+            Some((name, fallback)) => (name.trim(), Some(fallback)),
+            None => (inner.trim(), None),
+        };
+        return variables
+            .get(name)
+            .and_then(|v| resolve(v, variables, depth + 1))
+            .or_else(|| resolve(fallback?, variables, depth + 1));
+    }
+
+    parse_color(value)
+}
+
+impl GenericCommand for ScanCssCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut definitions = vec![];
+        for path in matches.values_of("file").expect("required argument") {
+            add_variable_definitions(&std::fs::read_to_string(path)?, &mut definitions);
+        }
+
+        let variables: HashMap<&str, &str> = definitions
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+
+        let palette = definitions
+            .iter()
+            .filter_map(|(name, value)| Some((name, resolve(value, &variables, 0)?)));
+
+        for (name, color) in palette {
+            out.show_named_color(config, name, &color)?;
+        }
+
+        Ok(())
+    }
+}
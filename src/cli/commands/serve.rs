@@ -0,0 +1,264 @@
+use serde_json::{json, Map, Value};
+
+use crate::commands::prelude::*;
+
+use super::format::{format_value, FormatOptions, ALL_FORMAT_TYPES};
+
+use pastel::distinct::{self, DistanceMetric, OptimizationBudget};
+use pastel::parser::parse_color;
+
+pub struct ServeCommand;
+
+/// Default number of iterations for '/distinct' (lower than for 'pastel distinct', to keep the
+/// response times short).
+const DEFAULT_DISTINCT_ITERATIONS: usize = 50_000;
+
+/// Upper limits for the parameters of '/distinct', such that a single request can not keep the
+/// server busy for a long time.
+const MAX_DISTINCT_COUNT: usize = 100;
+const MAX_DISTINCT_ITERATIONS: usize = 1_000_000;
+
+/// An error response: HTTP status code and message.
+type ApiError = (u16, String);
+
+fn bad_request(message: impl Into<String>) -> ApiError {
+    (400, message.into())
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parse the query string of a URL into (key, value) pairs.
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+struct Query(Vec<(String, String)>);
+
+impl Query {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> {
+        self.0
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn color(&self, key: &str) -> std::result::Result<Color, ApiError> {
+        let value = self
+            .get(key)
+            .ok_or_else(|| bad_request(format!("missing parameter '{}'", key)))?;
+        parse_color(value).ok_or_else(|| bad_request(format!("could not parse color '{}'", value)))
+    }
+
+    fn number(
+        &self,
+        key: &str,
+        default: usize,
+        max: usize,
+    ) -> std::result::Result<usize, ApiError> {
+        let number = match self.get(key) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| bad_request(format!("could not parse number '{}'", value)))?,
+            None => default,
+        };
+        if number > max {
+            return Err(bad_request(format!(
+                "parameter '{}' must not be larger than {}",
+                key, max
+            )));
+        }
+        Ok(number)
+    }
+}
+
+fn convert(query: &Query) -> std::result::Result<Value, ApiError> {
+    let color = query.color("color")?;
+
+    let format_types: Vec<&str> = match query.get("format") {
+        None | Some("all") => ALL_FORMAT_TYPES.to_vec(),
+        Some(format_types) => format_types.split(',').map(str::trim).collect(),
+    };
+
+    let mut result = Map::new();
+    for format_type in format_types {
+        let value = format_value(format_type, &color, FormatOptions::default())
+            .ok_or_else(|| bad_request(format!("unknown format type '{}'", format_type)))?;
+        result.insert(format_type.into(), value.into());
+    }
+    Ok(Value::Object(result))
+}
+
+fn contrast(query: &Query) -> std::result::Result<Value, ApiError> {
+    let foreground = query.color("foreground")?;
+    let background = query.color("background")?;
+
+    Ok(json!({
+        "wcag2": foreground.contrast_ratio(&background),
+        "apca": foreground.contrast_apca(&background),
+    }))
+}
+
+fn distinct(query: &Query) -> std::result::Result<Value, ApiError> {
+    let count = query.number("count", 10, MAX_DISTINCT_COUNT)?;
+    let metric = match query.get("metric").unwrap_or("CIE76") {
+        "CIE76" => DistanceMetric::CIE76,
+        "CIEDE2000" => DistanceMetric::CIEDE2000,
+        metric => return Err(bad_request(format!("unknown metric '{}'", metric))),
+    };
+    let fixed_colors = query
+        .get_all("fixed")
+        .map(|value| {
+            parse_color(value)
+                .ok_or_else(|| bad_request(format!("could not parse color '{}'", value)))
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    if count < 2 {
+        return Err(bad_request(
+            PastelError::DistinctColorCountMustBeLargerThanOne.message(),
+        ));
+    }
+    if fixed_colors.len() > count {
+        return Err(bad_request(
+            PastelError::DistinctColorFixedColorsCannotBeMoreThanCount.message(),
+        ));
+    }
+
+    let budget = OptimizationBudget {
+        num_iterations: query.number(
+            "iterations",
+            DEFAULT_DISTINCT_ITERATIONS,
+            MAX_DISTINCT_ITERATIONS,
+        )?,
+        ..OptimizationBudget::default()
+    };
+    let (mut colors, result) =
+        distinct::distinct_colors(count, metric, fixed_colors, budget, &mut |_| {});
+    distinct::rearrange_sequence(&mut colors, metric);
+
+    Ok(json!({
+        "colors": colors.iter().map(|c| c.to_rgb_hex_string(true)).collect::<Vec<_>>(),
+        "min_distance": result.min_closest_distance,
+    }))
+}
+
+/// Answer a request for the given URL (path and query string).
+fn handle(url: &str) -> std::result::Result<Value, ApiError> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let query = Query(parse_query(query));
+
+    match path {
+        "/convert" => convert(&query),
+        "/contrast" => contrast(&query),
+        "/distinct" => distinct(&query),
+        _ => Err((404, format!("unknown endpoint '{}'", path))),
+    }
+}
+
+impl GenericCommand for ServeCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, _: &Config) -> Result<()> {
+        let host = matches.value_of("host").expect("required argument");
+        let port = matches.value_of("port").expect("required argument");
+        let port = port
+            .parse::<u16>()
+            .map_err(|_| PastelError::CouldNotParseNumber(port.into()))?;
+
+        let server = tiny_http::Server::http((host, port))
+            .map_err(|err| PastelError::ServerError(err.to_string()))?;
+        writeln!(out.handle, "Listening on http://{}", server.server_addr())?;
+        out.handle.flush()?;
+
+        let content_type =
+            tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                .expect("valid header");
+
+        for request in server.incoming_requests() {
+            let (status, body) = match handle(request.url()) {
+                Ok(body) => (200, body),
+                Err((status, message)) => (status, json!({ "error": message })),
+            };
+
+            let response = tiny_http::Response::from_string(body.to_string())
+                .with_status_code(status)
+                .with_header(content_type.clone());
+
+            // A client that disconnected early should not stop the server
+            request.respond(response).ok();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_parameters_are_decoded() {
+        let query = Query(parse_query("color=%23ff8000&format=hsl,name&text=a+b"));
+        assert_eq!(Some("#ff8000"), query.get("color"));
+        assert_eq!(Some("a b"), query.get("text"));
+    }
+
+    #[test]
+    fn endpoints() {
+        assert_eq!(
+            json!({ "hex": "#ff0000", "name": "red" }),
+            handle("/convert?color=red&format=hex,name").unwrap()
+        );
+        assert_eq!(
+            json!(21.0),
+            handle("/contrast?foreground=white&background=black").unwrap()["wcag2"]
+        );
+
+        let result = handle("/distinct?count=3&fixed=red&iterations=1000").unwrap();
+        assert_eq!(json!("#ff0000"), result["colors"][0]);
+
+        assert_eq!(400, handle("/convert?color=nocolor").unwrap_err().0);
+        assert_eq!(400, handle("/distinct?count=101").unwrap_err().0);
+        assert_eq!(
+            400,
+            handle("/distinct?count=3&iterations=1000001")
+                .unwrap_err()
+                .0
+        );
+        assert_eq!(404, handle("/unknown").unwrap_err().0);
+    }
+}
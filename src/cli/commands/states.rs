@@ -0,0 +1,56 @@
+use crate::commands::prelude::*;
+
+pub struct StatesCommand;
+
+/// Opacities of the state layer for the interaction states, following the Material Design
+/// guidelines.
+const STATE_LAYERS: &[(&str, f64)] = &[
+    ("hover", 0.08),
+    ("focus", 0.10),
+    ("pressed", 0.10),
+    ("dragged", 0.16),
+];
+
+/// Opacity of the (desaturated) content in the disabled state.
+const DISABLED_OPACITY: f64 = 0.38;
+
+/// Place the color on top of the container with the given opacity.
+fn overlay(container: &Color, color: &Color, opacity: f64) -> Color {
+    let color = color.to_rgba_float();
+    container.composite(&Color::from_rgba_float(color.r, color.g, color.b, opacity))
+}
+
+impl GenericCommand for StatesCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut print_spectrum = PrintSpectrum::Yes;
+        let mut color_arg = |name: &str| {
+            ColorArgIterator::from_color_arg(
+                config,
+                matches.value_of(name).expect("required argument"),
+                &mut print_spectrum,
+            )
+        };
+
+        let base = color_arg("base")?;
+        let container = color_arg("on")?;
+
+        let mut states: Vec<(&str, Color)> = STATE_LAYERS
+            .iter()
+            .map(|&(state, opacity)| (state, overlay(&container, &base, opacity)))
+            .collect();
+        states.push((
+            "disabled",
+            overlay(&container, &base.to_gray(), DISABLED_OPACITY),
+        ));
+
+        for (state, color) in states {
+            let name = match matches.value_of("prefix") {
+                Some(prefix) => format!("{}-{}", prefix, state),
+                None => state.into(),
+            };
+            out.show_named_color(config, &name, &color)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::commands::prelude::*;
+
+pub struct StatsCommand;
+
+impl GenericCommand for StatsCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let mut colors: Vec<Color> = vec![];
+        for color in ColorArgIterator::from_matches(config, matches)? {
+            colors.push(color?);
+        }
+
+        if colors.is_empty() {
+            return Err(PastelError::ColorArgRequired);
+        }
+
+        let n = colors.len() as f64;
+        let mean = |f: &dyn Fn(&Color) -> f64| colors.iter().map(f).sum::<f64>() / n;
+
+        writeln!(out.handle, "count: {}", colors.len())?;
+        writeln!(out.handle, "mean lightness: {:.2}", mean(&|c| c.to_lch().l))?;
+        writeln!(out.handle, "mean chroma: {:.2}", mean(&|c| c.to_lch().c))?;
+        writeln!(
+            out.handle,
+            "mean luminance: {:.3}",
+            mean(&|c| c.luminance())
+        )?;
+        writeln!(
+            out.handle,
+            "mean colorfulness: {:.2}",
+            mean(&|c| c.colorfulness())
+        )?;
+        writeln!(
+            out.handle,
+            "colorfulness: {:.2}",
+            pastel::colorfulness(&colors)
+        )?;
+
+        Ok(())
+    }
+}
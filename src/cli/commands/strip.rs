@@ -0,0 +1,23 @@
+use std::io::{self, BufRead};
+
+use crate::commands::prelude::*;
+
+use pastel::ansi::strip_styles;
+
+pub struct StripCommand;
+
+impl GenericCommand for StripCommand {
+    fn run(&self, out: &mut Output, _: &ArgMatches, _: &Config) -> Result<()> {
+        // Process the input line by line (keeping the line endings), such that the output of
+        // long-running commands can be stripped as it comes in
+        let mut stdin = io::stdin().lock();
+        let mut line = String::new();
+        while stdin.read_line(&mut line)? > 0 {
+            write!(out.handle, "{}", strip_styles(&line))?;
+            out.handle.flush()?;
+            line.clear();
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+use crate::commands::prelude::*;
+use crate::system_colors;
+
+pub struct SystemAccentCommand;
+
+impl GenericCommand for SystemAccentCommand {
+    fn run(&self, out: &mut Output, _matches: &ArgMatches, config: &Config) -> Result<()> {
+        let accent = system_colors::accent_color()?.ok_or(PastelError::NoSystemAccent)?;
+        out.show_color(config, &accent)
+    }
+}
@@ -0,0 +1,54 @@
+use std::path::Path;
+
+use crate::commands::prelude::*;
+use crate::system_colors::{self, ThemeSource};
+
+pub struct SystemColorsCommand;
+
+impl GenericCommand for SystemColorsCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let (source, colors) = match matches.value_of("file") {
+            Some(path) => system_colors::read_file(Path::new(path))?,
+            None => {
+                let sources = match matches.value_of("source").expect("required argument") {
+                    "gtk" => vec![ThemeSource::Gtk],
+                    "kde" => vec![ThemeSource::Kde],
+                    _ => system_colors::preferred_sources().to_vec(),
+                };
+
+                let mut found = None;
+                for source in sources {
+                    let colors = system_colors::read_system(source)?;
+                    if !colors.is_empty() {
+                        found = Some((source, colors));
+                        break;
+                    }
+                }
+                found.ok_or(PastelError::NoSystemColors)?
+            }
+        };
+
+        if colors.is_empty() {
+            return Err(PastelError::NoSystemColors);
+        }
+        let colors = system_colors::with_roles(colors, source);
+
+        if let Some(names) = matches.values_of("name") {
+            for name in names {
+                let color = colors
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, c)| c)
+                    .ok_or_else(|| PastelError::UnknownSystemColor(name.into()))?;
+                out.show_color(config, color)?;
+            }
+            return Ok(());
+        }
+
+        for (name, color) in colors {
+            out.show_named_color(config, &name, &color)?;
+        }
+
+        Ok(())
+    }
+}
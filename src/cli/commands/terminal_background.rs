@@ -0,0 +1,11 @@
+use crate::commands::prelude::*;
+use crate::utility;
+
+pub struct TerminalBackgroundCommand;
+
+impl GenericCommand for TerminalBackgroundCommand {
+    fn run(&self, out: &mut Output, _matches: &ArgMatches, config: &Config) -> Result<()> {
+        let background = utility::terminal_background().ok_or(PastelError::NoTerminalBackground)?;
+        out.show_color(config, &background)
+    }
+}
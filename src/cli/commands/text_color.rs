@@ -0,0 +1,82 @@
+use std::io::{self, Write};
+
+use crate::commands::prelude::*;
+
+use pastel::parser::split_color_list;
+use pastel::ContrastAlgorithm;
+
+pub struct TextColorCommand;
+
+impl ColorCommand for TextColorCommand {
+    fn run(
+        &self,
+        out: &mut Output,
+        matches: &ArgMatches,
+        config: &Config,
+        color: &Color,
+    ) -> Result<()> {
+        let algorithm = match matches.value_of("algorithm").expect("required argument") {
+            "wcag2" => ContrastAlgorithm::Wcag2,
+            "apca" => ContrastAlgorithm::Apca,
+            _ => unreachable!("Unknown contrast algorithm"),
+        };
+        let contrast = |text: &Color| algorithm.contrast(text, color);
+
+        let mut print_spectrum = PrintSpectrum::Yes;
+        let mut candidates = vec![];
+        for candidate in matches.values_of("candidates").into_iter().flatten() {
+            for candidate in split_color_list(candidate) {
+                candidates.push(ColorArgIterator::from_color_arg(
+                    config,
+                    candidate,
+                    &mut print_spectrum,
+                )?);
+            }
+        }
+        if candidates.is_empty() {
+            candidates = vec![Color::black(), Color::white()];
+        }
+
+        // With a minimum contrast, the candidates are considered in order of preference: the
+        // first one that is readable enough wins. Otherwise, the most readable one is chosen.
+        let most_readable = candidates
+            .iter()
+            .map(|candidate| (candidate, contrast(candidate)))
+            .fold(
+                None,
+                |best: Option<(&Color, f64)>, (candidate, value)| match best {
+                    Some((_, best_value)) if best_value >= value => best,
+                    _ => Some((candidate, value)),
+                },
+            )
+            .expect("at least one candidate");
+
+        let (text_color, value) = match matches.value_of("min-contrast") {
+            Some(_) => {
+                let min_contrast = number_arg(matches, "min-contrast")?;
+                candidates
+                    .iter()
+                    .map(|candidate| (candidate, contrast(candidate)))
+                    .find(|(_, value)| *value >= min_contrast)
+                    .ok_or(PastelError::InsufficientContrast(
+                        min_contrast,
+                        most_readable.1,
+                    ))?
+            }
+            None => most_readable,
+        };
+
+        out.show_color(config, text_color)?;
+
+        if matches.is_present("verbose") {
+            match algorithm {
+                ContrastAlgorithm::Wcag2 => writeln!(io::stderr(), "contrast ratio: {:.2}", value)?,
+                ContrastAlgorithm::Apca => {
+                    writeln!(io::stderr(), "APCA contrast (Lc): {:.1}", value)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::commands::prelude::*;
+use crate::commands::tokens::{parse_named_lines, read_file_or_stdin};
+use crate::themes::{self, ThemeFormat};
+use crate::tokens;
+
+use pastel::theme::{TerminalTheme, ANSI_NAMES};
+
+pub struct ThemeCommand;
+
+/// Assemble a theme from a named palette, as printed by 'pastel theme generate'.
+fn parse_theme(source: &str) -> Result<TerminalTheme> {
+    let tokens = parse_named_lines(source)?;
+    let color = |name: &str| -> Result<Color> {
+        let token = tokens
+            .iter()
+            .find(|t| t.name == name)
+            .ok_or_else(|| PastelError::ThemeMissingColor(name.into()))?;
+        tokens::resolve(&tokens, &token.value)
+            .map_err(|reason| PastelError::TokenFileError("-".into(), reason))
+    };
+
+    let mut colors = Vec::with_capacity(ANSI_NAMES.len());
+    for name in ANSI_NAMES {
+        colors.push(color(name)?);
+    }
+    let foreground = color("foreground")?;
+
+    Ok(TerminalTheme {
+        background: color("background")?,
+        cursor: color("cursor").unwrap_or_else(|_| foreground.clone()),
+        foreground,
+        colors: colors.try_into().expect("16 ANSI colors"),
+    })
+}
+
+impl GenericCommand for ThemeCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        match matches.subcommand() {
+            Some(("generate", matches)) => {
+                let background = ColorArgIterator::from_color_arg(
+                    config,
+                    matches.value_of("background").expect("required argument"),
+                    &mut PrintSpectrum::Yes,
+                )?;
+                let accent = matches
+                    .value_of("accent")
+                    .map(|accent| {
+                        ColorArgIterator::from_color_arg(config, accent, &mut PrintSpectrum::Yes)
+                    })
+                    .transpose()?;
+
+                let theme = TerminalTheme::generate(&background, accent.as_ref())?;
+
+                for (name, color) in theme.named_colors() {
+                    out.show_named_color(config, name, color)?;
+                }
+            }
+            Some(("export", matches)) => {
+                let source = read_file_or_stdin(matches.value_of("file"))?;
+                let theme = parse_theme(&source)?;
+
+                let format = matches.value_of("format").expect("required argument");
+                let format = ThemeFormat::from_name(format).expect("possible value");
+                let name = matches.value_of("name").expect("default value");
+                write!(out.handle, "{}", themes::export(&theme, format, name))?;
+            }
+            _ => unreachable!("Unknown subcommand"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use std::io::Read;
+
+use crate::commands::prelude::*;
+use crate::tokens::{self, Token, TokenFormat, TokenValue};
+
+use pastel::pipeline::Pipeline;
+
+pub struct TokensCommand;
+
+fn read_file(path: &str) -> Result<(serde_json::Value, Vec<Token>)> {
+    let source = std::fs::read_to_string(path)?;
+    tokens::parse(&source).map_err(|reason| PastelError::TokenFileError(path.into(), reason))
+}
+
+/// Read the given file, or standard input if no path is given.
+pub(super) fn read_file_or_stdin(path: Option<&str>) -> Result<String> {
+    match path {
+        Some(path) => Ok(std::fs::read_to_string(path)?),
+        None => {
+            let mut source = String::new();
+            std::io::stdin().read_to_string(&mut source)?;
+            Ok(source)
+        }
+    }
+}
+
+/// Parse named palette lines of the form 'name: color' or 'name: {alias}'. Lines with just a
+/// color are named 'color-1', 'color-2', etc.
+pub(super) fn parse_named_lines(source: &str) -> Result<Vec<Token>> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            let invalid = || PastelError::TokenLineError(line.into());
+            let (name, value) = match line.split_once(':') {
+                Some((name, value)) => (name.trim().to_string(), value),
+                None => (format!("color-{}", i + 1), line),
+            };
+            Ok(Token {
+                name,
+                value: TokenValue::parse(value).ok_or_else(invalid)?,
+            })
+        })
+        .collect()
+}
+
+impl GenericCommand for TokensCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        match matches.subcommand() {
+            Some(("import", matches)) => {
+                let path = matches.value_of("file").expect("required argument");
+                let (_, tokens) = read_file(path)?;
+
+                for token in &tokens {
+                    let color = tokens::resolve(&tokens, &token.value)
+                        .map_err(|reason| PastelError::TokenFileError(path.into(), reason))?;
+                    let value = match &token.value {
+                        TokenValue::Alias(alias) if matches.is_present("aliases") => {
+                            format!("{{{}}}", alias)
+                        }
+                        _ => color.to_rgb_hex_string(true),
+                    };
+
+                    out.show_named_value(config, &token.name, &color, &value)?;
+                }
+            }
+            Some(("export", matches)) => {
+                let source = read_file_or_stdin(matches.value_of("file"))?;
+                let tokens = parse_named_lines(&source)?;
+
+                let format = matches.value_of("format").expect("required argument");
+                let document = match TokenFormat::from_name(format) {
+                    Some(format) => tokens::to_document(&tokens, format),
+                    // Sketch palettes do not support aliases
+                    None => tokens
+                        .iter()
+                        .map(|t| Ok((t.name.clone(), tokens::resolve(&tokens, &t.value)?)))
+                        .collect::<std::result::Result<Vec<_>, String>>()
+                        .map(|colors| tokens::to_sketch_palette(&colors)),
+                }
+                .map_err(|reason| PastelError::TokenFileError("-".into(), reason))?;
+                writeln!(out.handle, "{:#}", document)?;
+            }
+            Some(("transform", matches)) => {
+                let spec = matches.value_of("pipeline").expect("required argument");
+                let pipeline: Pipeline = spec.parse().map_err(|reason: pastel::Error| {
+                    PastelError::PipelineError(spec.into(), reason.to_string())
+                })?;
+
+                let path = matches.value_of("file").expect("required argument");
+                let (mut document, _) = read_file(path)?;
+                tokens::map_colors(&mut document, &|color| pipeline.apply(color))
+                    .map_err(|reason| PastelError::TokenFileError(path.into(), reason))?;
+                writeln!(out.handle, "{:#}", document)?;
+            }
+            _ => unreachable!("Unknown subcommand"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use crate::commands::prelude::*;
+
+pub struct TonalPaletteCommand;
+
+impl GenericCommand for TonalPaletteCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        let base = ColorArgIterator::from_color_arg(
+            config,
+            matches.value_of("color").expect("required argument"),
+            &mut PrintSpectrum::Yes,
+        )?;
+
+        let palette = base.tonal_palette();
+        let name = matches.value_of("name");
+        let hex = |color: &Color| color.to_rgb_hex_string(true);
+
+        match matches.value_of("format") {
+            Some("css-vars") => {
+                let name = name.unwrap_or("primary");
+                writeln!(out.handle, ":root {{")?;
+                for (step, color) in &palette {
+                    writeln!(out.handle, "  --{}-{}: {};", name, step, hex(color))?;
+                }
+                writeln!(out.handle, "}}")?;
+            }
+            Some("scss") => {
+                let name = name.unwrap_or("primary");
+                for (step, color) in &palette {
+                    writeln!(out.handle, "${}-{}: {};", name, step, hex(color))?;
+                }
+            }
+            Some("tailwind") => {
+                let name = name.unwrap_or("primary");
+                writeln!(out.handle, "'{}': {{", name)?;
+                for (step, color) in &palette {
+                    writeln!(out.handle, "  {}: '{}',", step, hex(color))?;
+                }
+                writeln!(out.handle, "}}")?;
+            }
+            _ => {
+                for (step, color) in &palette {
+                    let label = match name {
+                        Some(name) => format!("{}-{}", name, step),
+                        None => step.to_string(),
+                    };
+                    out.show_named_value(config, &label, color, &hex(color))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
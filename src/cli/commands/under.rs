@@ -0,0 +1,57 @@
+use crate::commands::prelude::*;
+use crate::hdcanvas::Canvas;
+
+use pastel::illuminant::Illuminant;
+
+pub struct UnderCommand;
+
+impl ColorCommand for UnderCommand {
+    fn run(
+        &self,
+        out: &mut Output,
+        matches: &ArgMatches,
+        config: &Config,
+        color: &Color,
+    ) -> Result<()> {
+        let illuminants: Vec<(&str, Illuminant)> = matches
+            .values_of("illuminant")
+            .expect("required argument")
+            .map(|name| {
+                (
+                    name,
+                    Illuminant::from_name(name).expect("Unknown illuminant"),
+                )
+            })
+            .collect();
+
+        if !config.interactive_mode {
+            for (_, illuminant) in illuminants {
+                out.show_color(config, &color.under_illuminant(illuminant))?;
+            }
+            return Ok(());
+        }
+
+        let swatch_width = 10;
+        let swatch_height = 4;
+
+        let mut canvas = Canvas::new(
+            swatch_height + 4,
+            config.padding + (illuminants.len() + 1) * (swatch_width + config.padding),
+            config.brush,
+        );
+
+        let swatches = std::iter::once(("daylight", color.clone())).chain(
+            illuminants
+                .iter()
+                .map(|(name, illuminant)| (*name, color.under_illuminant(*illuminant))),
+        );
+
+        for (i, (name, swatch)) in swatches.enumerate() {
+            let col = config.padding + i * (swatch_width + config.padding);
+            canvas.draw_rect(0, col, swatch_height, swatch_width, &swatch);
+            canvas.draw_text(swatch_height + 2, col, name);
+        }
+
+        canvas.print(out.handle)
+    }
+}
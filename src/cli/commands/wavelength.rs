@@ -0,0 +1,23 @@
+use crate::commands::prelude::*;
+
+use pastel::Color;
+
+pub struct WavelengthCommand;
+
+impl GenericCommand for WavelengthCommand {
+    fn run(&self, out: &mut Output, matches: &ArgMatches, config: &Config) -> Result<()> {
+        for value in matches.values_of("wavelength").expect("required argument") {
+            let number = value.trim().trim_end_matches("nm");
+            let nm = number
+                .parse::<f64>()
+                .map_err(|_| PastelError::CouldNotParseNumber(value.into()))?;
+            if !(380.0..=780.0).contains(&nm) {
+                return Err(PastelError::InvalidWavelength(value.into()));
+            }
+
+            out.show_color(config, &Color::from_wavelength(nm))?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use pastel::ansi::Brush;
 
 #[derive(Debug, Clone)]
@@ -8,4 +10,53 @@ pub struct Config<'p> {
     pub colorpicker: Option<&'p str>,
     pub interactive_mode: bool,
     pub brush: Brush,
+    /// URL template for hyperlinks on hex codes (see `utility::color_url`), if enabled
+    pub hyperlink_template: Option<String>,
+    /// Write colors as packed binary pixel data instead of text (see `--output`)
+    pub raw_output: Option<RawFormat>,
+}
+
+/// Layout of packed binary pixel data (see `--input-format` and `--output`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RawFormat {
+    Rgb8,
+    Rgba8,
+}
+
+impl RawFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "raw-rgb8" => Some(RawFormat::Rgb8),
+            "raw-rgba8" => Some(RawFormat::Rgba8),
+            _ => None,
+        }
+    }
+
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            RawFormat::Rgb8 => 3,
+            RawFormat::Rgba8 => 4,
+        }
+    }
+}
+
+/// The directory for user data like saved gradients and palettes: `$PASTEL_CONFIG_DIR`, or
+/// `pastel` within the platform's configuration directory.
+pub fn config_dir() -> Option<PathBuf> {
+    let env = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty());
+
+    env("PASTEL_CONFIG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| env("XDG_CONFIG_HOME").map(|d| PathBuf::from(d).join("pastel")))
+        .or_else(|| env("APPDATA").map(|d| PathBuf::from(d).join("pastel")))
+        .or_else(|| env("HOME").map(|d| PathBuf::from(d).join(".config").join("pastel")))
+}
+
+/// Whether the given name of a saved palette or gradient is valid. The name is used as a file
+/// name in the configuration directory, so it may only contain letters, digits, '-' and '_'.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
 }
@@ -1,5 +1,3 @@
-use crate::ansi;
-
 #[derive(Debug)]
 pub enum PastelError {
     UnknownColorMode(String),
@@ -8,13 +6,90 @@ pub enum PastelError {
     CouldNotReadFromStdin,
     ColorArgRequired,
     CouldNotParseNumber(String),
+    NonFiniteNumber(String),
     StdoutClosed,
+    /// The command has printed its result, but should exit with the given (non-zero) code
+    ExitCode(i32),
     GradientNumberMustBeLargerThanOne,
     GradientColorCountMustBeLargerThanOne,
     DistinctColorCountMustBeLargerThanOne,
     DistinctColorFixedColorsCannotBeMoreThanCount,
+    ClusterCountMustBeLargerThanZero,
+    ClusterCountCannotBeMoreThanColors,
+    HueBandWidthOutOfRange,
+    NoiseStepMustBePositive,
+    ImageColorCountMustBeLargerThanZero,
+    ImageError(String, String),
+    InvalidImagePosition(String),
+    ImagePositionOutOfBounds(String, u32, u32),
+    DiffColorCountMustBeLargerThanZero,
+    DiffColorLightnessOutOfRange,
+    CurveSpecError(String, String),
+    LutParseError(String, String),
+    LutSizeOutOfRange(usize),
+    PipelineError(String, String),
+    UnknownFilter(String),
+    UnknownFormatType(String),
+    InvalidTerminalPalette(String, pastel::Error),
+    PaletteFileEmpty(String),
+    ColorizeRuleError(usize, String),
+    StyleParseError(String, String),
+    LibraryError(pastel::Error),
+    IncompleteRawPixel,
+    SampleWeightCountMismatch(usize, usize),
+    InvalidSampleWeights,
+    UnknownGradient(String),
+    InvalidGradientName(String),
+    GradientFileError(String, String),
+    TokenFileError(String, String),
+    TokenLineError(String),
+    ThemeMissingColor(String),
+    UnknownTerminalColor(String),
+    NoConfigDirectory,
+    UnknownPalette(String),
+    InvalidPaletteName(String),
+    InvalidGradientRange(String),
+    InvalidEasing(String),
+    InvalidGradientStop(String),
+    InvalidMeshSize(String),
+    InvalidBitDepth(String),
+    OpacityOutOfRange,
+    InvalidElevationLevels(String),
+    InvalidDuration(String),
+    InvalidTemperature(String),
+    InvalidWavelength(String),
+    NoSystemColors,
+    NoSystemAccent,
+    NoTerminalBackground,
+    NoInteractiveTerminal,
+    EditCancelled,
+    PickCancelled,
+    UnknownSystemColor(String),
+    TemplateError(String, String),
+    InvalidColorMapping(String),
+    NothingToReplace,
+    InsufficientContrast(f64, f64),
+    ContrastTargetOutOfRange(f64, f64),
+    SeverityOutOfRange,
+    NegativeStrength,
+    OkLchLightnessOutOfRange,
+    NegativeChroma,
+    TranslucentColor(String),
+    DistinctStateError(String, String),
+    DistinctResumeWithFixedColors,
+    DistinctInvalidRegion(String),
+    DistinctContrastUnreachable(f64),
+    DistinctMetricNotApplicable(String),
     ColorPickerExecutionError(String),
+    ExporterError(String, String),
     NoColorPickerFound,
+    InvalidAverageSize(String),
+    AveragingUnsupported,
+    #[cfg(all(unix, feature = "native-picker"))]
+    NativeColorPickerError(String),
+    #[cfg(feature = "serve")]
+    ServerError(String),
+    FileReadError(String, std::io::Error),
     IoError(std::io::Error),
 }
 
@@ -35,7 +110,11 @@ impl PastelError {
             PastelError::CouldNotParseNumber(number) => {
                 format!("Could not parse number '{}'", number)
             }
+            PastelError::NonFiniteNumber(number) => {
+                format!("Expected a finite number, got '{}'", number)
+            }
             PastelError::StdoutClosed => "Output pipe has been closed".into(),
+            PastelError::ExitCode(code) => format!("Exited with code {}", code),
             PastelError::GradientNumberMustBeLargerThanOne => {
                 "The specified color count must be larger than one".into()
             }
@@ -48,12 +127,273 @@ impl PastelError {
             PastelError::DistinctColorFixedColorsCannotBeMoreThanCount => {
                 "The number of fixed colors must be smaller than the total number of colors".into()
             }
+            PastelError::ClusterCountMustBeLargerThanZero => {
+                "The number of clusters must be larger than zero".into()
+            }
+            PastelError::ClusterCountCannotBeMoreThanColors => {
+                "The number of clusters must not be larger than the number of colors".into()
+            }
+            PastelError::ImageColorCountMustBeLargerThanZero => {
+                "The number of colors must be larger than zero".into()
+            }
+            PastelError::ImageError(path, reason) => {
+                format!("Could not read image '{}': {}", path, reason)
+            }
+            PastelError::InvalidImagePosition(position) => format!(
+                "Invalid pixel position '{}'. Expected the x and y coordinates, e.g. '120,300'.",
+                position
+            ),
+            PastelError::ImagePositionOutOfBounds(position, width, height) => format!(
+                "The pixel position {} is outside of the image ({}x{} pixels)",
+                position, width, height
+            ),
+            PastelError::HueBandWidthOutOfRange => {
+                "The width of the hue bands must be between 0 and 360 degrees".into()
+            }
+            PastelError::NoiseStepMustBePositive => {
+                "The step along the noise path must be larger than zero".into()
+            }
+            PastelError::DiffColorCountMustBeLargerThanZero => {
+                "The number of intensity levels must be larger than zero".into()
+            }
+            PastelError::DiffColorLightnessOutOfRange => {
+                "The lightness must be between 0 and 100".into()
+            }
+            PastelError::CurveSpecError(spec, reason) => {
+                format!("Invalid curve specification '{}': {}", spec, reason)
+            }
+            PastelError::LutParseError(path, reason) => {
+                format!("Could not parse lookup table '{}': {}", path, reason)
+            }
+            PastelError::LutSizeOutOfRange(max) => {
+                format!("The size of the lookup table must be between 2 and {}", max)
+            }
+            PastelError::PipelineError(spec, reason) => {
+                format!("Invalid transformation pipeline '{}': {}", spec, reason)
+            }
+            PastelError::UnknownFilter(name) => format!(
+                "Unknown filter '{}'. Call 'pastel filter --help' to see the list of built-in \
+                 filters and how to define your own.",
+                name
+            ),
+            PastelError::UnknownFormatType(name) => format!(
+                "Unknown format type '{}'. Call 'pastel format --help' to see the list of \
+                 supported format types.",
+                name
+            ),
+            PastelError::InvalidTerminalPalette(palette, err) => format!(
+                "Could not parse PASTEL_TERMINAL_PALETTE value '{}' ({}). Expected a list of 16 \
+                 colors, separated by colons.",
+                palette, err
+            ),
+            PastelError::PaletteFileEmpty(path) => {
+                format!("The palette file '{}' does not contain any colors", path)
+            }
+            PastelError::ColorizeRuleError(line_number, reason) => {
+                format!("Invalid rule in line {}: {}", line_number, reason)
+            }
+            PastelError::StyleParseError(spec, reason) => {
+                format!("Could not parse style '{}': {}", spec, reason)
+            }
+            PastelError::LibraryError(err) => err.to_string(),
+            PastelError::IncompleteRawPixel => {
+                "The binary input ends with an incomplete pixel".into()
+            }
+            PastelError::SampleWeightCountMismatch(weights, colors) => format!(
+                "The number of weights ({}) does not match the number of colors ({})",
+                weights, colors
+            ),
+            PastelError::InvalidSampleWeights => {
+                "The weights must not be negative and at least one of them must be positive".into()
+            }
+            PastelError::UnknownGradient(name) => format!(
+                "Unknown gradient '{}'. Call 'pastel gradient --list' to see all available \
+                 gradients.",
+                name
+            ),
+            PastelError::InvalidGradientName(name) => format!(
+                "Invalid gradient name '{}'. Names may only contain letters, digits, '-' and '_'.",
+                name
+            ),
+            PastelError::GradientFileError(path, reason) => {
+                format!("Could not parse gradient file '{}': {}", path, reason)
+            }
+            PastelError::TokenFileError(path, reason) => {
+                format!("Could not process design tokens '{}': {}", path, reason)
+            }
+            PastelError::TokenLineError(line) => format!(
+                "Could not parse token '{}'. Expected a line of the form 'name: color' or \
+                 'name: {{alias}}'.",
+                line
+            ),
+            PastelError::ThemeMissingColor(name) => format!(
+                "The theme does not define the color '{}'. Expected a named palette as printed \
+                 by 'pastel theme generate'.",
+                name
+            ),
+            PastelError::UnknownTerminalColor(name) => format!(
+                "Unknown terminal color '{}'. Expected 'background', 'foreground', 'cursor', an \
+                 ANSI color name like 'red' or 'bright-red', or 'color0' to 'color255'.",
+                name
+            ),
+            PastelError::NoConfigDirectory => {
+                "Could not determine the configuration directory. Set PASTEL_CONFIG_DIR to \
+                 choose one."
+                    .into()
+            }
+            PastelError::UnknownPalette(name) => format!(
+                "Unknown palette '{}'. Call 'pastel palette list' to see all saved palettes.",
+                name
+            ),
+            PastelError::InvalidPaletteName(name) => format!(
+                "Invalid palette name '{}'. Names may only contain letters, digits, '-' and '_'.",
+                name
+            ),
+            PastelError::InvalidEasing(name) => format!(
+                "Unknown easing function '{}'. Expected one of: linear, ease-in, ease-out, \
+                 ease-in-out.",
+                name
+            ),
+            PastelError::InvalidGradientStop(stop) => format!(
+                "Invalid gradient stop '{}'. Expected a color, optionally followed by '@' and a \
+                 position between 0.0 and 1.0 (e.g. 'red@0.3'). The positions must be increasing.",
+                stop
+            ),
+            PastelError::InvalidGradientRange(range) => format!(
+                "Invalid gradient range '{}'. Expected two positions between 0.0 and 1.0, \
+                 e.g. '0.2..0.8'.",
+                range
+            ),
+            PastelError::InvalidMeshSize(size) => format!(
+                "Invalid grid size '{}'. Expected the number of columns and rows (each between \
+                 2 and 1024), e.g. '16x9'.",
+                size
+            ),
+            PastelError::InvalidBitDepth(bits) => format!(
+                "Invalid number of bits '{}'. Expected a number between 1 and 8.",
+                bits
+            ),
+            PastelError::OpacityOutOfRange => "The opacity must be between 0.0 and 1.0".into(),
+            PastelError::SeverityOutOfRange => "The severity must be between 0.0 and 1.0".into(),
+            PastelError::NegativeStrength => "The strength must not be negative".into(),
+            PastelError::OkLchLightnessOutOfRange => {
+                "The OkLCh lightness must be between 0.0 and 1.0".into()
+            }
+            PastelError::NegativeChroma => "The chroma must not be negative".into(),
+            PastelError::TranslucentColor(color) => format!(
+                "The color '{}' is not opaque. Use 'pastel alpha-report' to check the contrast \
+                 of translucent colors.",
+                color
+            ),
+            PastelError::InvalidElevationLevels(levels) => format!(
+                "Invalid elevation levels '{}'. Expected a range like '0..24dp' or a list like \
+                 '1,4,8dp'.",
+                levels
+            ),
+            PastelError::InvalidWavelength(nm) => format!(
+                "Invalid wavelength '{}'. Expected a number between 380 and 780 (in nanometers).",
+                nm
+            ),
+            PastelError::NoSystemColors => {
+                "Could not find any colors in the GTK or KDE theme configuration.".into()
+            }
+            PastelError::NoSystemAccent => {
+                "Could not determine the accent color of the operating system.".into()
+            }
+            PastelError::NoTerminalBackground => {
+                "Could not determine the background color of the terminal. The terminal does \
+                 not answer OSC 11 queries, or there is no terminal. Set \
+                 PASTEL_TERMINAL_BACKGROUND to specify it."
+                    .into()
+            }
+            PastelError::NoInteractiveTerminal => {
+                "The interactive mode needs a terminal: standard input and standard error \
+                 must not be redirected."
+                    .into()
+            }
+            PastelError::EditCancelled => "Editing was cancelled".into(),
+            PastelError::PickCancelled => "Picking was cancelled".into(),
+            PastelError::UnknownSystemColor(name) => format!(
+                "Unknown system color '{}'. Use 'pastel system-colors' to list all colors.",
+                name
+            ),
+            PastelError::InvalidTemperature(kelvin) => format!(
+                "Invalid color temperature '{}'. Expected a number between 1000 and 40000 \
+                 (in Kelvin).",
+                kelvin
+            ),
+            PastelError::InvalidDuration(duration) => format!(
+                "Invalid duration '{}'. Expected a number with an optional unit \
+                 ('ms', 's' or 'm'), e.g. '2s'.",
+                duration
+            ),
+            PastelError::InvalidColorMapping(mapping) => format!(
+                "Invalid replacement '{}'. Expected a pair of colors like 'red=#0080ff'.",
+                mapping
+            ),
+            PastelError::InsufficientContrast(min_contrast, best) => format!(
+                "None of the text colors reaches the minimum contrast of {} (the most readable \
+                 one has a contrast of {:.2})",
+                min_contrast, best
+            ),
+            PastelError::ContrastTargetOutOfRange(min, max) => {
+                format!("The contrast target must be between {} and {}", min, max)
+            }
+            PastelError::NothingToReplace => {
+                "Nothing to replace: specify a target format ('--to') and/or replacements \
+                 ('--map old=new')"
+                    .into()
+            }
+            PastelError::TemplateError(template, reason) => {
+                format!("Invalid template '{}': {}", template, reason)
+            }
+            PastelError::DistinctStateError(path, reason) => {
+                format!("Could not load optimizer state '{}': {}", path, reason)
+            }
+            PastelError::DistinctResumeWithFixedColors => {
+                "Fixed colors cannot be given when resuming from a saved state".into()
+            }
+            PastelError::DistinctInvalidRegion(name) => {
+                format!("The minimum {} must not be larger than the maximum", name)
+            }
+            PastelError::DistinctContrastUnreachable(ratio) => format!(
+                "No color (within the given lightness range) reaches a contrast ratio of {} \
+                 against the background",
+                ratio
+            ),
+            PastelError::DistinctMetricNotApplicable(space) => format!(
+                "The '--metric' option can not be used with '--space {}' (distances are always \
+                 Euclidean in that color space)",
+                space
+            ),
             PastelError::ColorPickerExecutionError(name) => {
                 format!("Error while running color picker '{}'", name)
             }
+            PastelError::ExporterError(exporter, reason) => {
+                format!("External exporter '{}' failed: {}", exporter, reason)
+            }
             PastelError::NoColorPickerFound => {
                 "Could not find any external color picker tool. See 'pastel pick --help' for more information.".into()
             }
+            PastelError::InvalidAverageSize(size) => format!(
+                "Invalid sample size '{}'. Expected the width and height of the area around the \
+                 picked pixel (between 1 and 100), e.g. '5x5'.",
+                size
+            ),
+            PastelError::AveragingUnsupported => {
+                "Averaging ('--average') needs to read the screen directly, which is only \
+                 possible with the built-in X11 color picker ('native-picker' feature)."
+                    .into()
+            }
+            #[cfg(all(unix, feature = "native-picker"))]
+            PastelError::NativeColorPickerError(reason) => {
+                format!("Could not pick a color from the screen: {}", reason)
+            }
+            #[cfg(feature = "serve")]
+            PastelError::ServerError(reason) => format!("Could not start server: {}", reason),
+            PastelError::FileReadError(path, err) => {
+                format!("Could not read file '{}': {}", path, err)
+            }
             PastelError::IoError(err) => format!("I/O error: {}", err),
         }
     }
@@ -68,9 +408,13 @@ impl From<std::io::Error> for PastelError {
     }
 }
 
-impl From<ansi::UnknownColorModeError> for PastelError {
-    fn from(err: ansi::UnknownColorModeError) -> PastelError {
-        PastelError::UnknownColorMode(err.0)
+impl From<pastel::Error> for PastelError {
+    fn from(err: pastel::Error) -> PastelError {
+        match err {
+            pastel::Error::UnknownColorMode(mode) => PastelError::UnknownColorMode(mode),
+            pastel::Error::InvalidColor(color) => PastelError::ColorParseError(color),
+            err => PastelError::LibraryError(err),
+        }
     }
 }
 
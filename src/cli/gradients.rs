@@ -0,0 +1,163 @@
+use std::path::PathBuf;
+
+use crate::config;
+use crate::{PastelError, Result};
+
+use pastel::parser::parse_color;
+use pastel::Color;
+
+const COLORSPACES: &[&str] = &["Lab", "LCh", "RGB", "HSL", "OkLab"];
+
+/// Built-in gradient presets (in the same format as user-defined gradient files).
+const GRADIENT_PRESETS: &[(&str, &str)] = &[
+    (
+        "sunset",
+        "colorspace: OkLab\n0.0 #355c7d\n0.5 #c06c84\n0.75 #f67280\n1.0 #f8b195\n",
+    ),
+    (
+        "ocean",
+        "colorspace: OkLab\n0.0 #0b1d51\n0.5 #1f6f8b\n1.0 #99e1d9\n",
+    ),
+    (
+        "fire",
+        "colorspace: Lab\n0.0 #000000\n0.4 #b30000\n0.75 #ff9900\n1.0 #ffffcc\n",
+    ),
+    (
+        "rainbow",
+        "colorspace: HSL\n0.0 #ff0000\n0.1667 #ffff00\n0.3333 #00ff00\n0.5 #00ffff\n\
+         0.6667 #0000ff\n0.8333 #ff00ff\n1.0 #ff0000\n",
+    ),
+    (
+        "forest",
+        "colorspace: OkLab\n0.0 #0b2e13\n0.5 #2d6a4f\n1.0 #d8f3dc\n",
+    ),
+];
+
+/// A gradient with named color stops, as stored in the gradient library.
+#[derive(Debug, Clone)]
+pub struct NamedGradient {
+    /// Color space for the interpolation (as in `--colorspace`)
+    pub colorspace: String,
+    /// Color stops with positions between 0.0 and 1.0
+    pub stops: Vec<(Color, f64)>,
+}
+
+impl NamedGradient {
+    /// Parse a gradient file with an optional `colorspace: <name>` line and one
+    /// `<position> <color>` stop per line. Empty lines and lines starting with '#' are ignored.
+    pub fn parse(source: &str) -> std::result::Result<Self, String> {
+        let mut colorspace = "Lab".to_string();
+        let mut stops = vec![];
+
+        for (i, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix("colorspace:") {
+                let name = name.trim();
+                colorspace = COLORSPACES
+                    .iter()
+                    .find(|c| c.eq_ignore_ascii_case(name))
+                    .ok_or_else(|| format!("line {}: unknown colorspace '{}'", i + 1, name))?
+                    .to_string();
+                continue;
+            }
+
+            let (position, color) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| format!("line {}: expected '<position> <color>'", i + 1))?;
+            let position = position
+                .parse::<f64>()
+                .ok()
+                .filter(|p| (0.0..=1.0).contains(p))
+                .ok_or_else(|| format!("line {}: invalid position '{}'", i + 1, position))?;
+            let color = parse_color(color.trim())
+                .ok_or_else(|| format!("line {}: could not parse color '{}'", i + 1, color))?;
+
+            stops.push((color, position));
+        }
+
+        if stops.len() < 2 {
+            return Err("a gradient needs at least two color stops".into());
+        }
+
+        Ok(NamedGradient { colorspace, stops })
+    }
+
+    pub fn to_file_string(&self) -> String {
+        let mut result = format!("colorspace: {}\n", self.colorspace);
+        for (color, position) in &self.stops {
+            result.push_str(&format!("{} {}\n", position, color.to_rgb_hex_string(true)));
+        }
+        result
+    }
+}
+
+/// The directory for user-defined gradients: `gradients` within the configuration directory.
+fn gradient_dir() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("gradients"))
+}
+
+fn check_name(name: &str) -> Result<()> {
+    if config::is_valid_name(name) {
+        Ok(())
+    } else {
+        Err(PastelError::InvalidGradientName(name.into()))
+    }
+}
+
+/// Load a gradient by name. User-defined gradients take precedence over the built-in presets.
+pub fn load(name: &str) -> Result<NamedGradient> {
+    check_name(name)?;
+
+    if let Some(path) = gradient_dir().map(|dir| dir.join(format!("{}.txt", name))) {
+        if path.exists() {
+            let source = std::fs::read_to_string(&path)?;
+            return NamedGradient::parse(&source).map_err(|reason| {
+                PastelError::GradientFileError(path.to_string_lossy().into(), reason)
+            });
+        }
+    }
+
+    let (_, source) = GRADIENT_PRESETS
+        .iter()
+        .find(|(preset, _)| *preset == name)
+        .ok_or_else(|| PastelError::UnknownGradient(name.into()))?;
+    Ok(NamedGradient::parse(source).expect("valid gradient preset"))
+}
+
+/// Save a gradient to the user's gradient library.
+pub fn save(name: &str, gradient: &NamedGradient) -> Result<()> {
+    check_name(name)?;
+
+    let dir = gradient_dir().ok_or(PastelError::NoConfigDirectory)?;
+    std::fs::create_dir_all(&dir)?;
+
+    std::fs::write(dir.join(format!("{}.txt", name)), gradient.to_file_string())?;
+    Ok(())
+}
+
+/// Names of all available gradients (built-in presets and user-defined gradients).
+pub fn names() -> Vec<String> {
+    let mut names: Vec<String> = GRADIENT_PRESETS
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    if let Some(entries) = gradient_dir().and_then(|dir| std::fs::read_dir(dir).ok()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "txt") {
+                if let Some(stem) = path.file_stem() {
+                    names.push(stem.to_string_lossy().into());
+                }
+            }
+        }
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
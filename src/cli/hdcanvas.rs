@@ -10,12 +10,14 @@ pub struct Canvas {
     width: usize,
     pixels: Vec<Option<Color>>,
     chars: Vec<Option<char>>,
+    links: Vec<(usize, usize, usize, String)>,
     brush: Brush,
+    text_color: Option<Color>,
 }
 
 impl Canvas {
     pub fn new(height: usize, width: usize, brush: Brush) -> Self {
-        assert!(height % 2 == 0);
+        assert!(height.is_multiple_of(2));
 
         let mut pixels = vec![];
         pixels.resize(height * width, None);
@@ -27,10 +29,17 @@ impl Canvas {
             width,
             pixels,
             chars,
+            links: vec![],
             brush,
+            text_color: None,
         }
     }
 
+    /// Paint the text in the given color instead of the default foreground color.
+    pub fn set_text_color(&mut self, color: Option<Color>) {
+        self.text_color = color;
+    }
+
     pub fn draw_rect(
         &mut self,
         row: usize,
@@ -68,13 +77,20 @@ impl Canvas {
     }
 
     pub fn draw_text(&mut self, row: usize, col: usize, text: &str) {
-        assert!(row % 2 == 0);
+        assert!(row.is_multiple_of(2));
 
         for (j, c) in text.chars().enumerate() {
             *self.char_mut(row / 2, col + j) = Some(c);
         }
     }
 
+    /// Draw text that is turned into a hyperlink to the given URL.
+    pub fn draw_link(&mut self, row: usize, col: usize, text: &str, url: &str) {
+        self.draw_text(row, col, text);
+        self.links
+            .push((row / 2, col, text.chars().count(), url.into()));
+    }
+
     // The kitty terminal has a feature text_fg_override_threshold that
     // checks the difference in luminosity between text and background and
     // changes the text to black or white to make it readable if the
@@ -84,9 +100,30 @@ impl Canvas {
     // The checkerboard should be ok unless the threshold is set fairly high.
     pub fn print(&self, out: &mut dyn Write) -> Result<()> {
         for i_div_2 in 0..self.height / 2 {
-            for j in 0..self.width {
+            let mut j = 0;
+            while j < self.width {
+                if let Some((_, _, len, url)) = self
+                    .links
+                    .iter()
+                    .find(|(row, col, _, _)| *row == i_div_2 && *col == j)
+                {
+                    let text: String = (j..j + len)
+                        .filter_map(|k| *self.char(i_div_2, k))
+                        .collect();
+                    let text = match &self.text_color {
+                        Some(color) => self.brush.paint(text, color),
+                        None => text,
+                    };
+                    write!(out, "{}", self.brush.hyperlink(text, url))?;
+                    j += len;
+                    continue;
+                }
+
                 if let Some(c) = self.char(i_div_2, j) {
-                    write!(out, "{}", c)?;
+                    match &self.text_color {
+                        Some(color) => write!(out, "{}", self.brush.paint(c.to_string(), color))?,
+                        None => write!(out, "{}", c)?,
+                    }
                 } else {
                     let p_top = self.pixel(2 * i_div_2, j);
                     let p_bottom = self.pixel(2 * i_div_2 + 1, j);
@@ -112,6 +149,7 @@ impl Canvas {
                         (None, None) => write!(out, " ")?,
                     };
                 }
+                j += 1;
             }
             writeln!(out)?;
         }
@@ -9,24 +9,34 @@ mod colorspace;
 mod commands;
 mod config;
 mod error;
+mod gradients;
 mod hdcanvas;
+#[cfg(all(unix, feature = "native-picker"))]
+mod native_picker;
 mod output;
+mod palettes;
+mod system_colors;
+mod terminal;
+mod themes;
+mod tokens;
 mod utility;
 
 use commands::Command;
-use config::Config;
+use config::{Config, RawFormat};
 use error::{PastelError, Result};
 
-use pastel::ansi::{self, Brush, Mode};
+use pastel::ansi::{self, Ansi8BitOptions, Brush, Mode};
 use pastel::Color;
 
 type ExitCode = i32;
 
+const DEFAULT_HYPERLINK_TEMPLATE: &str = "https://www.colorhexa.com/{hex}";
+
 fn write_stderr(c: Color, title: &str, message: &str) {
     writeln!(
         io::stderr(),
         "{}: {}",
-        Brush::from_environment(Stream::Stdout)
+        Brush::from_environment(Stream::Stderr)
             .unwrap_or_default()
             .paint(format!("[{}]", title), c),
         message
@@ -34,10 +44,13 @@ fn write_stderr(c: Color, title: &str, message: &str) {
     .ok();
 }
 
+/// Print a warning message to STDERR.
+pub fn print_warning(message: &str) {
+    write_stderr(Color::yellow(), "pastel warning", message);
+}
+
 fn print_pastel_warning() {
-    write_stderr(
-        Color::yellow(),
-        "pastel warning",
+    print_warning(
         "Your terminal emulator does not appear to support 24-bit colors \
         (this means that the COLORTERM environment variable is not set to \
         'truecolor' or '24bit'). \
@@ -98,23 +111,51 @@ fn run() -> Result<ExitCode> {
         }
     };
 
+    // The actual colors of the 16 base colors of the terminal, used to improve the 8-bit
+    // approximations of colors
+    let base_palette = match std::env::var("PASTEL_TERMINAL_PALETTE") {
+        Ok(palette) => Some(
+            ansi::parse_base_palette(&palette)
+                .map_err(|err| PastelError::InvalidTerminalPalette(palette.clone(), err))?,
+        ),
+        Err(_) => None,
+    };
+
     let config = Config {
         padding: 2,
         colorpicker_width: 48,
         colorcheck_width: 8,
         interactive_mode,
-        brush: Brush::from_mode(color_mode),
+        brush: Brush::from_mode(color_mode).with_ansi_8bit_options(Ansi8BitOptions {
+            base_palette,
+            ..Ansi8BitOptions::default()
+        }),
         colorpicker: global_matches.value_of("color-picker"),
+        hyperlink_template: if global_matches.is_present("hyperlinks") {
+            Some(
+                global_matches
+                    .value_of("hyperlinks")
+                    .map(|t| t.to_string())
+                    .or_else(|| std::env::var("PASTEL_HYPERLINK_TEMPLATE").ok())
+                    .unwrap_or_else(|| DEFAULT_HYPERLINK_TEMPLATE.into()),
+            )
+        } else {
+            None
+        },
+        raw_output: global_matches
+            .value_of("output")
+            .and_then(RawFormat::from_name),
     };
 
     if let Some((subcommand, matches)) = global_matches.subcommand() {
         let command = Command::from_string(subcommand);
-        command.execute(matches, &config)?;
+        match command.execute(matches, &config) {
+            Err(PastelError::ExitCode(code)) => Ok(code),
+            result => result.map(|_| 0),
+        }
     } else {
         unreachable!("Subcommand is required");
     }
-
-    Ok(0)
 }
 
 fn main() {
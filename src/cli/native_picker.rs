@@ -0,0 +1,330 @@
+//! Built-in screen color picking, without external tools: the color picker of the XDG desktop
+//! portal (Wayland and most desktop environments) or a pointer grab on X11.
+
+use std::collections::HashMap;
+
+use x11rb::connection::Connection as _;
+use x11rb::protocol::xproto::{
+    ConnectionExt as _, EventMask, GrabMode, GrabStatus, ImageFormat, ImageOrder,
+};
+use x11rb::protocol::Event;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+use crate::error::{PastelError, Result};
+
+use pastel::{linear_to_srgb, srgb_to_linear, Color};
+
+/// The crosshair glyph of the X11 cursor font
+const XC_CROSSHAIR: u16 = 34;
+
+fn error(reason: impl ToString) -> PastelError {
+    PastelError::NativeColorPickerError(reason.to_string())
+}
+
+fn rgb_string(r: u8, g: u8, b: u8) -> String {
+    format!("rgb({}, {}, {})", r, g, b)
+}
+
+/// Pick a color from the screen. On Wayland, the desktop portal is tried first (X11 clients can
+/// not read the screen contents there), otherwise a pointer grab on X11.
+pub fn pick_color() -> Result<String> {
+    let is_wayland = std::env::var_os("WAYLAND_DISPLAY").is_some_and(|d| !d.is_empty());
+    let backends: [fn() -> Result<String>; 2] = if is_wayland {
+        [pick_with_portal, pick_on_x11]
+    } else {
+        [pick_on_x11, pick_with_portal]
+    };
+
+    backends[0]().or_else(|first_error| backends[1]().map_err(|_| first_error))
+}
+
+/// Pick a color from the screen and average it with the surrounding pixels (an area of
+/// `width`x`height` pixels, centered on the picked pixel). This requires reading the screen
+/// contents, which is only possible on X11.
+pub fn pick_color_averaged(width: u16, height: u16) -> Result<String> {
+    pick_area_on_x11(width, height)
+}
+
+fn pick_on_x11() -> Result<String> {
+    pick_area_on_x11(1, 1)
+}
+
+/// The memory layout of the pixels of an X11 image (in the Z pixmap format) and the channel masks
+/// of its visual.
+struct PixelFormat {
+    bytes_per_pixel: usize,
+    bytes_per_row: usize,
+    lsb_first: bool,
+    /// The red, green and blue masks
+    masks: [u32; 3],
+}
+
+impl PixelFormat {
+    /// The (raw) pixel value at the given position.
+    fn pixel(&self, data: &[u8], x: usize, y: usize) -> u32 {
+        let start = y * self.bytes_per_row + x * self.bytes_per_pixel;
+        let bytes = data
+            .get(start..start + self.bytes_per_pixel)
+            .unwrap_or_default();
+        if self.lsb_first {
+            bytes
+                .iter()
+                .rev()
+                .fold(0u32, |p, &b| (p << 8) | u32::from(b))
+        } else {
+            bytes.iter().fold(0u32, |p, &b| (p << 8) | u32::from(b))
+        }
+    }
+
+    /// The red, green and blue channels of a pixel value, between 0.0 and 1.0.
+    fn channels(&self, pixel: u32) -> [f64; 3] {
+        self.masks.map(|mask| {
+            if mask == 0 {
+                return 0.0;
+            }
+            let value = (pixel & mask) >> mask.trailing_zeros();
+            let max = mask >> mask.trailing_zeros();
+            f64::from(value) / f64::from(max)
+        })
+    }
+}
+
+/// Average the pixels of an image with the given size in linear light.
+fn average_color(data: &[u8], format: &PixelFormat, width: usize, height: usize) -> Color {
+    let mut sum = [0.0; 3];
+    for y in 0..height {
+        for x in 0..width {
+            let channels = format.channels(format.pixel(data, x, y));
+            for (sum, channel) in sum.iter_mut().zip(channels) {
+                *sum += srgb_to_linear(channel);
+            }
+        }
+    }
+    let count = (width * height) as f64;
+    let [r, g, b] = sum.map(|sum| linear_to_srgb(sum / count).clamp(0.0, 1.0));
+
+    Color::from_rgb_float(r, g, b)
+}
+
+/// Grab the pointer (with a crosshair cursor) and read the pixels around the pointer when a
+/// mouse button is pressed. The pixels are averaged in linear light.
+fn pick_area_on_x11(width: u16, height: u16) -> Result<String> {
+    let (conn, screen_num) = x11rb::connect(None).map_err(error)?;
+    let setup = conn.setup();
+    let screen = &setup.roots[screen_num];
+    let root = screen.root;
+
+    let font = conn.generate_id().map_err(error)?;
+    conn.open_font(font, b"cursor").map_err(error)?;
+    let cursor = conn.generate_id().map_err(error)?;
+    conn.create_glyph_cursor(
+        cursor,
+        font,
+        font,
+        XC_CROSSHAIR,
+        XC_CROSSHAIR + 1,
+        0,
+        0,
+        0,
+        0xffff,
+        0xffff,
+        0xffff,
+    )
+    .map_err(error)?;
+
+    let grab = conn
+        .grab_pointer(
+            false,
+            root,
+            EventMask::BUTTON_PRESS,
+            GrabMode::ASYNC,
+            GrabMode::ASYNC,
+            x11rb::NONE,
+            cursor,
+            x11rb::CURRENT_TIME,
+        )
+        .map_err(error)?
+        .reply()
+        .map_err(error)?;
+    if grab.status != GrabStatus::SUCCESS {
+        return Err(error("could not grab the mouse pointer"));
+    }
+
+    let position = loop {
+        match conn.wait_for_event().map_err(error)? {
+            Event::ButtonPress(event) => break (event.root_x, event.root_y),
+            _ => continue,
+        }
+    };
+    conn.ungrab_pointer(x11rb::CURRENT_TIME).map_err(error)?;
+    conn.free_cursor(cursor).map_err(error)?;
+    conn.close_font(font).map_err(error)?;
+
+    // Center the area on the picked pixel, but keep it on the screen
+    let width = width.min(screen.width_in_pixels);
+    let height = height.min(screen.height_in_pixels);
+    let origin = |position: i16, size: u16, screen_size: u16| {
+        let max = i32::from(screen_size) - i32::from(size);
+        (i32::from(position) - i32::from(size / 2)).clamp(0, max) as i16
+    };
+
+    let image = conn
+        .get_image(
+            ImageFormat::Z_PIXMAP,
+            root,
+            origin(position.0, width, screen.width_in_pixels),
+            origin(position.1, height, screen.height_in_pixels),
+            width,
+            height,
+            !0,
+        )
+        .map_err(error)?
+        .reply()
+        .map_err(error)?;
+
+    let visual = screen
+        .allowed_depths
+        .iter()
+        .flat_map(|d| &d.visuals)
+        .find(|v| v.visual_id == image.visual)
+        .ok_or_else(|| error("unknown visual of the root window"))?;
+    let (bits_per_pixel, scanline_pad) = setup
+        .pixmap_formats
+        .iter()
+        .find(|f| f.depth == image.depth)
+        .map_or((32, 32), |f| (f.bits_per_pixel, f.scanline_pad));
+
+    let pad = usize::from(scanline_pad).max(8);
+    let format = PixelFormat {
+        bytes_per_pixel: usize::from(bits_per_pixel / 8).max(1),
+        bytes_per_row: (usize::from(width) * usize::from(bits_per_pixel)).div_ceil(pad) * pad / 8,
+        lsb_first: setup.image_byte_order == ImageOrder::LSB_FIRST,
+        masks: [visual.red_mask, visual.green_mask, visual.blue_mask],
+    };
+
+    let color = average_color(
+        &image.data,
+        &format,
+        usize::from(width),
+        usize::from(height),
+    );
+    let rgba = color.to_rgba();
+
+    Ok(rgb_string(rgba.r, rgba.g, rgba.b))
+}
+
+/// Use the PickColor method of the screenshot interface of the XDG desktop portal.
+fn pick_with_portal() -> Result<String> {
+    let conn = zbus::blocking::Connection::session().map_err(error)?;
+
+    // The path of the request object is known in advance, such that the response cannot be
+    // missed (see the documentation of org.freedesktop.portal.Request).
+    let token = format!("pastel{}", std::process::id());
+    let sender = conn
+        .unique_name()
+        .ok_or_else(|| error("no unique name on the session bus"))?
+        .trim_start_matches(':')
+        .replace('.', "_");
+    let request_path = format!(
+        "/org/freedesktop/portal/desktop/request/{}/{}",
+        sender, token
+    );
+
+    let request = zbus::blocking::Proxy::new(
+        &conn,
+        "org.freedesktop.portal.Desktop",
+        request_path.as_str(),
+        "org.freedesktop.portal.Request",
+    )
+    .map_err(error)?;
+    let mut responses = request.receive_signal("Response").map_err(error)?;
+
+    let screenshot = zbus::blocking::Proxy::new(
+        &conn,
+        "org.freedesktop.portal.Desktop",
+        "/org/freedesktop/portal/desktop",
+        "org.freedesktop.portal.Screenshot",
+    )
+    .map_err(error)?;
+    let mut options: HashMap<&str, Value> = HashMap::new();
+    options.insert("handle_token", Value::from(token.as_str()));
+    let _: OwnedObjectPath = screenshot
+        .call("PickColor", &("", options))
+        .map_err(error)?;
+
+    let message = responses
+        .next()
+        .ok_or_else(|| error("no response from the desktop portal"))?;
+    let (response, results): (u32, HashMap<String, OwnedValue>) =
+        message.body().deserialize().map_err(error)?;
+    if response != 0 {
+        return Err(error("the color picker was cancelled"));
+    }
+
+    let color = results
+        .get("color")
+        .ok_or_else(|| error("no color in the response of the desktop portal"))?;
+    let (r, g, b): (f64, f64, f64) = color
+        .try_clone()
+        .map_err(error)?
+        .try_into()
+        .map_err(error)?;
+    let to_u8 = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    Ok(rgb_string(to_u8(r), to_u8(g), to_u8(b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb_format(bytes_per_pixel: usize, lsb_first: bool) -> PixelFormat {
+        PixelFormat {
+            bytes_per_pixel,
+            bytes_per_row: 2 * bytes_per_pixel,
+            lsb_first,
+            masks: [0xff0000, 0x00ff00, 0x0000ff],
+        }
+    }
+
+    #[test]
+    fn decode_pixels() {
+        let data = [0x33, 0x22, 0x11, 0x00, 0x00, 0x11, 0x22, 0x33];
+
+        let lsb_first = rgb_format(4, true);
+        assert_eq!(0x112233, lsb_first.pixel(&data, 0, 0));
+        assert_eq!(0x33221100, lsb_first.pixel(&data, 1, 0));
+        // Out of bounds
+        assert_eq!(0, lsb_first.pixel(&data, 0, 1));
+
+        let msb_first = rgb_format(4, false);
+        assert_eq!(0x33221100, msb_first.pixel(&data, 0, 0));
+        assert_eq!(0x112233, msb_first.pixel(&data, 1, 0));
+    }
+
+    #[test]
+    fn decode_channels() {
+        let format = rgb_format(4, true);
+        assert_eq!([1.0, 0.0, 0.2], format.channels(0xff0033));
+
+        let rgb565 = PixelFormat {
+            masks: [0xf800, 0x07e0, 0x001f],
+            ..rgb_format(2, true)
+        };
+        assert_eq!([1.0, 0.0, 0.0], rgb565.channels(0xf800));
+        assert_eq!([0.0, 1.0, 1.0], rgb565.channels(0x07ff));
+    }
+
+    #[test]
+    fn average_in_linear_light() {
+        let data = [0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0x00];
+        let format = rgb_format(4, true);
+
+        assert_eq!(Color::black(), average_color(&data, &format, 1, 1));
+        // Black and white average to a light gray (not #808080)
+        assert_eq!(
+            Color::from_rgb(188, 188, 188),
+            average_color(&data, &format, 2, 1)
+        );
+    }
+}
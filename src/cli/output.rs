@@ -3,8 +3,9 @@ use std::io::Write;
 use crate::config::Config;
 use crate::error::Result;
 use crate::hdcanvas::Canvas;
-use crate::utility::similar_colors;
+use crate::utility::{checkerboard_colors, color_url, similar_colors, terminal_background};
 
+use pastel::ansi::ToAnsiStyle;
 use pastel::Color;
 use pastel::Format;
 
@@ -12,16 +13,30 @@ use pastel::Format;
 pub struct Output<'a> {
     pub handle: &'a mut dyn Write,
     colors_shown: usize,
+    /// The detected background color of the terminal (queried on first use)
+    terminal_background: Option<Option<Color>>,
 }
 
 impl Output<'_> {
-    pub fn new(handle: &mut dyn Write) -> Output {
+    pub fn new(handle: &mut dyn Write) -> Output<'_> {
         Output {
             handle,
             colors_shown: 0,
+            terminal_background: None,
         }
     }
 
+    /// The background color of the terminal, if it can be determined (see
+    /// `utility::terminal_background`). The terminal is only queried in interactive mode.
+    pub fn terminal_background(&mut self, config: &Config) -> Option<Color> {
+        if !config.interactive_mode {
+            return None;
+        }
+        self.terminal_background
+            .get_or_insert_with(terminal_background)
+            .clone()
+    }
+
     pub fn show_color_tty(&mut self, config: &Config, color: &Color) -> Result<()> {
         let checkerboard_size: usize = 16;
         let color_panel_size: usize = 12;
@@ -35,14 +50,18 @@ impl Output<'_> {
         let text_position_x: usize = checkerboard_size + 2 * config.padding;
         let text_position_y: usize = 0;
 
+        let background = self.terminal_background(config);
+        let (dark, light) = checkerboard_colors(background.as_ref());
+
         let mut canvas = Canvas::new(checkerboard_size, 60, config.brush);
+        canvas.set_text_color(background.as_ref().map(Color::text_color));
         canvas.draw_checkerboard(
             checkerboard_position_y,
             checkerboard_position_x,
             checkerboard_size,
             checkerboard_size,
-            &Color::graytone(0.94),
-            &Color::graytone(0.71),
+            &dark,
+            &light,
         );
         canvas.draw_rect(
             color_panel_position_y,
@@ -76,12 +95,23 @@ impl Output<'_> {
             );
         }
 
-        #[allow(clippy::identity_op)]
-        canvas.draw_text(
-            text_position_y + 0 + text_y_offset,
-            text_position_x,
-            &format!("Hex: {}", color.to_rgb_hex_string(true)),
-        );
+        let hex = color.to_rgb_hex_string(true);
+        match config.hyperlink_template {
+            Some(ref template) => {
+                canvas.draw_text(text_position_y + text_y_offset, text_position_x, "Hex: ");
+                canvas.draw_link(
+                    text_position_y + text_y_offset,
+                    text_position_x + 5,
+                    &hex,
+                    &color_url(template, color),
+                );
+            }
+            None => canvas.draw_text(
+                text_position_y + text_y_offset,
+                text_position_x,
+                &format!("Hex: {}", hex),
+            ),
+        }
         canvas.draw_text(
             text_position_y + 2 + text_y_offset,
             text_position_x,
@@ -103,7 +133,11 @@ impl Output<'_> {
     }
 
     pub fn show_color(&mut self, config: &Config, color: &Color) -> Result<()> {
-        if config.interactive_mode {
+        if let Some(format) = config.raw_output {
+            let rgba = color.to_rgba();
+            let pixel = [rgba.r, rgba.g, rgba.b, (rgba.alpha * 255.0).round() as u8];
+            self.handle.write_all(&pixel[..format.bytes_per_pixel()])?;
+        } else if config.interactive_mode {
             if self.colors_shown < 1 {
                 writeln!(self.handle)?
             };
@@ -116,4 +150,33 @@ impl Output<'_> {
 
         Ok(())
     }
+
+    /// Show a color along with its name: as 'name: #rrggbb', or as a color swatch followed by the
+    /// hex code and the name in interactive mode.
+    pub fn show_named_color(&mut self, config: &Config, name: &str, color: &Color) -> Result<()> {
+        self.show_named_value(config, name, color, &color.to_rgb_hex_string(true))
+    }
+
+    /// Like `show_named_color`, but with a different value instead of the hex code.
+    pub fn show_named_value(
+        &mut self,
+        config: &Config,
+        name: &str,
+        color: &Color,
+        value: &str,
+    ) -> Result<()> {
+        if config.interactive_mode {
+            writeln!(
+                self.handle,
+                "{} {:9} {}",
+                config.brush.paint("   ", color.ansi_style().on(color)),
+                value,
+                name
+            )?;
+        } else {
+            writeln!(self.handle, "{}: {}", name, value)?;
+        }
+
+        Ok(())
+    }
 }
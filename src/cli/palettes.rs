@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+
+use crate::config;
+use crate::{PastelError, Result};
+
+use pastel::parser::parse_color;
+use pastel::Color;
+
+/// The directory for saved palettes: `palettes` within the configuration directory.
+fn palette_dir() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("palettes"))
+}
+
+fn palette_path(name: &str) -> Result<PathBuf> {
+    if !config::is_valid_name(name) {
+        return Err(PastelError::InvalidPaletteName(name.into()));
+    }
+
+    let dir = palette_dir().ok_or(PastelError::NoConfigDirectory)?;
+    Ok(dir.join(format!("{}.txt", name)))
+}
+
+/// Parse a palette with one color per line. Empty lines and comments (lines starting with '#'
+/// that are not hex colors) are ignored.
+pub fn parse(source: &str) -> Result<Vec<Color>> {
+    let mut colors = vec![];
+
+    for line in source.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        match parse_color(line) {
+            Some(color) => colors.push(color),
+            None if line.starts_with('#') => {}
+            None => return Err(PastelError::ColorParseError(line.into())),
+        }
+    }
+
+    Ok(colors)
+}
+
+fn to_file_string(colors: &[Color]) -> String {
+    colors
+        .iter()
+        .map(|c| format!("{}\n", c.to_rgb_hex_string(true)))
+        .collect()
+}
+
+/// Load a saved palette by name.
+pub fn load(name: &str) -> Result<Vec<Color>> {
+    let path = palette_path(name)?;
+    if !path.exists() {
+        return Err(PastelError::UnknownPalette(name.into()));
+    }
+
+    parse(&std::fs::read_to_string(path)?)
+}
+
+/// Save a palette under the given name (replacing an existing palette with the same name).
+pub fn save(name: &str, colors: &[Color]) -> Result<()> {
+    let path = palette_path(name)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    std::fs::write(path, to_file_string(colors))?;
+    Ok(())
+}
+
+/// Append colors to a saved palette (which is created if it does not exist yet).
+pub fn append(name: &str, colors: &[Color]) -> Result<()> {
+    let mut palette = if palette_path(name)?.exists() {
+        load(name)?
+    } else {
+        vec![]
+    };
+    palette.extend_from_slice(colors);
+
+    save(name, &palette)
+}
+
+/// Remove a saved palette.
+pub fn remove(name: &str) -> Result<()> {
+    let path = palette_path(name)?;
+    if !path.exists() {
+        return Err(PastelError::UnknownPalette(name.into()));
+    }
+
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+/// Names of all saved palettes.
+pub fn names() -> Vec<String> {
+    let mut names = vec![];
+
+    if let Some(entries) = palette_dir().and_then(|dir| std::fs::read_dir(dir).ok()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|e| e == "txt") {
+                if let Some(stem) = path.file_stem() {
+                    names.push(stem.to_string_lossy().into());
+                }
+            }
+        }
+    }
+
+    names.sort();
+    names
+}
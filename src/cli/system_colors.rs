@@ -0,0 +1,363 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::Result;
+
+use pastel::parser::parse_color;
+use pastel::Color;
+
+/// The desktop toolkit whose theme configuration is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeSource {
+    Gtk,
+    Kde,
+}
+
+/// Portable role names, along with the toolkit-specific names they are taken from (in order of
+/// preference).
+const GTK_ROLES: &[(&str, &[&str])] = &[
+    (
+        "accent",
+        &[
+            "accent_bg_color",
+            "accent_color",
+            "theme_selected_bg_color",
+            "selected_bg_color",
+        ],
+    ),
+    (
+        "background",
+        &["window_bg_color", "theme_bg_color", "bg_color"],
+    ),
+    (
+        "foreground",
+        &["window_fg_color", "theme_fg_color", "fg_color"],
+    ),
+];
+
+const KDE_ROLES: &[(&str, &[&str])] = &[
+    ("accent", &["accent", "selection-background-normal"]),
+    ("background", &["window-background-normal"]),
+    ("foreground", &["window-foreground-normal"]),
+];
+
+/// The macOS accent colors, indexed by the value of `AppleAccentColor` (-1 is graphite, no
+/// value is the default blue).
+const MACOS_ACCENT_COLORS: &[(i32, &str)] = &[
+    (-1, "#8c8c8c"),
+    (0, "#ff5257"),
+    (1, "#f7821b"),
+    (2, "#ffc600"),
+    (3, "#62ba46"),
+    (4, "#007aff"),
+    (5, "#a550a7"),
+    (6, "#f74f9e"),
+];
+
+/// Parse the `@define-color name value;` statements of a GTK style sheet. References to other
+/// colors (`@name`) are resolved, values that can not be parsed (like `shade(…)` expressions)
+/// are skipped.
+pub fn parse_gtk_css(source: &str) -> Vec<(String, Color)> {
+    let mut colors: Vec<(String, Color)> = vec![];
+
+    for statement in source.split(';') {
+        let definition = match statement.find("@define-color") {
+            Some(start) => statement[start + "@define-color".len()..].trim(),
+            None => continue,
+        };
+
+        if let Some((name, value)) = definition.split_once(char::is_whitespace) {
+            let value = value.trim();
+            let color = match value.strip_prefix('@') {
+                Some(reference) => lookup(&colors, reference).cloned(),
+                None => parse_color(value),
+            };
+            if let Some(color) = color {
+                insert(&mut colors, name, color);
+            }
+        }
+    }
+
+    colors
+}
+
+/// Parse the `gtk-color-scheme` setting of a GTK `settings.ini` file, which contains entries
+/// like `bg_color:#ededed` separated by newlines (`\n`) or semicolons.
+pub fn parse_gtk_settings(source: &str) -> Vec<(String, Color)> {
+    let mut colors = vec![];
+
+    for (key, value) in ini_entries(source).into_iter().map(|(_, k, v)| (k, v)) {
+        if key != "gtk-color-scheme" {
+            continue;
+        }
+        let value = value.trim_matches('"');
+        for entry in value.split("\\n").flat_map(|e| e.split(['\n', ';'])) {
+            if let Some((name, color)) = entry.split_once(':') {
+                if let Some(color) = parse_color(color.trim()) {
+                    insert(&mut colors, name.trim(), color);
+                }
+            }
+        }
+    }
+
+    colors
+}
+
+/// Parse a KDE color scheme (`*.colors`) or the `kdeglobals` file. Colors are named after their
+/// section and key, e.g. `BackgroundNormal` in `[Colors:Window]` becomes
+/// `window-background-normal`. The accent color of `[General]` is called `accent`.
+pub fn parse_kde_colors(source: &str) -> Vec<(String, Color)> {
+    let mut colors = vec![];
+
+    for (section, key, value) in ini_entries(source) {
+        let prefix = match section.as_str() {
+            "General" if key == "AccentColor" => "",
+            "WM" => "wm",
+            s => match s.strip_prefix("Colors:") {
+                // Skip nested sections like '[Colors:Header][Inactive]'
+                Some(group) if !group.contains(']') => group,
+                _ => continue,
+            },
+        };
+
+        let color = match parse_kde_color(&value) {
+            Some(color) => color,
+            None => continue,
+        };
+
+        let name = if prefix.is_empty() {
+            "accent".to_string()
+        } else {
+            format!("{}-{}", kebab_case(prefix), kebab_case(&key))
+        };
+        insert(&mut colors, &name, color);
+    }
+
+    colors
+}
+
+/// Add the portable roles (`accent`, `background`, `foreground`) in front of the given colors.
+pub fn with_roles(colors: Vec<(String, Color)>, source: ThemeSource) -> Vec<(String, Color)> {
+    let roles = match source {
+        ThemeSource::Gtk => GTK_ROLES,
+        ThemeSource::Kde => KDE_ROLES,
+    };
+
+    let mut result = vec![];
+    for (role, candidates) in roles {
+        if let Some(color) = candidates.iter().find_map(|c| lookup(&colors, c)) {
+            result.push((role.to_string(), color.clone()));
+        }
+    }
+
+    for (name, color) in colors {
+        if lookup(&result, &name).is_none() {
+            result.push((name, color));
+        }
+    }
+
+    result
+}
+
+/// Read the colors of a theme configuration file. The kind of file is detected from its name.
+pub fn read_file(path: &Path) -> Result<(ThemeSource, Vec<(String, Color)>)> {
+    let source = std::fs::read_to_string(path)?;
+    let colors = match path.extension().and_then(|e| e.to_str()) {
+        Some("css") => (ThemeSource::Gtk, parse_gtk_css(&source)),
+        Some("ini") => (ThemeSource::Gtk, parse_gtk_settings(&source)),
+        _ => (ThemeSource::Kde, parse_kde_colors(&source)),
+    };
+    Ok(colors)
+}
+
+/// Read the colors of the current user's theme configuration for the given toolkit.
+pub fn read_system(source: ThemeSource) -> Result<Vec<(String, Color)>> {
+    let dir = match user_config_dir() {
+        Some(dir) => dir,
+        None => return Ok(vec![]),
+    };
+
+    let files: &[&str] = match source {
+        ThemeSource::Gtk => &[
+            "gtk-4.0/gtk.css",
+            "gtk-3.0/gtk.css",
+            "gtk-4.0/settings.ini",
+            "gtk-3.0/settings.ini",
+        ],
+        ThemeSource::Kde => &["kdeglobals"],
+    };
+
+    let mut colors = vec![];
+    for file in files {
+        let path = dir.join(file);
+        if path.is_file() {
+            for (name, color) in read_file(&path)?.1 {
+                insert(&mut colors, &name, color);
+            }
+        }
+    }
+
+    Ok(colors)
+}
+
+/// Query the accent color of the operating system: the `AppleAccentColor` user default on
+/// macOS, the DWM `AccentColor` registry value on Windows, and the accent color of the GTK or
+/// KDE theme on other systems.
+pub fn accent_color() -> Result<Option<Color>> {
+    if cfg!(target_os = "macos") {
+        let output = run("defaults", &["read", "-g", "AppleAccentColor"]);
+        return Ok(parse_macos_accent(output.as_deref()));
+    }
+
+    if cfg!(windows) {
+        let output = run(
+            "reg",
+            &[
+                "query",
+                r"HKCU\Software\Microsoft\Windows\DWM",
+                "/v",
+                "AccentColor",
+            ],
+        );
+        return Ok(output.as_deref().and_then(parse_windows_accent));
+    }
+
+    for source in preferred_sources() {
+        let colors = with_roles(read_system(source)?, source);
+        if let Some(accent) = lookup(&colors, "accent") {
+            return Ok(Some(accent.clone()));
+        }
+    }
+    Ok(None)
+}
+
+/// Parse the output of `defaults read -g AppleAccentColor` (`None` if the key is not set).
+fn parse_macos_accent(output: Option<&str>) -> Option<Color> {
+    let value = match output {
+        Some(output) => output.trim().parse::<i32>().ok()?,
+        None => 4,
+    };
+    MACOS_ACCENT_COLORS
+        .iter()
+        .find(|(v, _)| *v == value)
+        .and_then(|(_, hex)| parse_color(hex))
+}
+
+/// Parse the output of `reg query`, e.g. `AccentColor    REG_DWORD    0xffd77800`. The value is
+/// stored in ABGR order.
+fn parse_windows_accent(output: &str) -> Option<Color> {
+    let mut words = output.split_whitespace();
+    words.find(|w| *w == "REG_DWORD")?;
+    let value = words.next()?;
+    let abgr = u32::from_str_radix(value.trim_start_matches("0x"), 16).ok()?;
+
+    let [r, g, b, _] = abgr.to_le_bytes();
+    Some(Color::from_rgb(r, g, b))
+}
+
+/// Run a program and return its output, if it succeeded.
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// The toolkits to try (in order of preference) for the current desktop environment.
+pub fn preferred_sources() -> [ThemeSource; 2] {
+    let desktop = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+    if desktop.to_uppercase().contains("KDE") {
+        [ThemeSource::Kde, ThemeSource::Gtk]
+    } else {
+        [ThemeSource::Gtk, ThemeSource::Kde]
+    }
+}
+
+/// The user's (system-wide, not pastel-specific) configuration directory.
+fn user_config_dir() -> Option<PathBuf> {
+    let env = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty());
+    env("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env("HOME").map(|d| PathBuf::from(d).join(".config")))
+}
+
+fn lookup<'a>(colors: &'a [(String, Color)], name: &str) -> Option<&'a Color> {
+    colors.iter().find(|(n, _)| n == name).map(|(_, c)| c)
+}
+
+/// Add a color, replacing an earlier definition with the same name.
+fn insert(colors: &mut Vec<(String, Color)>, name: &str, color: Color) {
+    match colors.iter_mut().find(|(n, _)| n == name) {
+        Some(entry) => entry.1 = color,
+        None => colors.push((name.into(), color)),
+    }
+}
+
+/// All `key=value` entries of an INI file, along with the name of their section.
+fn ini_entries(source: &str) -> Vec<(String, String, String)> {
+    let mut section = String::new();
+    let mut entries = vec![];
+
+    for line in source.lines().map(str::trim) {
+        if line.starts_with('[') && line.ends_with(']') {
+            section = line[1..line.len() - 1].to_string();
+        } else if let Some((key, value)) = line.split_once('=') {
+            if !line.starts_with(['#', ';']) {
+                entries.push((section.clone(), key.trim().into(), value.trim().into()));
+            }
+        }
+    }
+
+    entries
+}
+
+/// Parse a KDE color value: `r,g,b` or `r,g,b,a` (0-255), or a color in any format pastel
+/// understands.
+fn parse_kde_color(value: &str) -> Option<Color> {
+    let parts: Vec<Option<u8>> = value.split(',').map(|p| p.trim().parse().ok()).collect();
+
+    match parts[..] {
+        [Some(r), Some(g), Some(b)] => Some(Color::from_rgb(r, g, b)),
+        [Some(r), Some(g), Some(b), Some(a)] => {
+            Some(Color::from_rgba(r, g, b, f64::from(a) / 255.0))
+        }
+        _ => parse_color(value),
+    }
+}
+
+fn kebab_case(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            result.push('-');
+        }
+        result.push(c.to_ascii_lowercase());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn macos_accent() {
+        let hex = |output| parse_macos_accent(output).map(|c| c.to_rgb_hex_string(true));
+        assert_eq!(Some("#007aff".into()), hex(None));
+        assert_eq!(Some("#8c8c8c".into()), hex(Some("-1\n")));
+        assert_eq!(Some("#a550a7".into()), hex(Some("5\n")));
+        assert_eq!(None, hex(Some("42\n")));
+    }
+
+    #[test]
+    fn windows_accent() {
+        let output = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\DWM\r\n    \
+                      AccentColor    REG_DWORD    0xffd77800\r\n\r\n";
+        assert_eq!(
+            Some("#0078d7".into()),
+            parse_windows_accent(output).map(|c| c.to_rgb_hex_string(true))
+        );
+        assert!(parse_windows_accent("ERROR: not found").is_none());
+    }
+}
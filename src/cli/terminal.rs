@@ -0,0 +1,144 @@
+//! Keyboard input for the interactive commands ('pastel edit', 'pastel pick-from-image') and
+//! wrappers for the terminal queries of the library (24-bit color support, background color).
+
+// Raw mode is only available on Unix
+#![cfg_attr(not(unix), allow(dead_code))]
+
+use std::io::Write;
+use std::time::Duration;
+
+use crate::error::Result;
+
+use pastel::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Left,
+    Right,
+    Up,
+    Down,
+    Enter,
+    Escape,
+    Interrupt,
+    Other,
+}
+
+impl Key {
+    pub fn parse(input: &[u8]) -> Key {
+        match input {
+            b"\x1b[D" | b"\x1bOD" => Key::Left,
+            b"\x1b[C" | b"\x1bOC" => Key::Right,
+            b"\x1b[A" | b"\x1bOA" => Key::Up,
+            b"\x1b[B" | b"\x1bOB" => Key::Down,
+            b"\r" | b"\n" => Key::Enter,
+            b"\x1b" => Key::Escape,
+            b"\x03" | b"\x04" => Key::Interrupt,
+            [c] if c.is_ascii_graphic() || *c == b' ' => Key::Char(*c as char),
+            _ => Key::Other,
+        }
+    }
+}
+
+/// Hides the cursor (on STDERR) and shows it again when dropped, also if the interactive
+/// command is left early because of an error.
+pub struct HiddenCursor;
+
+impl HiddenCursor {
+    pub fn hide() -> Result<HiddenCursor> {
+        write!(std::io::stderr(), "\x1b[?25l")?;
+        Ok(HiddenCursor)
+    }
+}
+
+impl Drop for HiddenCursor {
+    fn drop(&mut self) {
+        let mut stderr = std::io::stderr();
+        write!(stderr, "\x1b[?25h")
+            .and_then(|_| stderr.flush())
+            .ok();
+    }
+}
+
+/// Puts the terminal (standard input) into non-canonical mode without echo and restores the
+/// original settings when dropped.
+#[cfg(unix)]
+pub struct RawMode {
+    original: libc::termios,
+}
+
+#[cfg(unix)]
+impl RawMode {
+    pub fn enable() -> Option<RawMode> {
+        let mut termios = std::mem::MaybeUninit::<libc::termios>::uninit();
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, termios.as_mut_ptr()) } != 0 {
+            return None;
+        }
+        let original = unsafe { termios.assume_init() };
+        let mut raw = original;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+            return None;
+        }
+        Some(RawMode { original })
+    }
+
+    /// Read a single key press (escape sequences like the arrow keys are read at once).
+    pub fn read_key(&self) -> Result<Key> {
+        let mut buffer = [0u8; 8];
+        let n = unsafe {
+            libc::read(
+                libc::STDIN_FILENO,
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                buffer.len(),
+            )
+        };
+        if n <= 0 {
+            return Ok(Key::Interrupt);
+        }
+        Ok(Key::parse(&buffer[..n as usize]))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original) };
+    }
+}
+
+/// See `pastel::ansi::probe_truecolor` (terminal queries are only supported on Unix).
+pub fn probe_truecolor(timeout: Duration) -> Option<bool> {
+    #[cfg(unix)]
+    return pastel::ansi::probe_truecolor(timeout);
+    #[cfg(not(unix))]
+    return None;
+}
+
+/// See `pastel::ansi::query_background_color` (terminal queries are only supported on Unix).
+pub fn query_background_color(timeout: Duration) -> Option<Color> {
+    #[cfg(unix)]
+    return pastel::ansi::query_background_color(timeout);
+    #[cfg(not(unix))]
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keys() {
+        assert_eq!(Key::Left, Key::parse(b"\x1b[D"));
+        assert_eq!(Key::Up, Key::parse(b"\x1bOA"));
+        assert_eq!(Key::Enter, Key::parse(b"\r"));
+        assert_eq!(Key::Escape, Key::parse(b"\x1b"));
+        assert_eq!(Key::Interrupt, Key::parse(b"\x03"));
+        assert_eq!(Key::Char('H'), Key::parse(b"H"));
+        assert_eq!(Key::Char(' '), Key::parse(b" "));
+        assert_eq!(Key::Other, Key::parse(b"\x1b[5~"));
+        assert_eq!(Key::Other, Key::parse(b"\t"));
+    }
+}
@@ -0,0 +1,190 @@
+use serde_json::{json, Map, Value};
+
+use pastel::theme::TerminalTheme;
+use pastel::Color;
+
+/// The configuration format of a terminal emulator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeFormat {
+    /// Alacritty (TOML)
+    Alacritty,
+    /// kitty (`kitty.conf` syntax)
+    Kitty,
+    /// WezTerm color scheme file (TOML)
+    WezTerm,
+    /// Windows Terminal color scheme (JSON, for the `schemes` list of `settings.json`)
+    WindowsTerminal,
+    /// X resources (e.g. for xterm and urxvt)
+    Xresources,
+    /// iTerm2 color preset (XML property list)
+    Iterm2,
+}
+
+impl ThemeFormat {
+    pub fn from_name(name: &str) -> Option<ThemeFormat> {
+        match name {
+            "alacritty" => Some(ThemeFormat::Alacritty),
+            "kitty" => Some(ThemeFormat::Kitty),
+            "wezterm" => Some(ThemeFormat::WezTerm),
+            "windows-terminal" => Some(ThemeFormat::WindowsTerminal),
+            "xresources" => Some(ThemeFormat::Xresources),
+            "iterm2" => Some(ThemeFormat::Iterm2),
+            _ => None,
+        }
+    }
+}
+
+/// The names of the eight (normal) ANSI colors in the Alacritty and Windows Terminal formats.
+const COLOR_KEYS: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+fn hex(color: &Color) -> String {
+    color.to_rgb_hex_string(true)
+}
+
+/// Write a theme in the configuration format of a terminal emulator. The name is used by the
+/// formats that include the name of the color scheme.
+pub fn export(theme: &TerminalTheme, format: ThemeFormat, name: &str) -> String {
+    match format {
+        ThemeFormat::Alacritty => to_alacritty(theme),
+        ThemeFormat::Kitty => to_kitty(theme),
+        ThemeFormat::WezTerm => to_wezterm(theme, name),
+        ThemeFormat::WindowsTerminal => format!("{:#}\n", to_windows_terminal(theme, name)),
+        ThemeFormat::Xresources => to_xresources(theme),
+        ThemeFormat::Iterm2 => to_iterm2(theme),
+    }
+}
+
+fn to_alacritty(theme: &TerminalTheme) -> String {
+    let mut result = String::new();
+    result.push_str("[colors.primary]\n");
+    result.push_str(&format!("background = '{}'\n", hex(&theme.background)));
+    result.push_str(&format!("foreground = '{}'\n", hex(&theme.foreground)));
+    result.push_str("\n[colors.cursor]\n");
+    result.push_str(&format!("cursor = '{}'\n", hex(&theme.cursor)));
+    result.push_str(&format!("text = '{}'\n", hex(&theme.background)));
+
+    for (section, colors) in [
+        ("normal", &theme.colors[..8]),
+        ("bright", &theme.colors[8..]),
+    ] {
+        result.push_str(&format!("\n[colors.{}]\n", section));
+        for (key, color) in COLOR_KEYS.iter().zip(colors) {
+            result.push_str(&format!("{} = '{}'\n", key, hex(color)));
+        }
+    }
+    result
+}
+
+fn to_kitty(theme: &TerminalTheme) -> String {
+    let mut result = String::new();
+    result.push_str(&format!("background {}\n", hex(&theme.background)));
+    result.push_str(&format!("foreground {}\n", hex(&theme.foreground)));
+    result.push_str(&format!("cursor {}\n", hex(&theme.cursor)));
+    result.push_str(&format!("cursor_text_color {}\n", hex(&theme.background)));
+    for (i, color) in theme.colors.iter().enumerate() {
+        result.push_str(&format!("color{} {}\n", i, hex(color)));
+    }
+    result
+}
+
+fn to_wezterm(theme: &TerminalTheme, name: &str) -> String {
+    let list = |colors: &[Color]| {
+        colors
+            .iter()
+            .map(|c| format!("'{}'", hex(c)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut result = String::new();
+    result.push_str("[colors]\n");
+    result.push_str(&format!("background = '{}'\n", hex(&theme.background)));
+    result.push_str(&format!("foreground = '{}'\n", hex(&theme.foreground)));
+    result.push_str(&format!("cursor_bg = '{}'\n", hex(&theme.cursor)));
+    result.push_str(&format!("cursor_border = '{}'\n", hex(&theme.cursor)));
+    result.push_str(&format!("cursor_fg = '{}'\n", hex(&theme.background)));
+    result.push_str(&format!("ansi = [{}]\n", list(&theme.colors[..8])));
+    result.push_str(&format!("brights = [{}]\n", list(&theme.colors[8..])));
+    result.push_str("\n[metadata]\n");
+    result.push_str(&format!("name = '{}'\n", name.replace('\'', "")));
+    result
+}
+
+fn to_windows_terminal(theme: &TerminalTheme, name: &str) -> Value {
+    let mut scheme = Map::new();
+    scheme.insert("name".into(), json!(name));
+    scheme.insert("background".into(), json!(hex(&theme.background)));
+    scheme.insert("foreground".into(), json!(hex(&theme.foreground)));
+    scheme.insert("cursorColor".into(), json!(hex(&theme.cursor)));
+
+    for (i, color) in theme.colors.iter().enumerate() {
+        // Windows Terminal calls magenta 'purple'
+        let key = match COLOR_KEYS[i % 8] {
+            "magenta" => "purple",
+            key => key,
+        };
+        let key = if i < 8 {
+            key.to_string()
+        } else {
+            format!("bright{}{}", key[..1].to_uppercase(), &key[1..])
+        };
+        scheme.insert(key, json!(hex(color)));
+    }
+    Value::Object(scheme)
+}
+
+fn to_xresources(theme: &TerminalTheme) -> String {
+    let mut result = String::new();
+    result.push_str(&format!("*.background: {}\n", hex(&theme.background)));
+    result.push_str(&format!("*.foreground: {}\n", hex(&theme.foreground)));
+    result.push_str(&format!("*.cursorColor: {}\n", hex(&theme.cursor)));
+    for (i, color) in theme.colors.iter().enumerate() {
+        result.push_str(&format!("*.color{}: {}\n", i, hex(color)));
+    }
+    result
+}
+
+fn to_iterm2(theme: &TerminalTheme) -> String {
+    let entry = |key: &str, color: &Color| {
+        let rgba = color.to_rgba();
+        let component = |c: u8| format!("{:.6}", c as f64 / 255.0);
+        format!(
+            "\t<key>{}</key>\n\
+             \t<dict>\n\
+             \t\t<key>Alpha Component</key>\n\
+             \t\t<real>1</real>\n\
+             \t\t<key>Blue Component</key>\n\
+             \t\t<real>{}</real>\n\
+             \t\t<key>Color Space</key>\n\
+             \t\t<string>sRGB</string>\n\
+             \t\t<key>Green Component</key>\n\
+             \t\t<real>{}</real>\n\
+             \t\t<key>Red Component</key>\n\
+             \t\t<real>{}</real>\n\
+             \t</dict>\n",
+            key,
+            component(rgba.b),
+            component(rgba.g),
+            component(rgba.r),
+        )
+    };
+
+    let mut result = String::new();
+    result.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    result.push_str(
+        "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+         \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n",
+    );
+    result.push_str("<plist version=\"1.0\">\n<dict>\n");
+    for (i, color) in theme.colors.iter().enumerate() {
+        result.push_str(&entry(&format!("Ansi {} Color", i), color));
+    }
+    result.push_str(&entry("Background Color", &theme.background));
+    result.push_str(&entry("Cursor Color", &theme.cursor));
+    result.push_str(&entry("Cursor Text Color", &theme.background));
+    result.push_str(&entry("Foreground Color", &theme.foreground));
+    result.push_str("</dict>\n</plist>\n");
+    result
+}
@@ -0,0 +1,309 @@
+use serde_json::{json, Map, Value};
+
+use pastel::parser::parse_color;
+use pastel::Color;
+
+/// Maximum length of a chain of aliases (to detect cycles).
+const MAX_ALIAS_DEPTH: usize = 32;
+
+/// The JSON flavor of a design-token file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenFormat {
+    /// W3C Design Tokens format (`$value` and `$type`)
+    W3c,
+    /// Style Dictionary (`value` and `type`)
+    StyleDictionary,
+    /// Tokens Studio for Figma (like Style Dictionary, but within a `global` token set)
+    TokensStudio,
+}
+
+impl TokenFormat {
+    pub fn from_name(name: &str) -> Option<TokenFormat> {
+        match name {
+            "w3c" => Some(TokenFormat::W3c),
+            "style-dictionary" => Some(TokenFormat::StyleDictionary),
+            "figma" => Some(TokenFormat::TokensStudio),
+            _ => None,
+        }
+    }
+
+    /// Guess the format of a document: W3C tokens if any `$value` key is present.
+    pub fn detect(document: &Value) -> TokenFormat {
+        fn has_dollar_value(value: &Value) -> bool {
+            match value {
+                Value::Object(map) => {
+                    map.contains_key("$value") || map.values().any(has_dollar_value)
+                }
+                _ => false,
+            }
+        }
+
+        if has_dollar_value(document) {
+            TokenFormat::W3c
+        } else {
+            TokenFormat::StyleDictionary
+        }
+    }
+
+    fn value_key(self) -> &'static str {
+        match self {
+            TokenFormat::W3c => "$value",
+            TokenFormat::StyleDictionary | TokenFormat::TokensStudio => "value",
+        }
+    }
+
+    fn type_key(self) -> &'static str {
+        match self {
+            TokenFormat::W3c => "$type",
+            TokenFormat::StyleDictionary | TokenFormat::TokensStudio => "type",
+        }
+    }
+}
+
+/// The value of a color token: either a concrete color or a reference to another token.
+#[derive(Debug, Clone)]
+pub enum TokenValue {
+    Color(Color),
+    Alias(String),
+}
+
+impl TokenValue {
+    /// Parse a token value as written in a named palette line or in a token file (`{a.b}` for
+    /// aliases).
+    pub fn parse(value: &str) -> Option<TokenValue> {
+        let value = value.trim();
+        match value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+            Some(alias) => Some(TokenValue::Alias(alias.trim().into())),
+            None => parse_color(value).map(TokenValue::Color),
+        }
+    }
+
+    fn to_json_string(&self) -> String {
+        match self {
+            TokenValue::Color(color) => color.to_rgb_hex_string(true),
+            TokenValue::Alias(alias) => format!("{{{}}}", alias),
+        }
+    }
+}
+
+/// A named color token.
+#[derive(Debug, Clone)]
+pub struct Token {
+    /// Full path of the token, with groups separated by '.'
+    pub name: String,
+    pub value: TokenValue,
+}
+
+/// Parse the value of a token node. Returns `None` for tokens that are not colors.
+fn parse_value(value: &Value, token_type: Option<&str>) -> Result<Option<TokenValue>, String> {
+    let is_color = token_type == Some("color");
+    let parsed = match value {
+        Value::String(s) => TokenValue::parse(s),
+        // Newer drafts of the W3C format allow objects with a 'hex' fallback
+        Value::Object(map) => map.get("hex").and_then(Value::as_str).and_then(|hex| {
+            let alpha = map.get("alpha").and_then(Value::as_f64).unwrap_or(1.0);
+            parse_color(hex).map(|c| {
+                let rgba = c.to_rgba();
+                TokenValue::Color(Color::from_rgba(rgba.r, rgba.g, rgba.b, alpha))
+            })
+        }),
+        _ => None,
+    };
+
+    match parsed {
+        None if is_color => Err(format!("could not parse color value '{}'", value)),
+        parsed if token_type.is_none() || is_color => Ok(parsed),
+        _ => Ok(None),
+    }
+}
+
+fn collect(
+    node: &Map<String, Value>,
+    path: &mut Vec<String>,
+    inherited_type: Option<&str>,
+    format: TokenFormat,
+    tokens: &mut Vec<(Token, bool)>,
+) -> Result<(), String> {
+    let token_type = node
+        .get(format.type_key())
+        .and_then(Value::as_str)
+        .or(inherited_type);
+
+    if let Some(value) = node.get(format.value_key()) {
+        if let Some(value) = parse_value(value, token_type)
+            .map_err(|reason| format!("token '{}': {}", path.join("."), reason))?
+        {
+            let token = Token {
+                name: path.join("."),
+                value,
+            };
+            tokens.push((token, token_type.is_some()));
+        }
+        return Ok(());
+    }
+
+    for (key, child) in node {
+        if key.starts_with('$') {
+            continue;
+        }
+        if let Value::Object(child) = child {
+            path.push(key.clone());
+            collect(child, path, token_type, format, tokens)?;
+            path.pop();
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a design-token file and return all color tokens (in document order).
+pub fn parse(source: &str) -> Result<(Value, Vec<Token>), String> {
+    let document: Value = serde_json::from_str(source).map_err(|e| e.to_string())?;
+    let root = document
+        .as_object()
+        .ok_or_else(|| "expected a JSON object".to_string())?;
+
+    let mut tokens = vec![];
+    collect(
+        root,
+        &mut vec![],
+        None,
+        TokenFormat::detect(&document),
+        &mut tokens,
+    )?;
+
+    // Aliases without a type might refer to tokens that are not colors
+    let all: Vec<Token> = tokens.iter().map(|(t, _)| t.clone()).collect();
+    let tokens = tokens
+        .into_iter()
+        .filter(|(t, typed)| *typed || resolve(&all, &t.value).is_ok())
+        .map(|(t, _)| t)
+        .collect();
+
+    Ok((document, tokens))
+}
+
+/// Follow aliases until a concrete color is found.
+pub fn resolve(tokens: &[Token], value: &TokenValue) -> Result<Color, String> {
+    let mut value = value;
+    for _ in 0..MAX_ALIAS_DEPTH {
+        match value {
+            TokenValue::Color(color) => return Ok(color.clone()),
+            TokenValue::Alias(alias) => {
+                // Style Dictionary references often include the '.value' suffix
+                let name = alias.strip_suffix(".value").unwrap_or(alias);
+                value = &tokens
+                    .iter()
+                    .find(|t| t.name == name)
+                    .ok_or_else(|| format!("unknown alias '{{{}}}'", alias))?
+                    .value;
+            }
+        }
+    }
+    Err("circular alias".into())
+}
+
+/// Apply a function to all concrete color values of the document. Aliases and all other
+/// tokens are left unchanged.
+pub fn map_colors(document: &mut Value, f: &dyn Fn(&Color) -> Color) -> Result<(), String> {
+    fn walk(
+        node: &mut Map<String, Value>,
+        inherited_type: Option<String>,
+        format: TokenFormat,
+        f: &dyn Fn(&Color) -> Color,
+    ) -> Result<(), String> {
+        let token_type = node
+            .get(format.type_key())
+            .and_then(Value::as_str)
+            .map(String::from)
+            .or(inherited_type);
+
+        if let Some(value) = node.get_mut(format.value_key()) {
+            if let Some(TokenValue::Color(color)) = parse_value(value, token_type.as_deref())? {
+                *value = Value::String(f(&color).to_rgb_hex_string(true));
+            }
+            return Ok(());
+        }
+
+        for (key, child) in node.iter_mut() {
+            if let (false, Value::Object(child)) = (key.starts_with('$'), child) {
+                walk(child, token_type.clone(), format, f)?;
+            }
+        }
+        Ok(())
+    }
+
+    let format = TokenFormat::detect(document);
+    match document {
+        Value::Object(root) => walk(root, None, format, f),
+        _ => Err("expected a JSON object".into()),
+    }
+}
+
+/// Build a design-token document from a list of tokens. Groups are created from the
+/// dot-separated token names.
+pub fn to_document(tokens: &[Token], format: TokenFormat) -> Result<Value, String> {
+    let mut root = Map::new();
+
+    for token in tokens {
+        let mut node = &mut root;
+        let mut parts = token.name.split('.').peekable();
+        while let Some(part) = parts.next() {
+            if part.is_empty() {
+                return Err(format!("invalid token name '{}'", token.name));
+            }
+
+            if parts.peek().is_none() {
+                if node.contains_key(part) {
+                    return Err(format!("duplicate token '{}'", token.name));
+                }
+                let mut entry = Map::new();
+                entry.insert(format.type_key().into(), "color".into());
+                entry.insert(
+                    format.value_key().into(),
+                    token.value.to_json_string().into(),
+                );
+                node.insert(part.into(), Value::Object(entry));
+            } else {
+                let group = node
+                    .entry(part)
+                    .or_insert_with(|| Value::Object(Map::new()));
+                node = match group {
+                    Value::Object(group) if !group.contains_key(format.value_key()) => group,
+                    _ => return Err(format!("token '{}' is also used as a group", part)),
+                };
+            }
+        }
+    }
+
+    if format == TokenFormat::TokensStudio {
+        let mut sets = Map::new();
+        sets.insert("global".into(), Value::Object(root));
+        return Ok(Value::Object(sets));
+    }
+
+    Ok(Value::Object(root))
+}
+
+/// Build a Sketch palette (`.sketchpalette`, as used by the Sketch Palettes plugin).
+pub fn to_sketch_palette(colors: &[(String, Color)]) -> Value {
+    let colors = colors
+        .iter()
+        .map(|(name, color)| {
+            let rgba = color.to_rgba_float();
+            json!({
+                "name": name,
+                "red": rgba.r,
+                "green": rgba.g,
+                "blue": rgba.b,
+                "alpha": rgba.alpha,
+            })
+        })
+        .collect();
+
+    let mut palette = Map::new();
+    palette.insert("compatibleVersion".into(), "2.0".into());
+    palette.insert("pluginVersion".into(), "2.22".into());
+    palette.insert("colors".into(), Value::Array(colors));
+    Value::Object(palette)
+}
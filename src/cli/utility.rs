@@ -1,6 +1,14 @@
+use std::time::Duration;
+
+use crate::terminal;
+
 use pastel::named::{NamedColor, NAMED_COLORS};
+use pastel::parser::parse_color;
 use pastel::Color;
 
+/// How long to wait for the terminal to report its background color
+const BACKGROUND_QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
 /// Returns a list of named colors, sorted by the perceived distance to the given color
 pub fn similar_colors(color: &Color) -> Vec<&NamedColor> {
     let mut colors: Vec<&NamedColor> = NAMED_COLORS.iter().collect();
@@ -8,3 +16,29 @@ pub fn similar_colors(color: &Color) -> Vec<&NamedColor> {
     colors.dedup_by(|n1, n2| n1.color == n2.color);
     colors
 }
+
+/// Fill in a hyperlink URL template, where `{hex}` is replaced by the hex code of the color
+/// (without the leading '#')
+pub fn color_url(template: &str, color: &Color) -> String {
+    template.replace("{hex}", &color.to_rgb_hex_string(false))
+}
+
+/// The background color of the terminal: the color in `PASTEL_TERMINAL_BACKGROUND`, or the
+/// answer of the terminal to an OSC 11 query.
+pub fn terminal_background() -> Option<Color> {
+    match std::env::var("PASTEL_TERMINAL_BACKGROUND") {
+        Ok(color) => parse_color(&color),
+        Err(_) => terminal::query_background_color(BACKGROUND_QUERY_TIMEOUT),
+    }
+}
+
+/// The two shades of the checkerboard that makes transparency visible: light grays on dark (or
+/// unknown) terminal backgrounds, darker grays on light backgrounds.
+pub fn checkerboard_colors(background: Option<&Color>) -> (Color, Color) {
+    match background {
+        Some(background) if background.text_color() == Color::black() => {
+            (Color::graytone(0.55), Color::graytone(0.35))
+        }
+        _ => (Color::graytone(0.94), Color::graytone(0.71)),
+    }
+}
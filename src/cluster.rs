@@ -0,0 +1,207 @@
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::prelude::*;
+
+use crate::distinct::DistanceMetric;
+use crate::{Color, Lab};
+
+type Scalar = f64;
+
+/// Maximum number of refinement steps before the k-means iteration is stopped, even if the
+/// assignment of colors to clusters has not yet converged.
+const MAX_ITERATIONS: usize = 100;
+
+/// A group of colors, as computed by `kmeans`.
+#[derive(Debug, Clone)]
+pub struct Cluster {
+    /// The mean of all member colors (computed in the Lab color space)
+    pub centroid: Color,
+
+    /// The colors that belong to this cluster, in the order in which they appeared in the input
+    pub colors: Vec<Color>,
+}
+
+fn mean_lab(labs: &[&Lab]) -> Lab {
+    let n = labs.len() as Scalar;
+    Lab {
+        l: labs.iter().map(|lab| lab.l).sum::<Scalar>() / n,
+        a: labs.iter().map(|lab| lab.a).sum::<Scalar>() / n,
+        b: labs.iter().map(|lab| lab.b).sum::<Scalar>() / n,
+        alpha: labs.iter().map(|lab| lab.alpha).sum::<Scalar>() / n,
+    }
+}
+
+fn distance(metric: DistanceMetric, a: &Lab, b: &Lab) -> Scalar {
+    metric.lab_distance(a, b)
+}
+
+fn closest_centroid(lab: &Lab, centroids: &[Lab], metric: DistanceMetric) -> usize {
+    centroids
+        .iter()
+        .enumerate()
+        .min_by(|(_, c1), (_, c2)| {
+            distance(metric, lab, c1)
+                .partial_cmp(&distance(metric, lab, c2))
+                .expect("distances are not NaN")
+        })
+        .map(|(i, _)| i)
+        .expect("list of centroids can not be empty")
+}
+
+/// Choose `k` initial centroids according to the k-means++ scheme: every new centroid is drawn
+/// with a probability that is proportional to its squared distance to the closest centroid that
+/// has already been chosen.
+///
+/// See: <https://en.wikipedia.org/wiki/K-means%2B%2B>
+fn initial_centroids<R: Rng>(
+    labs: &[Lab],
+    k: usize,
+    metric: DistanceMetric,
+    rng: &mut R,
+) -> Vec<Lab> {
+    let mut centroids = vec![labs[rng.gen_range(0..labs.len())].clone()];
+
+    while centroids.len() < k {
+        let weights: Vec<Scalar> = labs
+            .iter()
+            .map(|lab| {
+                let closest = &centroids[closest_centroid(lab, &centroids, metric)];
+                distance(metric, lab, closest).powi(2)
+            })
+            .collect();
+
+        let next = match WeightedIndex::new(&weights) {
+            Ok(distribution) => distribution.sample(rng),
+            // All remaining colors coincide with one of the centroids
+            Err(_) => rng.gen_range(0..labs.len()),
+        };
+        centroids.push(labs[next].clone());
+    }
+
+    centroids
+}
+
+/// Partition a list of colors into (at most) `k` groups of similar colors by running the k-means
+/// algorithm in the CIE Lab color space. Clusters that end up without any member colors are
+/// dropped. The clusters are ordered by the position of their first member in `colors`.
+///
+/// See: <https://en.wikipedia.org/wiki/K-means_clustering>
+pub fn kmeans(colors: &[Color], k: usize) -> Vec<Cluster> {
+    kmeans_with_rng(colors, k, &mut thread_rng())
+}
+
+/// Like `kmeans`, but uses the given random number generator to pick the initial centroids.
+pub fn kmeans_with_rng<R: Rng>(colors: &[Color], k: usize, rng: &mut R) -> Vec<Cluster> {
+    kmeans_with_metric(colors, k, DistanceMetric::CIE76, rng)
+}
+
+/// Like `kmeans_with_rng`, but assigns colors to the closest centroid according to the given
+/// distance metric (the centroids are still the means in the Lab color space).
+pub fn kmeans_with_metric<R: Rng>(
+    colors: &[Color],
+    k: usize,
+    metric: DistanceMetric,
+    rng: &mut R,
+) -> Vec<Cluster> {
+    assert!(k > 0);
+    assert!(k <= colors.len());
+
+    let labs: Vec<Lab> = colors.iter().map(|c| c.to_lab()).collect();
+    let mut centroids = initial_centroids(&labs, k, metric, rng);
+    let mut assignments = vec![usize::MAX; labs.len()];
+
+    for _ in 0..MAX_ITERATIONS {
+        let new_assignments: Vec<usize> = labs
+            .iter()
+            .map(|lab| closest_centroid(lab, &centroids, metric))
+            .collect();
+
+        if new_assignments == assignments {
+            break;
+        }
+        assignments = new_assignments;
+
+        for (ci, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&Lab> = labs
+                .iter()
+                .zip(&assignments)
+                .filter(|(_, &a)| a == ci)
+                .map(|(lab, _)| lab)
+                .collect();
+
+            // Empty clusters keep their previous centroid
+            if !members.is_empty() {
+                *centroid = mean_lab(&members);
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = vec![];
+    for &a in &assignments {
+        if !order.contains(&a) {
+            order.push(a);
+        }
+    }
+
+    order
+        .into_iter()
+        .map(|ci| {
+            let centroid = &centroids[ci];
+            Cluster {
+                centroid: Color::from_lab(centroid.l, centroid.a, centroid.b, centroid.alpha),
+                colors: colors
+                    .iter()
+                    .zip(&assignments)
+                    .filter(|(_, &a)| a == ci)
+                    .map(|(c, _)| c.clone())
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::kmeans_with_rng;
+    use crate::Color;
+
+    use rand::prelude::*;
+    use rand_xoshiro::Xoshiro256StarStar;
+
+    #[test]
+    fn test_kmeans_separates_groups() {
+        let colors = [
+            Color::from_rgb(250, 10, 10),
+            Color::from_rgb(10, 10, 250),
+            Color::red(),
+            Color::blue(),
+            Color::from_rgb(240, 0, 0),
+        ];
+
+        let clusters = kmeans_with_rng(&colors, 2, &mut Xoshiro256StarStar::seed_from_u64(1));
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(
+            clusters[0].colors,
+            vec![
+                Color::from_rgb(250, 10, 10),
+                Color::red(),
+                Color::from_rgb(240, 0, 0)
+            ]
+        );
+        assert_eq!(
+            clusters[1].colors,
+            vec![Color::from_rgb(10, 10, 250), Color::blue()]
+        );
+    }
+
+    #[test]
+    fn test_kmeans_identical_colors() {
+        let colors = [Color::white(), Color::white(), Color::white()];
+
+        let clusters = kmeans_with_rng(&colors, 3, &mut Xoshiro256StarStar::seed_from_u64(2));
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].centroid, Color::white());
+        assert_eq!(clusters[0].colors.len(), 3);
+    }
+}
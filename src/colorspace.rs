@@ -1,9 +1,19 @@
 use crate::helper::Fraction;
-use crate::Color;
+use crate::{Color, HueInterpolation};
 
 pub trait ColorSpace {
     fn from_color(c: &Color) -> Self;
     fn into_color(self) -> Color;
 
     fn mix(&self, other: &Self, fraction: Fraction) -> Self;
+
+    /// Like `mix`, but with the given interpolation method for the hue. This only makes a
+    /// difference for cylindrical color spaces; the default implementation ignores the method.
+    fn mix_with_hue(&self, other: &Self, fraction: Fraction, hue: HueInterpolation) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = hue;
+        self.mix(other, fraction)
+    }
 }
@@ -0,0 +1,280 @@
+use std::str::FromStr;
+
+use crate::helper::{linear_to_srgb, srgb_to_linear};
+use crate::types::Scalar;
+use crate::{Color, Error};
+
+/// A tone curve that maps input values between 0.0 and 1.0 to output values, defined by a set of
+/// control points. Between the control points, the curve is interpolated with a monotone cubic
+/// spline (Fritsch-Carlson method), such that the curve does not overshoot if the control points
+/// are monotonic. Outside of the range of control points, the curve is constant.
+///
+/// See: <https://en.wikipedia.org/wiki/Monotone_cubic_interpolation>
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToneCurve {
+    points: Vec<(Scalar, Scalar)>,
+    tangents: Vec<Scalar>,
+}
+
+impl ToneCurve {
+    /// Create a tone curve from a list of `(input, output)` control points. At least two points
+    /// are required, all values must be between 0.0 and 1.0 and the input values must be strictly
+    /// increasing.
+    pub fn new(points: Vec<(Scalar, Scalar)>) -> Result<ToneCurve, Error> {
+        if points.len() < 2 {
+            return Err(Error::InvalidCurve(
+                "a tone curve needs at least two control points".into(),
+            ));
+        }
+        if points
+            .iter()
+            .any(|&(x, y)| !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y))
+        {
+            return Err(Error::InvalidCurve(
+                "the control points of a tone curve must be between 0 and 1".into(),
+            ));
+        }
+        if points.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err(Error::InvalidCurve(
+                "the input values of a tone curve must be strictly increasing".into(),
+            ));
+        }
+
+        let n = points.len();
+        let secants: Vec<Scalar> = points
+            .windows(2)
+            .map(|w| (w[1].1 - w[0].1) / (w[1].0 - w[0].0))
+            .collect();
+
+        let mut tangents = vec![0.0; n];
+        tangents[0] = secants[0];
+        tangents[n - 1] = secants[n - 2];
+        for k in 1..n - 1 {
+            tangents[k] = if secants[k - 1] * secants[k] > 0.0 {
+                (secants[k - 1] + secants[k]) / 2.0
+            } else {
+                0.0
+            };
+        }
+
+        for k in 0..n - 1 {
+            if secants[k] == 0.0 {
+                tangents[k] = 0.0;
+                tangents[k + 1] = 0.0;
+            } else {
+                let a = tangents[k] / secants[k];
+                let b = tangents[k + 1] / secants[k];
+                let norm = a * a + b * b;
+                if norm > 9.0 {
+                    let t = 3.0 / norm.sqrt();
+                    tangents[k] = t * a * secants[k];
+                    tangents[k + 1] = t * b * secants[k];
+                }
+            }
+        }
+
+        Ok(ToneCurve { points, tangents })
+    }
+
+    /// The identity curve that maps every value to itself.
+    pub fn identity() -> ToneCurve {
+        ToneCurve::new(vec![(0.0, 0.0), (1.0, 1.0)]).expect("valid control points")
+    }
+
+    /// Evaluate the tone curve at the given position.
+    pub fn eval(&self, x: Scalar) -> Scalar {
+        let first = self.points[0];
+        let last = self.points[self.points.len() - 1];
+        if x <= first.0 {
+            return first.1;
+        }
+        if x >= last.0 {
+            return last.1;
+        }
+
+        let k = self
+            .points
+            .windows(2)
+            .position(|w| x < w[1].0)
+            .expect("x lies within the control points");
+
+        let (x0, y0) = self.points[k];
+        let (x1, y1) = self.points[k + 1];
+        let h = x1 - x0;
+        let t = (x - x0) / h;
+
+        let h00 = 2.0 * t.powi(3) - 3.0 * t.powi(2) + 1.0;
+        let h10 = t.powi(3) - 2.0 * t.powi(2) + t;
+        let h01 = -2.0 * t.powi(3) + 3.0 * t.powi(2);
+        let h11 = t.powi(3) - t.powi(2);
+
+        h00 * y0 + h10 * h * self.tangents[k] + h01 * y1 + h11 * h * self.tangents[k + 1]
+    }
+}
+
+impl FromStr for ToneCurve {
+    type Err = Error;
+
+    /// Parse a tone curve from a list of control points, e.g. `0,0;0.5,0.6;1,1`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| Error::InvalidCurve(reason.into());
+        let points = s
+            .split(';')
+            .map(|point| {
+                let (x, y) = point
+                    .split_once(',')
+                    .ok_or_else(|| invalid("control points must be given as 'x,y'"))?;
+                let x = x
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid("invalid number in tone curve"))?;
+                let y = y
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid("invalid number in tone curve"))?;
+                Ok((x, y))
+            })
+            .collect::<Result<Vec<_>, Self::Err>>()?;
+
+        ToneCurve::new(points)
+    }
+}
+
+/// The space in which tone curves are applied to the RGB channels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurveSpace {
+    /// Gamma-encoded sRGB values (like in most image editors)
+    Gamma,
+    /// Linear light intensities
+    Linear,
+}
+
+/// A set of tone curves for the red, green and blue channel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelCurves {
+    pub red: ToneCurve,
+    pub green: ToneCurve,
+    pub blue: ToneCurve,
+}
+
+impl Default for ChannelCurves {
+    fn default() -> Self {
+        ChannelCurves {
+            red: ToneCurve::identity(),
+            green: ToneCurve::identity(),
+            blue: ToneCurve::identity(),
+        }
+    }
+}
+
+impl ChannelCurves {
+    /// Set the tone curve for one or more channels from a specification like
+    /// `r:0,0;0.5,0.6;1,1`. The channel prefix can be `r`, `g`, `b` or any combination of these
+    /// letters (e.g. `rgb` to apply the same curve to all channels).
+    pub fn add_spec(&mut self, spec: &str) -> Result<&mut Self, Error> {
+        let (channels, curve) = spec.split_once(':').ok_or_else(|| {
+            Error::InvalidCurve(
+                "curve specifications must start with a channel prefix, e.g. 'r:'".into(),
+            )
+        })?;
+
+        let channels = channels.trim().to_lowercase();
+        if channels.is_empty() || !channels.chars().all(|c| "rgb".contains(c)) {
+            return Err(Error::InvalidCurve(
+                "unknown channel in curve specification (use 'r', 'g' and/or 'b')".into(),
+            ));
+        }
+
+        let curve: ToneCurve = curve.parse()?;
+
+        for channel in channels.chars() {
+            match channel {
+                'r' => self.red = curve.clone(),
+                'g' => self.green = curve.clone(),
+                'b' => self.blue = curve.clone(),
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Apply the tone curves to the RGB channels of a color. The alpha channel is not modified.
+    pub fn apply(&self, color: &Color, space: CurveSpace) -> Color {
+        let rgba = color.to_rgba_float();
+
+        let map = |curve: &ToneCurve, c: Scalar| match space {
+            CurveSpace::Gamma => curve.eval(c),
+            CurveSpace::Linear => linear_to_srgb(curve.eval(srgb_to_linear(c)).max(0.0)),
+        };
+
+        Color::from_rgba_float(
+            map(&self.red, rgba.r),
+            map(&self.green, rgba.g),
+            map(&self.blue, rgba.b),
+            rgba.alpha,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn tone_curve_interpolates_control_points() {
+        let curve: ToneCurve = "0,0;0.5,0.6;1,1".parse().unwrap();
+
+        assert_relative_eq!(0.0, curve.eval(0.0));
+        assert_relative_eq!(0.6, curve.eval(0.5));
+        assert_relative_eq!(1.0, curve.eval(1.0));
+        assert_relative_eq!(1.0, curve.eval(1.5));
+
+        let identity = ToneCurve::identity();
+        assert_relative_eq!(0.3, identity.eval(0.3));
+    }
+
+    #[test]
+    fn tone_curve_is_monotonic() {
+        let curve: ToneCurve = "0,0;0.1,0.5;0.2,0.55;1,1".parse().unwrap();
+
+        let mut previous = 0.0;
+        for i in 0..=100 {
+            let y = curve.eval(i as Scalar / 100.0);
+            assert!(y >= previous);
+            previous = y;
+        }
+    }
+
+    #[test]
+    fn tone_curve_parse_errors() {
+        assert!("0,0".parse::<ToneCurve>().is_err());
+        assert!("0,0;0,1".parse::<ToneCurve>().is_err());
+        assert!("0,0;x,1".parse::<ToneCurve>().is_err());
+        assert!("0;1".parse::<ToneCurve>().is_err());
+        assert!("0,0;1,NaN".parse::<ToneCurve>().is_err());
+        assert!("0,0;1,inf".parse::<ToneCurve>().is_err());
+        assert!("0,0;1,5".parse::<ToneCurve>().is_err());
+        assert!("-0.5,0;1,1".parse::<ToneCurve>().is_err());
+    }
+
+    #[test]
+    fn channel_curves() {
+        let mut curves = ChannelCurves::default();
+        curves.add_spec("r:0,1;1,1").unwrap();
+        curves.add_spec("gb:0,0;1,0").unwrap();
+
+        assert_eq!(
+            Color::red(),
+            curves.apply(&Color::from_rgb(10, 200, 30), CurveSpace::Gamma)
+        );
+        assert_eq!(
+            Color::red(),
+            curves.apply(&Color::from_rgb(10, 200, 30), CurveSpace::Linear)
+        );
+
+        assert!(curves.add_spec("0,0;1,1").is_err());
+        assert!(curves.add_spec("x:0,0;1,1").is_err());
+    }
+}
@@ -27,11 +27,65 @@ use std::f64;
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+/// A color difference, broken down into its lightness, chroma and hue terms (from the first to
+/// the second color). For CIE76, these are ΔL*, ΔC* and ΔH* with ΔE² = ΔL*² + ΔC*² + ΔH*² (ΔH* is
+/// not signed). For CIEDE2000, the terms are weighted (ΔL'/S_L, ΔC'/S_C and ΔH'/S_H), and an
+/// additional interaction term between chroma and hue contributes for blue colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeltaE {
+    pub total: f64,
+    pub lightness: f64,
+    pub chroma: f64,
+    pub hue: f64,
+}
+
+impl DeltaE {
+    /// The name of the term with the largest magnitude (`lightness`, `chroma` or `hue`).
+    pub fn dominant_term(&self) -> &'static str {
+        let terms = [
+            ("lightness", self.lightness),
+            ("chroma", self.chroma),
+            ("hue", self.hue),
+        ];
+        terms
+            .iter()
+            .max_by(|(_, t1), (_, t2)| t1.abs().total_cmp(&t2.abs()))
+            .map(|(name, _)| *name)
+            .expect("non-empty list of terms")
+    }
+}
+
+/// Euclidean distance of two colors in a Lab-like color space (given by their L, a and b
+/// coordinates), broken down into lightness, chroma and hue differences.
+pub fn euclidean_components(c1: [f64; 3], c2: [f64; 3]) -> DeltaE {
+    let [l1, a1, b1] = c1;
+    let [l2, a2, b2] = c2;
+
+    let delta_l = l2 - l1;
+    let delta_c = b2.hypot(a2) - b1.hypot(a1);
+    let delta_ab_squared = (a2 - a1).powi(2) + (b2 - b1).powi(2);
+
+    DeltaE {
+        total: (delta_l.powi(2) + delta_ab_squared).sqrt(),
+        lightness: delta_l,
+        chroma: delta_c,
+        hue: (delta_ab_squared - delta_c.powi(2)).max(0.0).sqrt(),
+    }
+}
+
 pub fn cie76(c1: &Lab, c2: &Lab) -> f64 {
     ((c1.l - c2.l).powi(2) + (c1.a - c2.a).powi(2) + (c1.b - c2.b).powi(2)).sqrt()
 }
 
+pub fn cie76_components(c1: &Lab, c2: &Lab) -> DeltaE {
+    euclidean_components([c1.l, c1.a, c1.b], [c2.l, c2.a, c2.b])
+}
+
 pub fn ciede2000(color1: &Lab, color2: &Lab) -> f64 {
+    ciede2000_components(color1, color2).total
+}
+
+pub fn ciede2000_components(color1: &Lab, color2: &Lab) -> DeltaE {
     let ksub_l = 1.0;
     let ksub_c = 1.0;
     let ksub_h = 1.0;
@@ -83,7 +137,12 @@ pub fn ciede2000(color1: &Lab, color2: &Lab) -> f64 {
 
     let hue: f64 = delta_upcase_h_prime / (ksub_h * s_sub_upcase_h);
 
-    (lightness.powi(2) + chroma.powi(2) + hue.powi(2) + r_sub_t * chroma * hue).sqrt()
+    DeltaE {
+        total: (lightness.powi(2) + chroma.powi(2) + hue.powi(2) + r_sub_t * chroma * hue).sqrt(),
+        lightness,
+        chroma,
+        hue,
+    }
 }
 
 fn get_h_prime_fn(x: f64, y: f64) -> f64 {
@@ -147,7 +206,7 @@ fn degrees_to_radians(degrees: f64) -> f64 {
 
 #[cfg(test)]
 mod tests {
-    use super::{ciede2000, Lab};
+    use super::{cie76, cie76_components, ciede2000, ciede2000_components, Lab};
 
     fn round(val: f64) -> f64 {
         let rounded = val * 10000_f64;
@@ -350,4 +409,34 @@ mod tests {
             &[0.9033, -0.0636, -0.5514],
         );
     }
+
+    #[test]
+    fn components() {
+        let lab = |l, a, b| Lab {
+            l,
+            a,
+            b,
+            alpha: 1.0,
+        };
+        let c1 = lab(50.0, 20.0, 0.0);
+        let c2 = lab(60.0, 0.0, 30.0);
+
+        let delta = cie76_components(&c1, &c2);
+        assert_eq!(cie76(&c1, &c2), delta.total);
+        assert_eq!(10.0, delta.lightness);
+        assert_eq!(10.0, delta.chroma);
+        assert_eq!(
+            round(delta.total.powi(2)),
+            round(delta.lightness.powi(2) + delta.chroma.powi(2) + delta.hue.powi(2))
+        );
+        assert_eq!("hue", delta.dominant_term());
+
+        let delta = ciede2000_components(&lab(50.0, 0.0, 0.0), &lab(70.0, 0.0, 0.0));
+        assert_eq!(
+            ciede2000(&lab(50.0, 0.0, 0.0), &lab(70.0, 0.0, 0.0)),
+            delta.total
+        );
+        assert_eq!(0.0, delta.chroma);
+        assert_eq!("lightness", delta.dominant_term());
+    }
 }
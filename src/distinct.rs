@@ -1,8 +1,13 @@
+use std::time::{Duration, Instant};
+
 use rand::prelude::*;
+use rand::rngs::StdRng;
+use rand_chacha::ChaCha12Rng;
 
 use crate::delta_e;
+use crate::parser::parse_color;
 use crate::random::{self, RandomizationStrategy};
-use crate::{Color, Lab};
+use crate::{Color, Error, LCh, Lab};
 
 type Scalar = f64;
 
@@ -25,15 +30,111 @@ pub struct DistanceResult {
     /// The number of colors that are fixed and cannot be changed. The actual colors are the first
     /// `num_fixed_colors` elements in the `colors` array.
     pub num_fixed_colors: usize,
+
+    /// The total penalty of the soft constraints (see [`PaletteConstraints`])
+    pub penalty: Scalar,
+
+    /// The scaled OkLab coordinates of all colors (only for [`DistanceMetric::OkLab`])
+    oklab_values: Vec<Lab>,
 }
 
-pub struct IterationStatistics<'a> {
+pub struct IterationStatistics<'a, E = DistanceResult> {
     pub iteration: usize,
     pub temperature: Scalar,
-    pub distance_result: &'a DistanceResult,
+    pub evaluation: &'a E,
     pub colors: Vec<Color>,
 }
 
+/// An objective function for [`SimulatedAnnealing`]. The optimizer tries to find a set of colors
+/// with a maximal score.
+pub trait Objective {
+    /// The result of evaluating the objective for a set of colors. It can hold intermediate
+    /// results to speed up the evaluation after a single color has changed.
+    type Evaluation: Clone;
+
+    fn evaluate(&self, lab_values: &[Lab]) -> Self::Evaluation;
+
+    /// Evaluate the objective after the color with index `changed_color` has been modified. The
+    /// default implementation evaluates the objective from scratch.
+    fn update(
+        &self,
+        previous: &Self::Evaluation,
+        lab_values: &[Lab],
+        changed_color: usize,
+    ) -> Self::Evaluation {
+        let _ = (previous, changed_color);
+        self.evaluate(lab_values)
+    }
+
+    fn score(&self, evaluation: &Self::Evaluation) -> Scalar;
+
+    /// Indices of the colors that currently limit the score (e.g. the closest pair of colors).
+    /// The optimizer modifies one of these colors (unless they are fixed) instead of a random
+    /// one. The default implementation returns an empty list.
+    fn critical_colors(&self, evaluation: &Self::Evaluation) -> Vec<usize> {
+        let _ = evaluation;
+        vec![]
+    }
+}
+
+/// The objective of [`distinct_colors`]: maximize the mean or minimal distance between each color
+/// and its nearest neighbor, minus the penalties for the soft constraints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceObjective {
+    pub target: OptimizationTarget,
+    pub distance_metric: DistanceMetric,
+    /// The number of colors at the start of the set that cannot be changed
+    pub num_fixed_colors: usize,
+    pub constraints: PaletteConstraints,
+}
+
+impl Objective for DistanceObjective {
+    type Evaluation = DistanceResult;
+
+    fn evaluate(&self, lab_values: &[Lab]) -> DistanceResult {
+        let mut result =
+            DistanceResult::new(lab_values, self.distance_metric, self.num_fixed_colors);
+        result.penalty = self.penalty(lab_values);
+        result
+    }
+
+    fn update(
+        &self,
+        previous: &DistanceResult,
+        lab_values: &[Lab],
+        changed_color: usize,
+    ) -> DistanceResult {
+        let mut result = previous.update(lab_values, changed_color);
+        result.penalty = self.penalty(lab_values);
+        result
+    }
+
+    fn score(&self, evaluation: &DistanceResult) -> Scalar {
+        let distance = match self.target {
+            OptimizationTarget::Mean => evaluation.mean_closest_distance,
+            OptimizationTarget::Min => evaluation.min_closest_distance,
+        };
+        distance - evaluation.penalty
+    }
+
+    fn critical_colors(&self, evaluation: &DistanceResult) -> Vec<usize> {
+        match self.target {
+            OptimizationTarget::Mean => vec![],
+            OptimizationTarget::Min => vec![evaluation.closest_pair.0, evaluation.closest_pair.1],
+        }
+    }
+}
+
+impl DistanceObjective {
+    fn penalty(&self, lab_values: &[Lab]) -> Scalar {
+        if self.constraints.is_active() {
+            self.constraints.penalty(lab_values, self.distance_metric)
+        } else {
+            0.0
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OptimizationTarget {
     Mean,
@@ -47,37 +148,470 @@ pub enum OptimizationMode {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum DistanceMetric {
     CIE76,
     CIEDE2000,
+    /// Euclidean distance in the OkLab color space, scaled by a factor of 100 to be comparable to
+    /// the CIE delta-E values
+    OkLab,
+}
+
+impl DistanceMetric {
+    /// The distance between two colors.
+    pub fn distance(self, c1: &Color, c2: &Color) -> Scalar {
+        match self {
+            DistanceMetric::CIE76 => c1.distance_delta_e_cie76(c2),
+            DistanceMetric::CIEDE2000 => c1.distance_delta_e_ciede2000(c2),
+            DistanceMetric::OkLab => OKLAB_SCALE * c1.distance_oklab(c2),
+        }
+    }
+
+    /// The distance between two colors, given by their CIELab coordinates.
+    pub(crate) fn lab_distance(self, a: &Lab, b: &Lab) -> Scalar {
+        match self {
+            DistanceMetric::CIE76 => delta_e::cie76(a, b),
+            DistanceMetric::CIEDE2000 => delta_e::ciede2000(a, b),
+            DistanceMetric::OkLab => delta_e::cie76(&scaled_oklab(a), &scaled_oklab(b)),
+        }
+    }
+}
+
+/// The factor by which OkLab distances are scaled for [`DistanceMetric::OkLab`]. Divide by it to
+/// get the plain Euclidean distance (ΔE OK, see [`Color::distance_oklab`]).
+pub const OKLAB_SCALE: Scalar = 100.0;
+
+/// The (scaled) OkLab coordinates of a color, stored in a `Lab` struct such that the distance can
+/// be computed with [`delta_e::cie76`].
+fn scaled_oklab(lab: &Lab) -> Lab {
+    let oklab = Color::from(lab).to_oklab();
+    Lab {
+        l: OKLAB_SCALE * oklab.l,
+        a: OKLAB_SCALE * oklab.a,
+        b: OKLAB_SCALE * oklab.b,
+        alpha: oklab.alpha,
+    }
 }
 
 pub struct SimulationParameters {
     pub initial_temperature: Scalar,
     pub cooling_rate: Scalar,
     pub num_iterations: usize,
-    pub opt_target: OptimizationTarget,
     pub opt_mode: OptimizationMode,
-    pub distance_metric: DistanceMetric,
+    /// The number of colors at the start of the set that cannot be changed
     pub num_fixed_colors: usize,
+    /// Stop the simulation early (with the best result so far) once this point in time is reached
+    pub deadline: Option<Instant>,
+    /// Stop the simulation early once the score has not improved for this many iterations
+    pub patience: Option<usize>,
+    /// The region of the color space in which new colors are searched
+    pub region: ColorRegion,
+}
+
+/// A hard constraint for the (CIE LCh) lightness and chroma of the colors that are explored by
+/// the optimizer, and optionally for their contrast against a background color. Fixed colors are
+/// not affected. Swapped bounds are put in order, and NaN bounds are treated as unbounded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorRegion {
+    /// Minimum and maximum lightness (0 to 100)
+    pub lightness: (Scalar, Scalar),
+    /// Minimum and maximum chroma
+    pub chroma: (Scalar, Scalar),
+    /// Background color and the minimum (WCAG) contrast ratio against it
+    pub min_contrast: Option<(Color, Scalar)>,
 }
 
-pub struct SimulatedAnnealing<R: Rng> {
+impl Default for ColorRegion {
+    fn default() -> Self {
+        ColorRegion {
+            lightness: (0.0, 100.0),
+            chroma: (0.0, Scalar::INFINITY),
+            min_contrast: None,
+        }
+    }
+}
+
+/// Round a color to 8-bit RGB values, as used for the output.
+fn quantize(color: &Color) -> Color {
+    let rgba = color.to_rgba();
+    Color::from_rgba(rgba.r, rgba.g, rgba.b, rgba.alpha)
+}
+
+/// The CIE lightness L* of a color with the given relative luminance.
+fn lightness_from_luminance(y: Scalar) -> Scalar {
+    const CUT: Scalar = 216.0 / 24389.0;
+    if y > CUT {
+        116.0 * y.cbrt() - 16.0
+    } else {
+        24389.0 / 27.0 * y
+    }
+}
+
+impl ColorRegion {
+    /// An upper bound for the chroma of colors in the sRGB gamut
+    const MAX_SRGB_CHROMA: Scalar = 135.0;
+
+    pub fn is_bounded(&self) -> bool {
+        *self != ColorRegion::default()
+    }
+
+    /// The lightness bounds within 0 to 100, in increasing order. Invalid (NaN) bounds are
+    /// replaced by the limits of the full range.
+    fn lightness_bounds(&self) -> (Scalar, Scalar) {
+        Self::normalize(self.lightness, (0.0, 100.0))
+    }
+
+    /// The chroma bounds, see `lightness_bounds`.
+    fn chroma_bounds(&self) -> (Scalar, Scalar) {
+        Self::normalize(self.chroma, (0.0, Scalar::INFINITY))
+    }
+
+    fn normalize(bounds: (Scalar, Scalar), limits: (Scalar, Scalar)) -> (Scalar, Scalar) {
+        let bound = |value: Scalar, default: Scalar| {
+            if value.is_nan() {
+                default
+            } else {
+                value.max(limits.0).min(limits.1)
+            }
+        };
+        let (min, max) = (bound(bounds.0, limits.0), bound(bounds.1, limits.1));
+        (min.min(max), min.max(max))
+    }
+
+    /// The ranges of allowed lightness values. With a contrast constraint, there can be one
+    /// range below and one above the lightness of the background. If the list is empty, no
+    /// color satisfies the constraints.
+    pub fn lightness_ranges(&self) -> Vec<(Scalar, Scalar)> {
+        let (min, max) = self.lightness_bounds();
+        let ranges = match &self.min_contrast {
+            None => vec![(min, max)],
+            Some((background, ratio)) => {
+                let luminance = background.luminance() + 0.05;
+                let darker = luminance / ratio - 0.05;
+                let lighter = luminance * ratio - 0.05;
+
+                let mut ranges = vec![];
+                if darker >= 0.0 {
+                    ranges.push((min, max.min(lightness_from_luminance(darker))));
+                }
+                if lighter <= 1.0 {
+                    ranges.push((min.max(lightness_from_luminance(lighter)), max));
+                }
+                ranges
+            }
+        };
+        ranges.into_iter().filter(|(a, b)| a <= b).collect()
+    }
+
+    fn has_contrast(&self, color: &Color) -> bool {
+        match &self.min_contrast {
+            Some((background, ratio)) => color.contrast_ratio(background) >= *ratio,
+            None => true,
+        }
+    }
+
+    /// Move a color into the region by clamping its lightness and chroma (the hue is kept). If
+    /// the minimum chroma can not be reached within the sRGB gamut, the most saturated color
+    /// with that lightness and hue is returned.
+    pub fn project(&self, color: &Color) -> Color {
+        let lch = color.to_lch();
+
+        // Move the lightness into the closest of the allowed ranges
+        let l = self
+            .lightness_ranges()
+            .into_iter()
+            .map(|(min, max)| lch.l.clamp(min, max))
+            .min_by(|a, b| (a - lch.l).abs().total_cmp(&(b - lch.l).abs()))
+            .unwrap_or(lch.l);
+
+        let (min_chroma, max_chroma) = self.chroma_bounds();
+        let mut projected = LCh {
+            l,
+            c: lch.c.clamp(min_chroma, max_chroma),
+            ..lch
+        };
+        if !projected.is_in_gamut() {
+            projected.c = projected.c.min(LCh::max_chroma(projected.l, projected.h));
+        }
+        let mut result = quantize(&Color::from(&projected));
+
+        // Rounding to 8-bit values can reduce the contrast slightly. Move further away from the
+        // background until the contrast is reached.
+        if let Some((background, _)) = &self.min_contrast {
+            let step = if result.luminance() < background.luminance() {
+                -0.1
+            } else {
+                0.1
+            };
+            while !self.has_contrast(&result) && (0.0..=100.0).contains(&projected.l) {
+                projected.l += step;
+                projected.c = projected.c.min(LCh::max_chroma(projected.l, projected.h));
+                result = quantize(&Color::from(&projected));
+            }
+        }
+
+        result
+    }
+
+    /// Draw a random (in-gamut) color from the region.
+    pub fn sample(&self, rng: &mut dyn RngCore) -> Color {
+        let ranges = self.lightness_ranges();
+        let total: Scalar = ranges.iter().map(|(min, max)| max - min).sum();
+        let random_lightness = |rng: &mut dyn RngCore| {
+            let mut offset = rng.gen_range(0.0..=total);
+            for &(min, max) in &ranges {
+                if offset <= max - min {
+                    return min + offset;
+                }
+                offset -= max - min;
+            }
+            self.lightness_bounds().0
+        };
+
+        // Rejection sampling is much faster than computing the gamut boundary for each sample
+        let (min_chroma, max_chroma) = self.chroma_bounds();
+        let max_chroma = max_chroma.min(Self::MAX_SRGB_CHROMA);
+        for _ in 0..32 {
+            let l = random_lightness(rng);
+            let h = rng.gen_range(0.0..360.0);
+            let c = rng.gen_range(min_chroma.min(max_chroma)..=max_chroma);
+            let lch = LCh {
+                l,
+                c,
+                h,
+                alpha: 1.0,
+            };
+            if lch.is_in_gamut() {
+                let color = quantize(&Color::from(&lch));
+                if self.has_contrast(&color) {
+                    return color;
+                }
+            }
+        }
+
+        let l = random_lightness(rng);
+        let h = rng.gen_range(0.0..360.0);
+        self.project(&Color::from_lch(l, min_chroma, h, 1.0))
+    }
+}
+
+/// Soft constraints for the palette, turning the pure max-distance optimization into a more
+/// general palette design. Each active constraint adds a weighted penalty term (in units that are
+/// comparable to color distances) to the objective function. The `region`, in contrast, limits
+/// the colors that are explored in the first place.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PaletteConstraints {
+    /// Target standard deviation of the (Lab) lightness values and the weight of the deviation
+    /// from that target
+    pub lightness_spread: Option<(Scalar, Scalar)>,
+    /// Weight for the root-mean-square deviation (in degrees) of the hue gaps between the
+    /// chromatic colors from an equal spacing around the hue circle
+    pub hue_uniformity: Scalar,
+    /// Anchor (brand) color and the weight for the distance between the anchor and the closest
+    /// color of the palette
+    pub anchor: Option<(Color, Scalar)>,
+    /// Lightness and chroma limits for the new colors
+    pub region: ColorRegion,
+}
+
+impl PaletteConstraints {
+    /// Colors with a lower chroma than this do not take part in the hue uniformity constraint
+    const MIN_CHROMA: Scalar = 5.0;
+
+    pub fn is_active(&self) -> bool {
+        self.lightness_spread.is_some() || self.hue_uniformity != 0.0 || self.anchor.is_some()
+    }
+
+    /// The total (weighted) penalty for the given set of colors.
+    pub fn penalty(&self, lab_values: &[Lab], metric: DistanceMetric) -> Scalar {
+        let mut penalty = 0.0;
+
+        if let Some((target, weight)) = self.lightness_spread {
+            let n = lab_values.len() as Scalar;
+            let mean = lab_values.iter().map(|lab| lab.l).sum::<Scalar>() / n;
+            let variance = lab_values
+                .iter()
+                .map(|lab| (lab.l - mean).powi(2))
+                .sum::<Scalar>()
+                / n;
+            penalty += weight * (variance.sqrt() - target).abs();
+        }
+
+        if self.hue_uniformity != 0.0 {
+            let mut hues: Vec<Scalar> = lab_values
+                .iter()
+                .filter(|lab| lab.a.hypot(lab.b) >= Self::MIN_CHROMA)
+                .map(|lab| lab.b.atan2(lab.a).to_degrees().rem_euclid(360.0))
+                .collect();
+
+            if hues.len() > 1 {
+                hues.sort_by(|a, b| a.total_cmp(b));
+
+                let ideal_gap = 360.0 / hues.len() as Scalar;
+                let wrap_gap = hues[0] + 360.0 - hues[hues.len() - 1];
+                let sum_sq = hues
+                    .windows(2)
+                    .map(|w| w[1] - w[0])
+                    .chain(std::iter::once(wrap_gap))
+                    .map(|gap| (gap - ideal_gap).powi(2))
+                    .sum::<Scalar>();
+                penalty += self.hue_uniformity * (sum_sq / hues.len() as Scalar).sqrt();
+            }
+        }
+
+        if let Some((anchor, weight)) = &self.anchor {
+            let anchor = anchor.to_lab();
+            let closest = lab_values
+                .iter()
+                .map(|lab| metric.lab_distance(lab, &anchor))
+                .fold(Scalar::MAX, Scalar::min);
+            penalty += weight * closest;
+        }
+
+        penalty
+    }
+}
+
+/// Limits for the total amount of work spent in [`distinct_colors`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptimizationBudget {
+    /// Total number of annealing iterations (split between the global and the local phase)
+    pub num_iterations: usize,
+    /// Optional wall-clock time limit for the whole optimization
+    pub max_time: Option<Duration>,
+    /// Stop each phase of the optimization once the score (e.g. the minimal distance) has not
+    /// improved for this many iterations
+    pub patience: Option<usize>,
+}
+
+impl Default for OptimizationBudget {
+    fn default() -> Self {
+        OptimizationBudget {
+            num_iterations: 300_000,
+            max_time: None,
+            patience: None,
+        }
+    }
+}
+
+/// A snapshot of the optimizer that can be written to a file and used to continue (or refine) a
+/// previous run of [`distinct_colors_with_state`] via [`resume_distinct_colors`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptimizerState {
+    /// The current set of colors, starting with the fixed colors
+    pub colors: Vec<Color>,
+    pub num_fixed_colors: usize,
+    /// The annealing temperature at the end of the run
+    pub temperature: Scalar,
+    /// Seed for the random number generator of the next run
+    pub seed: u64,
+}
+
+impl OptimizerState {
+    /// Serialize the state as a (flat) JSON object.
+    pub fn to_json(&self) -> String {
+        let colors: Vec<String> = self
+            .colors
+            .iter()
+            .map(|c| c.to_rgb_hex_string(true))
+            .collect();
+
+        let document = serde_json::json!({
+            "colors": colors,
+            "num_fixed_colors": self.num_fixed_colors,
+            "temperature": self.temperature,
+            "seed": self.seed,
+        });
+        format!("{:#}\n", document)
+    }
+
+    /// Parse a state that has been written with [`OptimizerState::to_json`].
+    pub fn from_json(source: &str) -> Result<OptimizerState, Error> {
+        let invalid = |reason: &str| Error::InvalidOptimizerState(reason.into());
+
+        let document: serde_json::Value =
+            serde_json::from_str(source).map_err(|err| invalid(&err.to_string()))?;
+        let object = document
+            .as_object()
+            .ok_or_else(|| invalid("expected a JSON object"))?;
+        if let Some(key) = object.keys().find(|key| {
+            !["colors", "num_fixed_colors", "temperature", "seed"].contains(&key.as_str())
+        }) {
+            return Err(invalid(&format!("unknown key '{}'", key)));
+        }
+
+        let colors = object
+            .get("colors")
+            .ok_or_else(|| invalid("missing 'colors'"))?
+            .as_array()
+            .ok_or_else(|| invalid("'colors' must be an array of colors"))?
+            .iter()
+            .map(|item| {
+                let item = item
+                    .as_str()
+                    .ok_or_else(|| invalid("'colors' must be an array of colors"))?;
+                parse_color(item).ok_or_else(|| Error::InvalidColor(item.into()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let num_fixed_colors = object
+            .get("num_fixed_colors")
+            .ok_or_else(|| invalid("missing 'num_fixed_colors'"))?
+            .as_u64()
+            .ok_or_else(|| invalid("invalid 'num_fixed_colors'"))?;
+        let temperature = object
+            .get("temperature")
+            .ok_or_else(|| invalid("missing 'temperature'"))?
+            .as_f64()
+            .ok_or_else(|| invalid("invalid 'temperature'"))?;
+        let seed = object
+            .get("seed")
+            .ok_or_else(|| invalid("missing 'seed'"))?
+            .as_u64()
+            .ok_or_else(|| invalid("invalid 'seed'"))?;
+
+        let state = OptimizerState {
+            colors,
+            num_fixed_colors: num_fixed_colors as usize,
+            temperature,
+            seed,
+        };
+
+        if state.colors.len() < 2 {
+            return Err(invalid("the state must contain at least two colors"));
+        }
+        if state.num_fixed_colors > state.colors.len() {
+            return Err(invalid("more fixed colors than colors"));
+        }
+        if state.temperature.is_nan() || state.temperature <= 0.0 {
+            return Err(invalid("the temperature must be positive"));
+        }
+
+        Ok(state)
+    }
+}
+
+pub struct SimulatedAnnealing<R: Rng, O: Objective = DistanceObjective> {
     colors: Vec<Color>,
     lab_values: Vec<Lab>,
     temperature: Scalar,
     pub parameters: SimulationParameters,
+    pub objective: O,
     rng: R,
 }
 
-impl SimulatedAnnealing<ThreadRng> {
-    pub fn new(initial_colors: &[Color], parameters: SimulationParameters) -> Self {
-        Self::with_rng(initial_colors, parameters, thread_rng())
+impl<O: Objective> SimulatedAnnealing<ThreadRng, O> {
+    pub fn new(initial_colors: &[Color], parameters: SimulationParameters, objective: O) -> Self {
+        Self::with_rng(initial_colors, parameters, objective, thread_rng())
     }
 }
 
-impl<R: Rng> SimulatedAnnealing<R> {
-    pub fn with_rng(initial_colors: &[Color], parameters: SimulationParameters, rng: R) -> Self {
+impl<R: Rng, O: Objective> SimulatedAnnealing<R, O> {
+    pub fn with_rng(
+        initial_colors: &[Color],
+        parameters: SimulationParameters,
+        objective: O,
+        rng: R,
+    ) -> Self {
         let lab_values = initial_colors.iter().map(|c| c.to_lab()).collect();
 
         SimulatedAnnealing {
@@ -85,16 +619,19 @@ impl<R: Rng> SimulatedAnnealing<R> {
             lab_values,
             temperature: parameters.initial_temperature,
             parameters,
+            objective,
             rng,
         }
     }
-}
 
-impl<R: Rng> SimulatedAnnealing<R> {
     pub fn get_colors(&self) -> Vec<Color> {
         self.colors.clone()
     }
 
+    pub fn temperature(&self) -> Scalar {
+        self.temperature
+    }
+
     fn modify_channel(&mut self, c: &mut u8) {
         if self.rng.gen::<bool>() {
             *c = c.saturating_add(self.rng.gen::<u8>() % 10);
@@ -113,6 +650,12 @@ impl<R: Rng> SimulatedAnnealing<R> {
                 self.modify_channel(&mut rgb.g);
                 self.modify_channel(&mut rgb.b);
                 *color = Color::from_rgb(rgb.r, rgb.g, rgb.b);
+                if self.parameters.region.is_bounded() {
+                    *color = self.parameters.region.project(color);
+                }
+            }
+            OptimizationMode::Global if self.parameters.region.is_bounded() => {
+                *color = self.parameters.region.sample(&mut self.rng);
             }
             OptimizationMode::Global => {
                 *color = strategy.generate_with(&mut self.rng);
@@ -121,37 +664,41 @@ impl<R: Rng> SimulatedAnnealing<R> {
         *lab = color.to_lab();
     }
 
-    pub fn run(&mut self, callback: &mut dyn FnMut(&IterationStatistics)) -> DistanceResult {
+    /// Pick the index of the color that is modified next: one of the (non-fixed) critical colors
+    /// of the objective, or a random non-fixed color.
+    fn choose_index(&mut self, evaluation: &O::Evaluation) -> usize {
+        let num_fixed_colors = self.parameters.num_fixed_colors;
+
+        let candidates: Vec<usize> = self
+            .objective
+            .critical_colors(evaluation)
+            .into_iter()
+            .filter(|&i| i >= num_fixed_colors && i < self.colors.len())
+            .collect();
+
+        match candidates.choose(&mut self.rng) {
+            Some(&index) => index,
+            None => self.rng.gen_range(num_fixed_colors..self.colors.len()),
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        callback: &mut dyn FnMut(&IterationStatistics<O::Evaluation>),
+    ) -> O::Evaluation {
         self.temperature = self.parameters.initial_temperature;
 
-        let mut result = DistanceResult::new(
-            &self.lab_values,
-            self.parameters.distance_metric,
-            self.parameters.num_fixed_colors,
-        );
+        let mut result = self.objective.evaluate(&self.lab_values);
 
         if self.parameters.num_fixed_colors == self.colors.len() {
             return result;
         }
 
+        let mut best_score = self.objective.score(&result);
+        let mut last_improvement = 0;
+
         for iter in 0..self.parameters.num_iterations {
-            let random_index = if self.parameters.opt_target == OptimizationTarget::Mean {
-                self.rng
-                    .gen_range(self.parameters.num_fixed_colors..self.colors.len())
-            } else {
-                // first check if any of the colors cannot change, if that's the case just return
-                // the other color. Note that the closest_pair cannot contain only fixed colors.
-                #[allow(clippy::if_same_then_else)]
-                if result.closest_pair.0 < self.parameters.num_fixed_colors {
-                    result.closest_pair.1
-                } else if result.closest_pair.1 < self.parameters.num_fixed_colors {
-                    result.closest_pair.0
-                } else if self.rng.gen() {
-                    result.closest_pair.0
-                } else {
-                    result.closest_pair.1
-                }
-            };
+            let random_index = self.choose_index(&result);
 
             debug_assert!(
                 random_index >= self.parameters.num_fixed_colors,
@@ -164,17 +711,12 @@ impl<R: Rng> SimulatedAnnealing<R> {
 
             self.modify_color_and_lab(&mut new_colors, &mut new_lab_values[random_index]);
 
-            let new_result = result.update(&new_lab_values, random_index);
+            let new_result = self
+                .objective
+                .update(&result, &new_lab_values, random_index);
 
-            let (score, new_score) = match self.parameters.opt_target {
-                OptimizationTarget::Mean => (
-                    result.mean_closest_distance,
-                    new_result.mean_closest_distance,
-                ),
-                OptimizationTarget::Min => {
-                    (result.min_closest_distance, new_result.min_closest_distance)
-                }
-            };
+            let score = self.objective.score(&result);
+            let new_score = self.objective.score(&new_result);
 
             if new_score > score {
                 result = new_result;
@@ -189,11 +731,23 @@ impl<R: Rng> SimulatedAnnealing<R> {
                 }
             }
 
+            let current_score = self.objective.score(&result);
+            if current_score > best_score {
+                best_score = current_score;
+                last_improvement = iter;
+            } else if self
+                .parameters
+                .patience
+                .is_some_and(|patience| iter - last_improvement >= patience)
+            {
+                break;
+            }
+
             if iter % 5_000 == 0 {
                 let statistics = IterationStatistics {
                     iteration: iter,
                     temperature: self.temperature,
-                    distance_result: &result,
+                    evaluation: &result,
                     colors: self.colors.clone(),
                 };
                 callback(&statistics);
@@ -201,6 +755,14 @@ impl<R: Rng> SimulatedAnnealing<R> {
 
             if iter % 1_000 == 0 {
                 self.temperature *= self.parameters.cooling_rate;
+
+                if self
+                    .parameters
+                    .deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline)
+                {
+                    break;
+                }
             }
         }
 
@@ -216,10 +778,7 @@ impl<R: Rng> SimulatedAnnealing<R> {
 ///
 /// See: <https://en.wikipedia.org/wiki/Farthest-first_traversal>
 pub fn rearrange_sequence(colors: &mut [Color], metric: DistanceMetric) {
-    let distance = |c1: &Color, c2: &Color| match metric {
-        DistanceMetric::CIE76 => c1.distance_delta_e_cie76(c2),
-        DistanceMetric::CIEDE2000 => c1.distance_delta_e_ciede2000(c2),
-    };
+    let distance = |c1: &Color, c2: &Color| metric.distance(c1, c2);
 
     // vector where the i-th element contains the minimum distance to the colors from 0 to i-1.
     let mut min_distances = vec![i32::MAX; colors.len()];
@@ -247,42 +806,172 @@ pub fn distinct_colors(
     count: usize,
     distance_metric: DistanceMetric,
     fixed_colors: Vec<Color>,
+    budget: OptimizationBudget,
+    callback: &mut dyn FnMut(&IterationStatistics),
+) -> (Vec<Color>, DistanceResult) {
+    distinct_colors_with_rng(
+        count,
+        distance_metric,
+        fixed_colors,
+        budget,
+        callback,
+        &mut StdRng::from_entropy(),
+    )
+}
+
+/// Like [`distinct_colors`], but uses the given random number generator for the initial colors
+/// and the optimization. With a seeded generator (and without a time limit in the budget), the
+/// result is reproducible.
+pub fn distinct_colors_with_rng<R: Rng>(
+    count: usize,
+    distance_metric: DistanceMetric,
+    fixed_colors: Vec<Color>,
+    budget: OptimizationBudget,
     callback: &mut dyn FnMut(&IterationStatistics),
+    rng: &mut R,
 ) -> (Vec<Color>, DistanceResult) {
+    let (colors, result, _) = distinct_colors_with_state(
+        count,
+        distance_metric,
+        fixed_colors,
+        PaletteConstraints::default(),
+        budget,
+        callback,
+        rng,
+    );
+    (colors, result)
+}
+
+/// Like [`distinct_colors_with_rng`], but with additional soft constraints for the palette. Also
+/// returns the final state of the optimizer, which can be passed to [`resume_distinct_colors`]
+/// later on.
+pub fn distinct_colors_with_state<R: Rng>(
+    count: usize,
+    distance_metric: DistanceMetric,
+    fixed_colors: Vec<Color>,
+    constraints: PaletteConstraints,
+    budget: OptimizationBudget,
+    callback: &mut dyn FnMut(&IterationStatistics),
+    rng: &mut R,
+) -> (Vec<Color>, DistanceResult, OptimizerState) {
     assert!(count > 1);
     assert!(fixed_colors.len() <= count);
 
     let num_fixed_colors = fixed_colors.len();
     let mut colors = fixed_colors;
 
+    let region = constraints.region.clone();
     for _ in num_fixed_colors..count {
-        colors.push(random::strategies::UniformRGB.generate());
+        colors.push(if region.is_bounded() {
+            region.sample(rng)
+        } else {
+            random::strategies::UniformRGB.generate_with(rng)
+        });
     }
 
-    let mut annealing = SimulatedAnnealing::new(
+    // One third of the budget is spent on the global search, the rest on the local refinement
+    let start = Instant::now();
+    let deadline = |fraction: u32| budget.max_time.map(|t| start + t * fraction / 3);
+
+    let mut annealing = SimulatedAnnealing::with_rng(
         &colors,
         SimulationParameters {
             initial_temperature: 3.0,
             cooling_rate: 0.95,
-            num_iterations: 100_000,
-            opt_target: OptimizationTarget::Mean,
+            num_iterations: budget.num_iterations / 3,
             opt_mode: OptimizationMode::Global,
+            num_fixed_colors,
+            deadline: deadline(1),
+            patience: budget.patience,
+            region,
+        },
+        DistanceObjective {
+            target: OptimizationTarget::Mean,
             distance_metric,
             num_fixed_colors,
+            constraints,
         },
+        rng,
     );
 
     annealing.run(callback);
 
     annealing.parameters.initial_temperature = 0.5;
+    annealing.parameters.num_iterations = budget.num_iterations - budget.num_iterations / 3;
+    annealing.parameters.deadline = deadline(3);
+
+    refine(annealing, callback)
+}
+
+/// Continue the local refinement of a previous run from the given state. The state keeps the
+/// temperature of the previous run, so the optimization picks up where it stopped.
+pub fn resume_distinct_colors(
+    state: &OptimizerState,
+    distance_metric: DistanceMetric,
+    constraints: PaletteConstraints,
+    budget: OptimizationBudget,
+    callback: &mut dyn FnMut(&IterationStatistics),
+) -> (Vec<Color>, DistanceResult, OptimizerState) {
+    assert!(state.colors.len() > 1);
+    assert!(state.num_fixed_colors <= state.colors.len());
+
+    // The region might have changed since the state was saved
+    let region = constraints.region.clone();
+    let colors: Vec<Color> = state
+        .colors
+        .iter()
+        .enumerate()
+        .map(|(i, color)| {
+            if i >= state.num_fixed_colors && region.is_bounded() {
+                region.project(color)
+            } else {
+                color.clone()
+            }
+        })
+        .collect();
+
+    let annealing = SimulatedAnnealing::with_rng(
+        &colors,
+        SimulationParameters {
+            initial_temperature: state.temperature,
+            cooling_rate: 0.98,
+            num_iterations: budget.num_iterations,
+            opt_mode: OptimizationMode::Local,
+            num_fixed_colors: state.num_fixed_colors,
+            deadline: budget.max_time.map(|t| Instant::now() + t),
+            patience: budget.patience,
+            region,
+        },
+        DistanceObjective {
+            target: OptimizationTarget::Min,
+            distance_metric,
+            num_fixed_colors: state.num_fixed_colors,
+            constraints,
+        },
+        ChaCha12Rng::seed_from_u64(state.seed),
+    );
+
+    refine(annealing, callback)
+}
+
+fn refine<R: Rng>(
+    mut annealing: SimulatedAnnealing<R>,
+    callback: &mut dyn FnMut(&IterationStatistics),
+) -> (Vec<Color>, DistanceResult, OptimizerState) {
     annealing.parameters.cooling_rate = 0.98;
-    annealing.parameters.num_iterations = 200_000;
-    annealing.parameters.opt_target = OptimizationTarget::Min;
+    annealing.objective.target = OptimizationTarget::Min;
     annealing.parameters.opt_mode = OptimizationMode::Local;
 
     let result = annealing.run(callback);
 
-    (annealing.colors, result)
+    let state = OptimizerState {
+        colors: annealing.get_colors(),
+        num_fixed_colors: annealing.parameters.num_fixed_colors,
+        temperature: annealing.temperature(),
+        seed: annealing.rng.gen(),
+    };
+
+    (annealing.colors, result, state)
 }
 
 impl DistanceResult {
@@ -294,8 +983,14 @@ impl DistanceResult {
             min_closest_distance: Scalar::MAX,
             distance_metric,
             num_fixed_colors,
+            penalty: 0.0,
+            oklab_values: vec![],
         };
 
+        if distance_metric == DistanceMetric::OkLab {
+            result.oklab_values = lab_values.iter().map(scaled_oklab).collect();
+        }
+
         for i in 0..lab_values.len() {
             result.update_distances(lab_values, i, false);
         }
@@ -306,43 +1001,79 @@ impl DistanceResult {
 
     fn update(&self, lab_values: &[Lab], changed_color: usize) -> Self {
         let mut result = self.clone();
+        if self.distance_metric == DistanceMetric::OkLab {
+            result.oklab_values[changed_color] = scaled_oklab(&lab_values[changed_color]);
+        }
         result.update_distances(lab_values, changed_color, true);
         result.update_totals();
         result
     }
 
     fn update_distances(&mut self, lab_values: &[Lab], color: usize, changed: bool) {
-        self.closest_distances[color] = (Scalar::MAX, usize::MAX);
+        #[cfg(feature = "parallel")]
+        if self.is_parallel(lab_values.len()) {
+            self.update_distances_parallel(lab_values, color, changed);
+            return;
+        }
 
-        // we need to recalculate distances for nodes where the previous min dist was with
-        // changed_color but it's not anymore (potentially).
-        let mut to_recalc = Vec::with_capacity(lab_values.len());
-        let at_lab = lab_values[color].clone();
+        let metric = self.distance_metric;
+        let labs = coordinates(metric, &self.oklab_values, lab_values);
+        let at_lab = &labs[color];
+        let to_recalc = apply_distances(
+            &mut self.closest_distances,
+            color,
+            labs.iter().map(|l| distance(metric, l, at_lab)),
+            changed,
+        );
 
-        for (i, l) in lab_values.iter().enumerate() {
-            if i == color {
-                continue;
-            }
+        for i in to_recalc {
+            self.update_distances(lab_values, i, false);
+        }
+    }
 
-            let dist = self.distance(l, &at_lab);
+    /// Like `update_distances`, but the distances are computed in parallel. They are still
+    /// applied in the same order, such that the result does not depend on the number of threads.
+    #[cfg(feature = "parallel")]
+    fn update_distances_parallel(&mut self, lab_values: &[Lab], color: usize, changed: bool) {
+        use rayon::prelude::*;
+
+        let distances = self.distances_to(lab_values, color);
+        let to_recalc = apply_distances(
+            &mut self.closest_distances,
+            color,
+            distances.into_iter(),
+            changed,
+        );
 
-            if dist < self.closest_distances[i].0 {
-                self.closest_distances[i] = (dist, color);
-            } else if changed && self.closest_distances[i].1 == color {
-                // changed_color was the best before, but unfortunately we cannot say it now for
-                // sure because the distance between the two increased. Play it safe and just
-                // recalculate its distances.
-                to_recalc.push(i);
-            }
+        let recalculated: Vec<Vec<Scalar>> = to_recalc
+            .par_iter()
+            .map(|&i| self.distances_to(lab_values, i))
+            .collect();
 
-            if dist < self.closest_distances[color].0 {
-                self.closest_distances[color] = (dist, i);
-            }
+        for (i, distances) in to_recalc.into_iter().zip(recalculated) {
+            apply_distances(&mut self.closest_distances, i, distances.into_iter(), false);
         }
+    }
 
-        for i in to_recalc {
-            self.update_distances(lab_values, i, false);
-        }
+    /// Whether distances are computed in parallel. This only pays off for the (expensive)
+    /// CIEDE2000 metric, a larger number of colors and more than one thread.
+    #[cfg(feature = "parallel")]
+    fn is_parallel(&self, num_colors: usize) -> bool {
+        self.distance_metric == DistanceMetric::CIEDE2000
+            && num_colors >= 16
+            && rayon::current_num_threads() > 1
+    }
+
+    /// The distances between the given color and all colors, computed in parallel.
+    #[cfg(feature = "parallel")]
+    fn distances_to(&self, lab_values: &[Lab], color: usize) -> Vec<Scalar> {
+        use rayon::prelude::*;
+
+        let labs = coordinates(self.distance_metric, &self.oklab_values, lab_values);
+        let at_lab = &labs[color];
+        labs.par_iter()
+            .map(|l| distance(self.distance_metric, l, at_lab))
+            .collect()
     }
 
     fn update_totals(&mut self) {
@@ -374,22 +1105,78 @@ impl DistanceResult {
         self.mean_closest_distance /=
             (self.closest_distances.len() - self.num_fixed_colors) as Scalar;
     }
+}
 
-    fn distance(&self, a: &Lab, b: &Lab) -> Scalar {
-        match self.distance_metric {
-            DistanceMetric::CIE76 => delta_e::cie76(a, b),
-            DistanceMetric::CIEDE2000 => delta_e::ciede2000(a, b),
+/// The coordinates in which the distances are computed: the precomputed (scaled) OkLab values for
+/// the OkLab metric, the CIELab values otherwise.
+fn coordinates<'a>(
+    metric: DistanceMetric,
+    oklab_values: &'a [Lab],
+    lab_values: &'a [Lab],
+) -> &'a [Lab] {
+    if metric == DistanceMetric::OkLab {
+        oklab_values
+    } else {
+        lab_values
+    }
+}
+
+/// The distance between two colors, given by their `coordinates`.
+fn distance(metric: DistanceMetric, a: &Lab, b: &Lab) -> Scalar {
+    match metric {
+        // The OkLab coordinates are precomputed
+        DistanceMetric::OkLab => delta_e::cie76(a, b),
+        metric => metric.lab_distance(a, b),
+    }
+}
+
+/// Update the closest distances with the (new) distances of the given color to all colors.
+/// Returns the colors whose closest distances need to be recalculated from scratch.
+fn apply_distances(
+    closest_distances: &mut [(Scalar, usize)],
+    color: usize,
+    distances: impl Iterator<Item = Scalar>,
+    changed: bool,
+) -> Vec<usize> {
+    closest_distances[color] = (Scalar::MAX, usize::MAX);
+
+    // we need to recalculate distances for nodes where the previous min dist was with
+    // changed_color but it's not anymore (potentially).
+    let mut to_recalc = Vec::with_capacity(closest_distances.len());
+
+    for (i, dist) in distances.enumerate() {
+        if i == color {
+            continue;
+        }
+
+        if dist < closest_distances[i].0 {
+            closest_distances[i] = (dist, color);
+        } else if changed && closest_distances[i].1 == color {
+            // changed_color was the best before, but unfortunately we cannot say it now for
+            // sure because the distance between the two increased. Play it safe and just
+            // recalculate its distances.
+            to_recalc.push(i);
+        }
+
+        if dist < closest_distances[color].0 {
+            closest_distances[color] = (dist, i);
         }
     }
+
+    to_recalc
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "parallel")]
+    use super::distinct_colors_with_rng;
     use super::{
-        rearrange_sequence, DistanceMetric, OptimizationMode, OptimizationTarget,
-        SimulatedAnnealing, SimulationParameters,
+        distinct_colors_with_state, rearrange_sequence, ColorRegion, DistanceMetric,
+        DistanceObjective, DistanceResult, Objective, OptimizationBudget, OptimizationMode,
+        OptimizationTarget, OptimizerState, PaletteConstraints, SimulatedAnnealing,
+        SimulationParameters,
     };
-    use crate::Color;
+    use crate::{Color, Lab};
 
     use rand::prelude::*;
     use rand_xoshiro::Xoshiro256StarStar;
@@ -428,10 +1215,17 @@ mod tests {
                 initial_temperature: 3.0,
                 cooling_rate: 0.95,
                 num_iterations: 100,
-                opt_target: OptimizationTarget::Min,
                 opt_mode: OptimizationMode::Local,
+                num_fixed_colors: 3,
+                deadline: None,
+                patience: None,
+                region: ColorRegion::default(),
+            },
+            DistanceObjective {
+                target: OptimizationTarget::Min,
                 distance_metric: DistanceMetric::CIE76,
                 num_fixed_colors: 3,
+                constraints: PaletteConstraints::default(),
             },
             Xoshiro256StarStar::seed_from_u64(21),
         );
@@ -443,6 +1237,65 @@ mod tests {
         );
     }
 
+    /// The parallel computation of the distances must not change the result for a given seed. With
+    /// a single thread, the sequential code path (as without the `parallel` feature) is used.
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_distances_are_deterministic() {
+        let run = |num_threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .unwrap();
+            pool.install(|| {
+                distinct_colors_with_rng(
+                    20,
+                    DistanceMetric::CIEDE2000,
+                    vec![],
+                    OptimizationBudget {
+                        num_iterations: 2_000,
+                        ..OptimizationBudget::default()
+                    },
+                    &mut |_| {},
+                    &mut Xoshiro256StarStar::seed_from_u64(7),
+                )
+            })
+        };
+
+        let (sequential_colors, sequential_result) = run(1);
+        let (parallel_colors, parallel_result) = run(4);
+        assert_eq!(sequential_colors, parallel_colors);
+        assert_eq!(
+            sequential_result.closest_distances,
+            parallel_result.closest_distances
+        );
+    }
+
+    #[test]
+    fn test_oklab_metric() {
+        let distance = DistanceMetric::OkLab.distance(&Color::black(), &Color::white());
+        assert!((distance - 100.0).abs() < 1e-3);
+
+        let mut labs: Vec<Lab> = [Color::red(), Color::green(), Color::blue(), Color::yellow()]
+            .iter()
+            .map(|c| c.to_lab())
+            .collect();
+        let result = DistanceResult::new(&labs, DistanceMetric::OkLab, 0);
+
+        labs[1] = Color::from_rgb(250, 10, 10).to_lab();
+        let updated = result.update(&labs, 1);
+        let expected = DistanceResult::new(&labs, DistanceMetric::OkLab, 0);
+
+        assert_eq!(expected.closest_pair, updated.closest_pair);
+        assert!((expected.min_closest_distance - updated.min_closest_distance).abs() < 1e-9);
+        assert!(
+            (updated.min_closest_distance
+                - DistanceMetric::OkLab.distance(&Color::red(), &Color::from_rgb(250, 10, 10)))
+            .abs()
+                < 1e-6
+        );
+    }
+
     #[test]
     fn test_distinct_2_fixed_colors() {
         let colors = [Color::red(), Color::yellow()];
@@ -453,10 +1306,17 @@ mod tests {
                 initial_temperature: 3.0,
                 cooling_rate: 0.95,
                 num_iterations: 100,
-                opt_target: OptimizationTarget::Min,
                 opt_mode: OptimizationMode::Local,
+                num_fixed_colors: 1,
+                deadline: None,
+                patience: None,
+                region: ColorRegion::default(),
+            },
+            DistanceObjective {
+                target: OptimizationTarget::Min,
                 distance_metric: DistanceMetric::CIE76,
                 num_fixed_colors: 1,
+                constraints: PaletteConstraints::default(),
             },
             Xoshiro256StarStar::seed_from_u64(42),
         );
@@ -464,4 +1324,250 @@ mod tests {
 
         assert_eq!(sim.get_colors()[0], Color::red());
     }
+
+    #[test]
+    fn test_custom_objective() {
+        /// Maximize the lightness of all colors
+        struct Brightness;
+
+        impl Objective for Brightness {
+            type Evaluation = f64;
+
+            fn evaluate(&self, lab_values: &[Lab]) -> f64 {
+                lab_values.iter().map(|lab| lab.l).sum()
+            }
+
+            fn score(&self, evaluation: &f64) -> f64 {
+                *evaluation
+            }
+        }
+
+        let colors = [Color::black(), Color::graytone(0.2)];
+
+        let mut sim = SimulatedAnnealing::with_rng(
+            &colors,
+            SimulationParameters {
+                initial_temperature: 0.1,
+                cooling_rate: 0.95,
+                num_iterations: 2_000,
+                opt_mode: OptimizationMode::Local,
+                num_fixed_colors: 1,
+                deadline: None,
+                patience: None,
+                region: ColorRegion::default(),
+            },
+            Brightness,
+            Xoshiro256StarStar::seed_from_u64(7),
+        );
+        let lightness = sim.run(&mut |_| {});
+
+        assert_eq!(sim.get_colors()[0], Color::black());
+        assert!(sim.get_colors()[1].to_lab().l > 90.0);
+        assert!(lightness > 90.0);
+    }
+
+    #[test]
+    fn test_patience() {
+        /// A constant objective that counts how often it is evaluated
+        struct Constant(std::cell::Cell<usize>);
+
+        impl Objective for Constant {
+            type Evaluation = f64;
+
+            fn evaluate(&self, _: &[Lab]) -> f64 {
+                self.0.set(self.0.get() + 1);
+                1.0
+            }
+
+            fn score(&self, evaluation: &f64) -> f64 {
+                *evaluation
+            }
+        }
+
+        let colors = [Color::black(), Color::white()];
+
+        let mut sim = SimulatedAnnealing::with_rng(
+            &colors,
+            SimulationParameters {
+                initial_temperature: 0.1,
+                cooling_rate: 0.95,
+                num_iterations: 10_000,
+                opt_mode: OptimizationMode::Local,
+                num_fixed_colors: 0,
+                deadline: None,
+                patience: Some(50),
+                region: ColorRegion::default(),
+            },
+            Constant(std::cell::Cell::new(0)),
+            Xoshiro256StarStar::seed_from_u64(1),
+        );
+        sim.run(&mut |_| {});
+
+        // The initial evaluation plus one for each iteration until the patience ran out
+        assert_eq!(sim.objective.0.get(), 1 + 51);
+    }
+
+    #[test]
+    fn test_optimizer_state_roundtrip() {
+        let state = OptimizerState {
+            colors: vec![Color::red(), Color::from_rgb(12, 200, 99), Color::navy()],
+            num_fixed_colors: 1,
+            temperature: 0.012_345,
+            seed: u64::MAX,
+        };
+
+        assert_eq!(
+            Ok(state.clone()),
+            OptimizerState::from_json(&state.to_json())
+        );
+    }
+
+    #[test]
+    fn test_optimizer_state_invalid() {
+        assert!(OptimizerState::from_json("").is_err());
+        assert!(OptimizerState::from_json("{\"colors\": [\"#ff0000\", \"#00ff00\"]}").is_err());
+        assert!(OptimizerState::from_json(
+            "{\"colors\": [\"#ff0000\"], \"num_fixed_colors\": 0, \"temperature\": 1, \"seed\": 1}"
+        )
+        .is_err());
+        // Valid JSON of the wrong shape
+        assert!(OptimizerState::from_json(
+            "{\"colors\": 5, \"num_fixed_colors\": 0, \"temperature\": 1, \"seed\": 1}"
+        )
+        .is_err());
+        assert!(OptimizerState::from_json("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn test_color_region_invalid_bounds() {
+        let region = ColorRegion {
+            lightness: (80.0, f64::NAN),
+            chroma: (f64::NAN, -5.0),
+            min_contrast: None,
+        };
+        assert_eq!(vec![(80.0, 100.0)], region.lightness_ranges());
+
+        let projected = region.project(&Color::from_rgb(200, 20, 20));
+        assert!(projected.to_lch().l >= 79.5);
+        assert!(projected.to_lch().c < 1.0);
+    }
+
+    #[test]
+    fn test_palette_constraints_penalty() {
+        let labs = |colors: &[Color]| colors.iter().map(|c| c.to_lab()).collect::<Vec<_>>();
+
+        let constraints = PaletteConstraints::default();
+        assert!(!constraints.is_active());
+
+        let grays = labs(&[Color::black(), Color::white()]);
+        let constraints = PaletteConstraints {
+            lightness_spread: Some((50.0, 2.0)),
+            ..Default::default()
+        };
+        assert!(constraints.penalty(&grays, DistanceMetric::CIE76) < 1e-6);
+
+        let constraints = PaletteConstraints {
+            hue_uniformity: 1.0,
+            ..Default::default()
+        };
+        let uniform = labs(&[
+            Color::from_lch(60.0, 40.0, 10.0, 1.0),
+            Color::from_lch(60.0, 40.0, 130.0, 1.0),
+            Color::from_lch(60.0, 40.0, 250.0, 1.0),
+        ]);
+        let clustered = labs(&[
+            Color::from_lch(60.0, 40.0, 10.0, 1.0),
+            Color::from_lch(60.0, 40.0, 30.0, 1.0),
+            Color::from_lch(60.0, 40.0, 50.0, 1.0),
+        ]);
+        assert!(constraints.penalty(&uniform, DistanceMetric::CIE76) < 1.0);
+        assert!(constraints.penalty(&clustered, DistanceMetric::CIE76) > 100.0);
+
+        let constraints = PaletteConstraints {
+            anchor: Some((Color::red(), 1.0)),
+            ..Default::default()
+        };
+        assert!(
+            constraints.penalty(&labs(&[Color::red(), Color::blue()]), DistanceMetric::CIE76)
+                < 1e-6
+        );
+    }
+
+    #[test]
+    fn test_distinct_colors_in_region() {
+        let region = ColorRegion {
+            lightness: (30.0, 80.0),
+            chroma: (0.0, 60.0),
+            ..Default::default()
+        };
+        assert!(region.is_bounded());
+
+        let (colors, _, _) = distinct_colors_with_state(
+            6,
+            DistanceMetric::CIE76,
+            vec![Color::black()],
+            PaletteConstraints {
+                region,
+                ..Default::default()
+            },
+            OptimizationBudget {
+                num_iterations: 3_000,
+                ..Default::default()
+            },
+            &mut |_| {},
+            &mut Xoshiro256StarStar::seed_from_u64(1),
+        );
+
+        // The fixed color is kept, even though it lies outside of the region
+        assert_eq!(Color::black(), colors[0]);
+        // Colors are rounded to 8-bit RGB values, so the limits hold approximately
+        for color in &colors[1..] {
+            let lch = color.to_lch();
+            assert!((29.0..=81.0).contains(&lch.l));
+            assert!(lch.c <= 61.0);
+        }
+    }
+
+    #[test]
+    fn test_distinct_colors_with_contrast() {
+        let background = Color::from_rgb(30, 30, 30);
+        let region = ColorRegion {
+            min_contrast: Some((background.clone(), 4.5)),
+            ..Default::default()
+        };
+        assert_eq!(1, region.lightness_ranges().len());
+
+        let (colors, _, _) = distinct_colors_with_state(
+            8,
+            DistanceMetric::CIE76,
+            vec![],
+            PaletteConstraints {
+                region,
+                ..Default::default()
+            },
+            OptimizationBudget {
+                num_iterations: 3_000,
+                ..Default::default()
+            },
+            &mut |_| {},
+            &mut Xoshiro256StarStar::seed_from_u64(1),
+        );
+
+        for color in &colors {
+            assert!(color.contrast_ratio(&background) >= 4.5);
+        }
+
+        // A medium gray allows for both darker and lighter colors
+        let region = ColorRegion {
+            min_contrast: Some((Color::gray(), 2.0)),
+            ..Default::default()
+        };
+        assert_eq!(2, region.lightness_ranges().len());
+
+        let region = ColorRegion {
+            min_contrast: Some((Color::gray(), 7.0)),
+            ..Default::default()
+        };
+        assert!(region.lightness_ranges().is_empty());
+    }
 }
@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// Errors that can occur when parsing colors, styles and other inputs.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The given string is not a valid color
+    InvalidColor(String),
+    /// Unknown terminal color mode (e.g. in `PASTEL_COLOR_MODE`)
+    UnknownColorMode(String),
+    /// Invalid style specification (see `ansi::Style::from_str`)
+    InvalidStyle(String),
+    /// Invalid tone curve or curve specification
+    InvalidCurve(String),
+    /// Invalid transformation pipeline
+    InvalidPipeline(String),
+    /// Invalid 3D lookup table
+    InvalidLut(String),
+    /// Invalid saved state of the `distinct` optimizer
+    InvalidOptimizerState(String),
+    /// Invalid terminal palette (see `ansi::parse_base_palette`)
+    InvalidPalette(String),
+    /// The required contrast ratio cannot be reached (by enough colors) against the given
+    /// background
+    ContrastUnreachable(f64),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidColor(color) => write!(f, "could not parse color '{}'", color),
+            Error::UnknownColorMode(mode) => write!(f, "unknown color mode '{}'", mode),
+            Error::InvalidStyle(reason)
+            | Error::InvalidCurve(reason)
+            | Error::InvalidPipeline(reason)
+            | Error::InvalidLut(reason)
+            | Error::InvalidOptimizerState(reason)
+            | Error::InvalidPalette(reason) => write!(f, "{}", reason),
+            Error::ContrastUnreachable(ratio) => write!(
+                f,
+                "a contrast ratio of {} cannot be reached for distinct colors on this background",
+                ratio
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
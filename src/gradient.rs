@@ -0,0 +1,320 @@
+//! Color gradients: a [`ColorScale`] combined with a color space for the interpolation and
+//! optional easing functions for the individual segments between two color stops.
+
+use crate::types::Scalar;
+use crate::{Color, ColorScale, Fraction, HueInterpolation, LCh, Lab, OkLCh, OkLab, HSLA, RGBA};
+
+/// The color space in which the colors of a gradient are interpolated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterpolationSpace {
+    Rgb,
+    Hsl,
+    Lab,
+    LCh,
+    OkLab,
+    OkLCh,
+}
+
+impl InterpolationSpace {
+    /// All interpolation color spaces along with their names.
+    pub const ALL: &'static [(&'static str, InterpolationSpace)] = &[
+        ("rgb", InterpolationSpace::Rgb),
+        ("hsl", InterpolationSpace::Hsl),
+        ("lab", InterpolationSpace::Lab),
+        ("lch", InterpolationSpace::LCh),
+        ("oklab", InterpolationSpace::OkLab),
+        ("oklch", InterpolationSpace::OkLCh),
+    ];
+
+    /// Look up a color space by its (case-insensitive) name, e.g. `OkLab`.
+    pub fn from_name(name: &str) -> Option<InterpolationSpace> {
+        InterpolationSpace::ALL
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|&(_, space)| space)
+    }
+
+    /// Mix two colors in this color space.
+    pub fn mix(self, c1: &Color, c2: &Color, fraction: Fraction) -> Color {
+        self.mix_with_hue(c1, c2, fraction, HueInterpolation::Shorter)
+    }
+
+    /// Mix two colors in this color space, with the given hue interpolation method for the
+    /// cylindrical spaces (HSL, LCh and OkLCh).
+    pub fn mix_with_hue(
+        self,
+        c1: &Color,
+        c2: &Color,
+        fraction: Fraction,
+        hue: HueInterpolation,
+    ) -> Color {
+        match self {
+            InterpolationSpace::Rgb => c1.mix::<RGBA<f64>>(c2, fraction),
+            InterpolationSpace::Hsl => c1.mix_with_hue::<HSLA>(c2, fraction, hue),
+            InterpolationSpace::Lab => c1.mix::<Lab>(c2, fraction),
+            InterpolationSpace::LCh => c1.mix_with_hue::<LCh>(c2, fraction, hue),
+            InterpolationSpace::OkLab => c1.mix::<OkLab>(c2, fraction),
+            InterpolationSpace::OkLCh => c1.mix_with_hue::<OkLCh>(c2, fraction, hue),
+        }
+    }
+}
+
+/// An easing function that maps the position within a segment of a gradient (0.0 to 1.0) to the
+/// mixing fraction of the two adjacent color stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Constant speed
+    #[default]
+    Linear,
+    /// Start slowly (quadratic)
+    EaseIn,
+    /// End slowly (quadratic)
+    EaseOut,
+    /// Start and end slowly (smoothstep)
+    EaseInOut,
+}
+
+impl Easing {
+    /// All easing functions along with their names.
+    pub const ALL: &'static [(&'static str, Easing)] = &[
+        ("linear", Easing::Linear),
+        ("ease-in", Easing::EaseIn),
+        ("ease-out", Easing::EaseOut),
+        ("ease-in-out", Easing::EaseInOut),
+    ];
+
+    /// Look up an easing function by its name, e.g. `ease-in-out`.
+    pub fn from_name(name: &str) -> Option<Easing> {
+        Easing::ALL
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|&(_, easing)| easing)
+    }
+
+    /// Apply the easing function to a position between 0.0 and 1.0.
+    pub fn apply(self, t: Scalar) -> Scalar {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// A color gradient that interpolates between the color stops of a [`ColorScale`]. In contrast to
+/// [`ColorScale::sample`], the interpolation is fully described by the gradient itself, such that
+/// it can simply be sampled at a given position (or at `n` evenly spaced positions).
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    scale: ColorScale,
+    space: InterpolationSpace,
+    /// The easing functions of the individual segments. Segments without an entry use
+    /// `default_easing`.
+    easings: Vec<Option<Easing>>,
+    default_easing: Easing,
+    hue_interpolation: HueInterpolation,
+}
+
+impl Gradient {
+    /// Create a gradient from a color scale, with a linear interpolation in the given color
+    /// space.
+    pub fn new(scale: ColorScale, space: InterpolationSpace) -> Gradient {
+        Gradient {
+            scale,
+            space,
+            easings: vec![],
+            default_easing: Easing::Linear,
+            hue_interpolation: HueInterpolation::Shorter,
+        }
+    }
+
+    /// Create a gradient from evenly spaced colors.
+    pub fn from_colors(colors: &[Color], space: InterpolationSpace) -> Gradient {
+        let mut scale = ColorScale::empty();
+        let last = colors.len().saturating_sub(1).max(1) as Scalar;
+        for (i, color) in colors.iter().enumerate() {
+            scale.add_stop(color.clone(), Fraction::from(i as Scalar / last));
+        }
+        Gradient::new(scale, space)
+    }
+
+    /// Use the given easing function for all segments that do not have their own.
+    pub fn with_easing(mut self, easing: Easing) -> Gradient {
+        self.default_easing = easing;
+        self
+    }
+
+    /// Use the given easing function for the segment between the color stops with the indices
+    /// `segment` and `segment + 1`.
+    pub fn with_segment_easing(mut self, segment: usize, easing: Easing) -> Gradient {
+        if self.easings.len() <= segment {
+            self.easings.resize(segment + 1, None);
+        }
+        self.easings[segment] = Some(easing);
+        self
+    }
+
+    /// Use the given direction around the hue circle for cylindrical interpolation spaces.
+    pub fn with_hue_interpolation(mut self, hue: HueInterpolation) -> Gradient {
+        self.hue_interpolation = hue;
+        self
+    }
+
+    /// Whether all segments are interpolated linearly (without easing).
+    pub fn is_linear(&self) -> bool {
+        self.default_easing == Easing::Linear
+            && self.easings.iter().flatten().all(|&e| e == Easing::Linear)
+    }
+
+    pub fn hue_interpolation(&self) -> HueInterpolation {
+        self.hue_interpolation
+    }
+
+    pub fn color_scale(&self) -> &ColorScale {
+        &self.scale
+    }
+
+    pub fn interpolation_space(&self) -> InterpolationSpace {
+        self.space
+    }
+
+    /// The number of segments between adjacent color stops.
+    pub fn num_segments(&self) -> usize {
+        self.scale.color_stops.len().saturating_sub(1)
+    }
+
+    fn easing(&self, segment: usize) -> Easing {
+        self.easings
+            .get(segment)
+            .copied()
+            .flatten()
+            .unwrap_or(self.default_easing)
+    }
+
+    /// Get the color at the given position.
+    ///
+    /// Note:
+    /// - No color is returned if the position is not between two color stops, or if the gradient
+    ///   has less than two color stops.
+    pub fn sample(&self, position: Fraction) -> Option<Color> {
+        let stops = &self.scale.color_stops;
+        let position = position.value();
+
+        let segment = stops
+            .windows(2)
+            .position(|w| position <= w[1].position.value())?;
+        let (left, right) = (&stops[segment], &stops[segment + 1]);
+        if position < left.position.value() {
+            return None;
+        }
+
+        let width = right.position.value() - left.position.value();
+        let t = if width > 0.0 {
+            (position - left.position.value()) / width
+        } else {
+            0.0
+        };
+        let fraction = Fraction::from(self.easing(segment).apply(t));
+
+        Some(
+            self.space
+                .mix_with_hue(&left.color, &right.color, fraction, self.hue_interpolation),
+        )
+    }
+
+    /// Sample `n` evenly spaced colors, from the start (0.0) to the end (1.0) of the gradient.
+    /// Positions that are not covered by color stops are skipped.
+    pub fn take(&self, n: usize) -> impl Iterator<Item = Color> + '_ {
+        let last = n.saturating_sub(1).max(1) as Scalar;
+        (0..n).filter_map(move |i| self.sample(Fraction::from(i as Scalar / last)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_matches_color_scale() {
+        let colors = [Color::red(), Color::white(), Color::blue()];
+        let gradient = Gradient::from_colors(&colors, InterpolationSpace::Lab);
+
+        let mix = |c1: &Color, c2: &Color, f: Fraction| c1.mix::<Lab>(c2, f);
+        for i in 0..=10 {
+            let position = Fraction::from(i as Scalar / 10.0);
+            assert_eq!(
+                gradient.color_scale().sample(position, &mix),
+                gradient.sample(position)
+            );
+        }
+    }
+
+    #[test]
+    fn segment_easing() {
+        let gradient = Gradient::from_colors(
+            &[Color::black(), Color::white(), Color::black()],
+            InterpolationSpace::Rgb,
+        )
+        .with_segment_easing(1, Easing::EaseIn);
+
+        // First segment: linear
+        let first = gradient.sample(Fraction::from(0.25)).unwrap();
+        assert_eq!(
+            first,
+            Color::black().mix::<RGBA<f64>>(&Color::white(), Fraction::from(0.5))
+        );
+
+        // Second segment: quadratic
+        let second = gradient.sample(Fraction::from(0.75)).unwrap();
+        assert_eq!(
+            second,
+            Color::white().mix::<RGBA<f64>>(&Color::black(), Fraction::from(0.25))
+        );
+    }
+
+    #[test]
+    fn easing_end_points() {
+        for &(_, easing) in Easing::ALL {
+            assert_eq!(0.0, easing.apply(0.0));
+            assert_eq!(1.0, easing.apply(1.0));
+        }
+    }
+
+    #[test]
+    fn take() {
+        let gradient =
+            Gradient::from_colors(&[Color::red(), Color::blue()], InterpolationSpace::OkLab);
+        let colors: Vec<Color> = gradient.take(3).collect();
+        assert_eq!(3, colors.len());
+        assert_eq!(Color::red(), colors[0]);
+        assert_eq!(Color::blue(), colors[2]);
+
+        assert_eq!(
+            0,
+            Gradient::from_colors(&[], InterpolationSpace::Lab)
+                .take(4)
+                .count()
+        );
+    }
+
+    #[test]
+    fn rainbow() {
+        let gradient =
+            Gradient::from_colors(&[Color::red(), Color::red()], InterpolationSpace::Hsl)
+                .with_hue_interpolation(HueInterpolation::Longer);
+
+        let hues: Vec<f64> = gradient.take(5).map(|c| c.to_hsla().h).collect();
+        assert_eq!(vec![0.0, 90.0, 180.0, 270.0, 0.0], hues);
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(
+            Some(InterpolationSpace::OkLab),
+            InterpolationSpace::from_name("OkLab")
+        );
+        assert_eq!(Some(Easing::EaseInOut), Easing::from_name("ease-in-out"));
+        assert_eq!(None, Easing::from_name("bounce"));
+    }
+}
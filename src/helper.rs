@@ -4,6 +4,7 @@ use std::{
 };
 
 use crate::types::Scalar;
+use crate::HueInterpolation;
 
 /// Like `%`, but always positive.
 pub fn mod_positive(x: Scalar, y: Scalar) -> Scalar {
@@ -51,6 +52,84 @@ pub fn interpolate_angle(a: Scalar, b: Scalar, fraction: Fraction) -> Scalar {
     mod_positive(interpolate(shortest.0, shortest.1, fraction), 360.0)
 }
 
+/// Linearly interpolate between two angles (in degrees), going around the circle in the direction
+/// given by the hue interpolation method (see CSS Color Module Level 4).
+pub fn interpolate_hue(
+    a: Scalar,
+    b: Scalar,
+    fraction: Fraction,
+    method: HueInterpolation,
+) -> Scalar {
+    let (mut a, mut b) = (mod_positive(a, 360.0), mod_positive(b, 360.0));
+    let diff = b - a;
+
+    match method {
+        HueInterpolation::Shorter => return interpolate_angle(a, b, fraction),
+        HueInterpolation::Longer => {
+            if 0.0 < diff && diff < 180.0 {
+                a += 360.0;
+            } else if -180.0 < diff && diff <= 0.0 {
+                b += 360.0;
+            }
+        }
+        HueInterpolation::Increasing => {
+            if diff < 0.0 {
+                b += 360.0;
+            }
+        }
+        HueInterpolation::Decreasing => {
+            if diff > 0.0 {
+                a += 360.0;
+            }
+        }
+    }
+
+    mod_positive(interpolate(a, b, fraction), 360.0)
+}
+
+/// Find the largest value in the interval [lower, upper] for which the given predicate holds, via
+/// bisection. The predicate must be true at `lower` and monotonically change from true to false
+/// over the interval.
+pub fn bisect_max(lower: Scalar, upper: Scalar, predicate: impl Fn(Scalar) -> bool) -> Scalar {
+    let mut lower = lower;
+    let mut upper = upper;
+    for _ in 0..40 {
+        let middle = (lower + upper) / 2.0;
+        if predicate(middle) {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+    lower
+}
+
+/// Convert a gamma-encoded sRGB channel value to linear light intensity.
+pub fn srgb_to_linear(c: Scalar) -> Scalar {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        Scalar::powf((c + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Convert a linear light intensity to a gamma-encoded sRGB channel value.
+pub fn linear_to_srgb(c: Scalar) -> Scalar {
+    if c <= 0.003_130_8 {
+        12.92 * c
+    } else {
+        1.055 * Scalar::powf(c, 1.0 / 2.4) - 0.055
+    }
+}
+
+/// The 64-bit FNV-1a hash of the given bytes. In contrast to the hashers in `std`, the result is
+/// guaranteed to be stable across platforms and Rust versions.
+pub fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
 // `format!`-style format strings only allow specifying a fixed floating
 // point precision, e.g. `{:.3}` to print 3 decimal places. This always
 // displays trailing zeroes, while web colors generally omit them. For
@@ -80,6 +159,52 @@ impl Display for MaxPrecision {
     }
 }
 
+// Wrapper for floats that formats them with a fixed number of decimal places, independent of
+// the platform. Values are rounded half away from zero (`Fixed::wrap(1, 0.25)` => `0.3`),
+// negative zero is printed as `0` and non-finite values are printed as `0`:
+//
+//     Fixed::wrap(2, 0.5)     //=> 0.50
+//     Fixed::wrap(0, -0.3)    //=> 0
+//
+pub struct Fixed {
+    precision: u32,
+    inner: f64,
+}
+
+impl Fixed {
+    pub fn wrap(precision: u32, inner: f64) -> Self {
+        Self { precision, inner }
+    }
+}
+
+impl Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pow_10 = 10u64.pow(self.precision);
+        let scaled = if self.inner.is_finite() {
+            (self.inner * pow_10 as f64).round()
+        } else {
+            0.0
+        };
+
+        let sign = if scaled < 0.0 { "-" } else { "" };
+        let scaled = scaled.abs() as u64;
+        let (int_part, frac_part) = (scaled / pow_10, scaled % pow_10);
+
+        if self.precision == 0 {
+            write!(f, "{}{}", sign, int_part)
+        } else {
+            write!(
+                f,
+                "{}{}.{:0width$}",
+                sign,
+                int_part,
+                frac_part,
+                width = self.precision as usize
+            )
+        }
+    }
+}
+
 #[test]
 fn test_interpolate() {
     assert_eq!(0.0, interpolate_angle(0.0, 90.0, Fraction::from(0.0)));
@@ -96,6 +221,29 @@ fn test_interpolate_angle() {
     assert_eq!(0.0, interpolate_angle(350.0, 10.0, Fraction::from(0.5)));
 }
 
+#[test]
+fn test_interpolate_hue() {
+    let hue = |a, b, method| interpolate_hue(a, b, Fraction::from(0.25), method);
+
+    assert_eq!(hue(10.0, 350.0, HueInterpolation::Shorter), 5.0);
+    assert_eq!(hue(10.0, 350.0, HueInterpolation::Longer), 95.0);
+    assert_eq!(hue(10.0, 350.0, HueInterpolation::Increasing), 95.0);
+    assert_eq!(hue(10.0, 350.0, HueInterpolation::Decreasing), 5.0);
+
+    assert_eq!(hue(350.0, 10.0, HueInterpolation::Increasing), 355.0);
+    assert_eq!(hue(350.0, 10.0, HueInterpolation::Decreasing), 265.0);
+
+    // A full turn for identical hues
+    assert_eq!(hue(0.0, 0.0, HueInterpolation::Shorter), 0.0);
+    assert_eq!(hue(0.0, 0.0, HueInterpolation::Longer), 90.0);
+}
+
+#[test]
+fn test_bisect_max() {
+    assert!((bisect_max(0.0, 10.0, |x| x * x <= 2.0) - Scalar::sqrt(2.0)).abs() < 1e-9);
+    assert_eq!(0.0, bisect_max(0.0, 1.0, |_| false));
+}
+
 #[test]
 fn test_max_precision() {
     assert_eq!(format!("{}", MaxPrecision::wrap(3, 0.5)), "0.5");
@@ -104,3 +252,22 @@ fn test_max_precision() {
     assert_eq!(format!("{}", MaxPrecision::wrap(3, 0.5124)), "0.512");
     assert_eq!(format!("{}", MaxPrecision::wrap(3, 0.5125)), "0.513");
 }
+
+#[test]
+fn test_fixed() {
+    assert_eq!(format!("{}", Fixed::wrap(0, 12.5)), "13");
+    assert_eq!(format!("{}", Fixed::wrap(1, 0.25)), "0.3");
+    assert_eq!(format!("{}", Fixed::wrap(2, 0.5)), "0.50");
+    assert_eq!(format!("{}", Fixed::wrap(3, 1.0005)), "1.001");
+    assert_eq!(format!("{}", Fixed::wrap(4, -0.12344)), "-0.1234");
+    assert_eq!(format!("{}", Fixed::wrap(0, -0.3)), "0");
+    assert_eq!(format!("{}", Fixed::wrap(1, -0.0)), "0.0");
+    assert_eq!(format!("{}", Fixed::wrap(1, f64::NAN)), "0.0");
+}
+
+#[test]
+fn test_fnv1a_64() {
+    assert_eq!(0xcbf29ce484222325, fnv1a_64(b""));
+    assert_eq!(0xaf63dc4c8601ec8c, fnv1a_64(b"a"));
+    assert_eq!(0x85944171f73967e8, fnv1a_64(b"foobar"));
+}
@@ -0,0 +1,158 @@
+use crate::types::Scalar;
+use crate::XYZ;
+
+/// A standard illuminant, i.e. a theoretical source of visible light with a known spectrum.
+///
+/// See: <https://en.wikipedia.org/wiki/Standard_illuminant>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Illuminant {
+    /// Incandescent / tungsten light
+    A,
+    /// Average / north sky daylight (obsolete)
+    C,
+    /// Horizon light, commonly used in printing
+    D50,
+    /// Mid-morning / mid-afternoon daylight
+    D55,
+    /// Noon daylight (the white point of sRGB)
+    D65,
+    /// North sky daylight
+    D75,
+    /// Equal energy
+    E,
+    /// Cool white fluorescent lamp
+    F2,
+    /// Broad-band daylight fluorescent lamp
+    F7,
+    /// Narrow tri-band fluorescent lamp
+    F11,
+}
+
+// The names are also used for the command-line interface, which is compiled into `build.rs`
+include!("illuminant_names.rs");
+
+// Bradford cone response matrix and its inverse.
+const BRADFORD: [[Scalar; 3]; 3] = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+const BRADFORD_INV: [[Scalar; 3]; 3] = [
+    [0.986_992_9, -0.147_054_3, 0.159_962_7],
+    [0.432_305_3, 0.518_360_3, 0.049_291_2],
+    [-0.008_528_7, 0.040_042_8, 0.968_486_7],
+];
+
+fn multiply(m: &[[Scalar; 3]; 3], v: [Scalar; 3]) -> [Scalar; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+impl Illuminant {
+    /// Look up an illuminant by its name (case-insensitive), see `ILLUMINANT_NAMES`.
+    pub fn from_name(name: &str) -> Option<Illuminant> {
+        match name.to_uppercase().as_ref() {
+            "A" => Some(Illuminant::A),
+            "C" => Some(Illuminant::C),
+            "D50" => Some(Illuminant::D50),
+            "D55" => Some(Illuminant::D55),
+            "D65" => Some(Illuminant::D65),
+            "D75" => Some(Illuminant::D75),
+            "E" => Some(Illuminant::E),
+            "F2" => Some(Illuminant::F2),
+            "F7" => Some(Illuminant::F7),
+            "F11" => Some(Illuminant::F11),
+            _ => None,
+        }
+    }
+
+    /// The CIE 1931 (2° observer) chromaticity coordinates `(x, y)` of the white point.
+    pub fn chromaticity(self) -> (Scalar, Scalar) {
+        match self {
+            Illuminant::A => (0.44757, 0.40745),
+            Illuminant::C => (0.31006, 0.31616),
+            Illuminant::D50 => (0.34567, 0.35850),
+            Illuminant::D55 => (0.33242, 0.34743),
+            Illuminant::D65 => (0.31271, 0.32902),
+            Illuminant::D75 => (0.29902, 0.31485),
+            Illuminant::E => (1.0 / 3.0, 1.0 / 3.0),
+            Illuminant::F2 => (0.37208, 0.37529),
+            Illuminant::F7 => (0.31292, 0.32933),
+            Illuminant::F11 => (0.38052, 0.37713),
+        }
+    }
+
+    /// The XYZ coordinates of the white point, normalized to `Y = 1`.
+    pub fn white_point(self) -> XYZ {
+        let (x, y) = self.chromaticity();
+        XYZ {
+            x: x / y,
+            y: 1.0,
+            z: (1.0 - x - y) / y,
+            alpha: 1.0,
+        }
+    }
+}
+
+/// Transform XYZ coordinates of a color seen under the `source` illuminant to the coordinates of
+/// the corresponding color under the `target` illuminant, using the (linear) Bradford chromatic
+/// adaptation transform.
+///
+/// See: <https://en.wikipedia.org/wiki/Chromatic_adaptation>
+pub fn chromatic_adaptation(xyz: &XYZ, source: Illuminant, target: Illuminant) -> XYZ {
+    let ws = source.white_point();
+    let wt = target.white_point();
+
+    let cone_source = multiply(&BRADFORD, [ws.x, ws.y, ws.z]);
+    let cone_target = multiply(&BRADFORD, [wt.x, wt.y, wt.z]);
+
+    let cone = multiply(&BRADFORD, [xyz.x, xyz.y, xyz.z]);
+    let cone_adapted = [
+        cone[0] * cone_target[0] / cone_source[0],
+        cone[1] * cone_target[1] / cone_source[1],
+        cone[2] * cone_target[2] / cone_source[2],
+    ];
+
+    let [x, y, z] = multiply(&BRADFORD_INV, cone_adapted);
+    XYZ {
+        x,
+        y,
+        z,
+        alpha: xyz.alpha,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn from_name() {
+        assert_eq!(Some(Illuminant::D50), Illuminant::from_name("d50"));
+        assert_eq!(Some(Illuminant::F11), Illuminant::from_name("F11"));
+        assert_eq!(None, Illuminant::from_name("D42"));
+
+        for name in ILLUMINANT_NAMES {
+            assert!(Illuminant::from_name(name).is_some());
+        }
+    }
+
+    #[test]
+    fn adaptation_maps_white_points() {
+        let d50 = Illuminant::D50.white_point();
+        let adapted = chromatic_adaptation(
+            &Illuminant::D65.white_point(),
+            Illuminant::D65,
+            Illuminant::D50,
+        );
+
+        assert_relative_eq!(d50.x, adapted.x, epsilon = 1e-4);
+        assert_relative_eq!(d50.y, adapted.y, epsilon = 1e-4);
+        assert_relative_eq!(d50.z, adapted.z, epsilon = 1e-4);
+    }
+}
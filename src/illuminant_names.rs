@@ -0,0 +1,3 @@
+/// The names of all standard illuminants (see `Illuminant::from_name`).
+pub const ILLUMINANT_NAMES: &[&str] =
+    &["A", "C", "D50", "D55", "D65", "D75", "E", "F2", "F7", "F11"];
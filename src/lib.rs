@@ -1,18 +1,37 @@
 pub mod ansi;
+pub mod banding;
+pub mod blend;
+pub mod cluster;
 pub mod colorspace;
+pub mod curves;
 pub mod delta_e;
 pub mod distinct;
+mod error;
+pub mod gradient;
 mod helper;
+pub mod illuminant;
+pub mod lut;
+mod machado;
 pub mod named;
+pub mod noise;
 pub mod parser;
+pub mod pipeline;
+pub mod proof;
 pub mod random;
+pub mod theme;
 mod types;
 
 use std::{fmt, str::FromStr};
 
+use blend::BlendMode;
 use colorspace::ColorSpace;
-pub use helper::Fraction;
-use helper::{clamp, interpolate, interpolate_angle, mod_positive, MaxPrecision};
+use delta_e::DeltaE;
+pub use error::Error;
+use helper::{
+    bisect_max, clamp, fnv1a_64, interpolate, interpolate_hue, mod_positive, Fixed, MaxPrecision,
+};
+pub use helper::{linear_to_srgb, srgb_to_linear, Fraction};
+use illuminant::Illuminant;
 use types::{Hue, Scalar};
 
 /// The representation of a color.
@@ -153,8 +172,78 @@ impl Color {
         Self::from(&LCh { l, c, h, alpha })
     }
 
+    /// Deterministically map an arbitrary string (like a user name, host name or branch name)
+    /// to a color. The hue is derived from a stable hash of the string, while the lightness and
+    /// chroma are fixed (in OkLCh), such that all resulting colors look similarly pleasant. The
+    /// chroma is reduced if necessary to stay within the sRGB gamut.
+    ///
+    /// Reasonable values are a lightness of `0.7` and a chroma of `0.12`.
+    pub fn from_string_hash(text: &str, lightness: Scalar, chroma: Scalar) -> Color {
+        let hue = fnv1a_64(text.as_bytes()) as Scalar / u64::MAX as Scalar * 360.0;
+        let chroma = chroma.min(OkLab::max_chroma(lightness, hue));
+        Self::from(&OkLab::from_lch(lightness, chroma, hue, 1.0))
+    }
+
+    /// Create the color of a black-body radiator (Planckian light source) with the given
+    /// temperature in Kelvin, e.g. `2700` for warm incandescent light or `6500` for daylight.
+    /// The chromaticity is computed with the approximation of the Planckian locus by Krystek
+    /// (1985). The result is scaled to the brightest sRGB color with that chromaticity (channels
+    /// outside of the gamut are clipped). Temperatures are limited to the range from 1000 K to
+    /// 40000 K.
+    pub fn from_temperature(kelvin: Scalar) -> Color {
+        let t = clamp(1000.0, 40000.0, kelvin);
+
+        // Chromaticity in the CIE 1960 UCS
+        let u = (0.860_117_757 + 1.541_182_54e-4 * t + 1.286_412_12e-7 * t * t)
+            / (1.0 + 8.424_202_35e-4 * t + 7.081_451_63e-7 * t * t);
+        let v = (0.317_398_726 + 4.228_062_45e-5 * t + 4.204_816_91e-8 * t * t)
+            / (1.0 - 2.897_418_16e-5 * t + 1.614_560_53e-7 * t * t);
+
+        let d = 2.0 * u - 8.0 * v + 4.0;
+        let (x, y) = (3.0 * u / d, 2.0 * v / d);
+        let (x, y, z) = (x / y, 1.0, (1.0 - x - y) / y);
+
+        let (r, g, b) = xyz_to_linear_srgb(x, y, z);
+
+        let max = r.max(g).max(b);
+        let channel = |c: Scalar| linear_to_srgb(clamp(0.0, 1.0, c / max));
+        Color::from_rgb_float(channel(r), channel(g), channel(b))
+    }
+
+    /// Create the color of monochromatic light with the given wavelength in nanometers (visible
+    /// range: 380 nm to 780 nm), based on the CIE 1931 color matching functions. Spectral colors
+    /// lie outside of the sRGB gamut, so the channels are clipped. The result is scaled to full
+    /// brightness, but fades out towards both ends of the visible range. Wavelengths outside of
+    /// the visible range result in black.
+    pub fn from_wavelength(nm: Scalar) -> Color {
+        if !(380.0..=780.0).contains(&nm) {
+            return Color::black();
+        }
+
+        let position = (nm - 380.0) / 10.0;
+        let i = (position as usize).min(CIE_1931_CMF.len() - 2);
+        let t = position - i as Scalar;
+        let [x, y, z] =
+            [0, 1, 2].map(|c| (1.0 - t) * CIE_1931_CMF[i][c] + t * CIE_1931_CMF[i + 1][c]);
+
+        let (r, g, b) = xyz_to_linear_srgb(x, y, z);
+
+        let fade = if nm < 420.0 {
+            0.3 + 0.7 * (nm - 380.0) / 40.0
+        } else if nm > 700.0 {
+            0.3 + 0.7 * (780.0 - nm) / 80.0
+        } else {
+            1.0
+        };
+
+        let max = r.max(g).max(b);
+        let channel = |c: Scalar| linear_to_srgb(clamp(0.0, 1.0, fade * c / max));
+        Color::from_rgb_float(channel(r), channel(g), channel(b))
+    }
+
     /// Create a `Color` from  the four colours of the CMYK model: Cyan, Magenta, Yellow and Black.
-    /// The CMYK colours are subtractive. This means the colours get darker as you blend them together
+    /// The CMYK colours are subtractive. This means the colours get darker as you blend them together.
+    /// All values are numbers between 0.0 and 1.0 (as returned by `to_cmyk`).
     pub fn from_cmyk(c: Scalar, m: Scalar, y: Scalar, k: Scalar) -> Color {
         Self::from(&CMYK { c, m, y, k })
     }
@@ -183,12 +272,12 @@ impl Color {
             )
         };
         format!(
-            "hsl{a_prefix}({h:.0},{space}{s:.1}%,{space}{l:.1}%{a})",
+            "hsl{a_prefix}({h},{space}{s}%,{space}{l}%{a})",
             space = space,
             a_prefix = a_prefix,
-            h = self.hue.value(),
-            s = 100.0 * self.saturation,
-            l = 100.0 * self.lightness,
+            h = Fixed::wrap(0, self.hue.value()),
+            s = Fixed::wrap(1, 100.0 * self.saturation),
+            l = Fixed::wrap(1, 100.0 * self.lightness),
             a = a,
         )
     }
@@ -218,12 +307,12 @@ impl Color {
             )
         };
         format!(
-            "hsv{a_prefix}({h:.0},{space}{s:.1}%,{space}{v:.1}%{a})",
+            "hsv{a_prefix}({h},{space}{s}%,{space}{v}%{a})",
             space = space,
             a_prefix = a_prefix,
-            h = hsv.h,
-            s = 100.0 * hsv.s,
-            v = 100.0 * hsv.v,
+            h = Fixed::wrap(0, hsv.h),
+            s = Fixed::wrap(1, 100.0 * hsv.s),
+            v = Fixed::wrap(1, 100.0 * hsv.v),
             a = a,
         )
     }
@@ -273,10 +362,10 @@ impl Color {
         let cmyk = CMYK::from(self);
         format!(
             "cmyk({c},{space}{m},{space}{y},{space}{k})",
-            c = (cmyk.c * 100.0).round(),
-            m = (cmyk.m * 100.0).round(),
-            y = (cmyk.y * 100.0).round(),
-            k = (cmyk.k * 100.0).round(),
+            c = Fixed::wrap(0, cmyk.c * 100.0),
+            m = Fixed::wrap(0, cmyk.m * 100.0),
+            y = Fixed::wrap(0, cmyk.y * 100.0),
+            k = Fixed::wrap(0, cmyk.k * 100.0),
             space = if format == Format::Spaces { " " } else { "" }
         )
     }
@@ -299,12 +388,12 @@ impl Color {
             )
         };
         format!(
-            "rgb{a_prefix}({r:.3},{space}{g:.3},{space}{b:.3}{a})",
+            "rgb{a_prefix}({r},{space}{g},{space}{b}{a})",
             space = space,
             a_prefix = a_prefix,
-            r = rgba.r,
-            g = rgba.g,
-            b = rgba.b,
+            r = Fixed::wrap(3, rgba.r),
+            g = Fixed::wrap(3, rgba.g),
+            b = Fixed::wrap(3, rgba.b),
             a = a,
         )
     }
@@ -366,6 +455,22 @@ impl Color {
         LMS::from(self)
     }
 
+    /// Estimate the correlated color temperature (CCT) of this color in Kelvin, i.e. the
+    /// temperature of the black-body radiator whose color is closest to this color. This uses
+    /// the approximation by McCamy (1992), which is most accurate for colors close to the
+    /// Planckian locus (from about 2000 K to 12500 K).
+    pub fn estimate_cct(&self) -> Scalar {
+        let xyz = self.to_xyz();
+        let sum = xyz.x + xyz.y + xyz.z;
+        if sum == 0.0 {
+            return 0.0;
+        }
+        let (x, y) = (xyz.x / sum, xyz.y / sum);
+
+        let n = (x - 0.3320) / (0.1858 - y);
+        449.0 * n.powi(3) + 3525.0 * n.powi(2) + 6823.3 * n + 5520.33
+    }
+
     /// Get L, a and b coordinates according to the Lab color space.
     ///
     /// See: <https://en.wikipedia.org/wiki/Lab_color_space>
@@ -379,10 +484,10 @@ impl Color {
         let lab = Lab::from(self);
         let space = if format == Format::Spaces { " " } else { "" };
         format!(
-            "Lab({l:.0},{space}{a:.0},{space}{b:.0}{alpha})",
-            l = lab.l,
-            a = lab.a,
-            b = lab.b,
+            "Lab({l},{space}{a},{space}{b}{alpha})",
+            l = Fixed::wrap(0, lab.l),
+            a = Fixed::wrap(0, lab.a),
+            b = Fixed::wrap(0, lab.b),
             space = space,
             alpha = if self.alpha == 1.0 {
                 "".to_string()
@@ -409,10 +514,10 @@ impl Color {
         let oklab = OkLab::from(self);
         let space = if format == Format::Spaces { " " } else { "" };
         format!(
-            "OkLab({l:.4},{space}{a:.4},{space}{b:.4}{alpha})",
-            l = oklab.l,
-            a = oklab.a,
-            b = oklab.b,
+            "OkLab({l},{space}{a},{space}{b}{alpha})",
+            l = Fixed::wrap(4, oklab.l),
+            a = Fixed::wrap(4, oklab.a),
+            b = Fixed::wrap(4, oklab.b),
             space = space,
             alpha = if self.alpha == 1.0 {
                 "".to_string()
@@ -439,10 +544,10 @@ impl Color {
         let lch = LCh::from(self);
         let space = if format == Format::Spaces { " " } else { "" };
         format!(
-            "LCh({l:.0},{space}{c:.0},{space}{h:.0}{alpha})",
-            l = lch.l,
-            c = lch.c,
-            h = lch.h,
+            "LCh({l},{space}{c},{space}{h}{alpha})",
+            l = Fixed::wrap(0, lch.l),
+            c = Fixed::wrap(0, lch.c),
+            h = Fixed::wrap(0, lch.h),
             space = space,
             alpha = if self.alpha == 1.0 {
                 "".to_string()
@@ -556,6 +661,120 @@ impl Color {
         self.rotate_hue(180.0)
     }
 
+    /// Rotate the hue of this color by the given number of degrees in the given hue space.
+    /// In OkLCh, lightness and chroma are preserved (the chroma is reduced if necessary to stay
+    /// within the sRGB gamut).
+    pub fn rotate_hue_in(&self, delta: Scalar, space: HueSpace) -> Color {
+        match space {
+            HueSpace::HSL => self.rotate_hue(delta),
+            HueSpace::OkLCh => {
+                let OkLCh { l, c, h, alpha } = OkLCh::from(self);
+                let h = h + delta;
+                let c = c.min(OkLab::max_chroma(l, h));
+                OkLCh { l, c, h, alpha }.into_color()
+            }
+        }
+    }
+
+    /// The color harmony consisting of this color and the two colors adjacent to its
+    /// complementary color (hue rotated by 150° and 210°).
+    pub fn split_complementary(&self, space: HueSpace) -> Vec<Color> {
+        vec![
+            self.clone(),
+            self.rotate_hue_in(150.0, space),
+            self.rotate_hue_in(210.0, space),
+        ]
+    }
+
+    /// The color harmony consisting of three colors that are evenly spaced around the color
+    /// wheel (starting with this color).
+    pub fn triadic(&self, space: HueSpace) -> Vec<Color> {
+        vec![
+            self.clone(),
+            self.rotate_hue_in(120.0, space),
+            self.rotate_hue_in(240.0, space),
+        ]
+    }
+
+    /// The color harmony consisting of four colors that are evenly spaced around the color
+    /// wheel (starting with this color), i.e. two pairs of complementary colors.
+    pub fn tetradic(&self, space: HueSpace) -> Vec<Color> {
+        vec![
+            self.clone(),
+            self.rotate_hue_in(90.0, space),
+            self.rotate_hue_in(180.0, space),
+            self.rotate_hue_in(270.0, space),
+        ]
+    }
+
+    /// The color harmony consisting of `n` neighboring colors on the color wheel, separated by
+    /// the given angle (in degrees) and centered around this color.
+    pub fn analogous(&self, n: usize, angle: Scalar, space: HueSpace) -> Vec<Color> {
+        let center = (n as Scalar - 1.0) / 2.0;
+        (0..n)
+            .map(|i| self.rotate_hue_in((i as Scalar - center) * angle, space))
+            .collect()
+    }
+
+    /// The color harmony consisting of `n` colors with the same hue as this color, but with
+    /// evenly spaced lightness values (from dark to light). In OkLCh, the chroma is preserved
+    /// as far as possible within the sRGB gamut.
+    pub fn monochromatic(&self, n: usize, space: HueSpace) -> Vec<Color> {
+        let lightness = |i: usize| (i as Scalar + 1.0) / (n as Scalar + 1.0);
+        match space {
+            HueSpace::HSL => (0..n)
+                .map(|i| {
+                    Color::from_hsla(self.hue.value(), self.saturation, lightness(i), self.alpha)
+                })
+                .collect(),
+            HueSpace::OkLCh => {
+                let OkLCh { c, h, alpha, .. } = OkLCh::from(self);
+                (0..n)
+                    .map(|i| {
+                        let l = lightness(i);
+                        let c = c.min(OkLab::max_chroma(l, h));
+                        OkLCh { l, c, h, alpha }.into_color()
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// A Material/Tailwind-style tonal palette with the steps 50, 100, 200, …, 900 (from light to
+    /// dark). The steps have fixed lightness values in OkLCh and keep the hue of this color. The
+    /// chroma follows a curve that peaks in the mid tones (relative to the chroma of this color)
+    /// and is reduced where necessary to stay within the sRGB gamut. The step with the lightness
+    /// closest to this color is this color itself.
+    pub fn tonal_palette(&self) -> Vec<(u16, Color)> {
+        let oklch = OkLCh::from(self);
+
+        let distance = |&(_, l, _): &(u16, Scalar, Scalar)| (l - oklch.l).abs();
+        let anchor = (0..TONAL_PALETTE_STEPS.len())
+            .min_by(|&i, &j| {
+                distance(&TONAL_PALETTE_STEPS[i]).total_cmp(&distance(&TONAL_PALETTE_STEPS[j]))
+            })
+            .expect("at least one step");
+        let base_chroma = oklch.c / TONAL_PALETTE_STEPS[anchor].2;
+
+        TONAL_PALETTE_STEPS
+            .iter()
+            .enumerate()
+            .map(|(i, &(step, l, relative_chroma))| {
+                if i == anchor {
+                    return (step, self.clone());
+                }
+                let c = (base_chroma * relative_chroma).min(OkLab::max_chroma(l, oklch.h));
+                let color = OkLCh {
+                    l,
+                    c,
+                    h: oklch.h,
+                    alpha: oklch.alpha,
+                };
+                (step, color.into_color())
+            })
+            .collect()
+    }
+
     /// Lighten a color by adding a certain amount (number between -1.0 and 1.0) to the lightness
     /// channel. If the number is negative, the color is darkened.
     pub fn lighten(&self, f: Scalar) -> Color {
@@ -612,11 +831,112 @@ impl Color {
                 let s = -0.867_447_36 * l + 1.867_270_89 * m;
                 (l, m, s, alpha)
             }
+            ColorblindnessType::Achromatopsia => {
+                return self.simulate_color_vision_deficiency(cb_ty, 1.0);
+            }
         };
 
         Color::from_lms(l, m, s, alpha)
     }
 
+    /// Simulate what a person with a color vision deficiency of the given severity (between 0.0
+    /// for normal color vision and 1.0 for the full deficiency) sees, according to the model by
+    /// Machado et al. (2009). Severities below 1.0 correspond to anomalous trichromacy
+    /// (protanomaly, deuteranomaly and tritanomaly), which is much more common than dichromacy.
+    /// For achromatopsia, the colors are faded to gray tones of the same luminance.
+    pub fn simulate_color_vision_deficiency(
+        &self,
+        cb_ty: ColorblindnessType,
+        severity: Scalar,
+    ) -> Color {
+        let linear = self.to_linear_rgb();
+        let [r, g, b] = machado::apply(&machado::matrix(cb_ty, severity), linear);
+        let channel = |c: Scalar| linear_to_srgb(clamp(0.0, 1.0, c));
+
+        Color::from_rgba_float(channel(r), channel(g), channel(b), self.alpha)
+    }
+
+    /// Shift this color such that it is easier to distinguish for people with the given kind of
+    /// color vision deficiency (daltonization). The information that gets lost in the simulated
+    /// dichromatic view is redistributed into the color channels that can still be perceived,
+    /// following Fidaner et al. The strength scales the correction, 0.0 leaves the color
+    /// unchanged. Daltonization can not help with achromatopsia, so the color is returned as is.
+    pub fn daltonize(&self, cb_ty: ColorblindnessType, strength: Scalar) -> Color {
+        // How the lost information is shifted into the remaining channels
+        let shift = match cb_ty {
+            ColorblindnessType::Protanopia | ColorblindnessType::Deuteranopia => {
+                [[0.0, 0.0, 0.0], [0.7, 1.0, 0.0], [0.7, 0.0, 1.0]]
+            }
+            ColorblindnessType::Tritanopia => [[1.0, 0.0, 0.7], [0.0, 1.0, 0.7], [0.0, 0.0, 0.0]],
+            ColorblindnessType::Achromatopsia => return self.clone(),
+        };
+
+        let linear = self.to_linear_rgb();
+        let simulated = machado::apply(&machado::matrix(cb_ty, 1.0), linear);
+        let error = [
+            linear[0] - simulated[0],
+            linear[1] - simulated[1],
+            linear[2] - simulated[2],
+        ];
+        let correction = machado::apply(&shift, error);
+        let channel =
+            |i: usize| linear_to_srgb(clamp(0.0, 1.0, linear[i] + strength * correction[i]));
+
+        Color::from_rgba_float(channel(0), channel(1), channel(2), self.alpha)
+    }
+
+    /// The linear light intensities of the red, green and blue channels (sRGB).
+    fn to_linear_rgb(&self) -> [Scalar; 3] {
+        let rgba = self.to_rgba_float();
+        [
+            srgb_to_linear(rgba.r),
+            srgb_to_linear(rgba.g),
+            srgb_to_linear(rgba.b),
+        ]
+    }
+
+    /// Adjust the CIE LCh lightness of this color to match the lightness of the reference color,
+    /// such that both have the same visual weight. The hue is kept, and the chroma is reduced if
+    /// necessary to stay within the sRGB gamut.
+    pub fn match_lightness(&self, reference: &Color) -> Color {
+        let lch = self.to_lch();
+        let l = clamp(0.0, 100.0, reference.to_lch().l);
+        let c = lch.c.min(LCh::max_chroma(l, lch.h));
+        Color::from_lch(l, c, lch.h, self.alpha)
+    }
+
+    /// Set the OkLCh chroma of this color to the given value, keeping its lightness and hue. The
+    /// chroma is reduced if necessary to stay within the sRGB gamut. Achromatic colors (grays)
+    /// have no defined hue and are returned unchanged.
+    pub fn with_oklch_chroma(&self, chroma: Scalar) -> Color {
+        let oklch = OkLCh::from(self);
+        if oklch.c < 1e-4 {
+            return self.clone();
+        }
+        let c = chroma.max(0.0).min(OkLab::max_chroma(oklch.l, oklch.h));
+        OkLCh { c, ..oklch }.into_color()
+    }
+
+    /// Return the most colorful color with the same CIE LCh lightness and hue that can still be
+    /// represented in the sRGB gamut (see `LCh::max_chroma`). Achromatic colors (grays) have no
+    /// defined hue and are returned unchanged.
+    pub fn maximize_chroma(&self) -> Color {
+        let lch = self.to_lch();
+        if lch.c < 0.1 {
+            return self.clone();
+        }
+        Color::from_lch(lch.l, LCh::max_chroma(lch.l, lch.h), lch.h, lch.alpha)
+    }
+
+    /// Simulate how a surface of this color (as seen under daylight, i.e. the D65 white point of
+    /// sRGB) would appear under a different light source, assuming that the eye does not adapt
+    /// to the new light. The color is transformed with a chromatic adaptation transform from D65
+    /// to the given illuminant (see `illuminant::chromatic_adaptation`).
+    pub fn under_illuminant(&self, illuminant: Illuminant) -> Color {
+        let xyz = illuminant::chromatic_adaptation(&self.to_xyz(), Illuminant::D65, illuminant);
+        Color::from(&xyz)
+    }
+
     /// Convert a color to a gray tone with the same perceived luminance (see `luminance`).
     pub fn to_gray(&self) -> Color {
         let hue = self.hue;
@@ -673,16 +993,172 @@ impl Color {
     ///
     /// <https://www.w3.org/TR/2008/REC-WCAG20-20081211/#contrast-ratiodef>
     pub fn contrast_ratio(&self, other: &Color) -> Scalar {
+        self.contrast_ratio_details(other).contrast
+    }
+
+    /// Like `contrast_ratio`, but also returns the relative luminances of both colors.
+    pub fn contrast_ratio_details(&self, other: &Color) -> Contrast {
         let l_self = self.luminance();
         let l_other = other.luminance();
 
-        if l_self > l_other {
+        let contrast = if l_self > l_other {
             (l_self + 0.05) / (l_other + 0.05)
         } else {
             (l_other + 0.05) / (l_self + 0.05)
+        };
+
+        Contrast {
+            contrast,
+            text_luminance: l_self,
+            background_luminance: l_other,
+        }
+    }
+
+    /// Lightness contrast of this color (as text color) on the given background color, as
+    /// defined by the APCA algorithm (Accessible Perceptual Contrast Algorithm, the candidate
+    /// contrast method for WCAG 3). Unlike the WCAG 2 contrast ratio, the result depends on the
+    /// polarity: dark text on a light background yields a positive value (up to about 106),
+    /// light text on a dark background a negative value (down to about -108). Values around
+    /// ±60 or more (in magnitude) are recommended for body text.
+    ///
+    /// <https://github.com/Myndex/apca-w3>
+    pub fn contrast_apca(&self, background: &Color) -> Scalar {
+        self.contrast_apca_details(background).contrast
+    }
+
+    /// Like `contrast_apca`, but also returns the estimated screen luminances (Y) of both colors
+    /// on which the APCA contrast is based.
+    pub fn contrast_apca_details(&self, background: &Color) -> Contrast {
+        // Screen luminance estimate with a soft clamp for very dark colors
+        fn y(color: &Color) -> Scalar {
+            let c = color.to_rgba_float();
+            let y =
+                0.2126729 * c.r.powf(2.4) + 0.7151522 * c.g.powf(2.4) + 0.0721750 * c.b.powf(2.4);
+            if y < 0.022 {
+                y + (0.022 - y).powf(1.414)
+            } else {
+                y
+            }
+        }
+
+        let y_text = y(self);
+        let y_background = y(background);
+
+        let contrast = if (y_background - y_text).abs() < 0.0005 {
+            0.0
+        } else if y_background > y_text {
+            // Dark text on a light background
+            let sapc = (y_background.powf(0.56) - y_text.powf(0.57)) * 1.14;
+            if sapc < 0.1 {
+                0.0
+            } else {
+                sapc - 0.027
+            }
+        } else {
+            // Light text on a dark background
+            let sapc = (y_background.powf(0.65) - y_text.powf(0.62)) * 1.14;
+            if sapc > -0.1 {
+                0.0
+            } else {
+                sapc + 0.027
+            }
+        };
+
+        Contrast {
+            contrast: contrast * 100.0,
+            text_luminance: y_text,
+            background_luminance: y_background,
+        }
+    }
+
+    /// Adjust the (OkLCh) lightness of this color, such that it reaches at least the given
+    /// contrast ratio with respect to the background color. Hue and chroma are preserved as far
+    /// as possible. The color is made lighter or darker, whichever requires the smaller change.
+    /// If the contrast ratio can not be reached at all, black or white is returned (whichever
+    /// has the higher contrast).
+    pub fn with_minimum_contrast(&self, background: &Color, ratio: Scalar) -> Color {
+        self.adjust_contrast(background, ratio, ContrastAlgorithm::Wcag2)
+            .color
+    }
+
+    /// Minimally adjust the (OkLCh) lightness of this color (as text color), such that it
+    /// reaches at least the given contrast on the background color, according to the given
+    /// algorithm. For APCA, the target is an absolute Lc value, i.e. both polarities count.
+    /// Hue and chroma are preserved as far as possible. The color is made lighter or darker,
+    /// whichever requires the smaller change. If the target can not be reached at all, the
+    /// result is black or white (whichever has the higher contrast) and is marked as such.
+    pub fn adjust_contrast(
+        &self,
+        background: &Color,
+        target: Scalar,
+        algorithm: ContrastAlgorithm,
+    ) -> ContrastAdjustment {
+        let result = |color: Color| {
+            let contrast = algorithm.contrast(&color, background);
+            ContrastAdjustment {
+                color,
+                contrast,
+                achieved: contrast >= target,
+            }
+        };
+
+        if algorithm.contrast(self, background) >= target {
+            return result(self.clone());
+        }
+
+        let oklch = OkLCh::from(self);
+        let with_lightness = |l: Scalar| {
+            let c = oklch.c.min(OkLab::max_chroma(l, oklch.h));
+            OkLCh {
+                l,
+                c,
+                h: oklch.h,
+                alpha: oklch.alpha,
+            }
+            .into_color()
+        };
+
+        // Only consider colors on one side of the background, such that the contrast increases
+        // monotonically while moving away from it
+        let l_background = background.luminance();
+        let lighter_reaches = |l: Scalar| {
+            let color = with_lightness(l);
+            color.luminance() > l_background && algorithm.contrast(&color, background) >= target
+        };
+        let darker_reaches = |l: Scalar| {
+            let color = with_lightness(l);
+            color.luminance() < l_background && algorithm.contrast(&color, background) >= target
+        };
+
+        let lighter = lighter_reaches(1.0)
+            .then(|| 1.0 - bisect_max(0.0, 1.0 - oklch.l, |t| lighter_reaches(1.0 - t)));
+        let darker = darker_reaches(0.0).then(|| bisect_max(0.0, oklch.l, darker_reaches));
+
+        match (lighter, darker) {
+            (Some(lighter), Some(darker)) if lighter - oklch.l <= oklch.l - darker => {
+                result(with_lightness(lighter))
+            }
+            (Some(lighter), None) => result(with_lightness(lighter)),
+            (_, Some(darker)) => result(with_lightness(darker)),
+            (None, None) => {
+                if algorithm.contrast(&Color::white(), background)
+                    > algorithm.contrast(&Color::black(), background)
+                {
+                    result(Color::white())
+                } else {
+                    result(Color::black())
+                }
+            }
         }
     }
 
+    /// The colorfulness of a single color according to the metric by Hasler and Süsstrunk (see
+    /// the free function `colorfulness`). A value of 0.0 corresponds to a gray tone, while fully
+    /// saturated colors reach values between ~70 and ~110.
+    pub fn colorfulness(&self) -> Scalar {
+        colorfulness(std::slice::from_ref(self))
+    }
+
     /// Return a readable foreground text color (either `black` or `white`) for a
     /// given background color.
     pub fn text_color(&self) -> Color {
@@ -708,6 +1184,11 @@ impl Color {
         delta_e::cie76(&self.to_lab(), &other.to_lab())
     }
 
+    /// Like `distance_delta_e_cie76`, but broken down into lightness, chroma and hue differences.
+    pub fn distance_delta_e_cie76_details(&self, other: &Color) -> DeltaE {
+        delta_e::cie76_components(&self.to_lab(), &other.to_lab())
+    }
+
     /// Compute the perceived 'distance' between two colors according to the CIEDE2000 delta-E
     /// standard.
     ///
@@ -716,6 +1197,25 @@ impl Color {
         delta_e::ciede2000(&self.to_lab(), &other.to_lab())
     }
 
+    /// Like `distance_delta_e_ciede2000`, but broken down into the (weighted) lightness, chroma
+    /// and hue terms.
+    pub fn distance_delta_e_ciede2000_details(&self, other: &Color) -> DeltaE {
+        delta_e::ciede2000_components(&self.to_lab(), &other.to_lab())
+    }
+
+    /// Compute the perceived 'distance' between two colors as the Euclidean distance in the
+    /// OkLab color space (ΔE OK, as in CSS Color 4). Note that OkLab coordinates have a much
+    /// smaller range than CIELAB ones, a distance of ~0.02 is just noticeable.
+    pub fn distance_oklab(&self, other: &Color) -> Scalar {
+        self.distance_oklab_details(other).total
+    }
+
+    /// Like `distance_oklab`, but broken down into lightness, chroma and hue differences.
+    pub fn distance_oklab_details(&self, other: &Color) -> DeltaE {
+        let (c1, c2) = (self.to_oklab(), other.to_oklab());
+        delta_e::euclidean_components([c1.l, c1.a, c1.b], [c2.l, c2.a, c2.b])
+    }
+
     /// Mix two colors by linearly interpolating between them in the specified color space.
     /// For the angle-like components (hue), the shortest path along the unit circle is chosen.
     pub fn mix<C: ColorSpace>(self: &Color, other: &Color, fraction: Fraction) -> Color {
@@ -724,6 +1224,72 @@ impl Color {
             .into_color()
     }
 
+    /// Like `mix`, but with the given direction around the hue circle for cylindrical color
+    /// spaces (HSL, HSV, LCh and OkLCh). For example, mixing red with itself in HSL with
+    /// `HueInterpolation::Longer` passes through all hues.
+    pub fn mix_with_hue<C: ColorSpace>(
+        &self,
+        other: &Color,
+        fraction: Fraction,
+        hue: HueInterpolation,
+    ) -> Color {
+        C::from_color(self)
+            .mix_with_hue(&C::from_color(other), fraction, hue)
+            .into_color()
+    }
+
+    /// Apply a tint to this color by combining it channel-wise with the tint color, in linear
+    /// RGB space. This can be used to give a whole palette a warm or cool cast, for example by
+    /// multiplying all colors with `#ffeedd`. The alpha channel of this color is preserved.
+    pub fn tint(&self, tint: &Color, operation: TintOperation) -> Color {
+        let rgba = self.to_rgba_float();
+        let tint = tint.to_rgba_float();
+
+        let combine = |c: Scalar, t: Scalar| {
+            let (c, t) = (srgb_to_linear(c), srgb_to_linear(t));
+            let result = match operation {
+                TintOperation::Add => c + t,
+                TintOperation::Subtract => c - t,
+                TintOperation::Multiply => c * t,
+            };
+            linear_to_srgb(clamp(0.0, 1.0, result))
+        };
+
+        Color::from_rgba_float(
+            combine(rgba.r, tint.r),
+            combine(rgba.g, tint.g),
+            combine(rgba.b, tint.b),
+            rgba.alpha,
+        )
+    }
+
+    /// Blend the source color over this color (the backdrop) with the given blend mode. The
+    /// blending is performed on the gamma-encoded RGB values, followed by alpha compositing (as
+    /// for the `mix-blend-mode` property in CSS).
+    pub fn blend(&self, source: &Color, mode: BlendMode) -> Color {
+        let backdrop = self.to_rgba_float();
+        let source = source.to_rgba_float();
+
+        let cb = [backdrop.r, backdrop.g, backdrop.b];
+        let cs = [source.r, source.g, source.b];
+        let blended = blend::blend_rgb(mode, cb, cs);
+
+        // Where the backdrop is transparent, the source color shows through unchanged
+        let a_b = backdrop.alpha;
+        let a_s = source.alpha;
+        let a_o = a_s + a_b * (1.0 - a_s);
+        if a_o == 0.0 {
+            return Color::from_rgba_float(0.0, 0.0, 0.0, 0.0);
+        }
+
+        let channel = |i: usize| {
+            let c_s = (1.0 - a_b) * cs[i] + a_b * clamp(0.0, 1.0, blended[i]);
+            (a_s * c_s + a_b * cb[i] * (1.0 - a_s)) / a_o
+        };
+
+        Color::from_rgba_float(channel(0), channel(1), channel(2), a_o)
+    }
+
     /// Alpha composite two colors, placing the second over the first.
     pub fn composite(&self, source: &Color) -> Color {
         let backdrop = self.to_rgba();
@@ -775,10 +1341,10 @@ impl PartialEq for Color {
 }
 
 impl FromStr for Color {
-    type Err = &'static str;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parser::parse_color(s).ok_or("invalid color string")
+        parser::parse_color(s).ok_or_else(|| Error::InvalidColor(s.into()))
     }
 }
 
@@ -863,20 +1429,81 @@ impl From<&RGBA<f64>> for Color {
     }
 }
 
+/// CIE 1931 2° standard observer color matching functions (x̄, ȳ, z̄) from 380 nm to 780 nm in
+/// steps of 10 nm.
+#[rustfmt::skip]
+const CIE_1931_CMF: [[Scalar; 3]; 41] = [
+    [0.001368, 0.000039, 0.006450],
+    [0.004243, 0.000120, 0.020050],
+    [0.014310, 0.000396, 0.067850],
+    [0.043510, 0.001210, 0.207400],
+    [0.134380, 0.004000, 0.645600],
+    [0.283900, 0.011600, 1.385600],
+    [0.348280, 0.023000, 1.747060],
+    [0.336200, 0.038000, 1.772110],
+    [0.290800, 0.060000, 1.669200],
+    [0.195360, 0.090980, 1.287640],
+    [0.095640, 0.139020, 0.812950],
+    [0.032010, 0.208020, 0.465180],
+    [0.004900, 0.323000, 0.272000],
+    [0.009300, 0.503000, 0.158200],
+    [0.063270, 0.710000, 0.078250],
+    [0.165500, 0.862000, 0.042160],
+    [0.290400, 0.954000, 0.020300],
+    [0.433450, 0.994950, 0.008750],
+    [0.594500, 0.995000, 0.003900],
+    [0.762100, 0.952000, 0.002100],
+    [0.916300, 0.870000, 0.001650],
+    [1.026300, 0.757000, 0.001100],
+    [1.062200, 0.631000, 0.000800],
+    [1.002600, 0.503000, 0.000340],
+    [0.854450, 0.381000, 0.000190],
+    [0.642400, 0.265000, 0.000050],
+    [0.447900, 0.175000, 0.000020],
+    [0.283500, 0.107000, 0.000000],
+    [0.164900, 0.061000, 0.000000],
+    [0.087400, 0.032000, 0.000000],
+    [0.046770, 0.017000, 0.000000],
+    [0.022700, 0.008210, 0.000000],
+    [0.011359, 0.004102, 0.000000],
+    [0.005790, 0.002091, 0.000000],
+    [0.002899, 0.001047, 0.000000],
+    [0.001440, 0.000520, 0.000000],
+    [0.000690, 0.000249, 0.000000],
+    [0.000332, 0.000120, 0.000000],
+    [0.000166, 0.000060, 0.000000],
+    [0.000083, 0.000030, 0.000000],
+    [0.000042, 0.000015, 0.000000],
+];
+
+/// Convert XYZ coordinates to linear sRGB values (not clipped).
+fn xyz_to_linear_srgb(x: Scalar, y: Scalar, z: Scalar) -> (Scalar, Scalar, Scalar) {
+    (
+        3.2406 * x - 1.5372 * y - 0.4986 * z,
+        -0.9689 * x + 1.8758 * y + 0.0415 * z,
+        0.0557 * x - 0.2040 * y + 1.0570 * z,
+    )
+}
+
+/// Convert XYZ coordinates to (gamma-encoded) sRGB values. The values are not clipped and may lie
+/// outside of the range from 0.0 to 1.0 if the color is outside of the sRGB gamut.
+fn xyz_to_srgb(color: &XYZ) -> (Scalar, Scalar, Scalar) {
+    let f = |c| {
+        if c <= 0.003_130_8 {
+            12.92 * c
+        } else {
+            1.055 * Scalar::powf(c, 1.0 / 2.4) - 0.055
+        }
+    };
+
+    let (r, g, b) = xyz_to_linear_srgb(color.x, color.y, color.z);
+
+    (f(r), f(g), f(b))
+}
+
 impl From<&XYZ> for Color {
     fn from(color: &XYZ) -> Self {
-        #![allow(clippy::many_single_char_names)]
-        let f = |c| {
-            if c <= 0.003_130_8 {
-                12.92 * c
-            } else {
-                1.055 * Scalar::powf(c, 1.0 / 2.4) - 0.055
-            }
-        };
-
-        let r = f(3.2406 * color.x - 1.5372 * color.y - 0.4986 * color.z);
-        let g = f(-0.9689 * color.x + 1.8758 * color.y + 0.0415 * color.z);
-        let b = f(0.0557 * color.x - 0.2040 * color.y + 1.0570 * color.z);
+        let (r, g, b) = xyz_to_srgb(color);
 
         Self::from(&RGBA::<f64> {
             r,
@@ -904,64 +1531,19 @@ impl From<&LMS> for Color {
 
 impl From<&Lab> for Color {
     fn from(color: &Lab) -> Self {
-        #![allow(clippy::many_single_char_names)]
-        const DELTA: Scalar = 6.0 / 29.0;
-
-        let finv = |t| {
-            if t > DELTA {
-                Scalar::powf(t, 3.0)
-            } else {
-                3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
-            }
-        };
-
-        let l_ = (color.l + 16.0) / 116.0;
-        let x = D65_XN * finv(l_ + color.a / 500.0);
-        let y = D65_YN * finv(l_);
-        let z = D65_ZN * finv(l_ - color.b / 200.0);
-
-        Self::from(&XYZ {
-            x,
-            y,
-            z,
-            alpha: color.alpha,
-        })
+        Self::from(&XYZ::from(color))
     }
 }
 
 impl From<&OkLab> for Color {
     fn from(color: &OkLab) -> Self {
-        let l = (1.0 * color.l + 0.39633779 * color.a + 0.21580376 * color.b).powi(3);
-        let m = (1.00000001 * color.l + -0.10556134 * color.a + -0.06385417 * color.b).powi(3);
-        let s = (1.00000005 * color.l + -0.08948418 * color.a + -1.29148554 * color.b).powi(3);
-
-        let x = 1.22701385 * l + -0.55779998 * m + 0.28125615 * s;
-        let y = -0.04058018 * l + 1.11225687 * m + -0.07167668 * s;
-        let z = -0.07638128 * l + -0.42148198 * m + 1.58616322 * s;
-
-        Self::from(&XYZ {
-            x,
-            y,
-            z,
-            alpha: color.alpha,
-        })
+        Self::from(&XYZ::from(color))
     }
 }
 
 impl From<&LCh> for Color {
     fn from(color: &LCh) -> Self {
-        #![allow(clippy::many_single_char_names)]
-        const DEG2RAD: Scalar = std::f64::consts::PI / 180.0;
-
-        let a = color.c * Scalar::cos(color.h * DEG2RAD);
-        let b = color.c * Scalar::sin(color.h * DEG2RAD);
-
-        Self::from(&Lab {
-            l: color.l,
-            a,
-            b,
-            alpha: color.alpha,
-        })
+        Self::from(&Lab::from(color))
     }
 }
 
@@ -969,9 +1551,9 @@ impl From<&LCh> for Color {
 impl From<&CMYK> for Color {
     fn from(color: &CMYK) -> Self {
         #![allow(clippy::many_single_char_names)]
-        let r = 255.0 * ((1.0 - color.c) / 100.0) * ((1.0 - color.k) / 100.0);
-        let g = 255.0 * ((1.0 - color.m) / 100.0) * ((1.0 - color.k) / 100.0);
-        let b = 255.0 * ((1.0 - color.y) / 100.0) * ((1.0 - color.k) / 100.0);
+        let r = (1.0 - color.c) * (1.0 - color.k);
+        let g = (1.0 - color.m) * (1.0 - color.k);
+        let b = (1.0 - color.y) * (1.0 - color.k);
 
         Color::from(&RGBA::<f64> {
             r,
@@ -982,6 +1564,26 @@ impl From<&CMYK> for Color {
     }
 }
 
+/// The contrast between a text color and a background color, along with the luminances of both
+/// colors it is based on (see `Color::contrast_ratio_details` and `Color::contrast_apca_details`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contrast {
+    pub contrast: Scalar,
+    pub text_luminance: Scalar,
+    pub background_luminance: Scalar,
+}
+
+/// The result of `Color::adjust_contrast`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastAdjustment {
+    /// The adjusted color
+    pub color: Color,
+    /// The contrast of the adjusted color on the background
+    pub contrast: Scalar,
+    /// Whether the adjusted color reaches the target contrast
+    pub achieved: bool,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct RGBA<T> {
     pub r: T,
@@ -1088,12 +1690,22 @@ impl ColorSpace for HSLA {
     }
 
     fn mix(&self, other: &Self, fraction: Fraction) -> Self {
+        self.mix_with_hue(other, fraction, HueInterpolation::Shorter)
+    }
+
+    fn mix_with_hue(&self, other: &Self, fraction: Fraction, hue: HueInterpolation) -> Self {
         // make sure that the hue is preserved when mixing with gray colors
+        let is_gray = self.s < 0.0001 || other.s < 0.0001;
         let self_hue = if self.s < 0.0001 { other.h } else { self.h };
         let other_hue = if other.s < 0.0001 { self.h } else { other.h };
+        let hue = if is_gray {
+            HueInterpolation::Shorter
+        } else {
+            hue
+        };
 
         Self {
-            h: interpolate_angle(self_hue, other_hue, fraction),
+            h: interpolate_hue(self_hue, other_hue, fraction, hue),
             s: interpolate(self.s, other.s, fraction),
             l: interpolate(self.l, other.l, fraction),
             alpha: interpolate(self.alpha, other.alpha, fraction),
@@ -1136,12 +1748,22 @@ impl ColorSpace for HSVA {
     }
 
     fn mix(&self, other: &Self, fraction: Fraction) -> Self {
+        self.mix_with_hue(other, fraction, HueInterpolation::Shorter)
+    }
+
+    fn mix_with_hue(&self, other: &Self, fraction: Fraction, hue: HueInterpolation) -> Self {
         // make sure that the hue is preserved when mixing with gray colors
+        let is_gray = self.s < 0.0001 || other.s < 0.0001;
         let self_hue = if self.s < 0.0001 { other.h } else { self.h };
         let other_hue = if other.s < 0.0001 { self.h } else { other.h };
+        let hue = if is_gray {
+            HueInterpolation::Shorter
+        } else {
+            hue
+        };
 
         Self {
-            h: interpolate_angle(self_hue, other_hue, fraction),
+            h: interpolate_hue(self_hue, other_hue, fraction, hue),
             s: interpolate(self.s, other.s, fraction),
             v: interpolate(self.v, other.v, fraction),
             alpha: interpolate(self.alpha, other.alpha, fraction),
@@ -1212,6 +1834,66 @@ impl From<&Color> for XYZ {
     }
 }
 
+impl From<&Lab> for XYZ {
+    fn from(color: &Lab) -> Self {
+        #![allow(clippy::many_single_char_names)]
+        const DELTA: Scalar = 6.0 / 29.0;
+
+        let finv = |t| {
+            if t > DELTA {
+                Scalar::powf(t, 3.0)
+            } else {
+                3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+            }
+        };
+
+        let l_ = (color.l + 16.0) / 116.0;
+        let x = D65_XN * finv(l_ + color.a / 500.0);
+        let y = D65_YN * finv(l_);
+        let z = D65_ZN * finv(l_ - color.b / 200.0);
+
+        XYZ {
+            x,
+            y,
+            z,
+            alpha: color.alpha,
+        }
+    }
+}
+
+impl From<&OkLab> for XYZ {
+    fn from(color: &OkLab) -> Self {
+        let l = (1.0 * color.l + 0.39633779 * color.a + 0.21580376 * color.b).powi(3);
+        let m = (1.00000001 * color.l + -0.10556134 * color.a + -0.06385417 * color.b).powi(3);
+        let s = (1.00000005 * color.l + -0.08948418 * color.a + -1.29148554 * color.b).powi(3);
+
+        let x = 1.22701385 * l + -0.55779998 * m + 0.28125615 * s;
+        let y = -0.04058018 * l + 1.11225687 * m + -0.07167668 * s;
+        let z = -0.07638128 * l + -0.42148198 * m + 1.58616322 * s;
+
+        XYZ {
+            x,
+            y,
+            z,
+            alpha: color.alpha,
+        }
+    }
+}
+
+impl XYZ {
+    /// Check whether the color can be represented in the sRGB color space (up to the 8-bit
+    /// precision of the RGB channels), i.e. whether it can be converted to a `Color` without
+    /// clipping.
+    pub fn is_in_gamut(&self) -> bool {
+        const TOLERANCE: Scalar = 0.5 / 255.0;
+
+        let (r, g, b) = xyz_to_srgb(self);
+        [r, g, b]
+            .iter()
+            .all(|c| (-TOLERANCE..=1.0 + TOLERANCE).contains(c))
+    }
+}
+
 impl fmt::Display for XYZ {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "XYZ({x}, {y}, {z})", x = self.x, y = self.y, z = self.z,)
@@ -1301,14 +1983,37 @@ impl From<&Color> for Lab {
     }
 }
 
-impl fmt::Display for Lab {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Lab({l}, {a}, {b})", l = self.l, a = self.a, b = self.b,)
+impl From<&LCh> for Lab {
+    fn from(color: &LCh) -> Self {
+        const DEG2RAD: Scalar = std::f64::consts::PI / 180.0;
+
+        let a = color.c * Scalar::cos(color.h * DEG2RAD);
+        let b = color.c * Scalar::sin(color.h * DEG2RAD);
+
+        Lab {
+            l: color.l,
+            a,
+            b,
+            alpha: color.alpha,
+        }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct OkLab {
+impl Lab {
+    /// Check whether the color lies within the sRGB gamut (see `XYZ::is_in_gamut`).
+    pub fn is_in_gamut(&self) -> bool {
+        XYZ::from(self).is_in_gamut()
+    }
+}
+
+impl fmt::Display for Lab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Lab({l}, {a}, {b})", l = self.l, a = self.a, b = self.b,)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OkLab {
     pub l: Scalar,
     pub a: Scalar,
     pub b: Scalar,
@@ -1362,6 +2067,31 @@ impl From<&Color> for OkLab {
     }
 }
 
+impl OkLab {
+    /// Create an `OkLab` color from cylindrical coordinates (lightness, chroma and hue angle in
+    /// degrees), often called 'OkLCh'.
+    pub fn from_lch(l: Scalar, c: Scalar, h: Scalar, alpha: Scalar) -> OkLab {
+        let h = h.to_radians();
+        OkLab {
+            l,
+            a: c * h.cos(),
+            b: c * h.sin(),
+            alpha,
+        }
+    }
+
+    /// Check whether the color lies within the sRGB gamut (see `XYZ::is_in_gamut`).
+    pub fn is_in_gamut(&self) -> bool {
+        XYZ::from(self).is_in_gamut()
+    }
+
+    /// The largest (OkLCh) chroma for which a color with the given lightness and hue angle (in
+    /// degrees) still lies within the sRGB gamut.
+    pub fn max_chroma(l: Scalar, h: Scalar) -> Scalar {
+        bisect_max(0.0, 0.5, |c| OkLab::from_lch(l, c, h, 1.0).is_in_gamut())
+    }
+}
+
 impl fmt::Display for OkLab {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -1374,6 +2104,63 @@ impl fmt::Display for OkLab {
     }
 }
 
+/// The cylindrical representation of the OkLab color space (lightness, chroma and hue angle in
+/// degrees).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OkLCh {
+    pub l: Scalar,
+    pub c: Scalar,
+    pub h: Scalar,
+    pub alpha: Scalar,
+}
+
+impl ColorSpace for OkLCh {
+    fn from_color(c: &Color) -> Self {
+        OkLCh::from(c)
+    }
+
+    fn into_color(self) -> Color {
+        let OkLab { l, a, b, alpha } = OkLab::from_lch(self.l, self.c, self.h, self.alpha);
+        Color::from_oklab(l, a, b, alpha)
+    }
+
+    fn mix(&self, other: &Self, fraction: Fraction) -> Self {
+        self.mix_with_hue(other, fraction, HueInterpolation::Shorter)
+    }
+
+    fn mix_with_hue(&self, other: &Self, fraction: Fraction, hue: HueInterpolation) -> Self {
+        // make sure that the hue is preserved when mixing with gray colors
+        let is_gray = self.c < 1e-4 || other.c < 1e-4;
+        let self_hue = if self.c < 1e-4 { other.h } else { self.h };
+        let other_hue = if other.c < 1e-4 { self.h } else { other.h };
+        let hue = if is_gray {
+            HueInterpolation::Shorter
+        } else {
+            hue
+        };
+
+        Self {
+            l: interpolate(self.l, other.l, fraction),
+            c: interpolate(self.c, other.c, fraction),
+            h: interpolate_hue(self_hue, other_hue, fraction, hue),
+            alpha: interpolate(self.alpha, other.alpha, fraction),
+        }
+    }
+}
+
+impl From<&Color> for OkLCh {
+    fn from(color: &Color) -> Self {
+        let OkLab { l, a, b, alpha } = OkLab::from(color);
+
+        OkLCh {
+            l,
+            c: a.hypot(b),
+            h: mod_positive(b.atan2(a).to_degrees(), 360.0),
+            alpha,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct LCh {
     pub l: Scalar,
@@ -1392,14 +2179,24 @@ impl ColorSpace for LCh {
     }
 
     fn mix(&self, other: &Self, fraction: Fraction) -> Self {
+        self.mix_with_hue(other, fraction, HueInterpolation::Shorter)
+    }
+
+    fn mix_with_hue(&self, other: &Self, fraction: Fraction, hue: HueInterpolation) -> Self {
         // make sure that the hue is preserved when mixing with gray colors
+        let is_gray = self.c < 0.1 || other.c < 0.1;
         let self_hue = if self.c < 0.1 { other.h } else { self.h };
         let other_hue = if other.c < 0.1 { self.h } else { other.h };
+        let hue = if is_gray {
+            HueInterpolation::Shorter
+        } else {
+            hue
+        };
 
         Self {
             l: interpolate(self.l, other.l, fraction),
             c: interpolate(self.c, other.c, fraction),
-            h: interpolate_angle(self_hue, other_hue, fraction),
+            h: interpolate_hue(self_hue, other_hue, fraction, hue),
             alpha: interpolate(self.alpha, other.alpha, fraction),
         }
     }
@@ -1418,6 +2215,27 @@ impl From<&Color> for LCh {
     }
 }
 
+impl LCh {
+    /// Check whether the color lies within the sRGB gamut (see `XYZ::is_in_gamut`).
+    pub fn is_in_gamut(&self) -> bool {
+        Lab::from(self).is_in_gamut()
+    }
+
+    /// The largest chroma for which a color with the given lightness and hue still lies within
+    /// the sRGB gamut.
+    pub fn max_chroma(l: Scalar, h: Scalar) -> Scalar {
+        bisect_max(0.0, 200.0, |c| {
+            LCh {
+                l,
+                c,
+                h,
+                alpha: 1.0,
+            }
+            .is_in_gamut()
+        })
+    }
+}
+
 impl fmt::Display for LCh {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "LCh({l}, {c}, {h})", l = self.l, c = self.c, h = self.h,)
@@ -1472,8 +2290,77 @@ impl fmt::Display for CMYK {
     }
 }
 
+/// The steps of a tonal palette (see `Color::tonal_palette`): the name of the step, the OkLCh
+/// lightness and the chroma relative to the 500 step.
+const TONAL_PALETTE_STEPS: &[(u16, Scalar, Scalar)] = &[
+    (50, 0.97, 0.10),
+    (100, 0.93, 0.20),
+    (200, 0.88, 0.35),
+    (300, 0.81, 0.55),
+    (400, 0.71, 0.80),
+    (500, 0.62, 1.00),
+    (600, 0.55, 1.05),
+    (700, 0.48, 1.00),
+    (800, 0.41, 0.85),
+    (900, 0.35, 0.65),
+];
+
+/// The color space in which hue angles are measured for color harmonies (see
+/// `Color::rotate_hue_in`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HueSpace {
+    HSL,
+    OkLCh,
+}
+
+/// The direction in which hue angles are interpolated when mixing colors in a cylindrical color
+/// space (see `Color::mix_with_hue`). The semantics follow the 'hue-interpolation-method' of the
+/// CSS Color Module Level 4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HueInterpolation {
+    /// Take the shorter arc between the two hues
+    #[default]
+    Shorter,
+    /// Take the longer arc between the two hues (a full turn for identical hues)
+    Longer,
+    /// Always go around the hue circle in increasing direction
+    Increasing,
+    /// Always go around the hue circle in decreasing direction
+    Decreasing,
+}
+
+/// Algorithms to compute the contrast between a text color and a background color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContrastAlgorithm {
+    /// The WCAG 2 contrast ratio (see `Color::contrast_ratio`)
+    Wcag2,
+    /// The APCA lightness contrast (see `Color::contrast_apca`)
+    Apca,
+}
+
+impl ContrastAlgorithm {
+    /// The contrast of the text color on the background color. For APCA, this is the absolute
+    /// Lc value, such that both polarities can be compared.
+    pub fn contrast(self, text: &Color, background: &Color) -> Scalar {
+        match self {
+            ContrastAlgorithm::Wcag2 => text.contrast_ratio(background),
+            ContrastAlgorithm::Apca => text.contrast_apca(background).abs(),
+        }
+    }
+}
+
+/// Channel-wise operations to apply a tint color (see `Color::tint`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TintOperation {
+    Add,
+    Subtract,
+    Multiply,
+}
+
 /// A representation of the different kinds of colorblindness. More info
 /// [here](https://en.wikipedia.org/wiki/Color_blindness).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum ColorblindnessType {
     /// Protanopic people lack red cones
     Protanopia,
@@ -1481,6 +2368,8 @@ pub enum ColorblindnessType {
     Deuteranopia,
     /// Tritanopic people lack blue cones
     Tritanopia,
+    /// Achromatopic people do not perceive any colors, only luminance
+    Achromatopsia,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -1489,6 +2378,47 @@ pub enum Format {
     NoSpaces,
 }
 
+/// Compute the colorfulness metric `M` by Hasler and Süsstrunk for a set of colors. The metric is
+/// based on the mean and the standard deviation of the opponent color channels `rg = R - G` and
+/// `yb = (R + G) / 2 - B` (with RGB values between 0 and 255):
+///
+///   M = sqrt(σ_rg² + σ_yb²) + 0.3 * sqrt(μ_rg² + μ_yb²)
+///
+/// Rough interpretation: 15 (slightly colorful), 33 (moderately), 45 (averagely), 59 (quite),
+/// 82 (highly) and 109 (extremely colorful). Returns 0.0 for an empty list of colors.
+///
+/// See: D. Hasler and S. Süsstrunk, "Measuring colourfulness in natural images" (2003)
+pub fn colorfulness(colors: &[Color]) -> Scalar {
+    if colors.is_empty() {
+        return 0.0;
+    }
+
+    let n = colors.len() as Scalar;
+    let opponents: Vec<(Scalar, Scalar)> = colors
+        .iter()
+        .map(|c| {
+            let rgb = c.to_rgba_float();
+            let (r, g, b) = (255.0 * rgb.r, 255.0 * rgb.g, 255.0 * rgb.b);
+            (r - g, 0.5 * (r + g) - b)
+        })
+        .collect();
+
+    let mean_rg = opponents.iter().map(|o| o.0).sum::<Scalar>() / n;
+    let mean_yb = opponents.iter().map(|o| o.1).sum::<Scalar>() / n;
+    let var_rg = opponents
+        .iter()
+        .map(|o| (o.0 - mean_rg).powi(2))
+        .sum::<Scalar>()
+        / n;
+    let var_yb = opponents
+        .iter()
+        .map(|o| (o.1 - mean_yb).powi(2))
+        .sum::<Scalar>()
+        / n;
+
+    Scalar::sqrt(var_rg + var_yb) + 0.3 * Scalar::sqrt(mean_rg.powi(2) + mean_yb.powi(2))
+}
+
 /// The representation of a color stop for a `ColorScale`.
 /// The position defines where the color is placed from left (0.0) to right (1.0).
 #[derive(Debug, Clone)]
@@ -1540,6 +2470,13 @@ impl ColorScale {
         self
     }
 
+    /// The color stops of this scale along with their positions, from left to right.
+    pub fn stops(&self) -> impl Iterator<Item = (&Color, Fraction)> + '_ {
+        self.color_stops
+            .iter()
+            .map(|stop| (&stop.color, stop.position))
+    }
+
     /// Get the color at the given position using the mixing function.
     ///
     /// Note:
@@ -1577,6 +2514,58 @@ impl ColorScale {
             _ => None,
         }
     }
+
+    /// Get a `ColorScale` that runs in the opposite direction.
+    pub fn reversed(&self) -> ColorScale {
+        let mut scale = ColorScale::empty();
+        for stop in &self.color_stops {
+            scale.add_stop(
+                stop.color.clone(),
+                Fraction::from(1.0 - stop.position.value()),
+            );
+        }
+        scale
+    }
+
+    /// Get a `ColorScale` that covers the part of this scale between `start` and `end`, stretched
+    /// to the full range. If `start` is larger than `end`, the subrange is reversed.
+    pub fn subrange(
+        &self,
+        start: Fraction,
+        end: Fraction,
+        mix: &dyn Fn(&Color, &Color, Fraction) -> Color,
+    ) -> ColorScale {
+        #![allow(clippy::float_cmp)]
+        let (start, end) = (start.value(), end.value());
+        let mut scale = ColorScale::empty();
+
+        if start == end {
+            if let Some(color) = self.sample(Fraction::from(start), mix) {
+                scale
+                    .add_stop(color.clone(), Fraction::from(0.0))
+                    .add_stop(color, Fraction::from(1.0));
+            }
+            return scale;
+        }
+
+        let rescale = |position: Scalar| Fraction::from((position - start) / (end - start));
+
+        for position in [start, end] {
+            if let Some(color) = self.sample(Fraction::from(position), mix) {
+                scale.add_stop(color, rescale(position));
+            }
+        }
+
+        let (lower, upper) = (start.min(end), start.max(end));
+        for stop in &self.color_stops {
+            let position = stop.position.value();
+            if lower < position && position < upper {
+                scale.add_stop(stop.color.clone(), rescale(position));
+            }
+        }
+
+        scale
+    }
 }
 
 #[cfg(test)]
@@ -1781,6 +2770,166 @@ mod tests {
         }
     }
 
+    #[test]
+    fn is_in_gamut() {
+        assert!(Color::red().to_lab().is_in_gamut());
+        assert!(Color::white().to_oklab().is_in_gamut());
+        assert!(Color::black().to_lch().is_in_gamut());
+
+        assert!(!Lab {
+            l: 50.0,
+            a: 120.0,
+            b: 0.0,
+            alpha: 1.0
+        }
+        .is_in_gamut());
+        assert!(!LCh {
+            l: 95.0,
+            c: 80.0,
+            h: 260.0,
+            alpha: 1.0
+        }
+        .is_in_gamut());
+        assert!(!OkLab {
+            l: 1.2,
+            a: 0.0,
+            b: 0.0,
+            alpha: 1.0
+        }
+        .is_in_gamut());
+
+        for hue in 0..360 {
+            assert!(Color::from_hsl(Scalar::from(hue), 1.0, 0.5)
+                .to_lch()
+                .is_in_gamut());
+        }
+    }
+
+    #[test]
+    fn max_chroma() {
+        assert!(LCh::max_chroma(100.0, 0.0) < 1.0);
+        assert!(OkLab::max_chroma(0.0, 120.0) < 0.05);
+
+        let c = LCh::max_chroma(60.0, 40.0);
+        assert!(c > 50.0);
+        assert!(LCh {
+            l: 60.0,
+            c,
+            h: 40.0,
+            alpha: 1.0
+        }
+        .is_in_gamut());
+        assert!(!LCh {
+            l: 60.0,
+            c: c + 1.0,
+            h: 40.0,
+            alpha: 1.0
+        }
+        .is_in_gamut());
+
+        assert_eq!(Color::red(), Color::red().maximize_chroma());
+        assert_eq!(Color::blue(), Color::blue().maximize_chroma());
+        assert_eq!(Color::gray(), Color::gray().maximize_chroma());
+
+        let saturated = Color::from_lch(50.0, 10.0, 40.0, 1.0).maximize_chroma();
+        assert!(saturated.to_lch().c > 60.0);
+    }
+
+    #[test]
+    fn match_lightness() {
+        let gray = Color::graytone(0.5);
+        let l = gray.to_lch().l;
+
+        for color in [Color::red(), Color::yellow(), Color::blue(), Color::white()] {
+            let matched = color.match_lightness(&gray);
+            let lch = matched.to_lch();
+            assert_relative_eq!(l, lch.l, epsilon = 0.5);
+            if color != Color::white() {
+                assert_relative_eq!(color.to_lch().h, lch.h, epsilon = 2.0);
+            }
+        }
+
+        // Chroma is reduced to stay within the gamut
+        let yellow = Color::yellow().match_lightness(&Color::from_rgb(20, 20, 20));
+        assert!(yellow.to_lch().c < Color::yellow().to_lch().c);
+    }
+
+    #[test]
+    fn tonal_palette() {
+        let base = Color::from_rgb(59, 130, 246);
+        let palette = base.tonal_palette();
+
+        let steps: Vec<u16> = palette.iter().map(|(step, _)| *step).collect();
+        assert_eq!(vec![50, 100, 200, 300, 400, 500, 600, 700, 800, 900], steps);
+
+        // From light to dark, with the base color as one of the steps
+        let lightness: Vec<Scalar> = palette.iter().map(|(_, c)| c.to_oklab().l).collect();
+        assert!(lightness.windows(2).all(|w| w[0] > w[1]));
+        assert!(palette.iter().any(|(_, c)| *c == base));
+
+        // The light and dark ends are less colorful
+        let chroma = |c: &Color| OkLCh::from(c).c;
+        assert!(chroma(&palette[0].1) < chroma(&palette[5].1) / 2.0);
+        assert!(chroma(&palette[9].1) < chroma(&palette[6].1));
+
+        // Grays stay gray
+        assert!(Color::gray()
+            .tonal_palette()
+            .iter()
+            .all(|(_, c)| chroma(c) < 1e-3));
+    }
+
+    #[test]
+    fn with_oklch_chroma() {
+        let chroma = |c: &Color| OkLCh::from(c).c;
+
+        let color = Color::from_rgb(180, 90, 120).with_oklch_chroma(0.05);
+        assert_relative_eq!(0.05, chroma(&color), epsilon = 5e-3);
+        assert_relative_eq!(
+            Color::from_rgb(180, 90, 120).to_oklab().l,
+            color.to_oklab().l,
+            epsilon = 5e-3
+        );
+
+        // Chroma is reduced to stay within the gamut
+        let blue = Color::blue().with_oklch_chroma(1.0);
+        assert!(chroma(&blue) <= chroma(&Color::blue()) + 1e-3);
+
+        // Grays are left unchanged
+        assert_eq!(Color::gray(), Color::gray().with_oklch_chroma(0.1));
+    }
+
+    #[test]
+    fn colorfulness() {
+        assert_relative_eq!(0.0, Color::gray().colorfulness());
+        assert_relative_eq!(0.0, super::colorfulness(&[]));
+        assert_relative_eq!(
+            0.3 * Scalar::sqrt(255.0 * 255.0 + 127.5 * 127.5),
+            Color::red().colorfulness()
+        );
+
+        // A palette with different colors is more colorful than its mean color
+        let palette = [Color::red(), Color::blue()];
+        assert!(
+            super::colorfulness(&palette)
+                > Color::red()
+                    .mix::<RGBA<f64>>(&Color::blue(), Fraction::from(0.5))
+                    .colorfulness()
+        );
+    }
+
+    #[test]
+    fn under_illuminant() {
+        assert_eq!(Color::red(), Color::red().under_illuminant(Illuminant::D65));
+
+        // Incandescent light is warmer than daylight
+        let gray = Color::gray().under_illuminant(Illuminant::A).to_rgba();
+        assert!(gray.r > gray.b);
+
+        let gray = Color::gray().under_illuminant(Illuminant::D75).to_rgba();
+        assert!(gray.r < gray.b);
+    }
+
     #[test]
     fn lch_conversion() {
         assert_eq!(
@@ -1811,6 +2960,88 @@ mod tests {
         assert_eq!(Color::lime(), Color::fuchsia().complementary());
     }
 
+    #[test]
+    fn color_temperature() {
+        // D65 white point
+        assert_relative_eq!(6504.0, Color::white().estimate_cct(), epsilon = 5.0);
+
+        let daylight = Color::from_temperature(6500.0).to_rgba();
+        assert!(daylight.r >= 245 && daylight.g >= 245 && daylight.b >= 245);
+
+        let candle = Color::from_temperature(1900.0).to_rgba();
+        assert_eq!(255, candle.r);
+        assert!(candle.g < 200 && candle.b < 100);
+
+        for kelvin in [2000.0, 3000.0, 4500.0, 6500.0, 9000.0] {
+            assert_relative_eq!(
+                kelvin,
+                Color::from_temperature(kelvin).estimate_cct(),
+                max_relative = 0.03
+            );
+        }
+    }
+
+    #[test]
+    fn cmyk_roundtrip() {
+        assert_eq!(Color::white(), Color::from_cmyk(0.0, 0.0, 0.0, 0.0));
+        assert_eq!(Color::black(), Color::from_cmyk(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(Color::red(), Color::from_cmyk(0.0, 1.0, 1.0, 0.0));
+
+        for (r, g, b) in [
+            (51, 102, 153),
+            (255, 128, 0),
+            (127, 127, 127),
+            (10, 200, 30),
+        ] {
+            let color = Color::from_rgb(r, g, b);
+            assert_eq!(color.to_rgba(), Color::from(&color.to_cmyk()).to_rgba());
+        }
+    }
+
+    #[test]
+    fn wavelength() {
+        assert_eq!(Color::black(), Color::from_wavelength(300.0));
+        assert_eq!(Color::black(), Color::from_wavelength(800.0));
+
+        let hue = |nm: Scalar| Color::from_wavelength(nm).to_hsla().h;
+        assert!(hue(450.0) > 230.0 && hue(450.0) < 270.0);
+        assert!(hue(530.0) > 90.0 && hue(530.0) < 150.0);
+        assert!(hue(580.0) > 30.0 && hue(580.0) < 60.0);
+        assert!(hue(650.0) < 15.0 || hue(650.0) > 345.0);
+
+        // Fades out towards the ends of the visible range
+        assert!(
+            Color::from_wavelength(390.0).brightness() < Color::from_wavelength(450.0).brightness()
+        );
+        assert!(
+            Color::from_wavelength(770.0).brightness() < Color::from_wavelength(650.0).brightness()
+        );
+    }
+
+    #[test]
+    fn color_harmonies() {
+        assert_eq!(
+            vec![Color::red(), Color::lime(), Color::blue()],
+            Color::red().triadic(HueSpace::HSL)
+        );
+        assert_eq!(4, Color::red().tetradic(HueSpace::OkLCh).len());
+        assert_eq!(
+            Color::red(),
+            Color::red().analogous(3, 30.0, HueSpace::HSL)[1]
+        );
+
+        // Lightness and chroma are preserved in OkLCh (for in-gamut results)
+        let color = Color::from_rgb(120, 140, 160);
+        let rotated = color.rotate_hue_in(40.0, HueSpace::OkLCh).to_oklab();
+        assert_relative_eq!(color.to_oklab().l, rotated.l, epsilon = 0.01);
+
+        let shades = Color::red().monochromatic(5, HueSpace::HSL);
+        assert_eq!(5, shades.len());
+        assert!(shades
+            .windows(2)
+            .all(|w| w[0].to_hsla().l < w[1].to_hsla().l));
+    }
+
     #[test]
     fn lighten() {
         assert_eq!(
@@ -1866,6 +3097,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn contrast_apca() {
+        assert_relative_eq!(
+            106.04,
+            Color::black().contrast_apca(&Color::white()),
+            max_relative = 0.001
+        );
+        assert_relative_eq!(
+            -107.88,
+            Color::white().contrast_apca(&Color::black()),
+            max_relative = 0.001
+        );
+        assert_relative_eq!(
+            63.06,
+            Color::from_rgb(0x88, 0x88, 0x88).contrast_apca(&Color::white()),
+            max_relative = 0.001
+        );
+        assert_relative_eq!(
+            -68.54,
+            Color::white().contrast_apca(&Color::from_rgb(0x88, 0x88, 0x88)),
+            max_relative = 0.001
+        );
+
+        assert_eq!(0.0, Color::red().contrast_apca(&Color::red()));
+    }
+
     #[test]
     fn text_color() {
         assert_eq!(Color::white(), Color::graytone(0.4).text_color());
@@ -1882,6 +3139,22 @@ mod tests {
         assert_eq!(123.0, c1.distance_delta_e_cie76(&c2).round());
     }
 
+    #[test]
+    fn distance_oklab() {
+        let red = Color::red();
+        assert_eq!(0.0, red.distance_oklab(&red));
+        assert_relative_eq!(
+            1.0,
+            Color::black().distance_oklab(&Color::white()),
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            red.distance_oklab(&Color::blue()),
+            Color::blue().distance_oklab(&red),
+            epsilon = 1e-12
+        );
+    }
+
     #[test]
     fn to_hsl_string() {
         let c = Color::from_hsl(91.3, 0.541, 0.983);
@@ -1925,6 +3198,12 @@ mod tests {
     fn to_lab_string() {
         let c = Color::from_lab(41.0, 83.0, -93.0, 1.0);
         assert_eq!("Lab(41, 83, -93)", c.to_lab_string(Format::Spaces));
+
+        // No negative zeros for (almost) neutral colors
+        assert_eq!(
+            "Lab(100, 0, 0)",
+            Color::white().to_lab_string(Format::Spaces)
+        );
     }
 
     #[test]
@@ -2045,6 +3324,58 @@ mod tests {
         assert_eq!(sample_red_green, mix_red_green);
     }
 
+    #[test]
+    fn color_scale_reversed() {
+        let mix = Color::mix::<Lab>;
+
+        let mut color_scale = ColorScale::empty();
+        color_scale
+            .add_stop(Color::red(), Fraction::from(0.0))
+            .add_stop(Color::green(), Fraction::from(0.25))
+            .add_stop(Color::blue(), Fraction::from(1.0));
+
+        let reversed = color_scale.reversed();
+
+        for position in [0.0, 0.1, 0.25, 0.6, 1.0] {
+            assert_eq!(
+                color_scale.sample(Fraction::from(position), &mix),
+                reversed.sample(Fraction::from(1.0 - position), &mix)
+            );
+        }
+    }
+
+    #[test]
+    fn color_scale_subrange() {
+        let mix = Color::mix::<RGBA<f64>>;
+
+        let mut color_scale = ColorScale::empty();
+        color_scale
+            .add_stop(Color::black(), Fraction::from(0.0))
+            .add_stop(Color::red(), Fraction::from(0.5))
+            .add_stop(Color::white(), Fraction::from(1.0));
+
+        let sub = color_scale.subrange(Fraction::from(0.25), Fraction::from(0.75), &mix);
+        assert_eq!(
+            color_scale.sample(Fraction::from(0.25), &mix),
+            sub.sample(Fraction::from(0.0), &mix)
+        );
+        assert_eq!(Some(Color::red()), sub.sample(Fraction::from(0.5), &mix));
+        assert_eq!(
+            color_scale.sample(Fraction::from(0.75), &mix),
+            sub.sample(Fraction::from(1.0), &mix)
+        );
+
+        let flipped = color_scale.subrange(Fraction::from(1.0), Fraction::from(0.5), &mix);
+        assert_eq!(
+            Some(Color::white()),
+            flipped.sample(Fraction::from(0.0), &mix)
+        );
+        assert_eq!(
+            Some(Color::red()),
+            flipped.sample(Fraction::from(1.0), &mix)
+        );
+    }
+
     #[test]
     fn color_scale_sample_position() {
         let mix = Color::mix::<Lab>;
@@ -2109,4 +3440,154 @@ mod tests {
             assert_eq!(hex_string, parsed_from_rgba.to_rgb_hex_string(true));
         }
     }
+
+    #[test]
+    fn from_str_error() {
+        assert_eq!(
+            Err(Error::InvalidColor("nocolor".into())),
+            "nocolor".parse::<Color>()
+        );
+        assert_eq!(
+            "could not parse color 'nocolor'",
+            Error::InvalidColor("nocolor".into()).to_string()
+        );
+    }
+
+    #[test]
+    fn from_string_hash() {
+        let alice = Color::from_string_hash("alice", 0.7, 0.12);
+        assert_eq!("#9fa646", alice.to_rgb_hex_string(true));
+        assert_eq!(alice, Color::from_string_hash("alice", 0.7, 0.12));
+        assert_ne!(alice, Color::from_string_hash("bob", 0.7, 0.12));
+
+        let lightness = alice.to_oklab().l;
+        assert_relative_eq!(0.7, lightness, epsilon = 0.01);
+    }
+
+    #[test]
+    fn with_minimum_contrast() {
+        let background = Color::from_rgb(0x1e, 0x1e, 0x2e);
+        let dark_blue = Color::from_rgb(0x20, 0x30, 0x80);
+
+        let adjusted = dark_blue.with_minimum_contrast(&background, 4.5);
+        assert!(adjusted.contrast_ratio(&background) >= 4.5);
+        assert!(adjusted.contrast_ratio(&background) < 4.6);
+        assert!(adjusted.to_oklab().l > dark_blue.to_oklab().l);
+
+        let light = Color::white();
+        let adjusted = dark_blue.with_minimum_contrast(&light, 4.5);
+        assert_eq!(dark_blue, adjusted);
+
+        let gray = Color::graytone(0.5);
+        assert_eq!(Color::black(), gray.with_minimum_contrast(&gray, 21.0));
+    }
+
+    #[test]
+    fn adjust_contrast() {
+        let background = Color::from_rgb(0x1e, 0x1e, 0x2e);
+        let dark_blue = Color::from_rgb(0x20, 0x30, 0x80);
+
+        let adjusted = dark_blue.adjust_contrast(&background, 60.0, ContrastAlgorithm::Apca);
+        assert!(adjusted.achieved);
+        assert!(adjusted.contrast >= 60.0);
+        assert!(adjusted.contrast < 61.0);
+        assert_relative_eq!(
+            adjusted.contrast,
+            adjusted.color.contrast_apca(&background).abs()
+        );
+        assert!(adjusted.color.to_oklab().l > dark_blue.to_oklab().l);
+
+        // The smaller change wins
+        let gray = Color::graytone(0.6);
+        let adjusted = gray.adjust_contrast(&Color::graytone(0.5), 3.0, ContrastAlgorithm::Wcag2);
+        assert!(adjusted.achieved);
+        assert!(adjusted.color.to_oklab().l > gray.to_oklab().l);
+
+        let adjusted = dark_blue.adjust_contrast(&Color::white(), 4.5, ContrastAlgorithm::Wcag2);
+        assert!(adjusted.achieved);
+        assert_eq!(dark_blue, adjusted.color);
+
+        let adjusted = gray.adjust_contrast(&gray, 110.0, ContrastAlgorithm::Apca);
+        assert!(!adjusted.achieved);
+        assert_eq!(Color::white(), adjusted.color);
+    }
+
+    #[test]
+    fn simulate_color_vision_deficiency() {
+        let red = Color::from_rgb(255, 0, 0);
+        let green = Color::from_rgb(0, 255, 0);
+        let deuter = ColorblindnessType::Deuteranopia;
+
+        assert_eq!(red, red.simulate_color_vision_deficiency(deuter, 0.0));
+        assert_eq!(
+            Color::white(),
+            Color::white().simulate_color_vision_deficiency(deuter, 1.0)
+        );
+
+        // Red and green become harder to distinguish with increasing severity
+        let distance = |severity| {
+            red.simulate_color_vision_deficiency(deuter, severity)
+                .distance_delta_e_ciede2000(
+                    &green.simulate_color_vision_deficiency(deuter, severity),
+                )
+        };
+        assert!(distance(0.0) > distance(0.5));
+        assert!(distance(0.5) > distance(1.0));
+
+        let gray = red.simulate_colorblindness(ColorblindnessType::Achromatopsia);
+        assert_eq!(0.0, gray.to_hsla().s);
+        assert_relative_eq!(red.luminance(), gray.luminance(), max_relative = 0.01);
+    }
+
+    #[test]
+    fn daltonize() {
+        let red = Color::from_rgb(220, 40, 40);
+        let green = Color::from_rgb(40, 160, 40);
+        let deuter = ColorblindnessType::Deuteranopia;
+
+        assert_eq!(red, red.daltonize(deuter, 0.0));
+        assert_eq!(Color::white(), Color::white().daltonize(deuter, 1.0));
+        assert_eq!(red, red.daltonize(ColorblindnessType::Achromatopsia, 1.0));
+
+        // The daltonized colors are easier to distinguish in the simulation
+        let distance = |a: &Color, b: &Color| {
+            a.simulate_color_vision_deficiency(deuter, 1.0)
+                .distance_delta_e_ciede2000(&b.simulate_color_vision_deficiency(deuter, 1.0))
+        };
+        assert!(
+            distance(&red.daltonize(deuter, 1.0), &green.daltonize(deuter, 1.0))
+                > distance(&red, &green)
+        );
+    }
+
+    #[test]
+    fn tint() {
+        let c = Color::from_rgb(200, 100, 50);
+
+        assert_eq!(c, c.tint(&Color::white(), TintOperation::Multiply));
+        assert_eq!(
+            Color::black(),
+            c.tint(&Color::black(), TintOperation::Multiply)
+        );
+        assert_eq!(c, c.tint(&Color::black(), TintOperation::Add));
+        assert_eq!(c, c.tint(&Color::black(), TintOperation::Subtract));
+        assert_eq!(Color::white(), c.tint(&Color::white(), TintOperation::Add));
+        assert_eq!(
+            Color::black(),
+            c.tint(&Color::white(), TintOperation::Subtract)
+        );
+
+        let warm = c.tint(&Color::from_rgb(255, 238, 221), TintOperation::Multiply);
+        assert_eq!(200, warm.to_rgba().r);
+        assert!(warm.to_rgba().b < 50);
+
+        let transparent = Color::from_rgba(200, 100, 50, 0.5);
+        assert_eq!(
+            0.5,
+            transparent
+                .tint(&Color::red(), TintOperation::Multiply)
+                .to_rgba()
+                .alpha
+        );
+    }
 }
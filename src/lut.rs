@@ -0,0 +1,304 @@
+use std::fmt::Write;
+
+use crate::types::Scalar;
+use crate::{Color, Error};
+
+/// A three-dimensional color lookup table, as used by image and video editing software.
+///
+/// The table maps (gamma-encoded) RGB input values to RGB output values on a regular grid of
+/// `size × size × size` points. Input values between the grid points are mapped with trilinear
+/// interpolation.
+///
+/// See: <https://en.wikipedia.org/wiki/3D_lookup_table>
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut3D {
+    title: Option<String>,
+    size: usize,
+    domain_min: [Scalar; 3],
+    domain_max: [Scalar; 3],
+    /// The output values, with the red index changing fastest and the blue index slowest
+    table: Vec<[Scalar; 3]>,
+}
+
+impl Lut3D {
+    /// Create a lookup table with `size` grid points per axis for input values between
+    /// `domain_min` and `domain_max`. The table contains the `size³` output values, with the red
+    /// index changing fastest and the blue index slowest.
+    pub fn new(
+        size: usize,
+        domain_min: [Scalar; 3],
+        domain_max: [Scalar; 3],
+        table: Vec<[Scalar; 3]>,
+    ) -> Result<Lut3D, Error> {
+        let num_entries = Self::num_entries(size)?;
+        if table.len() != num_entries {
+            return Err(Error::InvalidLut(format!(
+                "expected {} table entries for LUT_3D_SIZE {}, found {}",
+                num_entries,
+                size,
+                table.len()
+            )));
+        }
+        let finite = domain_min.iter().chain(&domain_max).all(|v| v.is_finite());
+        if !finite || (0..3).any(|i| domain_min[i] >= domain_max[i]) {
+            return Err(Error::InvalidLut(
+                "DOMAIN_MIN must be smaller than DOMAIN_MAX".into(),
+            ));
+        }
+
+        Ok(Lut3D {
+            title: None,
+            size,
+            domain_min,
+            domain_max,
+            table,
+        })
+    }
+
+    /// Create a lookup table with `size` grid points per axis by evaluating `f` on each point.
+    pub fn from_fn(size: usize, f: impl Fn(&Color) -> Color) -> Result<Lut3D, Error> {
+        let num_entries = Self::num_entries(size)?;
+
+        let step = (size - 1) as Scalar;
+        let mut table = Vec::with_capacity(num_entries);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let input = Color::from_rgb_float(
+                        r as Scalar / step,
+                        g as Scalar / step,
+                        b as Scalar / step,
+                    );
+                    let output = f(&input).to_rgba_float();
+                    table.push([output.r, output.g, output.b]);
+                }
+            }
+        }
+
+        Ok(Lut3D {
+            title: None,
+            size,
+            domain_min: [0.0; 3],
+            domain_max: [1.0; 3],
+            table,
+        })
+    }
+
+    /// The number of table entries (`size³`) for a lookup table with the given size.
+    fn num_entries(size: usize) -> Result<usize, Error> {
+        if size < 2 {
+            return Err(Error::InvalidLut("the size must be at least 2".into()));
+        }
+        size.checked_pow(3)
+            .ok_or_else(|| Error::InvalidLut(format!("the size {} is too large", size)))
+    }
+
+    /// Parse a lookup table in the Adobe/Resolve `.cube` format.
+    pub fn parse_cube(source: &str) -> Result<Lut3D, Error> {
+        let mut title = None;
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut table = vec![];
+
+        let parse_triple = |line_number: usize, values: &[&str]| -> Result<[Scalar; 3], Error> {
+            if values.len() != 3 {
+                return Err(Error::InvalidLut(format!(
+                    "line {}: expected three values",
+                    line_number
+                )));
+            }
+            let mut triple = [0.0; 3];
+            for (t, v) in triple.iter_mut().zip(values) {
+                *t = v.parse().map_err(|_| {
+                    Error::InvalidLut(format!("line {}: invalid number '{}'", line_number, v))
+                })?;
+            }
+            Ok(triple)
+        };
+
+        for (i, line) in source.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let keyword = parts.next().expect("line is not empty");
+            let values: Vec<&str> = parts.collect();
+
+            match keyword {
+                "TITLE" => {
+                    let rest = line["TITLE".len()..].trim();
+                    title = Some(rest.trim_matches('"').to_string());
+                }
+                "LUT_3D_SIZE" => {
+                    let n = values
+                        .first()
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .filter(|&n| n >= 2)
+                        .ok_or_else(|| {
+                            Error::InvalidLut(format!("line {}: invalid LUT_3D_SIZE", line_number))
+                        })?;
+                    size = Some(n);
+                }
+                "LUT_1D_SIZE" => {
+                    return Err(Error::InvalidLut(
+                        "1D lookup tables are not supported".into(),
+                    ));
+                }
+                "DOMAIN_MIN" => domain_min = parse_triple(line_number, &values)?,
+                "DOMAIN_MAX" => domain_max = parse_triple(line_number, &values)?,
+                _ if keyword
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_alphabetic()) =>
+                {
+                    // Ignore unknown keywords (e.g. LUT_3D_INPUT_RANGE or vendor extensions)
+                }
+                _ => {
+                    let mut all_values = vec![keyword];
+                    all_values.extend(values);
+                    table.push(parse_triple(line_number, &all_values)?);
+                }
+            }
+        }
+
+        let size = size.ok_or_else(|| Error::InvalidLut("missing LUT_3D_SIZE".into()))?;
+        let mut lut = Lut3D::new(size, domain_min, domain_max, table)?;
+        lut.title = title;
+        Ok(lut)
+    }
+
+    /// The title of the lookup table (`TITLE` in the `.cube` format), if any.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.title = title;
+    }
+
+    /// The number of grid points per axis.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Serialize the lookup table in the `.cube` format.
+    pub fn to_cube(&self) -> String {
+        let mut out = String::new();
+        if let Some(ref title) = self.title {
+            writeln!(out, "TITLE \"{}\"", title).unwrap();
+        }
+        writeln!(out, "LUT_3D_SIZE {}", self.size).unwrap();
+        let [r0, g0, b0] = self.domain_min;
+        let [r1, g1, b1] = self.domain_max;
+        writeln!(out, "DOMAIN_MIN {} {} {}", r0, g0, b0).unwrap();
+        writeln!(out, "DOMAIN_MAX {} {} {}", r1, g1, b1).unwrap();
+        for [r, g, b] in &self.table {
+            writeln!(out, "{:.6} {:.6} {:.6}", r, g, b).unwrap();
+        }
+        out
+    }
+
+    fn entry(&self, r: usize, g: usize, b: usize) -> [Scalar; 3] {
+        self.table[r + self.size * (g + self.size * b)]
+    }
+
+    /// Map a color through the lookup table, using trilinear interpolation between the grid
+    /// points. The alpha channel is not modified.
+    pub fn apply(&self, color: &Color) -> Color {
+        let rgba = color.to_rgba_float();
+        let max_index = (self.size - 1) as Scalar;
+
+        let mut index = [0; 3];
+        let mut fraction = [0.0; 3];
+        for (i, c) in [rgba.r, rgba.g, rgba.b].iter().enumerate() {
+            let t = (c - self.domain_min[i]) / (self.domain_max[i] - self.domain_min[i]);
+            let position = (t * max_index).clamp(0.0, max_index);
+            index[i] = (position.floor() as usize).min(self.size - 2);
+            fraction[i] = position - index[i] as Scalar;
+        }
+
+        let [r, g, b] = index;
+        let [fr, fg, fb] = fraction;
+        let lerp = |x: [Scalar; 3], y: [Scalar; 3], t: Scalar| -> [Scalar; 3] {
+            [
+                x[0] + t * (y[0] - x[0]),
+                x[1] + t * (y[1] - x[1]),
+                x[2] + t * (y[2] - x[2]),
+            ]
+        };
+
+        let c00 = lerp(self.entry(r, g, b), self.entry(r + 1, g, b), fr);
+        let c10 = lerp(self.entry(r, g + 1, b), self.entry(r + 1, g + 1, b), fr);
+        let c01 = lerp(self.entry(r, g, b + 1), self.entry(r + 1, g, b + 1), fr);
+        let c11 = lerp(
+            self.entry(r, g + 1, b + 1),
+            self.entry(r + 1, g + 1, b + 1),
+            fr,
+        );
+        let c0 = lerp(c00, c10, fg);
+        let c1 = lerp(c01, c11, fg);
+        let [out_r, out_g, out_b] = lerp(c0, c1, fb);
+
+        Color::from_rgba_float(out_r, out_g, out_b, rgba.alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_lut() {
+        let lut = Lut3D::from_fn(5, |c| c.clone()).unwrap();
+
+        for color in &[
+            Color::red(),
+            Color::from_rgb(12, 200, 99),
+            Color::from_rgba(77, 8, 150, 0.5),
+        ] {
+            assert_eq!(*color, lut.apply(color));
+        }
+    }
+
+    #[test]
+    fn cube_roundtrip() {
+        let mut lut = Lut3D::from_fn(3, |c| c.complementary()).unwrap();
+        lut.set_title(Some("complement".into()));
+
+        let parsed = Lut3D::parse_cube(&lut.to_cube()).unwrap();
+        assert_eq!(Some("complement"), parsed.title());
+        assert_eq!(3, parsed.size());
+        assert_eq!(Color::red().complementary(), parsed.apply(&Color::red()));
+    }
+
+    #[test]
+    fn parse_cube() {
+        let source = "# inverting LUT\nLUT_3D_SIZE 2\n\n\
+                      1 1 1\n0 1 1\n1 0 1\n0 0 1\n1 1 0\n0 1 0\n1 0 0\n0 0 0\n";
+        let lut = Lut3D::parse_cube(source).unwrap();
+        assert_eq!(Color::from_rgb(0, 255, 255), lut.apply(&Color::red()));
+        assert_eq!(Color::yellow(), lut.apply(&Color::blue()));
+
+        assert!(Lut3D::parse_cube("LUT_3D_SIZE 2\n0 0 0\n").is_err());
+        assert!(Lut3D::parse_cube("LUT_1D_SIZE 2\n0 0 0\n1 1 1\n").is_err());
+        assert!(Lut3D::parse_cube("0 0 0\n").is_err());
+        assert!(Lut3D::parse_cube("LUT_3D_SIZE 4000000\n0 0 0\n").is_err());
+    }
+
+    #[test]
+    fn new_validates() {
+        let table = vec![[0.0; 3]; 8];
+        assert!(Lut3D::new(2, [0.0; 3], [1.0; 3], table.clone()).is_ok());
+        assert!(Lut3D::new(1, [0.0; 3], [1.0; 3], vec![[0.0; 3]]).is_err());
+        assert!(Lut3D::new(3, [0.0; 3], [1.0; 3], table.clone()).is_err());
+        assert!(Lut3D::new(2, [0.0; 3], [1.0, 0.0, 1.0], table.clone()).is_err());
+        assert!(Lut3D::new(2, [0.0; 3], [1.0, f64::NAN, 1.0], table).is_err());
+
+        assert!(Lut3D::from_fn(1, |c| c.clone()).is_err());
+        assert!(Lut3D::from_fn(usize::MAX / 2, |c| c.clone()).is_err());
+    }
+}
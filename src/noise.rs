@@ -0,0 +1,197 @@
+use rand::prelude::*;
+use rand_chacha::ChaCha12Rng;
+
+use crate::types::Scalar;
+use crate::{Color, OkLab};
+
+/// A smooth, seeded two-dimensional noise function with values between -1.0 and 1.0.
+pub trait Noise {
+    fn get(&self, x: Scalar, y: Scalar) -> Scalar;
+}
+
+/// A random permutation of `0..256`, repeated once to avoid index wrapping. The generator is a
+/// specific algorithm (unlike `StdRng`), such that a seed yields the same noise in every version.
+fn permutation_table(rng: &mut ChaCha12Rng) -> [u8; 512] {
+    let mut values: Vec<u8> = (0..=255).collect();
+    values.shuffle(rng);
+
+    let mut table = [0; 512];
+    for (i, entry) in table.iter_mut().enumerate() {
+        *entry = values[i % 256];
+    }
+    table
+}
+
+/// Two-dimensional simplex noise.
+///
+/// See: <https://en.wikipedia.org/wiki/Simplex_noise>
+pub struct SimplexNoise {
+    perm: [u8; 512],
+}
+
+impl SimplexNoise {
+    const GRADIENTS: [(Scalar, Scalar); 8] = [
+        (1.0, 1.0),
+        (-1.0, 1.0),
+        (1.0, -1.0),
+        (-1.0, -1.0),
+        (1.0, 0.0),
+        (-1.0, 0.0),
+        (0.0, 1.0),
+        (0.0, -1.0),
+    ];
+
+    pub fn new(seed: u64) -> Self {
+        SimplexNoise {
+            perm: permutation_table(&mut ChaCha12Rng::seed_from_u64(seed)),
+        }
+    }
+
+    fn corner(&self, i: usize, j: usize, x: Scalar, y: Scalar) -> Scalar {
+        let t = 0.5 - x * x - y * y;
+        if t < 0.0 {
+            return 0.0;
+        }
+
+        let (gx, gy) = Self::GRADIENTS[self.perm[i + self.perm[j] as usize] as usize % 8];
+        t.powi(4) * (gx * x + gy * y)
+    }
+}
+
+impl Noise for SimplexNoise {
+    fn get(&self, x: Scalar, y: Scalar) -> Scalar {
+        // Skewing and unskewing factors for two dimensions
+        let f2 = 0.5 * (Scalar::sqrt(3.0) - 1.0);
+        let g2 = (3.0 - Scalar::sqrt(3.0)) / 6.0;
+
+        // Find the simplex cell that contains the point
+        let s = (x + y) * f2;
+        let i = (x + s).floor();
+        let j = (y + s).floor();
+        let t = (i + j) * g2;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let x1 = x0 - i1 as Scalar + g2;
+        let y1 = y0 - j1 as Scalar + g2;
+        let x2 = x0 - 1.0 + 2.0 * g2;
+        let y2 = y0 - 1.0 + 2.0 * g2;
+
+        let ii = i.rem_euclid(256.0) as usize;
+        let jj = j.rem_euclid(256.0) as usize;
+
+        let n = self.corner(ii, jj, x0, y0)
+            + self.corner(ii + i1, jj + j1, x1, y1)
+            + self.corner(ii + 1, jj + 1, x2, y2);
+
+        // Scale the result to the interval [-1, 1]
+        (70.0 * n).clamp(-1.0, 1.0)
+    }
+}
+
+/// Two-dimensional value noise: random values on a square lattice, smoothly interpolated.
+pub struct ValueNoise {
+    perm: [u8; 512],
+    values: [Scalar; 256],
+}
+
+impl ValueNoise {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+
+        let perm = permutation_table(&mut rng);
+        let mut values = [0.0; 256];
+        for value in values.iter_mut() {
+            *value = rng.gen_range(-1.0..=1.0);
+        }
+
+        ValueNoise { perm, values }
+    }
+
+    fn lattice(&self, i: usize, j: usize) -> Scalar {
+        self.values[self.perm[(i & 255) + self.perm[j & 255] as usize] as usize]
+    }
+}
+
+impl Noise for ValueNoise {
+    fn get(&self, x: Scalar, y: Scalar) -> Scalar {
+        let smoothstep = |t: Scalar| t * t * (3.0 - 2.0 * t);
+        let lerp = |a: Scalar, b: Scalar, t: Scalar| a + (b - a) * t;
+
+        let i = x.floor().rem_euclid(256.0) as usize;
+        let j = y.floor().rem_euclid(256.0) as usize;
+        let tx = smoothstep(x - x.floor());
+        let ty = smoothstep(y - y.floor());
+
+        lerp(
+            lerp(self.lattice(i, j), self.lattice(i + 1, j), tx),
+            lerp(self.lattice(i, j + 1), self.lattice(i + 1, j + 1), tx),
+            ty,
+        )
+    }
+}
+
+/// Generate a palette of related but varied colors by walking along a path through the noise
+/// field. Lightness, chroma and hue (in OkLCh) follow independent rows of the noise, so
+/// neighboring colors are similar, while the palette as a whole wanders through color space.
+/// A smaller `step` leads to more closely related colors.
+pub fn noise_palette(noise: &dyn Noise, count: usize, step: Scalar) -> Vec<Color> {
+    (0..count)
+        .map(|i| {
+            let t = i as Scalar * step;
+
+            let lightness = 0.65 + 0.2 * noise.get(t, 0.5);
+            let hue = (180.0 + 240.0 * noise.get(t, 10.5)).rem_euclid(360.0);
+            let chroma = (0.11 + 0.07 * noise.get(t, 20.5)).min(OkLab::max_chroma(lightness, hue));
+
+            Color::from(&OkLab::from_lch(lightness, chroma, hue, 1.0))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noise_is_deterministic_and_bounded() {
+        let noises: [(Box<dyn Noise>, Box<dyn Noise>); 2] = [
+            (
+                Box::new(SimplexNoise::new(42)),
+                Box::new(SimplexNoise::new(42)),
+            ),
+            (Box::new(ValueNoise::new(42)), Box::new(ValueNoise::new(42))),
+        ];
+
+        for (noise, same_seed) in &noises {
+            for k in 0..1000 {
+                let (x, y) = (k as Scalar * 0.173, k as Scalar * -0.091);
+                let value = noise.get(x, y);
+                assert!((-1.0..=1.0).contains(&value));
+                assert_eq!(value, same_seed.get(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn noise_is_smooth() {
+        let noises: [Box<dyn Noise>; 2] =
+            [Box::new(SimplexNoise::new(7)), Box::new(ValueNoise::new(7))];
+
+        for noise in &noises {
+            for k in 0..1000 {
+                let x = k as Scalar * 0.01;
+                assert!((noise.get(x, 0.5) - noise.get(x + 0.001, 0.5)).abs() < 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn noise_palette_count() {
+        let palette = noise_palette(&SimplexNoise::new(1), 16, 0.15);
+        assert_eq!(16, palette.len());
+        assert_eq!(palette, noise_palette(&SimplexNoise::new(1), 16, 0.15));
+    }
+}
@@ -296,6 +296,34 @@ pub fn parse_color(input: &str) -> Option<Color> {
     .map(|(_, c)| c)
 }
 
+/// Split a list of colors at whitespace, except for whitespace within parentheses (such that
+/// colors like `rgb(255, 0, 0)` stay intact).
+pub fn split_color_list(list: &str) -> Vec<&str> {
+    let mut colors = vec![];
+    let mut depth = 0;
+    let mut start = None;
+
+    for (i, c) in list.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if c.is_whitespace() && depth <= 0 {
+            if let Some(s) = start.take() {
+                colors.push(&list[s..i]);
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(s) = start {
+        colors.push(&list[s..]);
+    }
+
+    colors
+}
+
 #[test]
 fn parse_rgb_hex_syntax() {
     assert_eq!(Some(rgb(255, 0, 153)), parse_color("f09"));
@@ -726,3 +754,12 @@ fn parse_alpha_syntax() {
         format!("{:?}", parse_color("0a000054"))
     );
 }
+
+#[test]
+fn split_color_list_syntax() {
+    assert_eq!(
+        vec!["red", "rgb(0, 0, 128)", "#eee"],
+        split_color_list("  red rgb(0, 0, 128)\t#eee ")
+    );
+    assert!(split_color_list(" ").is_empty());
+}
@@ -0,0 +1,223 @@
+use std::str::FromStr;
+
+use crate::curves::{ChannelCurves, CurveSpace};
+use crate::helper::Fraction;
+use crate::parser::parse_color;
+use crate::types::Scalar;
+use crate::{Color, ColorblindnessType, Error, Lab};
+
+/// Built-in color grading filters, given as `(name, pipeline)` pairs.
+pub const FILTER_PRESETS: &[(&str, &str)] = &[
+    (
+        "sepia",
+        "to-gray curves:r:0,0.08;1,1|g:0,0.04;1,0.9|b:0,0;1,0.72",
+    ),
+    ("fade", "desaturate:0.1 curves:rgb:0,0.1;1,0.92"),
+    (
+        "cool-shadows",
+        "curves:b:0,0.1;0.5,0.54;1,1|r:0,0;0.5,0.48;1,1",
+    ),
+    (
+        "warm-highlights",
+        "curves:r:0,0;0.5,0.51;1,1|b:0,0;0.5,0.49;1,0.88",
+    ),
+    ("duotone", "duotone:#1d2b53:#ffcf70"),
+];
+
+/// A single color transformation in a `Pipeline`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    Saturate(Scalar),
+    Desaturate(Scalar),
+    Lighten(Scalar),
+    Darken(Scalar),
+    Rotate(Scalar),
+    Complement,
+    ToGray,
+    Colorblind(ColorblindnessType),
+    Curves(ChannelCurves),
+    /// Map the lightness of a color onto a gradient from a shadow to a highlight color
+    Duotone(Color, Color),
+}
+
+impl Step {
+    pub fn apply(&self, color: &Color) -> Color {
+        match self {
+            Step::Saturate(amount) => color.saturate(*amount),
+            Step::Desaturate(amount) => color.desaturate(*amount),
+            Step::Lighten(amount) => color.lighten(*amount),
+            Step::Darken(amount) => color.darken(*amount),
+            Step::Rotate(degrees) => color.rotate_hue(*degrees),
+            Step::Complement => color.complementary(),
+            Step::ToGray => color.to_gray(),
+            Step::Colorblind(cb_ty) => color.simulate_colorblindness(*cb_ty),
+            Step::Curves(curves) => curves.apply(color, CurveSpace::Gamma),
+            Step::Duotone(shadow, highlight) => {
+                let fraction = Fraction::from(color.to_lab().l / 100.0);
+                let Lab { l, a, b, .. } = shadow.mix::<Lab>(highlight, fraction).to_lab();
+                Color::from_lab(l, a, b, color.to_rgba().alpha)
+            }
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = Error;
+
+    /// Parse a single transformation step of the form `name` or `name:argument`, e.g.
+    /// `saturate:0.2`, `complement`, `colorblind:deuter` or `curves:r:0,0;0.5,0.6;1,1`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, argument) = match s.split_once(':') {
+            Some((name, argument)) => (name, Some(argument)),
+            None => (s, None),
+        };
+
+        let invalid = |reason: &str| Error::InvalidPipeline(reason.into());
+
+        let number = || -> Result<Scalar, Self::Err> {
+            argument
+                .ok_or_else(|| invalid("missing numerical argument for pipeline step"))?
+                .parse()
+                .map_err(|_| invalid("invalid numerical argument for pipeline step"))
+        };
+
+        match name.to_lowercase().as_ref() {
+            "saturate" => Ok(Step::Saturate(number()?)),
+            "desaturate" => Ok(Step::Desaturate(number()?)),
+            "lighten" => Ok(Step::Lighten(number()?)),
+            "darken" => Ok(Step::Darken(number()?)),
+            "rotate" => Ok(Step::Rotate(number()?)),
+            "complement" => Ok(Step::Complement),
+            "to-gray" => Ok(Step::ToGray),
+            "colorblind" => match argument.map(|a| a.to_lowercase()).as_deref() {
+                Some("prot") => Ok(Step::Colorblind(ColorblindnessType::Protanopia)),
+                Some("deuter") => Ok(Step::Colorblind(ColorblindnessType::Deuteranopia)),
+                Some("trit") => Ok(Step::Colorblind(ColorblindnessType::Tritanopia)),
+                Some("achroma") => Ok(Step::Colorblind(ColorblindnessType::Achromatopsia)),
+                _ => Err(invalid(
+                    "colorblindness type must be one of 'prot', 'deuter', 'trit' or 'achroma'",
+                )),
+            },
+            "curves" => {
+                let mut curves = ChannelCurves::default();
+                for spec in argument
+                    .ok_or_else(|| invalid("missing curve specification for pipeline step"))?
+                    .split('|')
+                {
+                    curves.add_spec(spec)?;
+                }
+                Ok(Step::Curves(curves))
+            }
+            "duotone" => {
+                let (shadow, highlight) =
+                    argument.and_then(|a| a.split_once(':')).ok_or_else(|| {
+                        invalid("duotone steps must be given as 'duotone:<shadow>:<highlight>'")
+                    })?;
+                let color = |c: &str| parse_color(c).ok_or_else(|| Error::InvalidColor(c.into()));
+                Ok(Step::Duotone(color(shadow)?, color(highlight)?))
+            }
+            _ => Err(Error::InvalidPipeline(format!(
+                "unknown pipeline step '{}'",
+                name
+            ))),
+        }
+    }
+}
+
+/// A sequence of color transformations that are applied one after another.
+///
+/// A pipeline can be parsed from a whitespace-separated list of steps (see `Step::from_str`),
+/// for example `saturate:0.1 rotate:-20 curves:b:0,0.1;1,0.9|r:0,0;1,0.95`. Multiple curve
+/// specifications for a single `curves` step are separated by `|`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Pipeline {
+    pub steps: Vec<Step>,
+}
+
+impl Pipeline {
+    pub fn apply(&self, color: &Color) -> Color {
+        self.steps
+            .iter()
+            .fold(color.clone(), |color, step| step.apply(&color))
+    }
+}
+
+impl Pipeline {
+    /// Look up one of the built-in filters in `FILTER_PRESETS` by its name.
+    pub fn preset(name: &str) -> Option<Pipeline> {
+        FILTER_PRESETS
+            .iter()
+            .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
+            .map(|(_, spec)| spec.parse().expect("valid built-in pipeline"))
+    }
+}
+
+impl FromStr for Pipeline {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let steps = s
+            .split_whitespace()
+            .map(|step| step.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Pipeline { steps })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_steps() {
+        assert_eq!(Ok(Step::Saturate(0.2)), "saturate:0.2".parse());
+        assert_eq!(Ok(Step::Complement), "complement".parse());
+        assert_eq!(
+            Ok(Step::Colorblind(ColorblindnessType::Tritanopia)),
+            "colorblind:trit".parse()
+        );
+        assert!("curves:rgb:0,0.1;1,0.9".parse::<Step>().is_ok());
+
+        assert!("saturate".parse::<Step>().is_err());
+        assert!("rotate:abc".parse::<Step>().is_err());
+        assert_eq!(
+            Err(Error::InvalidPipeline(
+                "unknown pipeline step 'explode'".into()
+            )),
+            "explode:3".parse::<Step>()
+        );
+    }
+
+    #[test]
+    fn apply_pipeline() {
+        let pipeline: Pipeline = "rotate:120 rotate:120".parse().unwrap();
+        assert_eq!(Color::blue(), pipeline.apply(&Color::red()));
+
+        assert_eq!(Color::red(), Pipeline::default().apply(&Color::red()));
+    }
+
+    #[test]
+    fn filter_presets() {
+        for (name, _) in FILTER_PRESETS {
+            assert!(Pipeline::preset(name).is_some());
+        }
+        assert!(Pipeline::preset("does-not-exist").is_none());
+
+        let sepia = Pipeline::preset("Sepia").unwrap().apply(&Color::blue());
+        let rgba = sepia.to_rgba();
+        assert!(rgba.r > rgba.g && rgba.g > rgba.b);
+    }
+
+    #[test]
+    fn duotone() {
+        let step: Step = "duotone:navy:gold".parse().unwrap();
+        assert_eq!(Color::from_rgb(0, 0, 128), step.apply(&Color::black()));
+        assert_eq!(Color::from_rgb(255, 215, 0), step.apply(&Color::white()));
+
+        assert!("duotone:navy".parse::<Step>().is_err());
+        assert_eq!(
+            Err(Error::InvalidColor("nocolor".into())),
+            "duotone:navy:nocolor".parse::<Step>()
+        );
+    }
+}
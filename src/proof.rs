@@ -0,0 +1,160 @@
+//! Soft proofing: a simple approximation of how colors are reproduced by print processes with a
+//! limited gamut. This is not a replacement for proper ICC-based color management, but gives a
+//! quick impression of which colors will not survive printing.
+
+use crate::types::Scalar;
+use crate::{Color, Lab, CMYK};
+
+/// A print process (ink, paper and printing method).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrintProfile {
+    /// Offset printing on newsprint: grayish paper, strong dot gain, low total ink limit
+    Newsprint,
+    /// A standard office laser printer on plain (white, optically brightened) paper
+    OfficeLaser,
+}
+
+/// The characteristics of a print process.
+struct Parameters {
+    /// The color of the unprinted paper
+    paper: Lab,
+    /// The lightness of the darkest black that can be printed
+    black_lightness: Scalar,
+    /// Chroma values are compressed smoothly into this limit
+    max_chroma: Scalar,
+    /// Tone value increase at 50% coverage
+    dot_gain: Scalar,
+    /// Maximum total area coverage of all inks (1.0 = 100%)
+    ink_limit: Scalar,
+}
+
+impl PrintProfile {
+    /// All print profiles along with their names.
+    pub const ALL: &'static [(&'static str, PrintProfile)] = &[
+        ("newsprint", PrintProfile::Newsprint),
+        ("office-laser", PrintProfile::OfficeLaser),
+    ];
+
+    /// Look up a print profile by its name, e.g. `newsprint`.
+    pub fn from_name(name: &str) -> Option<PrintProfile> {
+        PrintProfile::ALL
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|&(_, profile)| profile)
+    }
+
+    fn parameters(self) -> Parameters {
+        match self {
+            PrintProfile::Newsprint => Parameters {
+                paper: Lab {
+                    l: 82.0,
+                    a: 0.0,
+                    b: 3.0,
+                    alpha: 1.0,
+                },
+                black_lightness: 30.0,
+                max_chroma: 55.0,
+                dot_gain: 0.26,
+                ink_limit: 2.4,
+            },
+            PrintProfile::OfficeLaser => Parameters {
+                paper: Lab {
+                    l: 94.0,
+                    a: 1.0,
+                    b: -4.0,
+                    alpha: 1.0,
+                },
+                black_lightness: 18.0,
+                max_chroma: 75.0,
+                dot_gain: 0.12,
+                ink_limit: 2.8,
+            },
+        }
+    }
+
+    /// Simulate how the given color would look when printed with this process.
+    ///
+    /// The color is separated into (naive) CMYK values, which are reduced to the total ink limit
+    /// and increased by the dot gain. The printed color is then compressed into the tonal range
+    /// between the paper white and the darkest black, and into the (smaller) chroma range of the
+    /// print process. Finally, the paper tint shows through in the lighter parts.
+    pub fn proof(self, color: &Color) -> Color {
+        let p = self.parameters();
+
+        let CMYK {
+            mut c,
+            mut m,
+            mut y,
+            k,
+        } = color.to_cmyk();
+
+        let total = c + m + y + k;
+        if total > p.ink_limit && c + m + y > 0.0 {
+            let factor = ((p.ink_limit - k) / (c + m + y)).max(0.0);
+            c *= factor;
+            m *= factor;
+            y *= factor;
+        }
+
+        let gain = |t: Scalar| (t + p.dot_gain * 4.0 * t * (1.0 - t)).clamp(0.0, 1.0);
+        let printed = Color::from(&CMYK {
+            c: gain(c),
+            m: gain(m),
+            y: gain(y),
+            k: gain(k),
+        })
+        .to_lab();
+
+        let fraction = printed.l / 100.0;
+        let l = p.black_lightness + (p.paper.l - p.black_lightness) * fraction;
+
+        let chroma = printed.a.hypot(printed.b);
+        let scale = if chroma > 0.0 {
+            p.max_chroma * (chroma / p.max_chroma).tanh() / chroma
+        } else {
+            0.0
+        };
+
+        Color::from(&Lab {
+            l,
+            a: printed.a * scale + p.paper.a * fraction,
+            b: printed.b * scale + p.paper.b * fraction,
+            alpha: color.alpha,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paper_white_and_black() {
+        let white = PrintProfile::Newsprint.proof(&Color::white()).to_lab();
+        assert!((white.l - 82.0).abs() < 1.0);
+        assert!(white.b > 1.0);
+
+        let black = PrintProfile::Newsprint.proof(&Color::black()).to_lab();
+        assert!((black.l - 30.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn saturated_colors_lose_chroma() {
+        let chroma = |c: &Color| c.to_lch().c;
+        let red = Color::red();
+
+        let newsprint = PrintProfile::Newsprint.proof(&red);
+        let laser = PrintProfile::OfficeLaser.proof(&red);
+        assert!(chroma(&newsprint) < chroma(&laser));
+        assert!(chroma(&laser) < chroma(&red));
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(
+            Some(PrintProfile::OfficeLaser),
+            PrintProfile::from_name("office-laser")
+        );
+        assert_eq!(None, PrintProfile::from_name("glossy"));
+    }
+}
@@ -0,0 +1,377 @@
+//! Terminal color schemes: the 16 ANSI colors along with the background, foreground and cursor
+//! colors, derived from a background color and an (optional) accent color.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+use crate::distinct::{
+    ColorRegion, DistanceMetric, Objective, OptimizationMode, SimulatedAnnealing,
+    SimulationParameters,
+};
+use crate::helper::mod_positive;
+use crate::types::Scalar;
+use crate::{Color, Error, Lab, OkLCh, OkLab};
+
+/// The names of the 16 ANSI colors, in the order of their indices.
+pub const ANSI_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright-black",
+    "bright-red",
+    "bright-green",
+    "bright-yellow",
+    "bright-blue",
+    "bright-magenta",
+    "bright-cyan",
+    "bright-white",
+];
+
+/// The (OkLCh) hue angles of the six chromatic ANSI colors (red, green, yellow, blue, magenta and
+/// cyan). These are the hues of the corresponding sRGB primaries and secondaries.
+const ANSI_HUES: [Scalar; 6] = [29.0, 142.0, 110.0, 264.0, 328.0, 195.0];
+
+/// Minimum (WCAG) contrast ratio of the chromatic colors against the background
+const MIN_CONTRAST: Scalar = 4.5;
+
+/// Minimum (WCAG) contrast ratio of the foreground color against the background
+const MIN_FOREGROUND_CONTRAST: Scalar = 7.0;
+
+/// Minimum width (in CIELab lightness) of the range of colors with the required contrast against
+/// the background. Narrower ranges (mid-gray backgrounds) leave no room for distinct hues.
+const MIN_LIGHTNESS_RANGE: Scalar = 10.0;
+
+/// A terminal color scheme.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalTheme {
+    pub background: Color,
+    pub foreground: Color,
+    pub cursor: Color,
+    /// The 16 ANSI colors, in the order of [`ANSI_NAMES`]
+    pub colors: [Color; 16],
+}
+
+impl TerminalTheme {
+    /// Derive a terminal color scheme from a background color and an optional accent color.
+    ///
+    /// The foreground and the gray tones take over the hue of the background (with a small
+    /// chroma). The six chromatic colors start out at the hues of the sRGB primaries and
+    /// secondaries, with a common lightness and chroma (the chroma of the accent color, if
+    /// given). Their lightness is adjusted to reach a contrast ratio of 4.5 against the
+    /// background. They are then optimized for distinctness with [`SimulatedAnnealing`], while
+    /// staying close to their hues. The accent color replaces the chromatic color with the closest
+    /// hue and is used as the cursor color. The bright variants are moved further away from the
+    /// background (lighter for dark themes, darker for light themes).
+    ///
+    /// The result is deterministic (across platforms and releases). Returns an error if a
+    /// contrast ratio of 4.5 cannot be reached for a sufficiently wide range of colors against the
+    /// background (e.g. for a mid-gray).
+    pub fn generate(background: &Color, accent: Option<&Color>) -> Result<TerminalTheme, Error> {
+        let region = ColorRegion {
+            min_contrast: Some((background.clone(), MIN_CONTRAST)),
+            ..ColorRegion::default()
+        };
+        let widest_range = region
+            .lightness_ranges()
+            .iter()
+            .map(|(min, max)| max - min)
+            .fold(0.0, Scalar::max);
+        if widest_range < MIN_LIGHTNESS_RANGE {
+            return Err(Error::ContrastUnreachable(MIN_CONTRAST));
+        }
+
+        let is_dark = is_dark(background);
+        let direction = if is_dark { 1.0 } else { -1.0 };
+
+        let background_lch = OkLCh::from(background);
+        let tint = background_lch.c.min(0.02);
+        let neutral =
+            |l: Scalar| from_oklch(l.clamp(0.0, 1.0), tint, background_lch.h, background.alpha);
+        let readable = |color: Color, ratio: Scalar| color.with_minimum_contrast(background, ratio);
+
+        let foreground = readable(
+            neutral(if is_dark { 0.9 } else { 0.3 }),
+            MIN_FOREGROUND_CONTRAST,
+        );
+
+        let accent_lch = accent.map(OkLCh::from);
+        let chroma = accent_lch.as_ref().map_or(0.14, |a| a.c.clamp(0.08, 0.2));
+        let lightness = if is_dark { 0.72 } else { 0.52 };
+
+        // The chromatic color that is replaced by the accent color
+        let accent_index = accent_lch.as_ref().map(|a| {
+            (0..ANSI_HUES.len())
+                .min_by(|&i, &j| {
+                    hue_difference(ANSI_HUES[i], a.h).total_cmp(&hue_difference(ANSI_HUES[j], a.h))
+                })
+                .expect("at least one hue")
+        });
+
+        // The set of colors for the optimizer: the fixed colors (background and accent) come
+        // first, followed by the remaining chromatic colors.
+        let mut initial_colors = vec![background.clone()];
+        initial_colors.extend(accent.cloned());
+        let num_fixed_colors = initial_colors.len();
+        let free_indices: Vec<usize> = (0..ANSI_HUES.len())
+            .filter(|&i| Some(i) != accent_index)
+            .collect();
+        for &i in &free_indices {
+            let hue = ANSI_HUES[i];
+            let color = from_oklch(
+                lightness,
+                chroma.min(OkLab::max_chroma(lightness, hue)),
+                hue,
+                1.0,
+            );
+            initial_colors.push(readable(color, MIN_CONTRAST));
+        }
+
+        let objective = ThemeObjective {
+            targets: initial_colors
+                .iter()
+                .enumerate()
+                .map(|(i, color)| (i >= num_fixed_colors).then(|| color.to_lab()))
+                .collect(),
+        };
+        let parameters = SimulationParameters {
+            initial_temperature: 3.0,
+            cooling_rate: 0.95,
+            num_iterations: 10_000,
+            opt_mode: OptimizationMode::Local,
+            num_fixed_colors,
+            deadline: None,
+            patience: None,
+            region,
+        };
+        let mut annealing = SimulatedAnnealing::with_rng(
+            &initial_colors,
+            parameters,
+            objective,
+            ChaCha12Rng::seed_from_u64(0),
+        );
+        annealing.run(&mut |_| {});
+        let optimized = annealing.get_colors();
+
+        let mut chromatic: Vec<Color> = vec![Color::black(); ANSI_HUES.len()];
+        for (&i, color) in free_indices.iter().zip(&optimized[num_fixed_colors..]) {
+            chromatic[i] = color.clone();
+        }
+        if let (Some(i), Some(accent)) = (accent_index, accent) {
+            chromatic[i] = accent.clone();
+        }
+
+        let brighten = |color: &Color| {
+            let lch = OkLCh::from(color);
+            let l = (lch.l + 0.1 * direction).clamp(0.0, 1.0);
+            let bright = from_oklch(l, lch.c.min(OkLab::max_chroma(l, lch.h)), lch.h, lch.alpha);
+            readable(bright, MIN_CONTRAST)
+        };
+
+        let (black, bright_black, white, bright_white) = if is_dark {
+            (
+                neutral(background_lch.l + 0.1),
+                readable(neutral(background_lch.l + 0.25), 3.0),
+                readable(neutral(0.82), MIN_CONTRAST),
+                neutral(0.97),
+            )
+        } else {
+            (
+                readable(neutral(0.25), MIN_CONTRAST),
+                readable(neutral(0.5), 3.0),
+                neutral(background_lch.l - 0.1),
+                neutral(background_lch.l - 0.03),
+            )
+        };
+
+        let bright: Vec<Color> = chromatic.iter().map(brighten).collect();
+        let colors = [
+            black,
+            chromatic[0].clone(),
+            chromatic[1].clone(),
+            chromatic[2].clone(),
+            chromatic[3].clone(),
+            chromatic[4].clone(),
+            chromatic[5].clone(),
+            white,
+            bright_black,
+            bright[0].clone(),
+            bright[1].clone(),
+            bright[2].clone(),
+            bright[3].clone(),
+            bright[4].clone(),
+            bright[5].clone(),
+            bright_white,
+        ];
+
+        Ok(TerminalTheme {
+            background: background.clone(),
+            cursor: accent.cloned().unwrap_or_else(|| foreground.clone()),
+            foreground,
+            colors,
+        })
+    }
+
+    /// Whether the background of this theme is dark.
+    pub fn is_dark(&self) -> bool {
+        is_dark(&self.background)
+    }
+
+    /// All colors of the theme along with their names: `background`, `foreground`, `cursor`
+    /// and the ANSI colors (see [`ANSI_NAMES`]).
+    pub fn named_colors(&self) -> Vec<(&'static str, &Color)> {
+        let mut result = vec![
+            ("background", &self.background),
+            ("foreground", &self.foreground),
+            ("cursor", &self.cursor),
+        ];
+        result.extend(ANSI_NAMES.iter().copied().zip(self.colors.iter()));
+        result
+    }
+}
+
+/// A background is considered dark if white text has a higher contrast on it than black text.
+fn is_dark(background: &Color) -> bool {
+    Color::white().contrast_ratio(background) > Color::black().contrast_ratio(background)
+}
+
+fn from_oklch(l: Scalar, c: Scalar, h: Scalar, alpha: Scalar) -> Color {
+    Color::from(&OkLab::from_lch(l, c, h, alpha))
+}
+
+/// The absolute difference between two hue angles (0 to 180 degrees).
+fn hue_difference(h1: Scalar, h2: Scalar) -> Scalar {
+    (mod_positive(h1 - h2 + 180.0, 360.0) - 180.0).abs()
+}
+
+/// Maximize the minimal (CIEDE2000) distance between the colors, while penalizing colors that
+/// move away from their target hue, lightness or chroma. The first color (the background) is not
+/// part of the distance computation, as it is kept apart by the contrast constraint.
+struct ThemeObjective {
+    /// The initial CIELab values of the colors that can be modified
+    targets: Vec<Option<Lab>>,
+}
+
+impl ThemeObjective {
+    fn penalty(&self, lab: &Lab, target: &Lab) -> Scalar {
+        let hue = |lab: &Lab| lab.b.atan2(lab.a).to_degrees();
+        let chroma = |lab: &Lab| lab.a.hypot(lab.b);
+
+        let hue_deviation = hue_difference(hue(lab), hue(target));
+        let lightness_deviation = (lab.l - target.l).abs();
+        let chroma_deviation = (chroma(lab) - chroma(target)).abs();
+
+        (hue_deviation - 15.0).max(0.0)
+            + (lightness_deviation - 5.0).max(0.0)
+            + (chroma_deviation - 0.3 * chroma(target)).max(0.0)
+    }
+}
+
+impl Objective for ThemeObjective {
+    type Evaluation = Scalar;
+
+    fn evaluate(&self, lab_values: &[Lab]) -> Scalar {
+        let metric = DistanceMetric::CIEDE2000;
+
+        let mut min_distance = Scalar::INFINITY;
+        for i in 1..lab_values.len() {
+            for j in (i + 1)..lab_values.len() {
+                min_distance =
+                    min_distance.min(metric.lab_distance(&lab_values[i], &lab_values[j]));
+            }
+        }
+
+        let penalty: Scalar = lab_values
+            .iter()
+            .zip(&self.targets)
+            .filter_map(|(lab, target)| target.as_ref().map(|t| self.penalty(lab, t)))
+            .sum();
+
+        min_distance - penalty
+    }
+
+    fn score(&self, evaluation: &Scalar) -> Scalar {
+        *evaluation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dark_theme() {
+        let background = Color::from_rgb(0x1e, 0x1e, 0x2e);
+        let theme = TerminalTheme::generate(&background, None).unwrap();
+
+        assert!(theme.is_dark());
+        assert_eq!(background, theme.background);
+        assert_eq!(theme.foreground, theme.cursor);
+        assert!(theme.foreground.contrast_ratio(&background) >= MIN_FOREGROUND_CONTRAST);
+
+        for i in (1..=6).chain(9..=14) {
+            assert!(theme.colors[i].contrast_ratio(&background) >= MIN_CONTRAST);
+        }
+        for i in 1..=6 {
+            let normal = OkLCh::from(&theme.colors[i]);
+            let bright = OkLCh::from(&theme.colors[i + 8]);
+            assert!(bright.l > normal.l);
+            assert!(hue_difference(normal.h, ANSI_HUES[i - 1]) < 30.0);
+        }
+    }
+
+    #[test]
+    fn light_theme() {
+        let background = Color::from_rgb(0xfa, 0xf8, 0xf0);
+        let theme = TerminalTheme::generate(&background, None).unwrap();
+
+        assert!(!theme.is_dark());
+        assert!(theme.colors[0].luminance() < theme.colors[7].luminance());
+        for i in (1..=6).chain(9..=14) {
+            assert!(theme.colors[i].contrast_ratio(&background) >= MIN_CONTRAST);
+        }
+    }
+
+    #[test]
+    fn accent_color() {
+        let background = Color::black();
+        let accent = Color::from_rgb(0x89, 0xb4, 0xfa);
+        let theme = TerminalTheme::generate(&background, Some(&accent)).unwrap();
+
+        assert_eq!(accent, theme.cursor);
+        assert_eq!(accent, theme.colors[4]);
+    }
+
+    #[test]
+    fn deterministic() {
+        let background = Color::from_rgb(0x28, 0x2c, 0x34);
+        assert_eq!(
+            TerminalTheme::generate(&background, None).unwrap(),
+            TerminalTheme::generate(&background, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn mid_gray_background() {
+        for gray in [0x77, 0x80] {
+            let background = Color::from_rgb(gray, gray, gray);
+            assert_eq!(
+                Err(Error::ContrastUnreachable(MIN_CONTRAST)),
+                TerminalTheme::generate(&background, None)
+            );
+        }
+    }
+
+    #[test]
+    fn named_colors() {
+        let theme = TerminalTheme::generate(&Color::white(), None).unwrap();
+        let names: Vec<&str> = theme.named_colors().iter().map(|(n, _)| *n).collect();
+        assert_eq!(19, names.len());
+        assert_eq!("background", names[0]);
+        assert_eq!("bright-white", names[18]);
+    }
+}
@@ -1,3 +1,7 @@
+use std::ffi::OsStr;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
 use assert_cmd::Command;
 
 fn pastel() -> Command {
@@ -6,6 +10,47 @@ fn pastel() -> Command {
     cmd
 }
 
+/// A file or directory in the temporary directory (unique to the test process), which is removed
+/// again when it goes out of scope, even if the test fails.
+struct TempPath(PathBuf);
+
+impl TempPath {
+    fn new(name: &str) -> TempPath {
+        let path = std::env::temp_dir().join(format!("pastel-{}-{}", std::process::id(), name));
+        TempPath(path)
+    }
+}
+
+impl Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<OsStr> for TempPath {
+    fn as_ref(&self) -> &OsStr {
+        self.0.as_os_str()
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        if self.0.is_dir() {
+            let _ = std::fs::remove_dir_all(&self.0);
+        } else {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+}
+
 #[test]
 fn color_reads_colors_from_args() {
     pastel()
@@ -42,6 +87,57 @@ fn color_reads_colors_from_stdin() {
         .failure();
 }
 
+#[test]
+fn format_reads_raw_pixels_from_stdin() {
+    pastel()
+        .arg("format")
+        .arg("hex")
+        .arg("--input-format=raw-rgb8")
+        .write_stdin(vec![0xff, 0x00, 0x00, 0x00, 0x00, 0xff])
+        .assert()
+        .success()
+        .stdout("#ff0000\n#0000ff\n");
+
+    pastel()
+        .arg("format")
+        .arg("rgb")
+        .arg("--input-format=raw-rgba8")
+        .write_stdin(vec![0xff, 0x00, 0x00, 0x80])
+        .assert()
+        .success()
+        .stdout("rgba(255, 0, 0, 0.502)\n");
+
+    pastel()
+        .arg("format")
+        .arg("hex")
+        .arg("--input-format=raw-rgb8")
+        .write_stdin(vec![0xff, 0x00, 0x00, 0x00])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn raw_output() {
+    pastel()
+        .arg("--output=raw-rgb8")
+        .arg("gradient")
+        .arg("red")
+        .arg("blue")
+        .arg("-n")
+        .arg("2")
+        .assert()
+        .success()
+        .stdout(&[0xff, 0x00, 0x00, 0x00, 0x00, 0xff][..]);
+
+    pastel()
+        .arg("--output=raw-rgba8")
+        .arg("color")
+        .arg("rgba(0, 0, 255, 0.5)")
+        .assert()
+        .success()
+        .stdout(&[0x00, 0x00, 0xff, 0x80][..]);
+}
+
 #[test]
 fn format_basic() {
     pastel()
@@ -62,6 +158,113 @@ fn format_basic() {
         .stdout("rgb(255, 0, 0)\nrgb(0, 0, 255)\n");
 }
 
+#[test]
+fn format_multiple_types() {
+    pastel()
+        .arg("format")
+        .arg("all")
+        .arg("teal")
+        .assert()
+        .success()
+        .stdout(
+            "#008080\n\
+             rgb(0, 128, 128)\n\
+             hsl(180, 100.0%, 25.1%)\n\
+             Lab(48, -29, -8)\n\
+             LCh(48, 30, 196)\n\
+             OkLab(0.5431, -0.0896, -0.0237)\n\
+             cmyk(100, 0, 0, 50)\n\
+             teal\n",
+        );
+
+    pastel()
+        .arg("format")
+        .arg("hex,name")
+        .arg("red")
+        .arg("blue")
+        .assert()
+        .success()
+        .stdout("#ff0000\nred\n#0000ff\nblue\n");
+
+    pastel()
+        .arg("format")
+        .arg("hex,unknown")
+        .arg("red")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn format_template() {
+    pastel()
+        .arg("format")
+        .arg("--template={name} {hex} rgba({r},{g},{b},{a:.2}) {{{lch-hue:.1}}}")
+        .write_stdin("teal\nred\n")
+        .assert()
+        .success()
+        .stdout(
+            "teal #008080 rgba(0,128,128,1.00) {196.4}\n\
+             red #ff0000 rgba(255,0,0,1.00) {40.0}\n",
+        );
+
+    pastel()
+        .arg("format")
+        .arg("--template={hsl-hue}")
+        .write_stdin("blue\n")
+        .assert()
+        .success()
+        .stdout("240\n");
+
+    pastel()
+        .arg("format")
+        .arg("--template={unknown}")
+        .write_stdin("red\n")
+        .assert()
+        .failure();
+
+    pastel()
+        .arg("format")
+        .arg("--template={hex:.2}")
+        .write_stdin("red\n")
+        .assert()
+        .failure();
+
+    pastel()
+        .arg("format")
+        .arg("unknown")
+        .arg("red")
+        .assert()
+        .failure();
+
+    // With a template, all positional arguments are colors
+    pastel()
+        .arg("format")
+        .arg("--template={hex}")
+        .arg("red")
+        .arg("rgb(0,0,255)")
+        .assert()
+        .success()
+        .stdout("#ff0000\n#0000ff\n");
+
+    pastel()
+        .arg("format")
+        .arg("--template={hex}")
+        .arg("--")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("#ff0000\n");
+
+    pastel()
+        .arg("format")
+        .arg("--template={hex}")
+        .arg("rgb")
+        .write_stdin("red\n")
+        .assert()
+        .failure()
+        .stderr("[pastel error]: Could not parse color 'rgb'\n");
+}
+
 #[test]
 fn pipe_into_format_command() {
     let first = pastel()
@@ -81,6 +284,29 @@ fn pipe_into_format_command() {
         .stdout("red\nteal\nhotpink\n");
 }
 
+#[test]
+fn format_name_with_confidence() {
+    pastel()
+        .arg("format")
+        .arg("--confidence")
+        .arg("name")
+        .arg("tomato")
+        .arg("#fa8071")
+        .assert()
+        .success()
+        .stdout("tomato (exact)\n~salmon (ΔE 0.3)\n");
+
+    pastel()
+        .arg("format")
+        .arg("--exact-only")
+        .arg("name")
+        .arg("#fa8071")
+        .arg("tomato")
+        .assert()
+        .success()
+        .stdout("\ntomato\n");
+}
+
 #[test]
 fn sort_by_basic() {
     pastel()
@@ -123,3 +349,2892 @@ fn set_basic() {
         .success()
         .stdout("hsl(0,0.0%,50.0%)\n");
 }
+
+#[test]
+fn distinct_extend_palette() {
+    let output = pastel()
+        .arg("distinct")
+        .arg("--extend")
+        .arg("-")
+        .arg("2")
+        .write_stdin("# existing palette\nred\n#0000ff\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(4, lines.len());
+    assert_eq!("hsl(0,100.0%,50.0%)", lines[0]);
+    assert_eq!("hsl(240,100.0%,50.0%)", lines[1]);
+}
+
+#[test]
+fn distinct_report() {
+    let output = pastel()
+        .arg("distinct")
+        .arg("--report")
+        .arg("3")
+        .arg("red")
+        .arg("blue")
+        .arg("green")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(10, lines.len());
+    assert_eq!("", lines[3]);
+    assert_eq!("metric: CIE76", lines[4]);
+    assert_eq!("min distance: 133.13 (#ff0000, #008000)", lines[5]);
+    assert!(lines[6].starts_with("mean distance: "));
+    assert!(lines[7].starts_with("min distance (protanopia): "));
+    assert!(lines[9].starts_with("min distance (tritanopia): "));
+}
+
+#[test]
+fn distinct_with_budget() {
+    let output = pastel()
+        .arg("distinct")
+        .arg("--iterations")
+        .arg("1000")
+        .arg("--max-time")
+        .arg("500ms")
+        .arg("4")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(4, String::from_utf8(output.stdout).unwrap().lines().count());
+
+    pastel()
+        .arg("distinct")
+        .arg("--max-time")
+        .arg("soon")
+        .arg("4")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn distinct_with_seed() {
+    let run = |seed: &str| {
+        let output = pastel()
+            .arg("distinct")
+            .arg("--iterations")
+            .arg("2000")
+            .arg("--seed")
+            .arg(seed)
+            .arg("5")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert_eq!(run("42"), run("42"));
+    assert_ne!(run("42"), run("43"));
+
+    pastel()
+        .arg("distinct")
+        .arg("--seed")
+        .arg("x")
+        .arg("4")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn distinct_in_oklab() {
+    let output = pastel()
+        .arg("distinct")
+        .arg("--space")
+        .arg("oklab")
+        .arg("--iterations")
+        .arg("2000")
+        .arg("--report")
+        .arg("4")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("metric: OkLab"));
+
+    // Distances are reported as plain ΔE OK values (like in 'pastel distance'), where black and
+    // white are 1.0 apart
+    let output = pastel()
+        .arg("distinct")
+        .arg("--space")
+        .arg("oklab")
+        .arg("--print-minimal-distance")
+        .arg("3")
+        .arg("black")
+        .arg("white")
+        .output()
+        .unwrap();
+    let min_distance: f64 = String::from_utf8(output.stdout)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+    assert!(min_distance > 0.1 && min_distance < 1.0);
+
+    pastel()
+        .arg("distinct")
+        .arg("--space")
+        .arg("oklab")
+        .arg("--metric")
+        .arg("CIEDE2000")
+        .arg("4")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn distinct_with_patience() {
+    let output = pastel()
+        .arg("distinct")
+        .arg("--patience")
+        .arg("500")
+        .arg("5")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 5);
+
+    pastel()
+        .arg("distinct")
+        .arg("--patience")
+        .arg("-1")
+        .arg("4")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn distinct_in_region() {
+    let output = pastel()
+        .arg("distinct")
+        .arg("--iterations")
+        .arg("3000")
+        .arg("--min-lightness")
+        .arg("60")
+        .arg("--max-chroma")
+        .arg("40")
+        .arg("--seed")
+        .arg("1")
+        .arg("4")
+        .arg("black")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let colors = String::from_utf8(output.stdout).unwrap();
+    let lightness: Vec<f64> = colors
+        .lines()
+        .map(|c| pastel::parser::parse_color(c).unwrap().to_lch().l)
+        .collect();
+    assert_eq!(4, lightness.len());
+    assert_eq!(1, lightness.iter().filter(|l| **l < 1.0).count());
+    assert_eq!(3, lightness.iter().filter(|l| **l > 59.0).count());
+
+    pastel()
+        .arg("distinct")
+        .arg("--min-lightness")
+        .arg("80")
+        .arg("--max-lightness")
+        .arg("20")
+        .arg("4")
+        .assert()
+        .failure();
+    pastel()
+        .arg("distinct")
+        .arg("--max-chroma")
+        .arg("nan")
+        .arg("3")
+        .assert()
+        .failure()
+        .code(1);
+}
+
+#[test]
+fn distinct_with_min_contrast() {
+    let output = pastel()
+        .arg("distinct")
+        .arg("--iterations")
+        .arg("3000")
+        .arg("--background")
+        .arg("#1e1e1e")
+        .arg("--min-contrast")
+        .arg("4.5")
+        .arg("5")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // The colors are printed with limited precision, so the contrast is only checked roughly
+    let background = pastel::parser::parse_color("#1e1e1e").unwrap();
+    let colors = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(5, colors.lines().count());
+    for color in colors.lines() {
+        let color = pastel::parser::parse_color(color).unwrap();
+        assert!(color.contrast_ratio(&background) > 4.4);
+    }
+
+    pastel()
+        .arg("distinct")
+        .arg("--background")
+        .arg("gray")
+        .arg("--min-contrast")
+        .arg("7")
+        .arg("4")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn distinct_resume() {
+    let path = TempPath::new("state.json");
+
+    let output = pastel()
+        .arg("distinct")
+        .arg("--iterations")
+        .arg("1000")
+        .arg("--resume")
+        .arg(&path)
+        .arg("3")
+        .arg("red")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let state = std::fs::read_to_string(&path).unwrap();
+    assert!(state.contains("\"num_fixed_colors\": 1"));
+
+    // The number of colors is taken from the saved state
+    let output = pastel()
+        .arg("distinct")
+        .arg("--iterations")
+        .arg("1000")
+        .arg("--resume")
+        .arg(&path)
+        .arg("7")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(3, String::from_utf8(output.stdout).unwrap().lines().count());
+
+    pastel()
+        .arg("distinct")
+        .arg("--resume")
+        .arg(&path)
+        .arg("3")
+        .arg("blue")
+        .assert()
+        .failure();
+
+    std::fs::write(&path, "{}").unwrap();
+    pastel()
+        .arg("distinct")
+        .arg("--resume")
+        .arg(&path)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn distinct_with_constraints() {
+    let output = pastel()
+        .arg("distinct")
+        .arg("--iterations")
+        .arg("3000")
+        .arg("--lightness-spread")
+        .arg("10:0.5")
+        .arg("--hue-uniformity")
+        .arg("0.1")
+        .arg("--anchor")
+        .arg("#3b82f6:2")
+        .arg("4")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(4, String::from_utf8(output.stdout).unwrap().lines().count());
+
+    pastel()
+        .arg("distinct")
+        .arg("--anchor")
+        .arg("red:heavy")
+        .arg("4")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn extract_from_image() {
+    let path = TempPath::new("image.png");
+
+    // Three quarters red, one quarter blue
+    let image = image::RgbImage::from_fn(8, 8, |x, _| {
+        if x < 6 {
+            image::Rgb([255, 0, 0])
+        } else {
+            image::Rgb([0, 0, 255])
+        }
+    });
+    image.save(&path).unwrap();
+
+    pastel()
+        .arg("extract-from-image")
+        .arg("--number")
+        .arg("2")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\nhsl(240,100.0%,50.0%)\n");
+
+    std::fs::remove_file(&path).unwrap();
+
+    pastel()
+        .arg("extract-from-image")
+        .arg(&path)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn generative_is_reproducible() {
+    for algorithm in ["simplex", "value"] {
+        let run = || {
+            pastel()
+                .arg("generative")
+                .arg("--algorithm")
+                .arg(algorithm)
+                .arg("--seed")
+                .arg("42")
+                .arg("-n")
+                .arg("16")
+                .output()
+                .unwrap()
+        };
+
+        let output = run();
+        assert!(output.status.success());
+        assert_eq!(16, String::from_utf8_lossy(&output.stdout).lines().count());
+        assert_eq!(output.stdout, run().stdout);
+    }
+
+    for step in ["nan", "inf", "0", "-0.1"] {
+        pastel()
+            .arg("generative")
+            .arg(format!("--step={}", step))
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn glass_over_backgrounds() {
+    pastel()
+        .arg("glass")
+        .arg("--list")
+        .arg("--opacity=0.5")
+        .arg("white")
+        .arg("black")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,49.8%)\nhsl(0,0.0%,100.0%)\n");
+
+    pastel()
+        .arg("glass")
+        .arg("--opacity=0.5")
+        .arg("--text=black")
+        .arg("white")
+        .arg("black")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout(
+            "glass: #ffffff80 at 50% opacity\n\
+             lightest: #ffffff\n\
+             darkest: #7f7f7f\n\
+             max. difference: 33.59\n\
+             worst contrast (wcag2, text #000000): 5.24 on #7f7f7f (background #000000)\n",
+        );
+
+    pastel()
+        .arg("glass")
+        .arg("--opacity=1.5")
+        .arg("white")
+        .arg("black")
+        .assert()
+        .failure();
+
+    pastel()
+        .arg("glass")
+        .arg("white")
+        .write_stdin("")
+        .assert()
+        .failure()
+        .code(1);
+}
+
+#[test]
+fn states_named_palette() {
+    pastel()
+        .arg("states")
+        .arg("#6750a4")
+        .arg("--on=#fffbfe")
+        .arg("--prefix=primary")
+        .assert()
+        .success()
+        .stdout(
+            "primary-hover: #f2edf6\n\
+             primary-focus: #efe9f5\n\
+             primary-pressed: #efe9f5\n\
+             primary-dragged: #e6dfef\n\
+             primary-disabled: #c1bfc1\n",
+        );
+
+    pastel().arg("states").arg("red").assert().success().stdout(
+        "hover: #ffeaea\nfocus: #ffe5e5\npressed: #ffe5e5\ndragged: #ffd6d6\ndisabled: #cecece\n",
+    );
+}
+
+#[test]
+fn harmonies() {
+    pastel()
+        .arg("harmonies")
+        .arg("triadic")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\nhsl(120,100.0%,50.0%)\nhsl(240,100.0%,50.0%)\n");
+
+    pastel()
+        .arg("harmonies")
+        .arg("analogous")
+        .arg("-n")
+        .arg("5")
+        .arg("--angle=20")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout(
+            "hsl(320,100.0%,50.0%)\nhsl(340,100.0%,50.0%)\nhsl(0,100.0%,50.0%)\n\
+             hsl(20,100.0%,50.0%)\nhsl(40,100.0%,50.0%)\n",
+        );
+
+    pastel()
+        .arg("harmonies")
+        .arg("--colorspace=OkLCh")
+        .arg("complementary")
+        .arg("coral")
+        .assert()
+        .success()
+        .stdout("hsl(16,100.0%,65.7%)\nhsl(190,100.0%,44.5%)\n");
+}
+
+#[test]
+fn shades_tints_and_tones() {
+    pastel()
+        .arg("tints")
+        .arg("-n")
+        .arg("3")
+        .arg("--colorspace=RGB")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\nhsl(0,100.0%,66.7%)\nhsl(0,100.0%,83.3%)\n");
+
+    pastel()
+        .arg("shades")
+        .arg("-n")
+        .arg("2")
+        .arg("--colorspace=RGB")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,100.0%)\nhsl(0,0.0%,50.2%)\n");
+
+    pastel()
+        .arg("tones")
+        .arg("-n")
+        .arg("2")
+        .arg("--colorspace=RGB")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,100.0%)\nhsl(0,0.0%,75.3%)\n");
+}
+
+#[test]
+fn blend_modes() {
+    pastel()
+        .arg("blend")
+        .arg("--mode=multiply")
+        .arg("#f4e8c1")
+        .arg("steelblue")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("hsl(196,34.0%,39.8%)\nhsl(46,69.9%,85.7%)\n");
+
+    pastel()
+        .arg("blend")
+        .arg("--mode=screen")
+        .arg("black")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\n");
+
+    pastel()
+        .arg("blend")
+        .arg("--mode=difference")
+        .arg("white")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(180,100.0%,50.0%)\n");
+}
+
+#[test]
+fn composite_layers() {
+    pastel()
+        .arg("composite")
+        .arg("white")
+        .arg("rgba(0, 0, 0, 0.5)")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,49.8%)\n");
+
+    pastel()
+        .arg("composite")
+        .arg("--preview")
+        .arg("white")
+        .arg("rgba(0, 0, 255, 0.3)")
+        .arg("#ff000040")
+        .assert()
+        .success()
+        .stdout("hsl(306,35.6%,64.7%)\n");
+}
+
+#[test]
+fn elevation_overlays() {
+    pastel()
+        .arg("elevation")
+        .arg("--named")
+        .arg("--levels=0..2dp")
+        .assert()
+        .success()
+        .stdout("elevation-0dp: #121212\nelevation-1dp: #1d1d1d\nelevation-2dp: #222222\n");
+
+    pastel()
+        .arg("elevation")
+        .arg("--levels=24dp")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,21.6%)\n");
+
+    pastel()
+        .arg("elevation")
+        .arg("--levels=5..4")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn contrast_wcag2_and_apca() {
+    pastel()
+        .arg("contrast")
+        .arg("white")
+        .arg("black")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("21.00\n1.00\n");
+
+    pastel()
+        .arg("contrast")
+        .arg("--algorithm=apca")
+        .arg("white")
+        .arg("black")
+        .arg("#888888")
+        .assert()
+        .success()
+        .stdout("106.0\n63.1\n");
+
+    pastel()
+        .arg("contrast")
+        .arg("--algorithm=apca")
+        .arg("black")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("-107.9\n");
+}
+
+#[test]
+fn cluster_basic() {
+    pastel()
+        .arg("cluster")
+        .arg("-k")
+        .arg("2")
+        .arg("red")
+        .arg("black")
+        .arg("red")
+        .arg("black")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\nhsl(0,100.0%,50.0%)\n\nhsl(0,0.0%,0.0%)\nhsl(0,0.0%,0.0%)\n");
+
+    pastel()
+        .arg("cluster")
+        .arg("-k")
+        .arg("2")
+        .arg("--centroids")
+        .arg("red")
+        .arg("black")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\nhsl(0,0.0%,0.0%)\n");
+}
+
+#[test]
+fn diff_colors_have_equal_lightness() {
+    for lightness in [60.0, 90.0] {
+        let output = pastel()
+            .arg("diff-colors")
+            .arg("--yellow-blue")
+            .arg(format!("--lightness={}", lightness))
+            .assert()
+            .success();
+
+        // The colors are not clipped, so they all keep the requested lightness
+        let colors: Vec<_> = String::from_utf8(output.get_output().stdout.clone())
+            .unwrap()
+            .lines()
+            .map(|line| pastel::parser::parse_color(line).unwrap())
+            .collect();
+        assert_eq!(colors.len(), 4);
+        assert!(colors
+            .iter()
+            .all(|c| (c.to_lch().l - lightness).abs() < 0.5));
+    }
+
+    for lightness in ["150", "-1", "nan"] {
+        pastel()
+            .arg("diff-colors")
+            .arg(format!("--lightness={}", lightness))
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn cyclic_is_continuous_at_the_endpoints() {
+    let output = pastel()
+        .arg("cyclic")
+        .arg("-n")
+        .arg("12")
+        .assert()
+        .success();
+
+    let colors: Vec<_> = String::from_utf8(output.get_output().stdout.clone())
+        .unwrap()
+        .lines()
+        .map(|line| pastel::parser::parse_color(line).unwrap())
+        .collect();
+    assert_eq!(colors.len(), 12);
+
+    let steps: Vec<f64> = (0..colors.len())
+        .map(|i| colors[i].distance_delta_e_ciede2000(&colors[(i + 1) % colors.len()]))
+        .collect();
+    let mean = steps.iter().sum::<f64>() / steps.len() as f64;
+
+    assert!(steps.iter().all(|step| (step - mean).abs() < 0.5 * mean));
+}
+
+#[test]
+fn tint_multiply_and_add() {
+    pastel()
+        .arg("tint")
+        .arg("--multiply")
+        .arg("#ffeedd")
+        .arg("white")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(30,100.0%,93.3%)\nhsl(0,100.0%,50.0%)\n");
+
+    pastel()
+        .arg("tint")
+        .arg("--add")
+        .arg("blue")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(300,100.0%,50.0%)\n");
+}
+
+#[test]
+fn gradient_reverse_and_range() {
+    pastel()
+        .arg("gradient")
+        .arg("black")
+        .arg("white")
+        .arg("--colorspace=RGB")
+        .arg("-n")
+        .arg("3")
+        .arg("--range=0.5..1")
+        .arg("--reverse")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,100.0%)\nhsl(0,0.0%,75.3%)\nhsl(0,0.0%,50.2%)\n");
+
+    pastel()
+        .arg("gradient")
+        .arg("black")
+        .arg("white")
+        .arg("--range=0.5")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn gradient_stop_positions() {
+    pastel()
+        .arg("gradient")
+        .arg("black")
+        .arg("white@0.25")
+        .arg("black")
+        .arg("--colorspace=RGB")
+        .arg("-n")
+        .arg("5")
+        .assert()
+        .success()
+        .stdout(
+            "hsl(0,0.0%,0.0%)\nhsl(0,0.0%,100.0%)\nhsl(0,0.0%,66.7%)\nhsl(0,0.0%,33.3%)\n\
+             hsl(0,0.0%,0.0%)\n",
+        );
+
+    pastel()
+        .arg("gradient")
+        .arg("white@0.5")
+        .arg("black@1")
+        .arg("--colorspace=RGB")
+        .arg("-n")
+        .arg("3")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,100.0%)\nhsl(0,0.0%,100.0%)\nhsl(0,0.0%,0.0%)\n");
+
+    pastel()
+        .arg("gradient")
+        .arg("red@0.6")
+        .arg("blue@0.4")
+        .assert()
+        .failure();
+
+    pastel()
+        .arg("gradient")
+        .arg("red")
+        .arg("blue@2")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn gradient_hue_interpolation() {
+    pastel()
+        .arg("gradient")
+        .arg("red")
+        .arg("red")
+        .arg("--colorspace=HSL")
+        .arg("--hue-interpolation=longer")
+        .arg("-n")
+        .arg("4")
+        .assert()
+        .success()
+        .stdout(
+            "hsl(0,100.0%,50.0%)\nhsl(120,100.0%,50.0%)\nhsl(240,100.0%,50.0%)\n\
+             hsl(0,100.0%,50.0%)\n",
+        );
+
+    pastel()
+        .arg("mix")
+        .arg("--colorspace=HSL")
+        .arg("--hue-interpolation=decreasing")
+        .arg("red")
+        .arg("hsl(90,100%,50%)")
+        .assert()
+        .success()
+        .stdout("hsl(225,100.0%,50.0%)\n");
+}
+
+#[test]
+fn gradient_css_and_svg() {
+    pastel()
+        .arg("gradient")
+        .arg("red")
+        .arg("yellow@0.3")
+        .arg("blue")
+        .arg("--colorspace=OkLab")
+        .arg("--format=css")
+        .assert()
+        .success()
+        .stdout("linear-gradient(in oklab, #ff0000 0%, #ffff00 30%, #0000ff 100%)\n");
+
+    pastel()
+        .arg("gradient")
+        .arg("red")
+        .arg("blue")
+        .arg("--colorspace=RGB")
+        .arg("--format=svg")
+        .assert()
+        .success()
+        .stdout(
+            "<linearGradient id=\"gradient\">\n  \
+             <stop offset=\"0%\" stop-color=\"#ff0000\"/>\n  \
+             <stop offset=\"100%\" stop-color=\"#0000ff\"/>\n\
+             </linearGradient>\n",
+        );
+
+    // SVG gradients are interpolated in sRGB, other color spaces are approximated
+    let output = pastel()
+        .arg("gradient")
+        .arg("red")
+        .arg("blue")
+        .arg("--colorspace=Lab")
+        .arg("-n")
+        .arg("5")
+        .arg("--format=svg")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.matches("<stop ").count(), 5);
+}
+
+#[test]
+fn gradient_easing() {
+    pastel()
+        .arg("gradient")
+        .arg("black")
+        .arg("white")
+        .arg("--colorspace=RGB")
+        .arg("-n")
+        .arg("3")
+        .arg("--easing=ease-in")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,0.0%)\nhsl(0,0.0%,25.1%)\nhsl(0,0.0%,100.0%)\n");
+
+    pastel()
+        .arg("gradient")
+        .arg("black")
+        .arg("white")
+        .arg("--easing=bounce")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn gradient_save_and_load() {
+    let config_dir = TempPath::new("config");
+
+    pastel()
+        .env("PASTEL_CONFIG_DIR", &config_dir)
+        .arg("gradient")
+        .arg("red")
+        .arg("blue")
+        .arg("--colorspace=RGB")
+        .arg("--save=red-blue")
+        .assert()
+        .success();
+
+    pastel()
+        .env("PASTEL_CONFIG_DIR", &config_dir)
+        .arg("gradient")
+        .arg("@red-blue")
+        .arg("-n")
+        .arg("3")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\nhsl(300,100.0%,25.1%)\nhsl(240,100.0%,50.0%)\n");
+
+    pastel()
+        .env("PASTEL_CONFIG_DIR", &config_dir)
+        .arg("gradient")
+        .arg("--list")
+        .assert()
+        .success()
+        .stdout("fire\nforest\nocean\nrainbow\nred-blue\nsunset\n");
+
+    pastel()
+        .env("PASTEL_CONFIG_DIR", &config_dir)
+        .arg("gradient")
+        .arg("@unknown")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn palette_save_append_and_show() {
+    let config_dir = TempPath::new("palettes");
+
+    pastel()
+        .env("PASTEL_CONFIG_DIR", &config_dir)
+        .arg("palette")
+        .arg("save")
+        .arg("mytheme")
+        .arg("red")
+        .arg("blue")
+        .assert()
+        .success();
+
+    pastel()
+        .env("PASTEL_CONFIG_DIR", &config_dir)
+        .arg("palette")
+        .arg("append")
+        .arg("mytheme")
+        .write_stdin("#008000\n")
+        .assert()
+        .success();
+
+    pastel()
+        .env("PASTEL_CONFIG_DIR", &config_dir)
+        .arg("palette")
+        .arg("show")
+        .arg("mytheme")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\nhsl(240,100.0%,50.0%)\nhsl(120,100.0%,25.1%)\n");
+
+    pastel()
+        .env("PASTEL_CONFIG_DIR", &config_dir)
+        .arg("palette")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout("mytheme\n");
+
+    pastel()
+        .env("PASTEL_CONFIG_DIR", &config_dir)
+        .arg("palette")
+        .arg("remove")
+        .arg("mytheme")
+        .assert()
+        .success();
+
+    pastel()
+        .env("PASTEL_CONFIG_DIR", &config_dir)
+        .arg("palette")
+        .arg("show")
+        .arg("mytheme")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn mesh_interpolates_corners() {
+    pastel()
+        .arg("mesh")
+        .arg("--size")
+        .arg("3x2")
+        .arg("--colorspace")
+        .arg("RGB")
+        .arg("black")
+        .arg("white")
+        .arg("white")
+        .arg("black")
+        .assert()
+        .success()
+        .stdout(
+            "hsl(0,0.0%,0.0%)\nhsl(0,0.0%,50.2%)\nhsl(0,0.0%,100.0%)\n\
+             hsl(0,0.0%,100.0%)\nhsl(0,0.0%,50.2%)\nhsl(0,0.0%,0.0%)\n",
+        );
+
+    let path = TempPath::new("mesh.png");
+    pastel()
+        .arg("mesh")
+        .arg("--size")
+        .arg("4x3")
+        .arg("--image")
+        .arg(&path)
+        .arg("red")
+        .arg("yellow")
+        .arg("blue")
+        .arg("white")
+        .assert()
+        .success();
+    let image = image::open(&path).unwrap().into_rgb8();
+    assert_eq!((4, 3), image.dimensions());
+    assert_eq!(&image::Rgb([255, 0, 0]), image.get_pixel(0, 0));
+    assert_eq!(&image::Rgb([255, 255, 255]), image.get_pixel(3, 2));
+
+    for size in ["1x3", "1025x2", "100000x100000"] {
+        pastel()
+            .arg("mesh")
+            .arg("--size")
+            .arg(size)
+            .arg("red")
+            .arg("yellow")
+            .arg("blue")
+            .arg("white")
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn gradient_banding_and_dither() {
+    pastel()
+        .arg("gradient")
+        .arg("black")
+        .arg("white")
+        .arg("-n")
+        .arg("3")
+        .arg("--check-banding")
+        .assert()
+        .success()
+        .stdout(
+            "0-1: visible step (delta E = 36.55)\n1-2: visible step (delta E = 36.49)\n\
+             2 visible steps, 0 plateaus\n",
+        );
+
+    pastel()
+        .arg("gradient")
+        .arg("black")
+        .arg("white")
+        .arg("-n")
+        .arg("4")
+        .arg("--colorspace=RGB")
+        .arg("--dither=ordered")
+        .arg("--bits=1")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,0.0%)\nhsl(0,0.0%,0.0%)\nhsl(0,0.0%,100.0%)\nhsl(0,0.0%,100.0%)\n");
+
+    pastel()
+        .arg("gradient")
+        .arg("black")
+        .arg("white")
+        .arg("--dither=ordered")
+        .arg("--bits=9")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn lut_export_and_apply() {
+    let output = pastel()
+        .arg("lut")
+        .arg("export")
+        .arg("--size")
+        .arg("9")
+        .arg("complement")
+        .assert()
+        .success();
+
+    let path = TempPath::new("test.cube");
+    std::fs::write(&path, &output.get_output().stdout).unwrap();
+
+    pastel()
+        .arg("lut")
+        .arg("apply")
+        .arg(&path)
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(180,100.0%,50.0%)\n");
+
+    for size in ["1", "257", "3000000"] {
+        pastel()
+            .arg("lut")
+            .arg("export")
+            .arg("--size")
+            .arg(size)
+            .arg("complement")
+            .assert()
+            .failure()
+            .stderr("[pastel error]: The size of the lookup table must be between 2 and 256\n");
+    }
+
+    let output = pastel()
+        .arg("lut")
+        .arg("apply")
+        .arg("does-not-exist.cube")
+        .arg("red")
+        .assert()
+        .failure();
+    let stderr = String::from_utf8(output.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("Could not read file 'does-not-exist.cube'"));
+}
+
+#[test]
+fn tokens_import_export_and_transform() {
+    let path = TempPath::new("tokens.json");
+    std::fs::write(
+        &path,
+        r##"{
+  "color": {
+    "$type": "color",
+    "base": { "blue": { "$value": "#0066cc" } },
+    "primary": { "$value": "{color.base.blue}" }
+  },
+  "spacing": { "small": { "$type": "dimension", "$value": "4px" } }
+}"##,
+    )
+    .unwrap();
+
+    pastel()
+        .arg("tokens")
+        .arg("import")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("color.base.blue: #0066cc\ncolor.primary: #0066cc\n");
+
+    pastel()
+        .arg("tokens")
+        .arg("import")
+        .arg("--aliases")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("color.base.blue: #0066cc\ncolor.primary: {color.base.blue}\n");
+
+    pastel()
+        .arg("tokens")
+        .arg("export")
+        .arg("--format=style-dictionary")
+        .write_stdin("color.blue: #0066cc\ncolor.primary: {color.blue}\n")
+        .assert()
+        .success()
+        .stdout(
+            r##"{
+  "color": {
+    "blue": {
+      "type": "color",
+      "value": "#0066cc"
+    },
+    "primary": {
+      "type": "color",
+      "value": "{color.blue}"
+    }
+  }
+}
+"##,
+        );
+
+    pastel()
+        .arg("tokens")
+        .arg("transform")
+        .arg(&path)
+        .arg("complement")
+        .assert()
+        .success()
+        .stdout(
+            r##"{
+  "color": {
+    "$type": "color",
+    "base": {
+      "blue": {
+        "$value": "#cc6600"
+      }
+    },
+    "primary": {
+      "$value": "{color.base.blue}"
+    }
+  },
+  "spacing": {
+    "small": {
+      "$type": "dimension",
+      "$value": "4px"
+    }
+  }
+}
+"##,
+        );
+}
+
+#[test]
+fn tokens_export_figma_and_sketch() {
+    pastel()
+        .arg("tokens")
+        .arg("export")
+        .arg("--format=figma")
+        .write_stdin("brand.blue: #0066cc\nbrand.primary: {brand.blue}\n")
+        .assert()
+        .success()
+        .stdout(
+            r##"{
+  "global": {
+    "brand": {
+      "blue": {
+        "type": "color",
+        "value": "#0066cc"
+      },
+      "primary": {
+        "type": "color",
+        "value": "{brand.blue}"
+      }
+    }
+  }
+}
+"##,
+        );
+
+    pastel()
+        .arg("tokens")
+        .arg("export")
+        .arg("--format=sketch")
+        .write_stdin("red\nprimary: {color-1}\n")
+        .assert()
+        .success()
+        .stdout(
+            r##"{
+  "compatibleVersion": "2.0",
+  "pluginVersion": "2.22",
+  "colors": [
+    {
+      "name": "color-1",
+      "red": 1.0,
+      "green": 0.0,
+      "blue": 0.0,
+      "alpha": 1.0
+    },
+    {
+      "name": "primary",
+      "red": 1.0,
+      "green": 0.0,
+      "blue": 0.0,
+      "alpha": 1.0
+    }
+  ]
+}
+"##,
+        );
+}
+
+#[test]
+fn filter_presets_and_user_defined_filters() {
+    pastel()
+        .arg("filter")
+        .arg("duotone")
+        .arg("black")
+        .assert()
+        .success()
+        .stdout("hsl(224,48.2%,22.0%)\n");
+
+    pastel()
+        .env("PASTEL_FILTER_MY_LOOK", "complement")
+        .arg("filter")
+        .arg("my-look")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(180,100.0%,50.0%)\n");
+
+    pastel()
+        .arg("filter")
+        .arg("does-not-exist")
+        .arg("red")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn ansi_map_c_array() {
+    let output = pastel()
+        .arg("ansi-map")
+        .arg("--format")
+        .arg("c")
+        .arg("black")
+        .arg("white")
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    let mut lines = stdout.lines();
+    assert_eq!(
+        lines.next(),
+        Some("static const unsigned char ansi_to_palette[256] = {")
+    );
+    assert_eq!(
+        lines.next(),
+        Some("    0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 1, 1, 0, 1, 1, 1,")
+    );
+    assert_eq!(stdout.lines().last(), Some("};"));
+
+    pastel()
+        .arg("ansi-map")
+        .arg("--reverse")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("  0 #ffffff ->  15 #ffffff\n");
+}
+
+#[test]
+fn format_ansi_8bit_options() {
+    pastel()
+        .arg("format")
+        .arg("ansi-8bit")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("\\x1b[38;5;196m\n");
+
+    pastel()
+        .arg("format")
+        .arg("ansi-8bit")
+        .arg("--include-base-colors")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("\\x1b[38;5;9m\n");
+
+    pastel()
+        .arg("format")
+        .arg("ansi-8bit")
+        .arg("--prefer-grays")
+        .arg("rgb(99,117,114)")
+        .assert()
+        .success()
+        .stdout("\\x1b[38;5;243m\n");
+}
+
+#[test]
+fn format_ansi_8bit_with_terminal_palette() {
+    let palette = "#000000:#cc5500:#00cd00:#cdcd00:#0000ee:#cd00cd:#00cdcd:#e5e5e5:\
+                   #7f7f7f:#ff0000:#00ff00:#ffff00:#5c5cff:#ff00ff:#00ffff:#ffffff";
+
+    pastel()
+        .env("PASTEL_TERMINAL_PALETTE", palette)
+        .arg("format")
+        .arg("ansi-8bit")
+        .arg("cc5500")
+        .assert()
+        .success()
+        .stdout("\\x1b[38;5;1m\n");
+
+    pastel()
+        .env("PASTEL_TERMINAL_PALETTE", "black:white")
+        .arg("format")
+        .arg("ansi-8bit")
+        .arg("red")
+        .assert()
+        .failure()
+        .stderr(
+            "[pastel error]: Could not parse PASTEL_TERMINAL_PALETTE value 'black:white' \
+             (expected 16 colors, got 2). Expected a list of 16 colors, separated by colons.\n",
+        );
+}
+
+#[test]
+fn paint_color_from_stdin_and_text_from_file() {
+    let path = TempPath::new("paint.txt");
+    std::fs::write(&path, "hello").unwrap();
+
+    pastel()
+        .arg("--force-color")
+        .arg("paint")
+        .arg("-")
+        .arg("--text-from")
+        .arg(&path)
+        .write_stdin("red\n")
+        .assert()
+        .success()
+        .stdout("\x1b[38;2;255;0;0mhello\x1b[0m\n");
+}
+
+#[test]
+fn paint_cycle_through_palette() {
+    let path = TempPath::new("palette.txt");
+    std::fs::write(&path, "# rainbow\nred\n\n#0000ff\n").unwrap();
+
+    pastel()
+        .arg("--force-color")
+        .arg("paint")
+        .arg("--cycle")
+        .arg(&path)
+        .write_stdin("one\ntwo\nthree\n")
+        .assert()
+        .success()
+        .stdout(
+            "\x1b[38;2;255;0;0mone\x1b[0m\n\
+             \x1b[38;2;0;0;255mtwo\x1b[0m\n\
+             \x1b[38;2;255;0;0mthree\x1b[0m\n",
+        );
+}
+
+#[test]
+fn colorize_with_rules() {
+    let path = TempPath::new("rules.txt");
+    std::fs::write(&path, "# log levels\nERROR => red bold\n[0-9]+ => blue\n").unwrap();
+
+    pastel()
+        .arg("--force-color")
+        .arg("colorize")
+        .arg("--rules")
+        .arg(&path)
+        .write_stdin("ERROR 42\nok\n")
+        .assert()
+        .success()
+        .stdout(
+            "\x1b[38;2;255;0;0;1mERROR\x1b[0m \x1b[38;2;0;0;255m42\x1b[0m\n\
+             ok\n",
+        );
+
+    pastel()
+        .arg("--force-color")
+        .arg("colorize")
+        .arg("--whole-line")
+        .arg("--rules")
+        .arg(&path)
+        .write_stdin("ERROR 42\n")
+        .assert()
+        .success()
+        .stdout("\x1b[38;2;255;0;0;1mERROR 42\x1b[0m\n");
+
+    std::fs::write(&path, "ERROR => notacolor\n").unwrap();
+    pastel()
+        .arg("colorize")
+        .arg("--rules")
+        .arg(&path)
+        .write_stdin("ERROR\n")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn hash_color_is_stable() {
+    pastel()
+        .arg("hash-color")
+        .arg("alice")
+        .arg("bob")
+        .assert()
+        .success()
+        .stdout("hsl(64,40.7%,46.3%)\nhsl(341,56.6%,67.5%)\n");
+
+    pastel()
+        .arg("hash-color")
+        .write_stdin("alice\n")
+        .assert()
+        .success()
+        .stdout("hsl(64,40.7%,46.3%)\n");
+
+    for arg in [
+        "--lightness=5",
+        "--lightness=nan",
+        "--chroma=-0.1",
+        "--chroma=inf",
+    ] {
+        pastel()
+            .arg("hash-color")
+            .arg(arg)
+            .arg("alice")
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn hash_color_with_minimum_contrast() {
+    pastel()
+        .arg("hash-color")
+        .arg("--lightness=0.2")
+        .arg("--format=hex")
+        .arg("alice")
+        .assert()
+        .success()
+        .stdout("#161800\n");
+
+    pastel()
+        .arg("hash-color")
+        .arg("--lightness=0.2")
+        .arg("--against=black")
+        .arg("--format=hex")
+        .arg("alice")
+        .assert()
+        .success()
+        .stdout("#737758\n");
+
+    pastel()
+        .arg("hash-color")
+        .arg("--format=ansi-24bit-escapecode")
+        .arg("alice")
+        .assert()
+        .success()
+        .stdout("\x1b[38;2;159;166;70m");
+
+    for ratio in ["nan", "0.5", "22"] {
+        pastel()
+            .arg("hash-color")
+            .arg("--against=black")
+            .arg(format!("--min-contrast={}", ratio))
+            .arg("alice")
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn sample_with_weights_and_seed() {
+    let path = TempPath::new("weights.txt");
+    std::fs::write(&path, "# red only\n1\n0\n").unwrap();
+
+    pastel()
+        .arg("sample")
+        .arg("-n")
+        .arg("2")
+        .arg("--weights")
+        .arg(&path)
+        .arg("red")
+        .arg("blue")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\nhsl(0,100.0%,50.0%)\n");
+
+    pastel()
+        .arg("sample")
+        .arg("--weights")
+        .arg(&path)
+        .arg("red")
+        .assert()
+        .failure();
+
+    let sample = |seed: &str| {
+        pastel()
+            .arg("sample")
+            .arg("-n")
+            .arg("20")
+            .arg("--seed")
+            .arg(seed)
+            .arg("red")
+            .arg("green")
+            .arg("blue")
+            .output()
+            .unwrap()
+            .stdout
+    };
+    assert_eq!(sample("42"), sample("42"));
+}
+
+#[test]
+fn roles_of_a_dark_theme() {
+    pastel()
+        .arg("roles")
+        .write_stdin("#f38ba8\n#1e1e2e\n#a6e3a1\n#cdd6f4\n#1e1e2e\n#f9e2af\n#89b4fa\n")
+        .assert()
+        .success()
+        .stdout(
+            "background: #1e1e2e\n\
+             foreground: #cdd6f4\n\
+             accent: #89b4fa\n\
+             error: #f38ba8\n\
+             warning: #f9e2af\n\
+             success: #a6e3a1\n",
+        );
+}
+
+#[test]
+fn roles_of_a_scanned_palette() {
+    let path = TempPath::new("theme.scss");
+    std::fs::write(
+        &path,
+        ":root {\n  --bg: #1e1e2e;\n  --fg: #cdd6f4;\n  --blue: #89b4fa;\n  --red: #f38ba8;\n}\n",
+    )
+    .unwrap();
+
+    let output = pastel().arg("scan-css").arg(&path).assert().success();
+
+    pastel()
+        .arg("roles")
+        .write_stdin(output.get_output().stdout.clone())
+        .assert()
+        .success()
+        .stdout(
+            "background: #1e1e2e\n\
+             foreground: #cdd6f4\n\
+             accent: #89b4fa\n\
+             error: #f38ba8\n",
+        );
+}
+
+#[test]
+fn scan_css_resolves_variables() {
+    let path = TempPath::new("styles.scss");
+    std::fs::write(
+        &path,
+        "/* brand */\n\
+         $brand: #336699 !default;\n\
+         $accent: $brand; // alias\n\
+         :root {\n  \
+           --primary: var(--missing, tomato);\n  \
+           --loop: var(--loop);\n  \
+           --hover: darken($brand, 10%);\n\
+         }\n",
+    )
+    .unwrap();
+
+    pastel()
+        .arg("scan-css")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout("$brand: #336699\n$accent: #336699\n--primary: #ff6347\n");
+}
+
+#[test]
+fn paint_with_style_specification() {
+    pastel()
+        .arg("--force-color")
+        .arg("paint")
+        .arg("bold red on #222")
+        .arg("hello")
+        .assert()
+        .success()
+        .stdout("\x1b[38;2;255;0;0;48;2;34;34;34;1mhello\x1b[0m\n");
+
+    pastel()
+        .arg("--force-color")
+        .arg("paint")
+        .arg("bold red")
+        .arg("--strikethrough")
+        .arg("--dim")
+        .arg("hello")
+        .assert()
+        .success()
+        .stdout("\x1b[38;2;255;0;0;1;2;9mhello\x1b[0m\n");
+
+    pastel()
+        .arg("paint")
+        .arg("bold nocolor")
+        .arg("hello")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn paint_honors_color_mode() {
+    pastel()
+        .arg("--color-mode")
+        .arg("8bit")
+        .arg("paint")
+        .arg("bold red on #222")
+        .arg("hello")
+        .assert()
+        .success()
+        .stdout("\x1b[38;5;196;48;5;235;1mhello\x1b[0m\n");
+
+    pastel()
+        .arg("--color-mode")
+        .arg("off")
+        .arg("paint")
+        .arg("red")
+        .arg("hello")
+        .assert()
+        .success()
+        .stdout("hello\n");
+}
+
+#[test]
+fn demo_shows_all_sections() {
+    let output = pastel().arg("demo").assert().success();
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+
+    for section in &[
+        "Gradients",
+        "Distinct colors",
+        "Colorblindness simulation",
+        "Contrast ratios",
+    ] {
+        assert!(stdout.contains(section));
+    }
+    assert!(stdout.contains("#1d2b53"));
+}
+
+#[test]
+fn kelvin_creates_light_source_colors() {
+    pastel()
+        .arg("kelvin")
+        .arg("1900")
+        .arg("2700K")
+        .arg("10000")
+        .assert()
+        .success()
+        .stdout("hsl(31,100.0%,50.2%)\nhsl(31,100.0%,67.3%)\nhsl(226,100.0%,90.2%)\n");
+
+    pastel().arg("kelvin").arg("500").assert().failure();
+
+    pastel()
+        .arg("format")
+        .arg("cct")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("6504\n");
+}
+
+#[test]
+fn system_colors_from_theme_configuration() {
+    let config_home = TempPath::new("system");
+    std::fs::create_dir_all(config_home.join("gtk-3.0")).unwrap();
+    std::fs::write(
+        config_home.join("gtk-3.0").join("gtk.css"),
+        "@define-color accent_bg_color #3584e4;\n\
+         @define-color dark #303030;\n\
+         @define-color window_fg_color @dark;\n\
+         @define-color shadow shade(@dark, 0.9);\n",
+    )
+    .unwrap();
+    std::fs::write(
+        config_home.join("kdeglobals"),
+        "[General]\nAccentColor=61,174,233\n\n[Colors:Window]\nBackgroundNormal=32,35,38\n",
+    )
+    .unwrap();
+
+    pastel()
+        .arg("system-colors")
+        .arg("--source=gtk")
+        .env("XDG_CONFIG_HOME", &config_home)
+        .assert()
+        .success()
+        .stdout(
+            "accent: #3584e4\nforeground: #303030\naccent_bg_color: #3584e4\n\
+             dark: #303030\nwindow_fg_color: #303030\n",
+        );
+
+    pastel()
+        .arg("system-colors")
+        .arg("--source=kde")
+        .arg("accent")
+        .arg("background")
+        .env("XDG_CONFIG_HOME", &config_home)
+        .assert()
+        .success()
+        .stdout("hsl(201,79.6%,57.6%)\nhsl(210,8.6%,13.7%)\n");
+
+    pastel()
+        .arg("system-colors")
+        .arg("--file")
+        .arg(config_home.join("kdeglobals"))
+        .arg("unknown")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn wavelength_creates_spectral_colors() {
+    pastel()
+        .arg("wavelength")
+        .arg("450")
+        .arg("532nm")
+        .arg("650")
+        .assert()
+        .success()
+        .stdout("hsl(259,100.0%,50.0%)\nhsl(120,100.0%,50.0%)\nhsl(0,100.0%,50.0%)\n");
+
+    pastel().arg("wavelength").arg("300").assert().failure();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn system_accent_falls_back_to_theme_configuration() {
+    let config_home = TempPath::new("accent");
+    std::fs::create_dir_all(&config_home).unwrap();
+    std::fs::write(
+        config_home.join("kdeglobals"),
+        "[General]\nAccentColor=61,174,233\n",
+    )
+    .unwrap();
+
+    pastel()
+        .arg("system-accent")
+        .env("XDG_CONFIG_HOME", &config_home)
+        .assert()
+        .success()
+        .stdout("hsl(201,79.6%,57.6%)\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn export_via_external_program() {
+    let output = pastel()
+        .arg("export")
+        .arg("--via=cat")
+        .write_stdin("primary: #ff8000\nsecondary: {primary}\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(1, document["version"]);
+    assert_eq!("secondary", document["colors"][1]["name"]);
+    assert_eq!("#ff8000", document["colors"][1]["hex"]);
+    assert_eq!(128, document["colors"][1]["rgb"]["g"]);
+
+    pastel()
+        .arg("export")
+        .arg("--via=false")
+        .arg("red")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn distance_between_colors() {
+    pastel()
+        .arg("distance")
+        .arg("red")
+        .arg("orange")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("33.76\n0.00\n");
+
+    pastel()
+        .arg("distance")
+        .arg("--metric=OkLab")
+        .arg("black")
+        .write_stdin("white\n")
+        .assert()
+        .success()
+        .stdout("1.0000\n");
+
+    pastel()
+        .arg("distance")
+        .arg("--threshold=10")
+        .arg("#333")
+        .arg("#fff")
+        .arg("#3a3a3a")
+        .assert()
+        .code(1)
+        .stdout("68.67\n2.26\n");
+}
+
+#[test]
+fn explain_contrast_and_distance() {
+    pastel()
+        .arg("contrast")
+        .arg("--explain")
+        .arg("white")
+        .arg("#777")
+        .assert()
+        .success()
+        .stdout(
+            "4.48\n  \
+             text luminance:       0.1845\n  \
+             background luminance: 1.0000\n  \
+             ratio:                (1.0000 + 0.05) / (0.1845 + 0.05) = 4.48\n",
+        );
+
+    pastel()
+        .arg("distance")
+        .arg("--explain")
+        .arg("#336699")
+        .arg("#3a6a9a")
+        .assert()
+        .success()
+        .stdout(
+            "1.51\n  \
+             Lab(42.01, -0.14, -32.85) → Lab(43.57, -0.92, -30.98)\n  \
+             lightness: +1.43\n  \
+             chroma:    -0.76\n  \
+             hue:       -0.62\n  \
+             dominant:  lightness\n",
+        );
+}
+
+#[test]
+fn roundtrip_through_all_color_spaces() {
+    pastel()
+        .arg("roundtrip")
+        .arg("#336699")
+        .arg("white")
+        .arg("black")
+        .arg("#ff8000")
+        .assert()
+        .success();
+
+    let output = pastel()
+        .arg("roundtrip")
+        .arg("rgba(10, 200, 30, 0.5)")
+        .assert()
+        .code(1);
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("cmyk       5.00e-1  precision lost"));
+    assert!(stdout.contains("oklab      0.00e0\n"));
+}
+
+#[test]
+fn distance_matrix_formats() {
+    pastel()
+        .arg("distance-matrix")
+        .arg("--format=csv")
+        .arg("red")
+        .arg("orange")
+        .assert()
+        .success()
+        .stdout(",#ff0000,#ffa500\n#ff0000,0.0000,33.7558\n#ffa500,33.7558,0.0000\n");
+
+    let output = pastel()
+        .arg("distance-matrix")
+        .arg("--format=json")
+        .arg("--metric=cie76")
+        .arg("red")
+        .arg("orange")
+        .arg("blue")
+        .output()
+        .unwrap();
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!("CIE76", document["metric"]);
+    assert_eq!("#0000ff", document["colors"][2]);
+    assert_eq!(0.0, document["distances"][1][1]);
+    assert_eq!(document["distances"][0][2], document["distances"][2][0]);
+}
+
+#[test]
+fn match_lightness_of_reference() {
+    pastel()
+        .arg("match-lightness")
+        .arg("steelblue")
+        .arg("crimson")
+        .arg("gold")
+        .assert()
+        .success()
+        .stdout("hsl(352,85.0%,55.7%)\nhsl(50,100.0%,29.0%)\n");
+}
+
+#[test]
+fn match_chroma_median_and_target() {
+    // The median chroma is the one of 'steelblue', gray colors are kept
+    pastel()
+        .arg("match-chroma")
+        .arg("crimson")
+        .arg("steelblue")
+        .arg("gray")
+        .assert()
+        .success()
+        .stdout("hsl(359,31.4%,52.0%)\nhsl(207,44.0%,49.0%)\nhsl(0,0.0%,50.2%)\n");
+
+    pastel()
+        .arg("match-chroma")
+        .arg("--target")
+        .arg("-1")
+        .arg("red")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn proof_warns_about_shifted_colors() {
+    let output = pastel()
+        .arg("proof")
+        .arg("--profile")
+        .arg("newsprint")
+        .arg("navy")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(1, String::from_utf8(output.stdout).unwrap().lines().count());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("#000080 shifts by"));
+
+    let output = pastel()
+        .arg("proof")
+        .arg("--profile")
+        .arg("office-laser")
+        .arg("--threshold")
+        .arg("100")
+        .arg("navy")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn alpha_report() {
+    let output = pastel()
+        .arg("alpha-report")
+        .arg("--background")
+        .arg("white")
+        .arg("red")
+        .arg("rgba(0,0,0,0.05)")
+        .arg("rgba(0,0,255,0)")
+        .assert()
+        .success();
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+
+    assert!(stdout.starts_with("count: 3\nopaque: 1\ntranslucent: 1\ntransparent: 1\n"));
+    assert!(stdout.contains("  0.0-0.1    2 "));
+    assert!(stdout.contains("#0000000d on #ffffff: #f2f2f2 (1.12)"));
+    assert!(stdout.contains("#0000ff00 on #ffffff: #ffffff (1.00)"));
+    assert!(!stdout.contains("#ff0000 on"));
+}
+
+#[test]
+fn tonal_palette() {
+    let output = pastel()
+        .arg("tonal-palette")
+        .arg("#3b82f6")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 10);
+    assert_eq!(lines[0], "50: #eff6ff");
+    assert_eq!(lines[5], "500: #3b82f6");
+
+    let output = pastel()
+        .arg("tonal-palette")
+        .arg("#3b82f6")
+        .arg("--name=brand")
+        .arg("--format=css-vars")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with(":root {\n  --brand-50: #eff6ff;\n"));
+    assert!(stdout.contains("  --brand-500: #3b82f6;\n"));
+}
+
+#[test]
+fn theme_generate() {
+    let output = pastel()
+        .arg("theme")
+        .arg("generate")
+        .arg("#1e1e2e")
+        .arg("#89b4fa")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 19);
+    assert_eq!(lines[0], "background: #1e1e2e");
+    assert_eq!(lines[2], "cursor: #89b4fa");
+    assert_eq!(lines[7], "blue: #89b4fa");
+    assert!(lines[18].starts_with("bright-white: #"));
+}
+
+#[test]
+fn theme_generate_for_mid_gray_background() {
+    let output = pastel()
+        .args(["theme", "generate", "#777777"])
+        .output()
+        .unwrap();
+    assert_eq!(Some(1), output.status.code());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        "a contrast ratio of 4.5 cannot be reached for distinct colors on this background"
+    ));
+}
+
+#[test]
+fn theme_export() {
+    let theme = "background: #000000\nforeground: #ffffff\ncursor: #ff8000\nblack: #000000\n\
+                 red: #010101\ngreen: #020202\nyellow: #030303\nblue: #040404\n\
+                 magenta: #050505\ncyan: #060606\nwhite: #070707\nbright-black: #080808\n\
+                 bright-red: #090909\nbright-green: #0a0a0a\nbright-yellow: #0b0b0b\n\
+                 bright-blue: #0c0c0c\nbright-magenta: #0d0d0d\nbright-cyan: #0e0e0e\n\
+                 bright-white: #0f0f0f\n";
+
+    let output = pastel()
+        .args(["theme", "export", "--format", "kitty"])
+        .write_stdin(theme)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("background #000000\nforeground #ffffff\ncursor #ff8000\n"));
+    assert!(stdout.ends_with("color15 #0f0f0f\n"));
+
+    let output = pastel()
+        .args([
+            "theme",
+            "export",
+            "--format",
+            "windows-terminal",
+            "--name=test",
+        ])
+        .write_stdin(theme)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\"name\": \"test\""));
+    assert!(stdout.contains("\"purple\": \"#050505\""));
+    assert!(stdout.contains("\"brightPurple\": \"#0d0d0d\""));
+
+    let output = pastel()
+        .args(["theme", "export", "--format", "alacritty"])
+        .write_stdin(theme)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[colors.bright]\nblack = '#080808'\n"));
+
+    pastel()
+        .args(["theme", "export", "--format", "xresources"])
+        .write_stdin("background: #000000\n")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn apply_sets_terminal_colors() {
+    pastel()
+        .arg("apply")
+        .write_stdin("red: #ff8000\nbackground: #000000\ncolor200: #ffffff\n")
+        .assert()
+        .success()
+        .stdout(
+            "\x1b]4;1;rgb:ff/80/00\x1b\\\x1b]11;rgb:00/00/00\x1b\\\x1b]4;200;rgb:ff/ff/ff\x1b\\",
+        );
+
+    pastel()
+        .arg("apply")
+        .write_stdin("primary: #ff8000\n")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn apply_reset() {
+    pastel()
+        .arg("apply")
+        .arg("--reset")
+        .assert()
+        .success()
+        .stdout("\x1b]104\x1b\\\x1b]110\x1b\\\x1b]111\x1b\\\x1b]112\x1b\\");
+}
+
+#[test]
+fn terminal_background_from_environment() {
+    pastel()
+        .arg("terminal-background")
+        .env("PASTEL_TERMINAL_BACKGROUND", "#fdf6e3")
+        .assert()
+        .success()
+        .stdout("hsl(44,86.7%,94.1%)\n");
+
+    pastel()
+        .arg("terminal-background")
+        .env("PASTEL_TERMINAL_BACKGROUND", "no color")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn edit_requires_terminal() {
+    pastel().arg("edit").arg("red").assert().failure();
+}
+
+#[test]
+fn pick_rejects_invalid_average_size() {
+    pastel()
+        .arg("pick")
+        .arg("--average")
+        .arg("0x3")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn pick_from_image() {
+    let path = TempPath::new("pick.png");
+
+    let image = image::RgbaImage::from_fn(4, 2, |x, y| match (x, y) {
+        (0, 0) => image::Rgba([255, 0, 0, 255]),
+        (3, 1) => image::Rgba([0, 0, 255, 255]),
+        _ => image::Rgba([255, 255, 255, 0]),
+    });
+    image.save(&path).unwrap();
+
+    pastel()
+        .arg("pick-from-image")
+        .arg(&path)
+        .arg("--at")
+        .arg("3,1")
+        .arg("--at")
+        .arg("0,0")
+        .assert()
+        .success()
+        .stdout("hsl(240,100.0%,50.0%)\nhsl(0,100.0%,50.0%)\n");
+
+    pastel()
+        .arg("pick-from-image")
+        .arg(&path)
+        .arg("--at")
+        .arg("4,0")
+        .assert()
+        .failure();
+
+    pastel()
+        .arg("pick-from-image")
+        .arg(&path)
+        .arg("--at")
+        .arg("1;1")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn grep_finds_color_literals() {
+    let text = "a { color: #ff0000; }\nno colors here, just a bad face\nb { background: rgb(0, 0, 255); border: 1px solid Red; }\n";
+
+    pastel()
+        .arg("grep")
+        .arg("-n")
+        .write_stdin(text)
+        .assert()
+        .success()
+        .stdout(
+            "1:a { color: #ff0000; }\n\
+             3:b { background: rgb(0, 0, 255); border: 1px solid Red; }\n",
+        );
+
+    pastel()
+        .arg("grep")
+        .arg("--unique")
+        .write_stdin(text)
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\nhsl(240,100.0%,50.0%)\n");
+
+    pastel()
+        .arg("grep")
+        .arg("--list")
+        .arg("--no-names")
+        .write_stdin(text)
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\nhsl(240,100.0%,50.0%)\n");
+}
+
+#[test]
+fn grep_skips_issue_references_and_identifiers() {
+    pastel()
+        .arg("grep")
+        .arg("--list")
+        .write_stdin("see issue #123 and PR #4567, &#123;\n.red-button { color: #abc; }\n")
+        .assert()
+        .success()
+        .stdout("hsl(210,25.0%,73.3%)\n");
+
+    pastel()
+        .arg("grep")
+        .arg("--list")
+        .write_stdin("border: 1px solid #abc;\nbox-shadow: 0 0 2px #0008;\nwidth: 10 #123\n")
+        .assert()
+        .success()
+        .stdout("hsl(210,25.0%,73.3%)\nhsla(0,0.0%,0.0%,0.533)\nhsl(210,50.0%,13.3%)\n");
+
+    let output = pastel()
+        .arg("grep")
+        .arg("does-not-exist.css")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Could not read file 'does-not-exist.css'"));
+}
+
+#[test]
+fn replace_rewrites_color_literals() {
+    let text = "a { color: #ff0000; border: 1px solid red; }\nb { background: rgb(0, 0, 128); }\n";
+
+    pastel()
+        .arg("replace")
+        .arg("--names")
+        .arg("--to")
+        .arg("hex")
+        .write_stdin(text)
+        .assert()
+        .success()
+        .stdout("a { color: #ff0000; border: 1px solid #ff0000; }\nb { background: #000080; }\n");
+
+    pastel()
+        .arg("replace")
+        .arg("--names")
+        .arg("--map")
+        .arg("red=blue")
+        .arg("--map")
+        .arg("navy=white")
+        .write_stdin(text)
+        .assert()
+        .success()
+        .stdout(
+            "a { color: #0000ff; border: 1px solid #0000ff; }\n\
+             b { background: rgb(255, 255, 255); }\n",
+        );
+
+    pastel()
+        .arg("replace")
+        .arg("--to")
+        .arg("oklch")
+        .write_stdin("#ffffff")
+        .assert()
+        .success()
+        .stdout("oklch(100.00% 0.0000 0.00)");
+
+    pastel()
+        .arg("replace")
+        .arg("--map")
+        .arg("red")
+        .write_stdin(text)
+        .assert()
+        .failure();
+}
+
+#[test]
+fn replace_leaves_identifiers_and_prose_alone() {
+    let text = ".red-button { … } /* see issue #123, tan lines */";
+
+    pastel()
+        .arg("replace")
+        .arg("--to")
+        .arg("hex")
+        .write_stdin(text)
+        .assert()
+        .success()
+        .stdout(text);
+
+    pastel()
+        .arg("replace")
+        .arg("--to")
+        .arg("rgb")
+        .write_stdin("border: 1px solid #abc;\nbox-shadow: 0 0 2px #0008;\n")
+        .assert()
+        .success()
+        .stdout(
+            "border: 1px solid rgb(170, 187, 204);\nbox-shadow: 0 0 2px rgba(0, 0, 0, 0.533);\n",
+        );
+
+    pastel()
+        .arg("replace")
+        .arg("--names")
+        .arg("--to")
+        .arg("hex")
+        .write_stdin(".red-button { color: red; }")
+        .assert()
+        .success()
+        .stdout(".red-button { color: #ff0000; }");
+}
+
+#[test]
+fn colorize_with_gradient() {
+    pastel()
+        .arg("-m")
+        .arg("24bit")
+        .arg("colorize")
+        .arg("--gradient")
+        .arg("red")
+        .arg("blue")
+        .arg("--colorspace")
+        .arg("RGB")
+        .write_stdin("abc\n a\n")
+        .assert()
+        .success()
+        .stdout(
+            "\x1b[38;2;255;0;0ma\x1b[0m\x1b[38;2;128;0;128mb\x1b[0m\x1b[38;2;0;0;255mc\x1b[0m\n \
+             \x1b[38;2;128;0;128ma\x1b[0m\n",
+        );
+
+    pastel()
+        .arg("-m")
+        .arg("24bit")
+        .arg("colorize")
+        .arg("--gradient")
+        .arg("red")
+        .arg("blue")
+        .arg("--angle")
+        .arg("90")
+        .write_stdin("ab\ncd\n")
+        .assert()
+        .success()
+        .stdout(
+            "\x1b[38;2;255;0;0ma\x1b[0m\x1b[38;2;255;0;0mb\x1b[0m\n\
+             \x1b[38;2;0;0;255mc\x1b[0m\x1b[38;2;0;0;255md\x1b[0m\n",
+        );
+}
+
+#[test]
+fn colorize_with_gradient_rejects_non_finite_angle() {
+    for angle in ["nan", "inf"] {
+        pastel()
+            .arg("colorize")
+            .arg("--gradient")
+            .arg("red")
+            .arg("blue")
+            .arg("--angle")
+            .arg(angle)
+            .write_stdin("abc\n")
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn strip_removes_styling() {
+    pastel()
+        .arg("strip")
+        .write_stdin("\x1b[38;2;255;0;0;1mred\x1b[0m and plain\nsecond line")
+        .assert()
+        .success()
+        .stdout("red and plain\nsecond line");
+}
+
+#[test]
+fn textcolor_with_candidates() {
+    pastel()
+        .arg("textcolor")
+        .arg("#ffff00")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,0.0%)\n");
+
+    pastel()
+        .arg("textcolor")
+        .arg("--candidates")
+        .arg("#ff0000 rgb(0, 0, 128) #eeeeee")
+        .arg("--verbose")
+        .arg("#ffff00")
+        .assert()
+        .success()
+        .stdout("hsl(240,100.0%,25.1%)\n")
+        .stderr("contrast ratio: 14.91\n");
+
+    // The first candidate that is readable enough
+    pastel()
+        .arg("textcolor")
+        .arg("--candidates")
+        .arg("#ff0000 navy")
+        .arg("--min-contrast")
+        .arg("3")
+        .arg("#ffff00")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\n");
+
+    pastel()
+        .arg("textcolor")
+        .arg("--candidates")
+        .arg("#ff0000 #eeeeee")
+        .arg("--algorithm")
+        .arg("apca")
+        .arg("--min-contrast")
+        .arg("60")
+        .arg("#ffff00")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn adjust_contrast() {
+    pastel()
+        .arg("adjust-contrast")
+        .arg("--verbose")
+        .arg("white")
+        .arg("#6a9fb5")
+        .assert()
+        .success()
+        .stdout("hsl(198,33.6%,43.1%)\n")
+        .stderr("contrast ratio: 4.53 (target 4.5: reached)\n");
+
+    // Colors that are readable enough stay unchanged
+    pastel()
+        .arg("adjust-contrast")
+        .arg("white")
+        .arg("black")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,0.0%)\n");
+
+    pastel()
+        .arg("adjust-contrast")
+        .arg("--algorithm=apca")
+        .arg("--target=100")
+        .arg("--verbose")
+        .arg("gray")
+        .arg("gray")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,100.0%)\n")
+        .stderr(
+            "[pastel warning]: #808080 can not reach a contrast of 100 on #808080 (using #ffffff \
+             with a contrast of 72.4)\nAPCA contrast (Lc): 72.4 (target 100: not reached)\n",
+        );
+
+    for (algorithm, target) in [
+        ("wcag2", "nan"),
+        ("wcag2", "0.5"),
+        ("wcag2", "22"),
+        ("apca", "120"),
+    ] {
+        pastel()
+            .arg("adjust-contrast")
+            .arg(format!("--algorithm={}", algorithm))
+            .arg(format!("--target={}", target))
+            .arg("white")
+            .arg("gray")
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn a11y_report() {
+    pastel()
+        .arg("a11y-report")
+        .arg("white")
+        .arg("black")
+        .arg("#777777")
+        .assert()
+        .success()
+        .stdout(
+            "contrast ratios (text in rows, background in columns):\n\
+             \n         #ffffff    #000000    #777777\n\
+             #ffffff             21.00 AAA   4.48 fail\n\
+             #000000  21.00 AAA              4.69 AA\n\
+             #777777   4.48 fail  4.69 AA\n\
+             \n\
+             color vision deficiency simulation:\n\
+             \n  normal        #ffffff #000000 #777777\n  \
+               protanopia    #fffaff #000000 #827577\n  \
+               deuteranopia  #fff6ff #000000 #807277\n  \
+               tritanopia    #fefffa #000000 #777775\n",
+        );
+
+    pastel()
+        .arg("a11y-report")
+        .arg("--large-text")
+        .arg("black")
+        .arg("#777777")
+        .assert()
+        .success()
+        .stdout(
+            "contrast ratios (text in rows, background in columns):\n\
+             \n         #000000    #777777\n\
+             #000000              4.69 AAA\n\
+             #777777   4.69 AAA\n\
+             \n\
+             color vision deficiency simulation:\n\
+             \n  normal        #000000 #777777\n  \
+               protanopia    #000000 #827577\n  \
+               deuteranopia  #000000 #807277\n  \
+               tritanopia    #000000 #777775\n",
+        );
+
+    pastel()
+        .arg("a11y-report")
+        .arg("black")
+        .arg("#77777780")
+        .assert()
+        .failure()
+        .stderr(
+            "[pastel error]: The color '#77777780' is not opaque. Use 'pastel alpha-report' to \
+             check the contrast of translucent colors.\n",
+        );
+}
+
+#[test]
+fn colorblind_with_severity() {
+    pastel()
+        .arg("colorblind")
+        .arg("--severity=0")
+        .arg("deuter")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\n");
+
+    pastel()
+        .arg("colorblind")
+        .arg("--severity=1")
+        .arg("deuter")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(53,100.0%,32.0%)\n");
+
+    pastel()
+        .arg("colorblind")
+        .arg("achroma")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,49.8%)\n");
+
+    pastel()
+        .arg("colorblind")
+        .arg("--severity=1.5")
+        .arg("prot")
+        .arg("red")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn daltonize() {
+    pastel()
+        .arg("daltonize")
+        .arg("deuter")
+        .arg("#dc2828")
+        .arg("#28a028")
+        .assert()
+        .success()
+        .stdout("hsl(332,62.6%,63.3%)\nhsl(103,100.0%,27.5%)\n");
+
+    pastel()
+        .arg("daltonize")
+        .arg("--strength=0")
+        .arg("trit")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\n");
+
+    for strength in ["-1", "nan", "inf"] {
+        pastel()
+            .arg("daltonize")
+            .arg(format!("--strength={}", strength))
+            .arg("deuter")
+            .arg("red")
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn bands() {
+    pastel()
+        .arg("bands")
+        .arg("--width=90")
+        .write_stdin("red\n#ff8000\nnavy\ngray\n")
+        .assert()
+        .success()
+        .stdout("  0°- 90° 2\n 90°-180° 0\n180°-270° 0\n270°-360° 1\ngray 1\n");
+
+    pastel()
+        .arg("bands")
+        .arg("--width=0")
+        .arg("red")
+        .assert()
+        .failure()
+        .stderr("[pastel error]: The width of the hue bands must be between 0 and 360 degrees\n");
+}
+
+#[test]
+fn diverging() {
+    pastel()
+        .arg("diverging")
+        .arg("-n")
+        .arg("5")
+        .arg("steelblue")
+        .arg("firebrick")
+        .assert()
+        .success()
+        .stdout(
+            "hsl(206,53.6%,41.4%)\nhsl(212,34.7%,71.8%)\nhsl(0,0.0%,100.0%)\n\
+             hsl(8,73.0%,75.3%)\nhsl(2,60.5%,48.6%)\n",
+        );
+
+    pastel()
+        .arg("diverging")
+        .arg("--midpoint=#f7f7f7")
+        .arg("-n")
+        .arg("3")
+        .arg("purple")
+        .arg("green")
+        .assert()
+        .success()
+        .stdout("hsl(301,60.6%,36.9%)\nhsl(0,0.0%,96.9%)\nhsl(120,100.0%,20.6%)\n");
+
+    // Saturated end colors are not clipped, so both ends keep the same lightness
+    for colorspace in ["Lab", "OkLab"] {
+        let output = pastel()
+            .arg("diverging")
+            .arg("-n")
+            .arg("3")
+            .arg(format!("--colorspace={}", colorspace))
+            .arg("red")
+            .arg("blue")
+            .assert()
+            .success();
+
+        let colors: Vec<_> = String::from_utf8(output.get_output().stdout.clone())
+            .unwrap()
+            .lines()
+            .map(|line| pastel::parser::parse_color(line).unwrap())
+            .collect();
+        let lightness = |c: &pastel::Color| match colorspace {
+            "Lab" => c.to_lab().l / 100.0,
+            _ => c.to_oklab().l,
+        };
+        assert!((lightness(&colors[0]) - lightness(&colors[2])).abs() < 0.005);
+    }
+}
+
+#[test]
+fn max_chroma() {
+    pastel()
+        .arg("max-chroma")
+        .arg("lch(60, 10, 140)")
+        .assert()
+        .success()
+        .stdout("hsl(136,100.0%,32.9%)\n");
+
+    pastel()
+        .arg("max-chroma")
+        .arg("gray")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(0,0.0%,50.2%)\nhsl(0,100.0%,50.0%)\n");
+}
+
+#[test]
+fn colorfulness_and_stats() {
+    pastel()
+        .arg("format")
+        .arg("colorfulness")
+        .arg("red")
+        .arg("gray")
+        .assert()
+        .success()
+        .stdout("85.53\n0.00\n");
+
+    pastel()
+        .arg("stats")
+        .write_stdin("red\ngray\n")
+        .assert()
+        .success()
+        .stdout(
+            "count: 2\nmean lightness: 53.41\nmean chroma: 52.29\nmean luminance: 0.214\n\
+             mean colorfulness: 42.76\ncolorfulness: 185.31\n",
+        );
+}
+
+#[test]
+fn under_illuminant() {
+    pastel()
+        .arg("under")
+        .arg("--illuminant=A,D65")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("hsl(50,100.0%,76.1%)\nhsl(0,0.0%,100.0%)\n");
+
+    pastel()
+        .arg("under")
+        .arg("--illuminant=d65")
+        .arg("cornflowerblue")
+        .assert()
+        .success()
+        .stdout("hsl(219,79.2%,66.1%)\n");
+
+    pastel()
+        .arg("under")
+        .arg("--illuminant=Z")
+        .arg("white")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn curves() {
+    pastel()
+        .arg("curves")
+        .arg("--spec=r:0,0;1,0.5")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("hsl(180,100.0%,75.1%)\n");
+
+    pastel()
+        .arg("curves")
+        .arg("--spec=r:0,0;1,0.5")
+        .arg("--space=linear")
+        .arg("white")
+        .assert()
+        .success()
+        .stdout("hsl(180,100.0%,86.9%)\n");
+
+    pastel()
+        .arg("curves")
+        .arg("--spec=x:0,0")
+        .arg("white")
+        .assert()
+        .failure()
+        .stderr(
+            "[pastel error]: Invalid curve specification 'x:0,0': unknown channel in curve \
+             specification (use 'r', 'g' and/or 'b')\n",
+        );
+
+    for spec in ["r:0,0;1,NaN", "r:0,0;1,5"] {
+        pastel()
+            .arg("curves")
+            .arg(format!("--spec={}", spec))
+            .arg("red")
+            .assert()
+            .failure()
+            .stderr(format!(
+                "[pastel error]: Invalid curve specification '{}': the control points of a tone \
+                 curve must be between 0 and 1\n",
+                spec
+            ));
+    }
+}
+
+#[test]
+fn hyperlinks_are_plain_when_piped() {
+    pastel()
+        .arg("--hyperlinks")
+        .arg("color")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\n");
+
+    pastel()
+        .arg("--hyperlinks=https://example.com/{hex}")
+        .arg("color")
+        .arg("red")
+        .assert()
+        .success()
+        .stdout("hsl(0,100.0%,50.0%)\n");
+}
+
+#[cfg(feature = "serve")]
+#[test]
+fn serve_prints_the_bound_port() {
+    use assert_cmd::cargo::CommandCargoExt;
+    use std::io::{BufRead, BufReader};
+
+    let mut server = std::process::Command::cargo_bin("pastel")
+        .unwrap()
+        .arg("serve")
+        .arg("--port=0")
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut line = String::new();
+    let result = BufReader::new(server.stdout.take().unwrap()).read_line(&mut line);
+    server.kill().unwrap();
+    server.wait().unwrap();
+    result.unwrap();
+
+    let port = line
+        .trim_end()
+        .strip_prefix("Listening on http://127.0.0.1:")
+        .and_then(|port| port.parse::<u16>().ok());
+    assert!(port.is_some_and(|port| port != 0), "{}", line);
+}